@@ -1,4 +1,29 @@
-/// Shared helper functions for technical indicators
+//! Shared helper functions for technical indicators
+
+use std::borrow::Cow;
+use std::collections::VecDeque;
+use numpy::PyReadonlyArray1;
+use pyo3::prelude::*;
+
+/// 1-D numeric input accepted where integer columns are common (e.g. volume).
+/// float64 arrays are borrowed zero-copy; int32/int64 arrays are cast to f64.
+#[derive(FromPyObject)]
+pub enum FloatArray<'py> {
+    F64(PyReadonlyArray1<'py, f64>),
+    I64(PyReadonlyArray1<'py, i64>),
+    I32(PyReadonlyArray1<'py, i32>),
+}
+
+impl FloatArray<'_> {
+    /// View the input as f64 values, copying only when a cast is needed
+    pub fn to_f64(&self) -> PyResult<Cow<'_, [f64]>> {
+        match self {
+            FloatArray::F64(arr) => Ok(Cow::Borrowed(arr.as_slice()?)),
+            FloatArray::I64(arr) => Ok(Cow::Owned(arr.as_array().iter().map(|&x| x as f64).collect())),
+            FloatArray::I32(arr) => Ok(Cow::Owned(arr.as_array().iter().map(|&x| x as f64).collect())),
+        }
+    }
+}
 
 /// Simple Moving Average kernel using running sum for O(n) complexity
 pub fn sma_kernel(data: &[f64], window: usize) -> Vec<f64> {
@@ -25,6 +50,42 @@ pub fn sma_kernel(data: &[f64], window: usize) -> Vec<f64> {
     result
 }
 
+/// Expanding mean kernel: mean of data[0..=i], NaN until `min_periods` values are seen
+pub fn expanding_mean_kernel(data: &[f64], min_periods: usize) -> Vec<f64> {
+    let n = data.len();
+    let mut result = vec![f64::NAN; n];
+    let mut sum = 0.0;
+
+    for i in 0..n {
+        sum += data[i];
+        if i + 1 >= min_periods.max(1) {
+            result[i] = sum / (i + 1) as f64;
+        }
+    }
+
+    result
+}
+
+/// Expanding population std kernel: std of data[0..=i] by Welford's method, NaN
+/// until `min_periods` values are seen
+pub fn expanding_std_kernel(data: &[f64], min_periods: usize) -> Vec<f64> {
+    let n = data.len();
+    let mut result = vec![f64::NAN; n];
+    let (mut mean, mut m2) = (0.0, 0.0);
+
+    for i in 0..n {
+        let count = (i + 1) as f64;
+        let delta = data[i] - mean;
+        mean += delta / count;
+        m2 += delta * (data[i] - mean);
+        if i + 1 >= min_periods.max(1) {
+            result[i] = (m2 / count).sqrt();
+        }
+    }
+
+    result
+}
+
 /// NaN-aware SMA kernel: only computes mean when all values in window are non-NaN
 pub fn sma_kernel_nan_aware(data: &[f64], window: usize) -> Vec<f64> {
     let n = data.len();
@@ -54,6 +115,41 @@ pub fn sma_kernel_nan_aware(data: &[f64], window: usize) -> Vec<f64> {
     result
 }
 
+/// Weighted Moving Average kernel with linear weights 1..=window
+pub fn wma_kernel(data: &[f64], window: usize) -> Vec<f64> {
+    let n = data.len();
+    let mut result = vec![f64::NAN; n];
+
+    if window > n || window == 0 {
+        return result;
+    }
+
+    let weights: Vec<f64> = (1..=window).map(|i| i as f64).collect();
+    let weight_sum: f64 = weights.iter().sum();
+
+    for i in (window - 1)..n {
+        let window_start = i + 1 - window;
+        let weighted_sum: f64 = data[window_start..=i]
+            .iter()
+            .zip(&weights)
+            .map(|(price, weight)| price * weight)
+            .sum();
+        result[i] = weighted_sum / weight_sum;
+    }
+
+    result
+}
+
+/// Moving average selected by name: "sma", "ema" (alpha = 2/(n+1)) or "wma"
+pub fn moving_average_kernel(data: &[f64], window: usize, ma_type: &str, adjusted: bool) -> Result<Vec<f64>, String> {
+    match ma_type {
+        "sma" => Ok(sma_kernel(data, window)),
+        "ema" => Ok(ema_kernel(data, 2.0 / (window as f64 + 1.0), adjusted)),
+        "wma" => Ok(wma_kernel(data, window)),
+        other => Err(format!("unknown ma_type '{}', expected one of: sma, ema, wma", other)),
+    }
+}
+
 /// Exponential Moving Average kernel with optional pandas-style adjustment
 pub fn ema_kernel(data: &[f64], alpha: f64, adjusted: bool) -> Vec<f64> {
     let n = data.len();
@@ -87,6 +183,249 @@ pub fn ema_kernel(data: &[f64], alpha: f64, adjusted: bool) -> Vec<f64> {
     result
 }
 
+/// Gain/loss smoothing used by `rsi_kernel`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RsiMethod {
+    /// SMA seed over the first n changes, then Wilder's smoothing (alpha = 1/n)
+    Wilder,
+    /// Cutler's RSI: simple rolling mean of gains and losses
+    Cutler,
+}
+
+impl RsiMethod {
+    pub fn parse(method: &str) -> Result<Self, String> {
+        match method {
+            "wilder" => Ok(RsiMethod::Wilder),
+            "cutler" | "sma" => Ok(RsiMethod::Cutler),
+            other => Err(format!("unknown RSI method '{}', expected one of: wilder, cutler", other)),
+        }
+    }
+}
+
+/// Warmup alignment for indicators whose first valid index differs from the
+/// Python `ta` library. "native" keeps this crate's output; "ta" shifts or masks
+/// the warmup to match `ta` with `fillna=False` (see each indicator's docs).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Compat {
+    Native,
+    Ta,
+}
+
+impl Compat {
+    pub fn parse(compat: &str) -> Result<Self, String> {
+        match compat {
+            "native" => Ok(Compat::Native),
+            "ta" => Ok(Compat::Ta),
+            other => Err(format!("unknown compat mode '{}', expected one of: native, ta", other)),
+        }
+    }
+}
+
+/// How a streaming class treats a NaN input: "propagate" feeds it into the state
+/// as before, "skip" ignores it and repeats the last output, "reset" clears the
+/// state and starts warming up again.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum NanPolicy {
+    Propagate,
+    Skip,
+    Reset,
+}
+
+impl NanPolicy {
+    pub fn parse(policy: &str) -> Result<Self, String> {
+        match policy {
+            "propagate" => Ok(NanPolicy::Propagate),
+            "skip" => Ok(NanPolicy::Skip),
+            "reset" => Ok(NanPolicy::Reset),
+            other => Err(format!(
+                "unknown nan_policy '{}', expected one of: propagate, skip, reset",
+                other
+            )),
+        }
+    }
+}
+
+/// One-period return used by the streaming risk estimators: "log" is ln(curr/prev),
+/// "simple" is curr/prev - 1.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ReturnType {
+    Log,
+    Simple,
+}
+
+impl ReturnType {
+    pub fn parse(return_type: &str) -> Result<Self, String> {
+        match return_type {
+            "log" => Ok(ReturnType::Log),
+            "simple" => Ok(ReturnType::Simple),
+            other => Err(format!("unknown return_type '{}', expected one of: log, simple", other)),
+        }
+    }
+
+    pub fn compute(self, prev: f64, curr: f64) -> f64 {
+        match self {
+            ReturnType::Log => (curr / prev).ln(),
+            ReturnType::Simple => curr / prev - 1.0,
+        }
+    }
+}
+
+/// Bar frequency used to annualize risk and return metrics. Equity frequencies
+/// assume 252 sessions of 6.5 hours: "daily" = 252, "hourly" = 252 * 6.5 = 1638,
+/// "minute" = 252 * 390 = 98280; "crypto_daily" trades every day, 365.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BarFrequency {
+    Daily,
+    Hourly,
+    Minute,
+    CryptoDaily,
+}
+
+impl BarFrequency {
+    pub fn parse(bar_frequency: &str) -> Result<Self, String> {
+        match bar_frequency {
+            "daily" => Ok(BarFrequency::Daily),
+            "hourly" => Ok(BarFrequency::Hourly),
+            "minute" => Ok(BarFrequency::Minute),
+            "crypto_daily" => Ok(BarFrequency::CryptoDaily),
+            other => Err(format!(
+                "unknown bar_frequency '{}', expected one of: daily, hourly, minute, crypto_daily",
+                other
+            )),
+        }
+    }
+
+    pub fn periods_per_year(self) -> f64 {
+        match self {
+            BarFrequency::Daily => 252.0,
+            BarFrequency::Hourly => 252.0 * 6.5,
+            BarFrequency::Minute => 252.0 * 390.0,
+            BarFrequency::CryptoDaily => 365.0,
+        }
+    }
+}
+
+/// Periods per year for annualizing: `periods_per_year` when given (for calendars the
+/// presets don't cover, e.g. 260 or 24/7 hourly bars), otherwise the `bar_frequency`
+/// preset. `bar_frequency` is validated either way.
+pub fn resolve_periods_per_year(bar_frequency: &str, periods_per_year: Option<f64>) -> Result<f64, String> {
+    let preset = BarFrequency::parse(bar_frequency)?.periods_per_year();
+    match periods_per_year {
+        None => Ok(preset),
+        Some(p) if p.is_finite() && p > 0.0 => Ok(p),
+        Some(p) => Err(format!("periods_per_year must be positive, got {}", p)),
+    }
+}
+
+/// Blank out values before `first_valid` so a warmup matches another alignment
+pub fn mask_warmup(values: &mut [f64], first_valid: usize) {
+    let end = first_valid.min(values.len());
+    values[..end].iter_mut().for_each(|v| *v = f64::NAN);
+}
+
+/// RSI as computed by `ta.momentum.RSIIndicator`: gains/losses smoothed with an
+/// unadjusted EWM (alpha = 1/n) seeded from the zero change at bar 0, emitted from
+/// index n-1 (`min_periods=n`). Wilder's SMA seed in `rsi_kernel` starts at n.
+pub fn rsi_ta_kernel(data: &[f64], n: usize) -> Vec<f64> {
+    let len = data.len();
+    let mut rsi_values = vec![f64::NAN; len];
+
+    if n == 0 || len < n {
+        return rsi_values;
+    }
+
+    let alpha = 1.0 / n as f64;
+    let (mut avg_gain, mut avg_loss) = (0.0, 0.0);
+
+    for i in 1..len {
+        let delta = data[i] - data[i - 1];
+        let (gain, loss) = if delta > 0.0 { (delta, 0.0) } else { (0.0, -delta) };
+        avg_gain = alpha * gain + (1.0 - alpha) * avg_gain;
+        avg_loss = alpha * loss + (1.0 - alpha) * avg_loss;
+
+        if i + 1 >= n {
+            rsi_values[i] = if avg_loss == 0.0 {
+                100.0
+            } else {
+                100.0 - 100.0 / (1.0 + avg_gain / avg_loss)
+            };
+        }
+    }
+
+    // Bar 0 has no change; with n == 1 ta still emits it (0/0 -> emadn == 0 -> 100)
+    if n == 1 {
+        rsi_values[0] = 100.0;
+    }
+
+    rsi_values
+}
+
+/// RSI kernel over any series; first n values are NaN
+pub fn rsi_kernel(data: &[f64], n: usize, method: RsiMethod) -> Vec<f64> {
+    let len = data.len();
+    let mut rsi_values = vec![f64::NAN; len];
+
+    if len < 2 {
+        return rsi_values;
+    }
+
+    let mut gains = vec![0.0; len];
+    let mut losses = vec![0.0; len];
+
+    for i in 1..len {
+        let delta = data[i] - data[i - 1];
+        if delta > 0.0 {
+            gains[i] = delta;
+            losses[i] = 0.0;
+        } else {
+            gains[i] = 0.0;
+            losses[i] = -delta;
+        }
+    }
+
+    let (avg_gain, avg_loss) = match method {
+        RsiMethod::Wilder => {
+            let alpha = 1.0 / n as f64;
+            let mut avg_gain = vec![f64::NAN; len];
+            let mut avg_loss = vec![f64::NAN; len];
+
+            if len > n {
+                let mut sum_gain = 0.0;
+                let mut sum_loss = 0.0;
+                for i in 1..=n {
+                    sum_gain += gains[i];
+                    sum_loss += losses[i];
+                }
+                avg_gain[n] = sum_gain / n as f64;
+                avg_loss[n] = sum_loss / n as f64;
+
+                for i in (n + 1)..len {
+                    avg_gain[i] = alpha * gains[i] + (1.0 - alpha) * avg_gain[i - 1];
+                    avg_loss[i] = alpha * losses[i] + (1.0 - alpha) * avg_loss[i - 1];
+                }
+            }
+
+            (avg_gain, avg_loss)
+        }
+        // gains[0] is a placeholder, so the window ending at n covers changes 1..=n
+        RsiMethod::Cutler => (sma_kernel(&gains, n), sma_kernel(&losses, n)),
+    };
+
+    for i in n..len {
+        if avg_gain[i].is_nan() {
+            continue;
+        }
+        if avg_loss[i] == 0.0 {
+            rsi_values[i] = 100.0;
+        } else {
+            let rs = avg_gain[i] / avg_loss[i];
+            rsi_values[i] = 100.0 - (100.0 / (1.0 + rs));
+        }
+    }
+
+    rsi_values
+}
+
 /// Wilder's smoothing - matches ta-lib style: SMA seed from first n non-NaN values
 pub fn wilders_ema_kernel(data: &[f64], window: usize) -> Vec<f64> {
     let n = data.len();
@@ -212,9 +551,131 @@ pub fn rolling_max(data: &[f64], window: usize) -> Vec<f64> {
     result
 }
 
-/// Rolling mean for arbitrary window calculations
-pub fn rolling_mean(data: &[f64], window: usize) -> Vec<f64> {
-    sma_kernel(data, window)
+/// Rolling median over window (mean of the two middle values for even windows)
+pub fn rolling_median(data: &[f64], window: usize) -> Vec<f64> {
+    let n = data.len();
+    let mut result = vec![f64::NAN; n];
+
+    if window > n || window == 0 {
+        return result;
+    }
+
+    let mut sorted = Vec::with_capacity(window);
+    for i in (window - 1)..n {
+        sorted.clear();
+        sorted.extend_from_slice(&data[(i + 1 - window)..=i]);
+        sorted.sort_by(|a, b| a.total_cmp(b));
+        let mid = window / 2;
+        result[i] = if window % 2 == 1 {
+            sorted[mid]
+        } else {
+            (sorted[mid - 1] + sorted[mid]) / 2.0
+        };
+    }
+
+    result
+}
+
+/// Number of values dropped from each end of a `window`-sized sample when trimming
+/// the `trim` fraction, which must be in [0, 0.5)
+pub fn trim_count(window: usize, trim: f64) -> Result<usize, String> {
+    if !(0.0..0.5).contains(&trim) {
+        return Err(format!("trim must be in [0, 0.5), got {}", trim));
+    }
+    Ok((window as f64 * trim).floor() as usize)
+}
+
+/// Mean of an ascending-sorted sample with `k` values dropped from each end
+pub fn trimmed_mean_of_sorted(sorted: &[f64], k: usize) -> f64 {
+    let kept = &sorted[k..sorted.len() - k];
+    kept.iter().sum::<f64>() / kept.len() as f64
+}
+
+/// `q`-quantile of an ascending-sorted, non-empty sample with linear interpolation
+/// between order statistics (numpy's default)
+pub fn quantile_of_sorted(sorted: &[f64], q: f64) -> f64 {
+    let pos = q * (sorted.len() - 1) as f64;
+    let lo = pos.floor() as usize;
+    let hi = pos.ceil() as usize;
+    sorted[lo] + (sorted[hi] - sorted[lo]) * (pos - lo as f64)
+}
+
+/// Multiplier for return-style outputs: 100 for percentages, 1 for raw fractions
+pub fn return_scale(pct: bool) -> f64 {
+    if pct { 100.0 } else { 1.0 }
+}
+
+/// Linearly annualized return over a window of `bars` prices from `start` to `end`
+pub fn annualized_return(start: f64, end: f64, bars: usize, periods_per_year: f64) -> f64 {
+    (end / start - 1.0) * (periods_per_year / bars as f64)
+}
+
+/// Rate of change kernel: (x[i] - x[i-n]) / x[i-n], scaled by 100 when `pct` is set.
+/// NaN for the first n values.
+pub fn roc_kernel(data: &[f64], n: usize, pct: bool) -> Vec<f64> {
+    let len = data.len();
+    let mut roc_values = vec![f64::NAN; len];
+    let scale = return_scale(pct);
+
+    for i in n..len {
+        if data[i - n] != 0.0 {
+            roc_values[i] = (data[i] - data[i - n]) / data[i - n] * scale;
+        }
+    }
+
+    roc_values
+}
+
+/// Cumulative sums and sums of squares for O(1) queries over any window.
+///
+/// Window sums are differences of two cumulative totals, so their absolute error
+/// grows with the magnitude of the running total rather than the window. That is
+/// fine for price-scale data and a handful of thousands of bars, but single-window
+/// kernels such as `sma_kernel` keep their running sums; use this where many
+/// windows are queried over the same data. Variance is E[x²] - E[x]² clamped at
+/// zero, so it is subject to cancellation when the mean is large relative to the
+/// spread. NaN inputs propagate to every window that follows them.
+#[derive(Clone, Debug)]
+pub struct PrefixSum {
+    sums: Vec<f64>,
+    sq_sums: Vec<f64>,
+}
+
+impl PrefixSum {
+    pub fn new(data: &[f64]) -> Self {
+        let mut sums = Vec::with_capacity(data.len() + 1);
+        let mut sq_sums = Vec::with_capacity(data.len() + 1);
+        let (mut sum, mut sq_sum) = (0.0, 0.0);
+        sums.push(sum);
+        sq_sums.push(sq_sum);
+
+        for &x in data {
+            sum += x;
+            sq_sum += x * x;
+            sums.push(sum);
+            sq_sums.push(sq_sum);
+        }
+
+        Self { sums, sq_sums }
+    }
+
+    /// Sum of data[start..end]
+    pub fn sum(&self, start: usize, end: usize) -> f64 {
+        self.sums[end] - self.sums[start]
+    }
+
+    /// Mean of data[start..end]
+    pub fn mean(&self, start: usize, end: usize) -> f64 {
+        self.sum(start, end) / (end - start) as f64
+    }
+
+    /// Population variance of data[start..end]
+    pub fn variance(&self, start: usize, end: usize) -> f64 {
+        let count = (end - start) as f64;
+        let mean = self.mean(start, end);
+        let mean_sq = (self.sq_sums[end] - self.sq_sums[start]) / count;
+        (mean_sq - mean * mean).max(0.0)
+    }
 }
 
 /// Rolling sum over window (O(n) complexity using running sum)
@@ -333,6 +794,539 @@ pub fn sma_kernel_min0(data: &[f64], window: usize) -> Vec<f64> {
     result
 }
 
+/// Acceleration-factor progression for Parabolic SAR
+#[derive(Clone, Debug)]
+pub enum AfSchedule {
+    /// Start at `start`, add `inc` per new extreme point, capped at `max`
+    Linear { start: f64, inc: f64, max: f64 },
+    /// Step through the listed factors per new extreme point, holding the last
+    Steps(Vec<f64>),
+}
+
+impl AfSchedule {
+    /// Build from the usual start/inc/max triple, or an explicit ascending list
+    pub fn new(start: f64, inc: f64, max: f64, schedule: Option<Vec<f64>>) -> Result<Self, String> {
+        match schedule {
+            None => Ok(AfSchedule::Linear { start, inc, max }),
+            Some(steps) => {
+                if steps.is_empty() {
+                    return Err("af_schedule must not be empty".to_string());
+                }
+                if steps.windows(2).any(|w| w[1] < w[0]) {
+                    return Err("af_schedule must be ascending".to_string());
+                }
+                Ok(AfSchedule::Steps(steps))
+            }
+        }
+    }
+
+    /// Factor used right after a reversal
+    pub fn initial(&self) -> f64 {
+        match self {
+            AfSchedule::Linear { start, .. } => *start,
+            AfSchedule::Steps(steps) => steps[0],
+        }
+    }
+
+    /// Factor after the `advances`-th new extreme point, given the current factor
+    pub fn advance(&self, af: f64, advances: usize) -> f64 {
+        match self {
+            AfSchedule::Linear { inc, max, .. } => (af + inc).min(*max),
+            AfSchedule::Steps(steps) => steps[advances.min(steps.len() - 1)],
+        }
+    }
+}
+
+/// One bar of an ATR trailing stop. `direction` is 1.0 (long) or -1.0 (short) and
+/// `offset` is multiplier * ATR. A long stop only ratchets up and a short stop only
+/// down; a close through the previous stop flips direction and restarts the stop
+/// `offset` away on the other side. Returns the new (stop, direction).
+pub fn trailing_stop_step(prev_stop: f64, direction: f64, close: f64, offset: f64) -> (f64, f64) {
+    if direction > 0.0 {
+        if close < prev_stop {
+            (close + offset, -1.0)
+        } else {
+            (prev_stop.max(close - offset), 1.0)
+        }
+    } else if close > prev_stop {
+        (close - offset, 1.0)
+    } else {
+        (prev_stop.min(close + offset), -1.0)
+    }
+}
+
+/// Streaming rolling max or min over the last `window` values in amortized O(1),
+/// via a monotonic deque of (index, value). NaN until `window` values are seen.
+#[derive(Clone, Debug)]
+pub struct RollingExtreme {
+    window: usize,
+    is_max: bool,
+    count: usize,
+    deque: VecDeque<(usize, f64)>,
+}
+
+impl RollingExtreme {
+    pub fn max(window: usize) -> Self {
+        Self { window, is_max: true, count: 0, deque: VecDeque::with_capacity(window) }
+    }
+
+    pub fn min(window: usize) -> Self {
+        Self { window, is_max: false, count: 0, deque: VecDeque::with_capacity(window) }
+    }
+
+    pub fn update(&mut self, value: f64) -> f64 {
+        // Drop values the new one dominates; they can never be the extreme again
+        while let Some(&(_, back)) = self.deque.back() {
+            let dominated = if self.is_max { back <= value } else { back >= value };
+            if !dominated {
+                break;
+            }
+            self.deque.pop_back();
+        }
+        self.deque.push_back((self.count, value));
+        self.count += 1;
+
+        while let Some(&(idx, _)) = self.deque.front() {
+            if idx + self.window >= self.count {
+                break;
+            }
+            self.deque.pop_front();
+        }
+
+        if self.window == 0 || self.count < self.window {
+            f64::NAN
+        } else {
+            self.deque.front().map_or(f64::NAN, |&(_, v)| v)
+        }
+    }
+
+    /// Extreme of the values seen so far in the window; unlike `update`, this is
+    /// defined before the window fills
+    pub fn extreme(&self) -> f64 {
+        self.deque.front().map_or(f64::NAN, |&(_, v)| v)
+    }
+
+    /// Bars since the extreme of the values seen so far in the window, 0 when
+    /// the latest value is (or ties) it. Unlike `update`, this is defined before
+    /// the window fills.
+    pub fn bars_since(&self) -> usize {
+        self.deque.front().map_or(0, |&(idx, _)| self.count - 1 - idx)
+    }
+
+    pub fn reset(&mut self) {
+        self.count = 0;
+        self.deque.clear();
+    }
+}
+
+/// Streaming window of the last `window` values kept alongside a sorted copy of
+/// its non-NaN values, for rank and order-statistic queries. Each push is a
+/// binary search plus a shift of the sorted Vec, rather than a rescan of the window.
+#[derive(Clone, Debug)]
+pub struct SortedWindow {
+    window: usize,
+    values: VecDeque<f64>,
+    sorted: Vec<f64>,
+    nan_count: usize,
+}
+
+impl SortedWindow {
+    pub fn new(window: usize) -> Self {
+        Self {
+            window,
+            values: VecDeque::with_capacity(window + 1),
+            sorted: Vec::with_capacity(window + 1),
+            nan_count: 0,
+        }
+    }
+
+    /// Append `value`, dropping the oldest once more than `window` are held
+    pub fn push(&mut self, value: f64) {
+        self.values.push_back(value);
+        if value.is_nan() {
+            self.nan_count += 1;
+        } else {
+            let pos = self.sorted.partition_point(|&x| x < value);
+            self.sorted.insert(pos, value);
+        }
+
+        if self.values.len() > self.window {
+            if let Some(old) = self.values.pop_front() {
+                if old.is_nan() {
+                    self.nan_count -= 1;
+                } else {
+                    let pos = self.sorted.partition_point(|&x| x < old);
+                    self.sorted.remove(pos);
+                }
+            }
+        }
+    }
+
+    /// True once `window` values are held
+    pub fn is_full(&self) -> bool {
+        self.values.len() >= self.window
+    }
+
+    /// Number of non-NaN values strictly below `value`
+    pub fn count_below(&self, value: f64) -> usize {
+        self.sorted.partition_point(|&x| x < value)
+    }
+
+    /// Number of non-NaN values at or below `value`; 0 when `value` is NaN
+    pub fn count_at_or_below(&self, value: f64) -> usize {
+        self.sorted.partition_point(|&x| x <= value)
+    }
+
+    /// Number of NaN values in the window
+    pub fn nan_count(&self) -> usize {
+        self.nan_count
+    }
+
+    /// The window's non-NaN values in ascending order
+    pub fn sorted(&self) -> &[f64] {
+        &self.sorted
+    }
+
+    pub fn clear(&mut self) {
+        self.values.clear();
+        self.sorted.clear();
+        self.nan_count = 0;
+    }
+}
+
+/// Sample count a ddof=1 rolling estimate needs before emitting: `window` when
+/// `min_periods` is None, otherwise `min_periods`, which must lie in 2..=window.
+pub fn resolve_min_periods(window: usize, min_periods: Option<usize>) -> Result<usize, String> {
+    match min_periods {
+        None => Ok(window),
+        Some(m) if (2..=window).contains(&m) => Ok(m),
+        Some(m) => Err(format!("min_periods must be between 2 and window ({}), got {}", window, m)),
+    }
+}
+
+/// Check that every named input series has the same length as the first one.
+/// Returns a message suitable for `PyValueError` on mismatch.
+pub fn check_equal_lengths(series: &[(&str, usize)]) -> Result<(), String> {
+    if let Some(&(first_name, first_len)) = series.first() {
+        for &(name, len) in &series[1..] {
+            if len != first_len {
+                return Err(format!(
+                    "{} has length {}, expected {} to match {}",
+                    name, len, first_len, first_name
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Build the price series an indicator runs on from OHLC inputs.
+///
+/// Supported sources: "hlc3" (typical price), "ohlc4" (requires `open`), "hl2", "close".
+/// Errors are plain messages so callers can surface them as `PyValueError`.
+pub fn price_source(
+    source: &str,
+    open: Option<&[f64]>,
+    high: &[f64],
+    low: &[f64],
+    close: &[f64],
+) -> Result<Vec<f64>, String> {
+    let n = close.len();
+    let mut result = vec![0.0; n];
+
+    match source {
+        "hlc3" => {
+            for i in 0..n {
+                result[i] = (high[i] + low[i] + close[i]) / 3.0;
+            }
+        }
+        "ohlc4" => {
+            let open = open.ok_or("price_source=\"ohlc4\" requires the open series")?;
+            if open.len() != n {
+                return Err("open must have the same length as close".to_string());
+            }
+            for i in 0..n {
+                result[i] = (open[i] + high[i] + low[i] + close[i]) / 4.0;
+            }
+        }
+        "hl2" => {
+            for i in 0..n {
+                result[i] = (high[i] + low[i]) / 2.0;
+            }
+        }
+        "close" => result.copy_from_slice(close),
+        _ => {
+            return Err(format!(
+                "Unknown price_source '{}': expected one of hlc3, ohlc4, hl2, close",
+                source
+            ))
+        }
+    }
+
+    Ok(result)
+}
+
+/// Series for a single-input indicator selected by `source`. "close" borrows
+/// `close` as-is; "hlc3", "hl2" and "ohlc4" are built by `price_source` and
+/// need `high` and `low` (plus `open` for "ohlc4") of the same length.
+pub fn select_source<'a>(
+    source: &str,
+    close: &'a [f64],
+    open: Option<&[f64]>,
+    high: Option<&[f64]>,
+    low: Option<&[f64]>,
+) -> Result<Cow<'a, [f64]>, String> {
+    if source == "close" {
+        return Ok(Cow::Borrowed(close));
+    }
+
+    let (high, low) = match (high, low) {
+        (Some(high), Some(low)) => (high, low),
+        _ => return Err(format!("price_source=\"{}\" requires the high and low series", source)),
+    };
+    check_equal_lengths(&[("close", close.len()), ("high", high.len()), ("low", low.len())])?;
+
+    price_source(source, open, high, low, close).map(Cow::Owned)
+}
+
+/// Oscillator histogram: line - signal, NaN wherever either input is NaN
+pub fn histogram_kernel(line: &[f64], signal: &[f64]) -> Vec<f64> {
+    let n = line.len();
+    let mut result = vec![f64::NAN; n];
+
+    for i in 0..n {
+        if !line[i].is_nan() && !signal[i].is_nan() {
+            result[i] = line[i] - signal[i];
+        }
+    }
+
+    result
+}
+
+/// Histogram definition for oscillators with a signal line. "diff" is the MACD
+/// histogram (line - signal); "pct" scales it by the signal,
+/// (line - signal) / |signal| * 100, and is NaN where the signal is zero.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum HistMode {
+    Diff,
+    Pct,
+}
+
+impl HistMode {
+    pub fn parse(mode: &str) -> Result<Self, String> {
+        match mode {
+            "diff" => Ok(HistMode::Diff),
+            "pct" => Ok(HistMode::Pct),
+            other => Err(format!("unknown hist_mode '{}', expected one of: diff, pct", other)),
+        }
+    }
+}
+
+/// Crossover kernel: 1.0 on bars where `a` crosses above `b` (a <= b on the previous
+/// bar, a > b now), -1.0 where it crosses below, 0.0 otherwise or when any of the
+/// four values is NaN
+pub fn crossover_kernel(a: &[f64], b: &[f64]) -> Vec<f64> {
+    let len = a.len();
+    let mut result = vec![0.0; len];
+
+    for i in 1..len {
+        if a[i - 1] <= b[i - 1] && a[i] > b[i] {
+            result[i] = 1.0;
+        } else if a[i - 1] >= b[i - 1] && a[i] < b[i] {
+            result[i] = -1.0;
+        }
+    }
+
+    result
+}
+
+/// Oscillator histogram under `mode`, NaN wherever either input is NaN
+pub fn histogram_mode_kernel(line: &[f64], signal: &[f64], mode: HistMode) -> Vec<f64> {
+    let mut result = histogram_kernel(line, signal);
+
+    if mode == HistMode::Pct {
+        for (h, &s) in result.iter_mut().zip(signal) {
+            *h = if s != 0.0 { *h / s.abs() * 100.0 } else { f64::NAN };
+        }
+    }
+
+    result
+}
+
+/// Rolling least-squares line fit of each window against x = 0..window-1.
+/// Returns (slope, intercept, sse) where `sse` is the residual sum of squares
+/// of the window around its fitted line. All NaN until `window` values exist
+/// and wherever the window holds a NaN.
+///
+/// Sums of y, y² and x·y are carried across windows, so each bar costs O(1);
+/// sums of x and x² depend only on `window`. When the window slides every
+/// remaining value moves one x position left, which takes the window's sum of
+/// y off the sum of x·y.
+pub fn rolling_linreg(data: &[f64], window: usize) -> (Vec<f64>, Vec<f64>, Vec<f64>) {
+    let n = data.len();
+    let mut slope = vec![f64::NAN; n];
+    let mut intercept = vec![f64::NAN; n];
+    let mut sse = vec![f64::NAN; n];
+
+    if window == 0 || window > n {
+        return (slope, intercept, sse);
+    }
+
+    let w = window as f64;
+    let sum_x = w * (w - 1.0) / 2.0;
+    let sum_x2 = w * (w - 1.0) * (2.0 * w - 1.0) / 6.0;
+    let denom = w * sum_x2 - sum_x * sum_x;
+
+    if denom == 0.0 {
+        return (slope, intercept, sse);
+    }
+
+    let mut sum_y = 0.0;
+    let mut sum_y2 = 0.0;
+    let mut sum_xy = 0.0;
+    let mut nan_count = 0usize;
+
+    for i in 0..n {
+        if i >= window {
+            let out = data[i - window];
+            if out.is_nan() {
+                nan_count -= 1;
+            } else {
+                sum_y -= out;
+                sum_y2 -= out * out;
+            }
+            // The leaving value sat at x = 0; the rest shift down by one
+            sum_xy -= sum_y;
+        }
+
+        let y = data[i];
+        if y.is_nan() {
+            nan_count += 1;
+        } else {
+            sum_y += y;
+            sum_y2 += y * y;
+            sum_xy += i.min(window - 1) as f64 * y;
+        }
+
+        if i + 1 < window || nan_count > 0 {
+            continue;
+        }
+
+        let b = (w * sum_xy - sum_x * sum_y) / denom;
+        let a = (sum_y - b * sum_x) / w;
+
+        // Centered sums of squares; clamped since rounding can leave a tiny
+        // negative residual on (near-)linear windows
+        let syy = sum_y2 - sum_y * sum_y / w;
+        let sxy = sum_xy - sum_x * sum_y / w;
+
+        slope[i] = b;
+        intercept[i] = a;
+        sse[i] = (syy - b * sxy).max(0.0);
+    }
+
+    (slope, intercept, sse)
+}
+
+/// Rolling bivariate least-squares fit of `y` on `x`. Returns (alpha, beta) with
+/// y ~ alpha + beta * x over each window; NaN until `window` pairs exist, where
+/// either window holds a NaN, or where `x` is constant over the window.
+///
+/// Sums of x, y, x·y and x² are updated as pairs enter and leave the window, so
+/// each bar costs O(1).
+pub fn rolling_ols(y: &[f64], x: &[f64], window: usize) -> (Vec<f64>, Vec<f64>) {
+    let n = y.len();
+    let mut alpha = vec![f64::NAN; n];
+    let mut beta = vec![f64::NAN; n];
+
+    if window == 0 || window > n {
+        return (alpha, beta);
+    }
+
+    let w = window as f64;
+    let mut sum_x = 0.0;
+    let mut sum_y = 0.0;
+    let mut sum_xy = 0.0;
+    let mut sum_x2 = 0.0;
+    let mut nan_count = 0usize;
+
+    for i in 0..n {
+        if i >= window {
+            let (yo, xo) = (y[i - window], x[i - window]);
+            if yo.is_nan() || xo.is_nan() {
+                nan_count -= 1;
+            } else {
+                sum_x -= xo;
+                sum_y -= yo;
+                sum_xy -= xo * yo;
+                sum_x2 -= xo * xo;
+            }
+        }
+
+        let (yi, xi) = (y[i], x[i]);
+        if yi.is_nan() || xi.is_nan() {
+            nan_count += 1;
+        } else {
+            sum_x += xi;
+            sum_y += yi;
+            sum_xy += xi * yi;
+            sum_x2 += xi * xi;
+        }
+
+        if i + 1 < window || nan_count > 0 {
+            continue;
+        }
+
+        let sxy = sum_xy - sum_x * sum_y / w;
+        let sxx = sum_x2 - sum_x * sum_x / w;
+
+        // Raw sums leave rounding residue where x is constant, so a spread
+        // within a few ulps of sum_x2 counts as no spread
+        if sxx > sum_x2 * f64::EPSILON * w {
+            let b = sxy / sxx;
+            beta[i] = b;
+            alpha[i] = (sum_y - b * sum_x) / w;
+        }
+    }
+
+    (alpha, beta)
+}
+
+// ============================================================================
+// Preprocessing
+// ============================================================================
+
+/// Round each value to the nearest multiple of `tick_size`; NaN stays NaN
+pub fn round_to_tick_kernel(data: &[f64], tick_size: f64) -> Result<Vec<f64>, String> {
+    if tick_size.is_nan() || tick_size <= 0.0 || tick_size.is_infinite() {
+        return Err(format!("tick_size must be a positive number, got {}", tick_size));
+    }
+
+    Ok(data.iter().map(|&x| (x / tick_size).round() * tick_size).collect())
+}
+
+/// Hold the last accepted value until the input moves strictly more than
+/// `min_move` away from it. The first valid value is accepted as-is; NaN inputs
+/// hold the prior value (leading NaN stay NaN).
+pub fn min_move_filter_kernel(data: &[f64], min_move: f64) -> Result<Vec<f64>, String> {
+    if min_move.is_nan() || min_move < 0.0 {
+        return Err(format!("min_move must be non-negative, got {}", min_move));
+    }
+
+    let mut held = f64::NAN;
+    let result = data
+        .iter()
+        .map(|&x| {
+            if !x.is_nan() && (held.is_nan() || (x - held).abs() > min_move) {
+                held = x;
+            }
+            held
+        })
+        .collect();
+
+    Ok(result)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -382,4 +1376,242 @@ mod tests {
         // std([1,2,3]) = sqrt(((1-2)^2 + (2-2)^2 + (3-2)^2)/3) = sqrt(2/3) ≈ 0.8165
         assert!((result[2] - 0.816496580927726).abs() < 1e-10);
     }
+
+    #[test]
+    fn test_prefix_sum_windows_match_running_sums() {
+        let data: Vec<f64> = (0..500)
+            .map(|i| 100.0 + (i as f64 * 0.37).sin() * 4.0 + i as f64 * 0.05)
+            .collect();
+        let prefix = PrefixSum::new(&data);
+
+        for window in [1, 5, 20, 200] {
+            let means = sma_kernel(&data, window);
+            let stds = rolling_std(&data, window);
+            for i in (window - 1)..data.len() {
+                let start = i + 1 - window;
+                assert!((prefix.mean(start, i + 1) - means[i]).abs() < 1e-9);
+                assert!((prefix.variance(start, i + 1) - stds[i] * stds[i]).abs() < 1e-6);
+            }
+        }
+    }
+
+    #[test]
+    fn test_rsi_ta_alignment() {
+        // n = 2, alpha = 0.5: gains [0, 1, 0, 1], losses [0, 0, 0.5, 0]
+        // ewm gain [0, 0.5, 0.25, 0.625], ewm loss [0, 0, 0.25, 0.125]
+        let close = [1.0, 2.0, 1.5, 2.5];
+        let ta = rsi_ta_kernel(&close, 2);
+        assert!(ta[0].is_nan());
+        assert_eq!(ta[1], 100.0);
+        assert!((ta[2] - 50.0).abs() < 1e-12);
+        assert!((ta[3] - (100.0 - 100.0 / 6.0)).abs() < 1e-12);
+
+        // Native Wilder RSI starts one bar later
+        let native = rsi_kernel(&close, 2, RsiMethod::Wilder);
+        assert!(native[1].is_nan() && !native[2].is_nan());
+
+        assert!(Compat::parse("pandas").is_err());
+    }
+
+    #[test]
+    fn test_bar_frequency_periods_per_year() {
+        assert_eq!(BarFrequency::parse("daily").unwrap().periods_per_year(), 252.0);
+        assert_eq!(BarFrequency::parse("hourly").unwrap().periods_per_year(), 1638.0);
+        assert_eq!(BarFrequency::parse("minute").unwrap().periods_per_year(), 98280.0);
+        assert_eq!(BarFrequency::parse("crypto_daily").unwrap().periods_per_year(), 365.0);
+        assert!(BarFrequency::parse("weekly").is_err());
+
+        assert_eq!(resolve_periods_per_year("crypto_daily", None).unwrap(), 365.0);
+        assert_eq!(resolve_periods_per_year("daily", Some(260.0)).unwrap(), 260.0);
+        assert!(resolve_periods_per_year("weekly", Some(52.0)).is_err());
+        assert!(resolve_periods_per_year("daily", Some(0.0)).is_err());
+        assert!(resolve_periods_per_year("daily", Some(f64::NAN)).is_err());
+    }
+
+    #[test]
+    fn test_rolling_linreg() {
+        let data = vec![1.0, 3.0, 5.0, 7.0, 9.0];
+        let (slope, intercept, sse) = rolling_linreg(&data, 3);
+
+        assert!(slope[1].is_nan());
+        assert!((slope[4] - 2.0).abs() < 1e-10);
+        assert!((intercept[4] - 5.0).abs() < 1e-10);  // window [5,7,9] starts at x=0
+        assert!(sse[4].abs() < 1e-20);
+    }
+
+    /// Direct per-window least squares, the reference for the running-sum versions
+    fn naive_ols(ys: &[f64], xs: &[f64]) -> Option<(f64, f64, f64)> {
+        if ys.iter().chain(xs).any(|v| v.is_nan()) {
+            return None;
+        }
+        let w = ys.len() as f64;
+        let mean_y = ys.iter().sum::<f64>() / w;
+        let mean_x = xs.iter().sum::<f64>() / w;
+        let sxy: f64 = ys.iter().zip(xs).map(|(&y, &x)| (x - mean_x) * (y - mean_y)).sum();
+        let sxx: f64 = xs.iter().map(|&x| (x - mean_x) * (x - mean_x)).sum();
+        if sxx == 0.0 {
+            return None;
+        }
+        let b = sxy / sxx;
+        let a = mean_y - b * mean_x;
+        let sse = ys.iter().zip(xs).map(|(&y, &x)| (y - a - b * x).powi(2)).sum();
+        Some((a, b, sse))
+    }
+
+    #[test]
+    fn test_rolling_regressions_match_per_window_fit() {
+        let y: Vec<f64> = (0..200)
+            .map(|i| if i == 57 { f64::NAN } else { 100.0 + (i as f64 * 0.37).sin() * 5.0 + i as f64 * 0.1 })
+            .collect();
+        let x: Vec<f64> = (0..200)
+            .map(|i| if (120..130).contains(&i) { 2.5 } else { (i as f64 * 0.91).cos() })
+            .collect();
+        let window = 8;
+
+        let (slope, intercept, sse) = rolling_linreg(&y, window);
+        let (alpha, beta) = rolling_ols(&y, &x, window);
+        let positions: Vec<f64> = (0..window).map(|j| j as f64).collect();
+
+        for i in 0..y.len() {
+            if i + 1 < window {
+                assert!(slope[i].is_nan() && beta[i].is_nan());
+                continue;
+            }
+            let start = i + 1 - window;
+
+            match naive_ols(&y[start..=i], &positions) {
+                Some((a, b, e)) => {
+                    assert!((slope[i] - b).abs() < 1e-9, "slope at {}", i);
+                    assert!((intercept[i] - a).abs() < 1e-9, "intercept at {}", i);
+                    assert!((sse[i] - e).abs() < 1e-7, "sse at {}", i);
+                }
+                None => assert!(slope[i].is_nan() && sse[i].is_nan(), "linreg at {}", i),
+            }
+
+            match naive_ols(&y[start..=i], &x[start..=i]) {
+                Some((a, b, _)) => {
+                    assert!((beta[i] - b).abs() < 1e-8, "beta at {}", i);
+                    assert!((alpha[i] - a).abs() < 1e-8, "alpha at {}", i);
+                }
+                None => assert!(alpha[i].is_nan() && beta[i].is_nan(), "ols at {}", i),
+            }
+        }
+    }
+
+    #[test]
+    fn test_price_source() {
+        let open = vec![9.0, 10.0];
+        let high = vec![12.0, 14.0];
+        let low = vec![8.0, 10.0];
+        let close = vec![10.0, 13.0];
+
+        let hl2 = price_source("hl2", None, &high, &low, &close).unwrap();
+        assert_eq!(hl2, vec![10.0, 12.0]);
+
+        let raw = price_source("close", None, &high, &low, &close).unwrap();
+        assert_eq!(raw, close);
+
+        let hlc3 = price_source("hlc3", None, &high, &low, &close).unwrap();
+        assert!((hlc3[1] - 37.0 / 3.0).abs() < 1e-12);
+
+        let ohlc4 = price_source("ohlc4", Some(&open), &high, &low, &close).unwrap();
+        assert!((ohlc4[0] - 9.75).abs() < 1e-12);
+
+        assert!(price_source("ohlc4", None, &high, &low, &close).is_err());
+        assert!(price_source("median", None, &high, &low, &close).is_err());
+    }
+
+    #[test]
+    fn test_select_source() {
+        let high = [11.0, 12.0, 13.0];
+        let low = [9.0, 9.0, 10.0];
+        let close = [10.0, 11.5, 12.0];
+
+        assert!(matches!(select_source("close", &close, None, None, None), Ok(Cow::Borrowed(_))));
+
+        let hlc3 = select_source("hlc3", &close, None, Some(&high), Some(&low)).unwrap();
+        let expected: Vec<f64> = (0..3).map(|i| (high[i] + low[i] + close[i]) / 3.0).collect();
+        assert_eq!(&*hlc3, expected.as_slice());
+
+        assert!(select_source("hl2", &close, None, Some(&high), None).is_err());
+        assert!(select_source("hl2", &close, None, Some(&high), Some(&low[..2])).is_err());
+    }
+
+    #[test]
+    fn test_rolling_extreme_matches_bulk() {
+        let data: Vec<f64> = (0..50).map(|i| (i as f64 * 0.7).sin() * 10.0 + (i % 7) as f64).collect();
+        let bulk_max = rolling_max(&data, 5);
+        let bulk_min = rolling_min(&data, 5);
+
+        let mut max = RollingExtreme::max(5);
+        let mut min = RollingExtreme::min(5);
+        for i in 0..data.len() {
+            let (hi, lo) = (max.update(data[i]), min.update(data[i]));
+            if i < 4 {
+                assert!(hi.is_nan() && lo.is_nan());
+            } else {
+                assert_eq!(hi, bulk_max[i]);
+                assert_eq!(lo, bulk_min[i]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_sorted_window_tracks_last_values() {
+        let data = [3.0, 1.0, f64::NAN, 2.0, 2.0, 5.0, 1.0, 4.0];
+        let mut values = SortedWindow::new(3);
+        for i in 0..data.len() {
+            values.push(data[i]);
+            let window = &data[i.saturating_sub(2)..=i];
+            assert_eq!(values.is_full(), i >= 2);
+
+            let mut expected: Vec<f64> = window.iter().copied().filter(|x| !x.is_nan()).collect();
+            expected.sort_by(|a, b| a.total_cmp(b));
+            assert_eq!(values.sorted(), &expected[..]);
+            assert_eq!(values.nan_count(), window.len() - expected.len());
+
+            let below = expected.iter().filter(|&&x| x < data[i]).count();
+            let at_or_below = expected.iter().filter(|&&x| x <= data[i]).count();
+            assert_eq!(values.count_below(data[i]), below);
+            assert_eq!(values.count_at_or_below(data[i]), at_or_below);
+        }
+
+        values.clear();
+        assert!(values.sorted().is_empty() && values.nan_count() == 0 && !values.is_full());
+    }
+
+    #[test]
+    fn test_round_to_tick() {
+        let rounded = round_to_tick_kernel(&[100.1, 100.13, 100.37, 99.88, f64::NAN], 0.25).unwrap();
+        assert_eq!(&rounded[..4], &[100.0, 100.25, 100.25, 100.0]);
+        assert!(rounded[4].is_nan());
+        assert!(round_to_tick_kernel(&[1.0], 0.0).is_err());
+    }
+
+    #[test]
+    fn test_min_move_filter() {
+        let data = [f64::NAN, 100.0, 100.3, 99.6, 100.6, f64::NAN, 100.9, 101.2];
+        let filtered = min_move_filter_kernel(&data, 0.5).unwrap();
+        assert!(filtered[0].is_nan());
+        assert_eq!(&filtered[1..], &[100.0, 100.0, 100.0, 100.6, 100.6, 100.6, 101.2]);
+        assert!(min_move_filter_kernel(&data, -1.0).is_err());
+    }
+
+    #[test]
+    fn test_rolling_median_odd_and_even() {
+        let data = [5.0, 1.0, 4.0, 2.0, 3.0];
+        let odd = rolling_median(&data, 3);
+        assert!(odd[1].is_nan());
+        assert_eq!(odd[2..], [4.0, 2.0, 3.0]);
+
+        let even = rolling_median(&data, 4);
+        assert_eq!(even[3..], [3.0, 2.5]);
+    }
+
+    #[test]
+    fn test_crossover_kernel() {
+        let a = [1.0, 2.0, 3.0, 2.0, f64::NAN, 3.0, 1.0];
+        let b = [2.0, 2.0, 2.0, 2.5, 2.0, 2.0, 2.0];
+        assert_eq!(crossover_kernel(&a, &b), vec![0.0, 0.0, 1.0, -1.0, 0.0, 0.0, -1.0]);
+    }
 }
@@ -1,3 +1,6 @@
+// Index-based loops and wide PyO3 signatures mirror the reference Numba kernels
+#![allow(clippy::needless_range_loop, clippy::too_many_arguments, clippy::type_complexity)]
+
 use pyo3::prelude::*;
 
 mod helpers;
@@ -14,22 +17,36 @@ fn _ta_numba_rs(m: &Bound<'_, PyModule>) -> PyResult<()> {
     // Trend indicators (bulk)
     m.add_function(wrap_pyfunction!(trend::sma, m)?)?;
     m.add_function(wrap_pyfunction!(trend::ema, m)?)?;
+    m.add_function(wrap_pyfunction!(trend::sma_multi, m)?)?;
+    m.add_function(wrap_pyfunction!(trend::ema_multi, m)?)?;
+    m.add_function(wrap_pyfunction!(trend::std_multi, m)?)?;
+    m.add_function(wrap_pyfunction!(trend::gmma_compression, m)?)?;
     m.add_function(wrap_pyfunction!(trend::wma, m)?)?;
     m.add_function(wrap_pyfunction!(trend::macd, m)?)?;
+    m.add_function(wrap_pyfunction!(trend::ma_distance, m)?)?;
+    m.add_function(wrap_pyfunction!(trend::price_oscillator, m)?)?;
+    m.add_function(wrap_pyfunction!(trend::ma_cross_signal, m)?)?;
+    m.add_function(wrap_pyfunction!(trend::disparity_index, m)?)?;
+    m.add_function(wrap_pyfunction!(trend::signal_line, m)?)?;
     m.add_function(wrap_pyfunction!(trend::adx, m)?)?;
+    m.add_function(wrap_pyfunction!(trend::adxr, m)?)?;
     m.add_function(wrap_pyfunction!(trend::cci, m)?)?;
     m.add_function(wrap_pyfunction!(trend::dpo, m)?)?;
     m.add_function(wrap_pyfunction!(trend::vortex_indicator, m)?)?;
     m.add_function(wrap_pyfunction!(trend::parabolic_sar, m)?)?;
     m.add_function(wrap_pyfunction!(trend::trix, m)?)?;
+    m.add_function(wrap_pyfunction!(trend::trix_of, m)?)?;
     m.add_function(wrap_pyfunction!(trend::mass_index, m)?)?;
     m.add_function(wrap_pyfunction!(trend::kst, m)?)?;
+    m.add_function(wrap_pyfunction!(trend::weighted_roc, m)?)?;
     m.add_function(wrap_pyfunction!(trend::ichimoku, m)?)?;
     m.add_function(wrap_pyfunction!(trend::schaff_trend_cycle, m)?)?;
     m.add_function(wrap_pyfunction!(trend::aroon, m)?)?;
+    m.add_function(wrap_pyfunction!(trend::vertical_horizontal_filter, m)?)?;
 
     // Momentum indicators (bulk)
     m.add_function(wrap_pyfunction!(momentum::rsi, m)?)?;
+    m.add_function(wrap_pyfunction!(momentum::rsi_of, m)?)?;
     m.add_function(wrap_pyfunction!(momentum::stochastic, m)?)?;
     m.add_function(wrap_pyfunction!(momentum::williams_r, m)?)?;
     m.add_function(wrap_pyfunction!(momentum::ppo, m)?)?;
@@ -38,27 +55,57 @@ fn _ta_numba_rs(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(momentum::tsi, m)?)?;
     m.add_function(wrap_pyfunction!(momentum::awesome_oscillator, m)?)?;
     m.add_function(wrap_pyfunction!(momentum::kama, m)?)?;
+    m.add_function(wrap_pyfunction!(momentum::efficiency_ratio, m)?)?;
+    m.add_function(wrap_pyfunction!(momentum::snr, m)?)?;
     m.add_function(wrap_pyfunction!(momentum::roc, m)?)?;
     m.add_function(wrap_pyfunction!(momentum::pvo, m)?)?;
     m.add_function(wrap_pyfunction!(momentum::momentum, m)?)?;
+    m.add_function(wrap_pyfunction!(momentum::rsi_divergence, m)?)?;
+    m.add_function(wrap_pyfunction!(momentum::connors_rsi, m)?)?;
+    m.add_function(wrap_pyfunction!(momentum::up_down_streak, m)?)?;
+    m.add_function(wrap_pyfunction!(momentum::smoothed_rsi, m)?)?;
+    m.add_function(wrap_pyfunction!(momentum::detrended_rsi, m)?)?;
+    m.add_function(wrap_pyfunction!(momentum::cmo, m)?)?;
+    m.add_function(wrap_pyfunction!(momentum::frama, m)?)?;
 
     // Volatility indicators (bulk)
     m.add_function(wrap_pyfunction!(volatility::atr, m)?)?;
+    m.add_function(wrap_pyfunction!(volatility::atr_bands, m)?)?;
     m.add_function(wrap_pyfunction!(volatility::bollinger_bands, m)?)?;
+    m.add_function(wrap_pyfunction!(volatility::standard_deviation, m)?)?;
     m.add_function(wrap_pyfunction!(volatility::keltner_channel, m)?)?;
     m.add_function(wrap_pyfunction!(volatility::donchian_channel, m)?)?;
     m.add_function(wrap_pyfunction!(volatility::ulcer_index, m)?)?;
+    m.add_function(wrap_pyfunction!(volatility::standard_error_bands, m)?)?;
+    m.add_function(wrap_pyfunction!(volatility::regression_channel, m)?)?;
+    m.add_function(wrap_pyfunction!(volatility::channel_position, m)?)?;
+    m.add_function(wrap_pyfunction!(volatility::atr_trailing_stop, m)?)?;
+    m.add_function(wrap_pyfunction!(volatility::volatility_scaled, m)?)?;
+    m.add_function(wrap_pyfunction!(volatility::bollinger_squeeze, m)?)?;
+    m.add_function(wrap_pyfunction!(volatility::chaikin_volatility, m)?)?;
+    m.add_function(wrap_pyfunction!(volatility::pivot_distance, m)?)?;
+    m.add_function(wrap_pyfunction!(volatility::parkinson_volatility, m)?)?;
+    m.add_function(wrap_pyfunction!(volatility::garman_klass_volatility, m)?)?;
+    m.add_function(wrap_pyfunction!(volatility::ma_envelope, m)?)?;
+    m.add_function(wrap_pyfunction!(volatility::atr_percentile, m)?)?;
+    m.add_function(wrap_pyfunction!(volatility::atr_breakout, m)?)?;
 
     // Volume indicators (bulk)
     m.add_function(wrap_pyfunction!(volume::mfi, m)?)?;
     m.add_function(wrap_pyfunction!(volume::acc_dist_index, m)?)?;
+    m.add_function(wrap_pyfunction!(volume::close_location_value, m)?)?;
     m.add_function(wrap_pyfunction!(volume::obv, m)?)?;
+    m.add_function(wrap_pyfunction!(volume::obv_divergence, m)?)?;
+    m.add_function(wrap_pyfunction!(volume::up_down_volume_ratio, m)?)?;
+    m.add_function(wrap_pyfunction!(volume::volume_zscore, m)?)?;
+    m.add_function(wrap_pyfunction!(volume::volume_spike, m)?)?;
     m.add_function(wrap_pyfunction!(volume::chaikin_money_flow, m)?)?;
     m.add_function(wrap_pyfunction!(volume::force_index, m)?)?;
     m.add_function(wrap_pyfunction!(volume::eom, m)?)?;
     m.add_function(wrap_pyfunction!(volume::vpt, m)?)?;
     m.add_function(wrap_pyfunction!(volume::nvi, m)?)?;
     m.add_function(wrap_pyfunction!(volume::vwap, m)?)?;
+    m.add_function(wrap_pyfunction!(volume::vwap_bands, m)?)?;
     m.add_function(wrap_pyfunction!(volume::vwema, m)?)?;
     m.add_function(wrap_pyfunction!(volume::volume_ratio, m)?)?;
 
@@ -69,13 +116,50 @@ fn _ta_numba_rs(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(others::compound_log_return, m)?)?;
     m.add_function(wrap_pyfunction!(others::rolling_zscore, m)?)?;
     m.add_function(wrap_pyfunction!(others::linear_regression_slope, m)?)?;
+    m.add_function(wrap_pyfunction!(others::regression_forecast, m)?)?;
     m.add_function(wrap_pyfunction!(others::rolling_percentile, m)?)?;
+    m.add_function(wrap_pyfunction!(others::rolling_sum_py, m)?)?;
+    m.add_function(wrap_pyfunction!(others::rolling_rms, m)?)?;
+    m.add_function(wrap_pyfunction!(others::pct_above_ma, m)?)?;
+    m.add_function(wrap_pyfunction!(others::rolling_pct_above_ma, m)?)?;
+    m.add_function(wrap_pyfunction!(others::fill_warmup, m)?)?;
+    m.add_function(wrap_pyfunction!(others::drawdown_duration, m)?)?;
+    m.add_function(wrap_pyfunction!(others::max_drawdown, m)?)?;
+    m.add_function(wrap_pyfunction!(others::rolling_entropy, m)?)?;
+    m.add_function(wrap_pyfunction!(others::rolling_var, m)?)?;
+    m.add_function(wrap_pyfunction!(others::rolling_cvar, m)?)?;
+    m.add_function(wrap_pyfunction!(others::rolling_count_valid, m)?)?;
+    m.add_function(wrap_pyfunction!(others::up_bar_ratio, m)?)?;
+    m.add_function(wrap_pyfunction!(others::win_rate, m)?)?;
+    m.add_function(wrap_pyfunction!(others::profit_factor, m)?)?;
+    m.add_function(wrap_pyfunction!(others::rolling_ols_residual, m)?)?;
+    m.add_function(wrap_pyfunction!(others::spread_zscore, m)?)?;
+    m.add_function(wrap_pyfunction!(others::pct_from_high, m)?)?;
+    m.add_function(wrap_pyfunction!(others::pct_from_low, m)?)?;
+    m.add_function(wrap_pyfunction!(others::new_high_low_oscillator, m)?)?;
+    m.add_function(wrap_pyfunction!(others::round_to_tick, m)?)?;
+    m.add_function(wrap_pyfunction!(others::min_move_filter, m)?)?;
+    m.add_function(wrap_pyfunction!(others::max_up_streak, m)?)?;
+    m.add_function(wrap_pyfunction!(others::max_down_streak, m)?)?;
+    m.add_function(wrap_pyfunction!(others::rolling_reduce, m)?)?;
+    m.add_function(wrap_pyfunction!(others::gap, m)?)?;
+    m.add_function(wrap_pyfunction!(others::gap_fill, m)?)?;
+    m.add_function(wrap_pyfunction!(others::downside_beta, m)?)?;
+    m.add_function(wrap_pyfunction!(others::rolling_spearman, m)?)?;
+    m.add_function(wrap_pyfunction!(others::calmar_ratio, m)?)?;
+    m.add_function(wrap_pyfunction!(others::martin_ratio, m)?)?;
+    m.add_function(wrap_pyfunction!(others::rolling_trimmed_mean, m)?)?;
+    m.add_function(wrap_pyfunction!(others::standardize, m)?)?;
+    m.add_function(wrap_pyfunction!(others::rolling_information_ratio, m)?)?;
 
     // Streaming classes - Trend (11)
     m.add_class::<streaming::SMAStreaming>()?;
     m.add_class::<streaming::EMAStreaming>()?;
     m.add_class::<streaming::WMAStreaming>()?;
     m.add_class::<streaming::MACDStreaming>()?;
+    m.add_class::<streaming::MADistanceStreaming>()?;
+    m.add_class::<streaming::PriceOscillatorStreaming>()?;
+    m.add_class::<streaming::DisparityIndexStreaming>()?;
     m.add_class::<streaming::ADXStreaming>()?;
     m.add_class::<streaming::CCIStreaming>()?;
     m.add_class::<streaming::DPOStreaming>()?;
@@ -86,6 +170,10 @@ fn _ta_numba_rs(m: &Bound<'_, PyModule>) -> PyResult<()> {
 
     // Streaming classes - Momentum (12)
     m.add_class::<streaming::RSIStreaming>()?;
+    m.add_class::<streaming::ConnorsRSIStreaming>()?;
+    m.add_class::<streaming::UpDownStreakStreaming>()?;
+    m.add_class::<streaming::SmoothedRSIStreaming>()?;
+    m.add_class::<streaming::DetrendedRSIStreaming>()?;
     m.add_class::<streaming::StochasticStreaming>()?;
     m.add_class::<streaming::WilliamsRStreaming>()?;
     m.add_class::<streaming::ROCStreaming>()?;
@@ -96,18 +184,24 @@ fn _ta_numba_rs(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<streaming::TSIStreaming>()?;
     m.add_class::<streaming::AwesomeOscillatorStreaming>()?;
     m.add_class::<streaming::KAMAStreaming>()?;
+    m.add_class::<streaming::EfficiencyRatioStreaming>()?;
     m.add_class::<streaming::MomentumStreaming>()?;
+    m.add_class::<streaming::CMOStreaming>()?;
 
     // Streaming classes - Volatility (9)
     m.add_class::<streaming::ATRStreaming>()?;
+    m.add_class::<streaming::ATRTrailingStopStreaming>()?;
     m.add_class::<streaming::BollingerBandsStreaming>()?;
     m.add_class::<streaming::KeltnerChannelStreaming>()?;
+    m.add_class::<streaming::MAEnvelopeStreaming>()?;
+    m.add_class::<streaming::SqueezeStreaming>()?;
     m.add_class::<streaming::DonchianChannelStreaming>()?;
     m.add_class::<streaming::UlcerIndexStreaming>()?;
     m.add_class::<streaming::StandardDeviationStreaming>()?;
     m.add_class::<streaming::VarianceStreaming>()?;
     m.add_class::<streaming::RangeStreaming>()?;
     m.add_class::<streaming::HistoricalVolatilityStreaming>()?;
+    m.add_class::<streaming::ChaikinVolatilityStreaming>()?;
 
     // Streaming classes - Volume (10)
     m.add_class::<streaming::MFIStreaming>()?;
@@ -119,8 +213,10 @@ fn _ta_numba_rs(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<streaming::VPTStreaming>()?;
     m.add_class::<streaming::NVIStreaming>()?;
     m.add_class::<streaming::VWAPStreaming>()?;
+    m.add_class::<streaming::VWAPBandsStreaming>()?;
     m.add_class::<streaming::VWEMAStreaming>()?;
     m.add_class::<streaming::VolumeRatioStreaming>()?;
+    m.add_class::<streaming::VolumeZScoreStreaming>()?;
 
     // Streaming classes - Others (11)
     m.add_class::<streaming::DailyReturnStreaming>()?;
@@ -129,11 +225,22 @@ fn _ta_numba_rs(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<streaming::CompoundLogReturnStreaming>()?;
     m.add_class::<streaming::RollingReturnStreaming>()?;
     m.add_class::<streaming::MaxDrawdownStreaming>()?;
+    m.add_class::<streaming::DrawdownStreaming>()?;
     m.add_class::<streaming::SharpeRatioStreaming>()?;
     m.add_class::<streaming::CalmarRatioStreaming>()?;
     m.add_class::<streaming::RollingZScoreStreaming>()?;
     m.add_class::<streaming::LinearRegressionSlopeStreaming>()?;
     m.add_class::<streaming::RollingPercentileStreaming>()?;
+    m.add_class::<streaming::RollingRMSStreaming>()?;
+    m.add_class::<streaming::RollingCountValidStreaming>()?;
+    m.add_class::<streaming::DrawdownDurationStreaming>()?;
+    m.add_class::<streaming::PctFromHighStreaming>()?;
+    m.add_class::<streaming::PctFromLowStreaming>()?;
+    m.add_class::<streaming::RollingTrimmedMeanStreaming>()?;
+    m.add_class::<streaming::OHLCAggregator>()?;
+
+    // Streaming factory
+    m.add_function(wrap_pyfunction!(streaming::create_streaming, m)?)?;
 
     Ok(())
 }
@@ -1,76 +1,121 @@
-/// Momentum indicators: RSI, Stochastic, Williams %R, PPO, Ultimate Oscillator
+//! Momentum indicators: RSI, Stochastic, Williams %R, PPO, Ultimate Oscillator, RSI Divergence,
+//! Connors RSI, Up/Down Streak, Smoothed RSI, Efficiency Ratio, Signal-to-Noise Ratio,
+//! Chande Momentum Oscillator, FRAMA, Detrended RSI
 
 use numpy::{PyArray1, PyReadonlyArray1};
+use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
-use crate::helpers::{sma_kernel, sma_kernel_nan_aware, rolling_min, rolling_max, ema_kernel, true_range, rolling_sum};
+use pyo3::types::PyTuple;
+use crate::trend::signal_line_kernel;
+use crate::helpers::{sma_kernel, sma_kernel_nan_aware, rolling_min, rolling_max, ema_kernel, true_range, rolling_sum, rsi_kernel, rsi_ta_kernel, roc_kernel, RsiMethod, Compat, HistMode, select_source, histogram_mode_kernel, FloatArray, check_equal_lengths};
 
 /// RSI - Relative Strength Index (Wilder's method)
 ///
 /// # Arguments
 /// * `close` - Close price series
 /// * `n` - RSI period (default: 14)
+/// * `compat` - "native" or "ta" warmup alignment (default: "native").
+///   "native" seeds with the SMA of the first n changes, first value at index n.
+///   "ta" follows `ta.RSIIndicator`: EWM seeded at bar 0, first value at index n-1.
+/// * `price_source` - "close" (default), "hlc3", "hl2" or "ohlc4"; other sources are
+///   built from `high`, `low` and, for "ohlc4", `open`
 ///
 /// # Returns
 /// Numpy array with RSI values (0-100)
 #[pyfunction]
-#[pyo3(name = "relative_strength_index_numba", signature = (close, n=14))]
+#[pyo3(name = "relative_strength_index_numba", signature = (close, n=14, compat="native", price_source="close", open=None, high=None, low=None))]
 pub fn rsi<'py>(
     py: Python<'py>,
     close: PyReadonlyArray1<'py, f64>,
     n: usize,
+    compat: &str,
+    price_source: &str,
+    open: Option<PyReadonlyArray1<'py, f64>>,
+    high: Option<PyReadonlyArray1<'py, f64>>,
+    low: Option<PyReadonlyArray1<'py, f64>>,
 ) -> PyResult<Bound<'py, PyArray1<f64>>> {
     let close_slice = close.as_slice()?;
-    let len = close_slice.len();
-    let mut rsi_values = vec![f64::NAN; len];
+    let open_slice = open.as_ref().map(|a| a.as_slice()).transpose()?;
+    let high_slice = high.as_ref().map(|a| a.as_slice()).transpose()?;
+    let low_slice = low.as_ref().map(|a| a.as_slice()).transpose()?;
+    let source = select_source(price_source, close_slice, open_slice, high_slice, low_slice)
+        .map_err(PyValueError::new_err)?;
+    let rsi_values = match Compat::parse(compat).map_err(PyValueError::new_err)? {
+        Compat::Native => rsi_kernel(&source, n, RsiMethod::Wilder),
+        Compat::Ta => rsi_ta_kernel(&source, n),
+    };
+    Ok(PyArray1::from_vec(py, rsi_values))
+}
 
-    if len < 2 {
-        return Ok(PyArray1::from_vec(py, rsi_values));
+/// RSI of an arbitrary series (volume, spreads, other indicators)
+///
+/// # Arguments
+/// * `data` - Input series
+/// * `n` - RSI period (default: 14)
+/// * `method` - Gain/loss smoothing: "wilder" or "cutler" (default: "wilder")
+///
+/// # Returns
+/// Numpy array with RSI values (0-100)
+#[pyfunction]
+#[pyo3(name = "rsi_of_numba", signature = (data, n=14, method="wilder"))]
+pub fn rsi_of<'py>(
+    py: Python<'py>,
+    data: PyReadonlyArray1<'py, f64>,
+    n: usize,
+    method: &str,
+) -> PyResult<Bound<'py, PyArray1<f64>>> {
+    let data_slice = data.as_slice()?;
+    let method = RsiMethod::parse(method).map_err(PyValueError::new_err)?;
+    let rsi_values = rsi_kernel(data_slice, n, method);
+    Ok(PyArray1::from_vec(py, rsi_values))
+}
+
+/// Stochastic Oscillator kernel. A zero-range window gives %K = 50 natively;
+/// in "ta" mode it is NaN, as `ta` divides by the zero range. Raw %K is smoothed
+/// by an SMA over `slowing` bars (1 = fast stochastic) and %D is the `d_ma`
+/// average of that slow %K. With `slowing = 1` and an SMA, %K starts at index
+/// n-1 and %D at index n+d-2 in both modes.
+pub fn stochastic_kernel(
+    high: &[f64],
+    low: &[f64],
+    close: &[f64],
+    n: usize,
+    d: usize,
+    slowing: usize,
+    d_ma: &str,
+    compat: Compat,
+) -> Result<(Vec<f64>, Vec<f64>), String> {
+    if slowing == 0 {
+        return Err("slowing must be at least 1".to_string());
     }
 
-    let mut gains = vec![0.0; len];
-    let mut losses = vec![0.0; len];
+    let len = high.len();
+    let mut percent_k = vec![f64::NAN; len];
 
-    for i in 1..len {
-        let delta = close_slice[i] - close_slice[i - 1];
-        if delta > 0.0 {
-            gains[i] = delta;
-            losses[i] = 0.0;
-        } else {
-            gains[i] = 0.0;
-            losses[i] = -delta;
-        }
+    if n == 0 || len < n {
+        // Still reject an unknown d_ma on short input
+        signal_line_kernel(&percent_k, d, d_ma, false)?;
+        return Ok((percent_k.clone(), percent_k));
     }
 
-    let alpha = 1.0 / n as f64;
-    let mut avg_gain = vec![f64::NAN; len];
-    let mut avg_loss = vec![f64::NAN; len];
+    let lowest_low = rolling_min(low, n);
+    let highest_high = rolling_max(high, n);
 
-    if len > n {
-        let mut sum_gain = 0.0;
-        let mut sum_loss = 0.0;
-        for i in 1..=n {
-            sum_gain += gains[i];
-            sum_loss += losses[i];
-        }
-        avg_gain[n] = sum_gain / n as f64;
-        avg_loss[n] = sum_loss / n as f64;
-
-        for i in (n + 1)..len {
-            avg_gain[i] = alpha * gains[i] + (1.0 - alpha) * avg_gain[i - 1];
-            avg_loss[i] = alpha * losses[i] + (1.0 - alpha) * avg_loss[i - 1];
+    for i in (n - 1)..len {
+        let range = highest_high[i] - lowest_low[i];
+        if range != 0.0 {
+            percent_k[i] = 100.0 * (close[i] - lowest_low[i]) / range;
+        } else if compat == Compat::Native {
+            percent_k[i] = 50.0;
         }
     }
 
-    for i in n..len {
-        if avg_loss[i] == 0.0 {
-            rsi_values[i] = 100.0;
-        } else {
-            let rs = avg_gain[i] / avg_loss[i];
-            rsi_values[i] = 100.0 - (100.0 / (1.0 + rs));
-        }
+    if slowing > 1 {
+        percent_k = sma_kernel_nan_aware(&percent_k, slowing);
     }
 
-    Ok(PyArray1::from_vec(py, rsi_values))
+    let percent_d = signal_line_kernel(&percent_k, d, d_ma, false)?;
+    Ok((percent_k, percent_d))
 }
 
 /// Stochastic Oscillator
@@ -81,11 +126,15 @@ pub fn rsi<'py>(
 /// * `close` - Close price series
 /// * `k_period` - %K period (default: 14)
 /// * `d_period` - %D smoothing period (default: 3)
+/// * `compat` - "native" or "ta" (default: "native"). No index shift: %K starts at
+///   n-1 and %D at n+d-2 in both; "ta" leaves zero-range windows NaN instead of 50.
+/// * `slowing` - SMA period applied to raw %K; 3 gives the slow stochastic (default: 1)
+/// * `d_ma` - Moving average for %D: "sma", "ema" or "wma" (default: "sma")
 ///
 /// # Returns
 /// Tuple of (%K, %D) as numpy arrays
 #[pyfunction]
-#[pyo3(name = "stochastic_oscillator_numba", signature = (high, low, close, n=14, d=3))]
+#[pyo3(name = "stochastic_oscillator_numba", signature = (high, low, close, n=14, d=3, compat="native", slowing=1, d_ma="sma"))]
 pub fn stochastic<'py>(
     py: Python<'py>,
     high: PyReadonlyArray1<'py, f64>,
@@ -93,31 +142,68 @@ pub fn stochastic<'py>(
     close: PyReadonlyArray1<'py, f64>,
     n: usize,
     d: usize,
+    compat: &str,
+    slowing: usize,
+    d_ma: &str,
 ) -> PyResult<(Bound<'py, PyArray1<f64>>, Bound<'py, PyArray1<f64>>)> {
     let high_slice = high.as_slice()?;
     let low_slice = low.as_slice()?;
     let close_slice = close.as_slice()?;
-    let len = high_slice.len();
+    check_equal_lengths(&[
+        ("high", high_slice.len()),
+        ("low", low_slice.len()),
+        ("close", close_slice.len()),
+    ])
+    .map_err(PyValueError::new_err)?;
+    let compat = Compat::parse(compat).map_err(PyValueError::new_err)?;
+
+    let (percent_k, percent_d) =
+        stochastic_kernel(high_slice, low_slice, close_slice, n, d, slowing, d_ma, compat)
+            .map_err(PyValueError::new_err)?;
 
-    let lowest_low = rolling_min(low_slice, n);
-    let highest_high = rolling_max(high_slice, n);
+    Ok((
+        PyArray1::from_vec(py, percent_k),
+        PyArray1::from_vec(py, percent_d),
+    ))
+}
+
+/// Williams %R kernel, -100 on a zero-range window. With `smooth > 1` the raw %R
+/// is averaged by an SMA of that length, adding `smooth - 1` warmup bars.
+pub fn williams_r_kernel(
+    high: &[f64],
+    low: &[f64],
+    close: &[f64],
+    n: usize,
+    smooth: usize,
+) -> Result<Vec<f64>, String> {
+    if smooth == 0 {
+        return Err("smooth must be at least 1".to_string());
+    }
+
+    let len = high.len();
+    let mut wr = vec![f64::NAN; len];
+
+    if n == 0 || len < n {
+        return Ok(wr);
+    }
+
+    let lowest_low = rolling_min(low, n);
+    let highest_high = rolling_max(high, n);
 
-    let mut percent_k = vec![f64::NAN; len];
     for i in (n - 1)..len {
         let range = highest_high[i] - lowest_low[i];
         if range != 0.0 {
-            percent_k[i] = 100.0 * (close_slice[i] - lowest_low[i]) / range;
+            wr[i] = -100.0 * (highest_high[i] - close[i]) / range;
         } else {
-            percent_k[i] = 50.0;
+            wr[i] = -100.0;
         }
     }
 
-    let percent_d = sma_kernel_nan_aware(&percent_k, d);
+    if smooth > 1 {
+        wr = sma_kernel_nan_aware(&wr, smooth);
+    }
 
-    Ok((
-        PyArray1::from_vec(py, percent_k),
-        PyArray1::from_vec(py, percent_d),
-    ))
+    Ok(wr)
 }
 
 /// Williams %R
@@ -127,83 +213,59 @@ pub fn stochastic<'py>(
 /// * `low` - Low price series
 /// * `close` - Close price series
 /// * `n` - Period for calculation (default: 14)
+/// * `smooth` - SMA period applied to the raw %R; 1 leaves it unsmoothed (default: 1)
 ///
 /// # Returns
 /// Numpy array with Williams %R values (-100 to 0)
 #[pyfunction]
-#[pyo3(name = "williams_r_numba", signature = (high, low, close, n=14))]
+#[pyo3(name = "williams_r_numba", signature = (high, low, close, n=14, smooth=1))]
 pub fn williams_r<'py>(
     py: Python<'py>,
     high: PyReadonlyArray1<'py, f64>,
     low: PyReadonlyArray1<'py, f64>,
     close: PyReadonlyArray1<'py, f64>,
     n: usize,
+    smooth: usize,
 ) -> PyResult<Bound<'py, PyArray1<f64>>> {
     let high_slice = high.as_slice()?;
     let low_slice = low.as_slice()?;
     let close_slice = close.as_slice()?;
-    let len = high_slice.len();
 
-    let lowest_low = rolling_min(low_slice, n);
-    let highest_high = rolling_max(high_slice, n);
-
-    let mut wr = vec![f64::NAN; len];
-    for i in (n - 1)..len {
-        let range = highest_high[i] - lowest_low[i];
-        if range != 0.0 {
-            wr[i] = -100.0 * (highest_high[i] - close_slice[i]) / range;
-        } else {
-            wr[i] = -100.0;
-        }
-    }
+    let wr = williams_r_kernel(high_slice, low_slice, close_slice, n, smooth)
+        .map_err(PyValueError::new_err)?;
 
     Ok(PyArray1::from_vec(py, wr))
 }
 
 /// PPO - Percentage Price Oscillator
 ///
+/// All three EMAs are unadjusted and seeded from their first input, the same
+/// recursion `PPOStreaming` uses, so the two agree from the first bar.
+///
 /// # Arguments
 /// * `close` - Close price series
 /// * `n_fast` - Fast EMA period (default: 12)
 /// * `n_slow` - Slow EMA period (default: 26)
 /// * `n_signal` - Signal line EMA period (default: 9)
+/// * `hist_mode` - "diff" for line - signal, or "pct" for (line - signal) / |signal| * 100,
+///   NaN where the signal is zero (default: "diff")
 ///
 /// # Returns
 /// Tuple of (ppo_line, signal, histogram) as numpy arrays
 #[pyfunction]
-#[pyo3(name = "percentage_price_oscillator_numba", signature = (close, n_fast=12, n_slow=26, n_signal=9))]
+#[pyo3(name = "percentage_price_oscillator_numba", signature = (close, n_fast=12, n_slow=26, n_signal=9, hist_mode="diff"))]
 pub fn ppo<'py>(
     py: Python<'py>,
     close: PyReadonlyArray1<'py, f64>,
     n_fast: usize,
     n_slow: usize,
     n_signal: usize,
+    hist_mode: &str,
 ) -> PyResult<(Bound<'py, PyArray1<f64>>, Bound<'py, PyArray1<f64>>, Bound<'py, PyArray1<f64>>)> {
     let close_slice = close.as_slice()?;
-    let len = close_slice.len();
-
-    let alpha_fast = 2.0 / (n_fast as f64 + 1.0);
-    let alpha_slow = 2.0 / (n_slow as f64 + 1.0);
-
-    let ema_fast = ema_kernel(close_slice, alpha_fast, false);
-    let ema_slow = ema_kernel(close_slice, alpha_slow, false);
-
-    let mut ppo_line = vec![f64::NAN; len];
-    for i in 0..len {
-        if ema_slow[i] != 0.0 && !ema_slow[i].is_nan() {
-            ppo_line[i] = (ema_fast[i] - ema_slow[i]) / ema_slow[i] * 100.0;
-        }
-    }
-
-    let alpha_signal = 2.0 / (n_signal as f64 + 1.0);
-    let signal = ema_kernel(&ppo_line, alpha_signal, false);
-
-    let mut histogram = vec![f64::NAN; len];
-    for i in 0..len {
-        if !ppo_line[i].is_nan() && !signal[i].is_nan() {
-            histogram[i] = ppo_line[i] - signal[i];
-        }
-    }
+    let hist_mode = HistMode::parse(hist_mode).map_err(PyValueError::new_err)?;
+    let (ppo_line, signal, histogram) =
+        percentage_oscillator_kernel(close_slice, n_fast, n_slow, n_signal, false, hist_mode);
 
     Ok((
         PyArray1::from_vec(py, ppo_line),
@@ -271,84 +333,48 @@ pub fn ultimate_oscillator<'py>(
     Ok(PyArray1::from_vec(py, uo))
 }
 
-/// Stochastic RSI - Apply stochastic oscillator to RSI values
-///
-/// # Arguments
-/// * `data` - Price data series (typically close prices)
-/// * `rsi_window` - RSI period (default: 14)
-/// * `stoch_window` - Stochastic period for RSI (default: 14)
-/// * `smooth_k` - %K smoothing period (default: 3)
-/// * `smooth_d` - %D smoothing period (default: 3)
+/// Output convention for `stochastic_rsi_numba`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum StochRsiCompat {
+    Native,
+    TradingView,
+}
+
+impl StochRsiCompat {
+    pub fn parse(compat: &str) -> Result<Self, String> {
+        match compat {
+            "native" => Ok(StochRsiCompat::Native),
+            "tradingview" => Ok(StochRsiCompat::TradingView),
+            other => Err(format!("unknown compat mode '{}', expected one of: native, tradingview", other)),
+        }
+    }
+}
+
+/// Stochastic RSI kernel returning (stoch_rsi, %K, %D)
 ///
-/// # Returns
-/// Tuple of (%K, %D) as numpy arrays
-#[pyfunction]
-#[pyo3(name = "stochastic_rsi_numba", signature = (close, n=14, k=3, d=3))]
-pub fn stochastic_rsi<'py>(
-    py: Python<'py>,
-    close: PyReadonlyArray1<'py, f64>,
+/// Native starts at index 2n-2 with a window holding n-1 valid RSI values, scales
+/// to 0..1 and maps a flat RSI window to 0. TradingView (`ta.stoch` on `ta.rsi`)
+/// waits for n valid RSI values (index 2n-1), scales to 0..100 and leaves a flat
+/// window as NaN, which then propagates through the %K/%D SMAs like Pine's `na`.
+pub fn stochastic_rsi_kernel(
+    close: &[f64],
     n: usize,
     k: usize,
     d: usize,
-) -> PyResult<(Bound<'py, PyArray1<f64>>, Bound<'py, PyArray1<f64>>, Bound<'py, PyArray1<f64>>)> {
-    let close_slice = close.as_slice()?;
-    let len = close_slice.len();
-
-    let mut rsi_values = vec![f64::NAN; len];
+    compat: StochRsiCompat,
+) -> (Vec<f64>, Vec<f64>, Vec<f64>) {
+    let len = close.len();
+    let rsi_values = rsi_kernel(close, n, RsiMethod::Wilder);
 
     if len < 2 {
-        return Ok((
-            PyArray1::from_vec(py, rsi_values.clone()),
-            PyArray1::from_vec(py, rsi_values.clone()),
-            PyArray1::from_vec(py, rsi_values),
-        ));
-    }
-
-    let mut gains = vec![0.0; len];
-    let mut losses = vec![0.0; len];
-
-    for i in 1..len {
-        let delta = close_slice[i] - close_slice[i - 1];
-        if delta > 0.0 {
-            gains[i] = delta;
-            losses[i] = 0.0;
-        } else {
-            gains[i] = 0.0;
-            losses[i] = -delta;
-        }
-    }
-
-    let alpha = 1.0 / n as f64;
-    let mut avg_gain = vec![f64::NAN; len];
-    let mut avg_loss = vec![f64::NAN; len];
-
-    if len > n {
-        let mut sum_gain = 0.0;
-        let mut sum_loss = 0.0;
-        for i in 1..=n {
-            sum_gain += gains[i];
-            sum_loss += losses[i];
-        }
-        avg_gain[n] = sum_gain / n as f64;
-        avg_loss[n] = sum_loss / n as f64;
-
-        for i in (n + 1)..len {
-            avg_gain[i] = alpha * gains[i] + (1.0 - alpha) * avg_gain[i - 1];
-            avg_loss[i] = alpha * losses[i] + (1.0 - alpha) * avg_loss[i - 1];
-        }
-    }
-
-    for i in n..len {
-        if avg_loss[i] == 0.0 {
-            rsi_values[i] = 100.0;
-        } else {
-            let rs = avg_gain[i] / avg_loss[i];
-            rsi_values[i] = 100.0 - (100.0 / (1.0 + rs));
-        }
+        return (rsi_values.clone(), rsi_values.clone(), rsi_values);
     }
 
+    let (start_idx, scale, flat_value) = match compat {
+        StochRsiCompat::Native => ((n - 1) + (n - 1), 1.0, 0.0),
+        StochRsiCompat::TradingView => (n + (n - 1), 100.0, f64::NAN),
+    };
     let mut stoch_rsi = vec![f64::NAN; len];
-    let start_idx = (n - 1) + (n - 1);
 
     for i in start_idx..len {
         let window_start = i + 1 - n;
@@ -365,9 +391,9 @@ pub fn stochastic_rsi<'py>(
 
         if !rsi_values[i].is_nan() && !low_rsi.is_infinite() && !high_rsi.is_infinite() {
             if high_rsi > low_rsi {
-                stoch_rsi[i] = (rsi_values[i] - low_rsi) / (high_rsi - low_rsi);
+                stoch_rsi[i] = scale * (rsi_values[i] - low_rsi) / (high_rsi - low_rsi);
             } else {
-                stoch_rsi[i] = 0.0;
+                stoch_rsi[i] = flat_value;
             }
         }
     }
@@ -375,6 +401,37 @@ pub fn stochastic_rsi<'py>(
     let stoch_k = sma_kernel_nan_aware(&stoch_rsi, k);
     let stoch_d = sma_kernel_nan_aware(&stoch_k, d);
 
+    (stoch_rsi, stoch_k, stoch_d)
+}
+
+/// Stochastic RSI - Apply stochastic oscillator to RSI values
+///
+/// # Arguments
+/// * `close` - Close price series
+/// * `n` - RSI period, also used as the stochastic lookback (default: 14)
+/// * `k` - %K smoothing period (default: 3)
+/// * `d` - %D smoothing period (default: 3)
+/// * `compat` - "native" or "tradingview" (default: "native"). "tradingview"
+///   matches Pine's `ta.stoch(rsi, rsi, rsi, n)`: the first stoch value is at
+///   index 2n-1 instead of 2n-2 (so %K at 2n+k-2, %D at 2n+k+d-3), values are
+///   on a 0..100 scale instead of 0..1, and a flat RSI window gives NaN instead of 0.
+///
+/// # Returns
+/// Tuple of (stoch_rsi, %K, %D) as numpy arrays
+#[pyfunction]
+#[pyo3(name = "stochastic_rsi_numba", signature = (close, n=14, k=3, d=3, compat="native"))]
+pub fn stochastic_rsi<'py>(
+    py: Python<'py>,
+    close: PyReadonlyArray1<'py, f64>,
+    n: usize,
+    k: usize,
+    d: usize,
+    compat: &str,
+) -> PyResult<(Bound<'py, PyArray1<f64>>, Bound<'py, PyArray1<f64>>, Bound<'py, PyArray1<f64>>)> {
+    let close_slice = close.as_slice()?;
+    let compat = StochRsiCompat::parse(compat).map_err(PyValueError::new_err)?;
+    let (stoch_rsi, stoch_k, stoch_d) = stochastic_rsi_kernel(close_slice, n, k, d, compat);
+
     Ok((
         PyArray1::from_vec(py, stoch_rsi),
         PyArray1::from_vec(py, stoch_k),
@@ -382,50 +439,110 @@ pub fn stochastic_rsi<'py>(
     ))
 }
 
+/// TSI kernel: double-smoothed momentum over double-smoothed absolute momentum.
+/// `ma_type` picks the smoother for both passes: "ema" (alpha = 2/(n+1)) or "sma".
+/// With `adjusted=false`, and always for "sma", the smoothing starts at the first
+/// price change, matching `TSIStreaming`; `adjusted` is ignored for "sma".
+pub fn tsi_kernel(close: &[f64], r: usize, s: usize, adjusted: bool, ma_type: &str) -> Result<Vec<f64>, String> {
+    let len = close.len();
+    let mut tsi_values = vec![f64::NAN; len];
+
+    let mut price_change = vec![0.0; len];
+    for i in 1..len {
+        price_change[i] = close[i] - close[i - 1];
+    }
+
+    // The unadjusted recursion seeds from its first input, so skip the
+    // undefined change at bar 0 rather than seeding from zero
+    let start = if adjusted && ma_type == "ema" { 0 } else { 1.min(len) };
+    let price_change = &price_change[start..];
+    let abs_price_change: Vec<f64> = price_change.iter().map(|x| x.abs()).collect();
+
+    let double_smooth = |data: &[f64]| -> Result<Vec<f64>, String> {
+        match ma_type {
+            "ema" => {
+                let first = ema_kernel(data, 2.0 / (r as f64 + 1.0), adjusted);
+                Ok(ema_kernel(&first, 2.0 / (s as f64 + 1.0), adjusted))
+            }
+            "sma" => Ok(sma_kernel_nan_aware(&sma_kernel(data, r), s)),
+            other => Err(format!("unknown ma_type '{}', expected one of: ema, sma", other)),
+        }
+    };
+
+    let smooth_pc = double_smooth(price_change)?;
+    let smooth_abspc = double_smooth(&abs_price_change)?;
+
+    for i in 0..smooth_abspc.len() {
+        if smooth_abspc[i] != 0.0 && !smooth_abspc[i].is_nan() {
+            tsi_values[i + start] = 100.0 * (smooth_pc[i] / smooth_abspc[i]);
+        }
+    }
+
+    Ok(tsi_values)
+}
+
 /// TSI - True Strength Index
 ///
 /// # Arguments
 /// * `data` - Price data series (typically close prices)
 /// * `long_window` - Long period for double smoothing (default: 25)
 /// * `short_window` - Short period for double smoothing (default: 13)
+/// * `adjusted` - Use pandas-style adjusted EMAs; `false` matches `TSIStreaming` (default: true)
+/// * `ma_type` - Smoother for both passes: "ema" or "sma" (default: "ema")
+/// * `price_source` - "close" (default), "hlc3", "hl2" or "ohlc4"; other sources are
+///   built from `high`, `low` and, for "ohlc4", `open`
 ///
 /// # Returns
 /// Numpy array with TSI values
 #[pyfunction]
-#[pyo3(name = "true_strength_index_numba", signature = (close, r=25, s=13))]
+#[pyo3(name = "true_strength_index_numba", signature = (close, r=25, s=13, adjusted=true, ma_type="ema", price_source="close", open=None, high=None, low=None))]
 pub fn tsi<'py>(
     py: Python<'py>,
     close: PyReadonlyArray1<'py, f64>,
     r: usize,
     s: usize,
+    adjusted: bool,
+    ma_type: &str,
+    price_source: &str,
+    open: Option<PyReadonlyArray1<'py, f64>>,
+    high: Option<PyReadonlyArray1<'py, f64>>,
+    low: Option<PyReadonlyArray1<'py, f64>>,
 ) -> PyResult<Bound<'py, PyArray1<f64>>> {
     let close_slice = close.as_slice()?;
-    let len = close_slice.len();
-
-    let mut price_change = vec![0.0; len];
-    for i in 1..len {
-        price_change[i] = close_slice[i] - close_slice[i - 1];
-    }
-
-    let abs_price_change: Vec<f64> = price_change.iter().map(|x| x.abs()).collect();
+    let open_slice = open.as_ref().map(|a| a.as_slice()).transpose()?;
+    let high_slice = high.as_ref().map(|a| a.as_slice()).transpose()?;
+    let low_slice = low.as_ref().map(|a| a.as_slice()).transpose()?;
+    let source = select_source(price_source, close_slice, open_slice, high_slice, low_slice)
+        .map_err(PyValueError::new_err)?;
+    let tsi_values = tsi_kernel(&source, r, s, adjusted, ma_type).map_err(PyValueError::new_err)?;
+    Ok(PyArray1::from_vec(py, tsi_values))
+}
 
-    let alpha_long = 2.0 / (r as f64 + 1.0);
-    let alpha_short = 2.0 / (s as f64 + 1.0);
+/// Awesome Oscillator kernel: SMA(source, n1) - SMA(source, n2)
+pub fn awesome_oscillator_kernel(source: &[f64], n1: usize, n2: usize) -> Vec<f64> {
+    let sma_fast = sma_kernel(source, n1);
+    let sma_slow = sma_kernel(source, n2);
 
-    let ema1_pc = ema_kernel(&price_change, alpha_long, true);
-    let ema2_pc = ema_kernel(&ema1_pc, alpha_short, true);
+    let mut ao = vec![f64::NAN; source.len()];
+    for i in 0..source.len() {
+        if !sma_fast[i].is_nan() && !sma_slow[i].is_nan() {
+            ao[i] = sma_fast[i] - sma_slow[i];
+        }
+    }
 
-    let ema1_abspc = ema_kernel(&abs_price_change, alpha_long, true);
-    let ema2_abspc = ema_kernel(&ema1_abspc, alpha_short, true);
+    ao
+}
 
-    let mut tsi_values = vec![f64::NAN; len];
-    for i in 0..len {
-        if ema2_abspc[i] != 0.0 && !ema2_abspc[i].is_nan() {
-            tsi_values[i] = 100.0 * (ema2_pc[i] / ema2_abspc[i]);
+/// Awesome Oscillator bar color: 1.0 (green) when AO rises from the previous bar,
+/// -1.0 (red) otherwise, NaN until two AO values exist
+pub fn awesome_oscillator_color_kernel(ao: &[f64]) -> Vec<f64> {
+    let mut color = vec![f64::NAN; ao.len()];
+    for i in 1..ao.len() {
+        if !ao[i].is_nan() && !ao[i - 1].is_nan() {
+            color[i] = if ao[i] > ao[i - 1] { 1.0 } else { -1.0 };
         }
     }
-
-    Ok(PyArray1::from_vec(py, tsi_values))
+    color
 }
 
 /// Awesome Oscillator
@@ -433,40 +550,130 @@ pub fn tsi<'py>(
 /// # Arguments
 /// * `high` - High price series
 /// * `low` - Low price series
-/// * `fast_window` - Fast SMA period (default: 5)
-/// * `slow_window` - Slow SMA period (default: 34)
+/// * `n1` - Fast SMA period (default: 5)
+/// * `n2` - Slow SMA period (default: 34)
+/// * `source` - Price averaged by the SMAs: "hl2", "hlc3" or "close" (default: "hl2")
+/// * `close` - Close price series, required for "hlc3" and "close" (default: None)
+/// * `return_hist_color` - Also return the bar color series (default: false)
 ///
 /// # Returns
-/// Numpy array with Awesome Oscillator values
+/// Numpy array with Awesome Oscillator values, or a tuple (ao, color) when
+/// `return_hist_color` is true, with color 1.0 when AO rises and -1.0 when it falls
 #[pyfunction]
-#[pyo3(name = "awesome_oscillator_numba", signature = (high, low, n1=5, n2=34))]
+#[pyo3(name = "awesome_oscillator_numba", signature = (high, low, n1=5, n2=34, source="hl2", close=None, return_hist_color=false))]
 pub fn awesome_oscillator<'py>(
     py: Python<'py>,
     high: PyReadonlyArray1<'py, f64>,
     low: PyReadonlyArray1<'py, f64>,
     n1: usize,
     n2: usize,
-) -> PyResult<Bound<'py, PyArray1<f64>>> {
+    source: &str,
+    close: Option<PyReadonlyArray1<'py, f64>>,
+    return_hist_color: bool,
+) -> PyResult<Bound<'py, PyAny>> {
     let high_slice = high.as_slice()?;
     let low_slice = low.as_slice()?;
-    let len = high_slice.len();
+    check_equal_lengths(&[("high", high_slice.len()), ("low", low_slice.len())])
+        .map_err(PyValueError::new_err)?;
+
+    let series = match (&close, source) {
+        (Some(close), _) => select_source(source, close.as_slice()?, None, Some(high_slice), Some(low_slice))
+            .map_err(PyValueError::new_err)?
+            .into_owned(),
+        (None, "hl2") => high_slice.iter().zip(low_slice).map(|(h, l)| (h + l) / 2.0).collect(),
+        (None, other) => {
+            return Err(PyValueError::new_err(format!("source=\"{}\" requires the close series", other)))
+        }
+    };
 
-    let mut midpoint = vec![0.0; len];
-    for i in 0..len {
-        midpoint[i] = (high_slice[i] + low_slice[i]) / 2.0;
+    let ao = awesome_oscillator_kernel(&series, n1, n2);
+
+    if return_hist_color {
+        let color = awesome_oscillator_color_kernel(&ao);
+        let outputs = [PyArray1::from_vec(py, ao), PyArray1::from_vec(py, color)];
+        Ok(PyTuple::new(py, outputs)?.into_any())
+    } else {
+        Ok(PyArray1::from_vec(py, ao).into_any())
     }
+}
 
-    let sma_fast = sma_kernel(&midpoint, n1);
-    let sma_slow = sma_kernel(&midpoint, n2);
+/// Signal-to-noise kernel: |close[i] - close[i - lag]| / sum of the last `n` bar
+/// changes |close[j] - close[j - 1]|. NaN for the first max(n, lag) bars, 0 when
+/// there is no movement in the path window.
+pub fn snr_kernel(close: &[f64], n: usize, lag: usize) -> Vec<f64> {
+    let len = close.len();
+    let mut snr = vec![f64::NAN; len];
+    let start = n.max(lag);
 
-    let mut ao = vec![f64::NAN; len];
-    for i in 0..len {
-        if !sma_fast[i].is_nan() && !sma_slow[i].is_nan() {
-            ao[i] = sma_fast[i] - sma_slow[i];
+    if n == 0 || lag == 0 || len <= start {
+        return snr;
+    }
+
+    let mut diffs = vec![0.0; len];
+    for i in 1..len {
+        diffs[i] = (close[i] - close[i - 1]).abs();
+    }
+
+    for i in start..len {
+        let direction = (close[i] - close[i - lag]).abs();
+        let mut volatility = 0.0;
+        for j in (i + 1 - n)..=i {
+            volatility += diffs[j];
         }
+        snr[i] = if volatility != 0.0 { direction / volatility } else { 0.0 };
     }
 
-    Ok(PyArray1::from_vec(py, ao))
+    snr
+}
+
+/// Kaufman's Efficiency Ratio kernel: |net change over n bars| / sum of |bar changes|.
+/// NaN for the first `n` bars, 0 when there is no movement in the window.
+pub fn efficiency_ratio_kernel(close: &[f64], n: usize) -> Vec<f64> {
+    snr_kernel(close, n, n)
+}
+
+/// Efficiency Ratio (Kaufman)
+///
+/// # Arguments
+/// * `close` - Close price series
+/// * `n` - Lookback period (default: 10)
+///
+/// # Returns
+/// Numpy array with efficiency ratio values in [0, 1]
+#[pyfunction]
+#[pyo3(name = "efficiency_ratio_numba", signature = (close, n=10))]
+pub fn efficiency_ratio<'py>(
+    py: Python<'py>,
+    close: PyReadonlyArray1<'py, f64>,
+    n: usize,
+) -> PyResult<Bound<'py, PyArray1<f64>>> {
+    let close_slice = close.as_slice()?;
+    let result = efficiency_ratio_kernel(close_slice, n);
+    Ok(PyArray1::from_vec(py, result))
+}
+
+/// Signal-to-Noise Ratio
+///
+/// The efficiency ratio with the net-change lag decoupled from the path window.
+///
+/// # Arguments
+/// * `close` - Close price series
+/// * `n` - Path-length window: number of bar changes summed (default: 20)
+/// * `lag` - Lag of the net change in the numerator (default: `n`)
+///
+/// # Returns
+/// Numpy array with SNR values (in [0, 1] when lag <= n)
+#[pyfunction]
+#[pyo3(name = "snr_numba", signature = (close, n=20, lag=None))]
+pub fn snr<'py>(
+    py: Python<'py>,
+    close: PyReadonlyArray1<'py, f64>,
+    n: usize,
+    lag: Option<usize>,
+) -> PyResult<Bound<'py, PyArray1<f64>>> {
+    let close_slice = close.as_slice()?;
+    let result = snr_kernel(close_slice, n, lag.unwrap_or(n));
+    Ok(PyArray1::from_vec(py, result))
 }
 
 /// KAMA - Kaufman's Adaptive Moving Average
@@ -496,33 +703,7 @@ pub fn kama<'py>(
         return Ok(PyArray1::from_vec(py, kama_values));
     }
 
-    let mut direction = vec![f64::NAN; len];
-    for i in n..len {
-        direction[i] = (close_slice[i] - close_slice[i - n]).abs();
-    }
-
-    let mut diffs = vec![0.0; len];
-    for i in 1..len {
-        diffs[i] = (close_slice[i] - close_slice[i - 1]).abs();
-    }
-
-    let mut volatility = vec![f64::NAN; len];
-    for i in n..len {
-        let mut sum = 0.0;
-        for j in (i + 1 - n)..=i {
-            sum += diffs[j];
-        }
-        volatility[i] = sum;
-    }
-
-    let mut er = vec![f64::NAN; len];
-    for i in n..len {
-        if volatility[i] != 0.0 {
-            er[i] = direction[i] / volatility[i];
-        } else {
-            er[i] = 0.0;
-        }
-    }
+    let er = efficiency_ratio_kernel(close_slice, n);
 
     let fast_sc = 2.0 / (n_fast as f64 + 1.0);
     let slow_sc = 2.0 / (n_slow as f64 + 1.0);
@@ -551,73 +732,99 @@ pub fn kama<'py>(
 /// # Arguments
 /// * `data` - Price data series (typically close prices)
 /// * `n` - Period for rate of change calculation (default: 12)
+/// * `pct` - Return percentages instead of raw fractions (default: true)
+/// * `price_source` - "close" (default), "hlc3", "hl2" or "ohlc4"; other sources are
+///   built from `high`, `low` and, for "ohlc4", `open`
 ///
 /// # Returns
-/// Numpy array with ROC values (percentage)
+/// Numpy array with ROC values
 #[pyfunction]
-#[pyo3(name = "rate_of_change_numba", signature = (close, n=12))]
+#[pyo3(name = "rate_of_change_numba", signature = (close, n=12, pct=true, price_source="close", open=None, high=None, low=None))]
 pub fn roc<'py>(
     py: Python<'py>,
     close: PyReadonlyArray1<'py, f64>,
     n: usize,
+    pct: bool,
+    price_source: &str,
+    open: Option<PyReadonlyArray1<'py, f64>>,
+    high: Option<PyReadonlyArray1<'py, f64>>,
+    low: Option<PyReadonlyArray1<'py, f64>>,
 ) -> PyResult<Bound<'py, PyArray1<f64>>> {
     let close_slice = close.as_slice()?;
-    let len = close_slice.len();
-    let mut roc_values = vec![f64::NAN; len];
-
-    for i in n..len {
-        if close_slice[i - n] != 0.0 {
-            roc_values[i] = (close_slice[i] - close_slice[i - n]) / close_slice[i - n] * 100.0;
-        }
-    }
-
+    let open_slice = open.as_ref().map(|a| a.as_slice()).transpose()?;
+    let high_slice = high.as_ref().map(|a| a.as_slice()).transpose()?;
+    let low_slice = low.as_ref().map(|a| a.as_slice()).transpose()?;
+    let source = select_source(price_source, close_slice, open_slice, high_slice, low_slice)
+        .map_err(PyValueError::new_err)?;
+    let roc_values = roc_kernel(&source, n, pct);
     Ok(PyArray1::from_vec(py, roc_values))
 }
 
-/// PVO - Percentage Volume Oscillator
-///
-/// # Arguments
-/// * `volume` - Volume series
-/// * `n_fast` - Fast EMA period (default: 12)
-/// * `n_slow` - Slow EMA period (default: 26)
-/// * `n_signal` - Signal line EMA period (default: 9)
-///
-/// # Returns
-/// Tuple of (pvo_line, signal, histogram) as numpy arrays
-#[pyfunction]
-#[pyo3(name = "percentage_volume_oscillator_numba", signature = (volume, n_fast=12, n_slow=26, n_signal=9))]
-pub fn pvo<'py>(
-    py: Python<'py>,
-    volume: PyReadonlyArray1<'py, f64>,
+/// Percentage oscillator kernel shared by PPO and PVO: 100 * (fast EMA - slow EMA) / slow EMA,
+/// with first-value-seeded (unadjusted) line EMAs and a signal EMA that is adjusted or not.
+/// Returns (line, signal, histogram).
+pub fn percentage_oscillator_kernel(
+    data: &[f64],
     n_fast: usize,
     n_slow: usize,
     n_signal: usize,
-) -> PyResult<(Bound<'py, PyArray1<f64>>, Bound<'py, PyArray1<f64>>, Bound<'py, PyArray1<f64>>)> {
-    let volume_slice = volume.as_slice()?;
-    let len = volume_slice.len();
+    signal_adjusted: bool,
+    hist_mode: HistMode,
+) -> (Vec<f64>, Vec<f64>, Vec<f64>) {
+    let len = data.len();
 
     let alpha_fast = 2.0 / (n_fast as f64 + 1.0);
     let alpha_slow = 2.0 / (n_slow as f64 + 1.0);
 
-    let ema_fast = ema_kernel(volume_slice, alpha_fast, false);
-    let ema_slow = ema_kernel(volume_slice, alpha_slow, false);
+    let ema_fast = ema_kernel(data, alpha_fast, false);
+    let ema_slow = ema_kernel(data, alpha_slow, false);
 
-    let mut pvo_line = vec![f64::NAN; len];
+    let mut line = vec![f64::NAN; len];
     for i in 0..len {
         if ema_slow[i] != 0.0 && !ema_slow[i].is_nan() {
-            pvo_line[i] = (ema_fast[i] - ema_slow[i]) / ema_slow[i] * 100.0;
+            line[i] = (ema_fast[i] - ema_slow[i]) / ema_slow[i] * 100.0;
         }
     }
 
     let alpha_signal = 2.0 / (n_signal as f64 + 1.0);
-    let signal = ema_kernel(&pvo_line, alpha_signal, true);
+    let signal = ema_kernel(&line, alpha_signal, signal_adjusted);
 
-    let mut histogram = vec![f64::NAN; len];
-    for i in 0..len {
-        if !pvo_line[i].is_nan() && !signal[i].is_nan() {
-            histogram[i] = pvo_line[i] - signal[i];
-        }
-    }
+    let histogram = histogram_mode_kernel(&line, &signal, hist_mode);
+
+    (line, signal, histogram)
+}
+
+/// PVO - Percentage Volume Oscillator
+///
+/// # Arguments
+/// * `volume` - Volume series
+/// * `n_fast` - Fast EMA period (default: 12)
+/// * `n_slow` - Slow EMA period (default: 26)
+/// * `n_signal` - Signal line EMA period (default: 9)
+/// * `signal_adjusted` - Use a pandas-style adjusted signal EMA; `false` matches
+///   `PVOStreaming` (default: true)
+/// * `hist_mode` - "diff" for line - signal, or "pct" for (line - signal) / |signal| * 100,
+///   NaN where the signal is zero (default: "diff")
+///
+/// # Returns
+/// Tuple of (pvo_line, signal, histogram) as numpy arrays
+#[pyfunction]
+#[pyo3(name = "percentage_volume_oscillator_numba", signature = (volume, n_fast=12, n_slow=26, n_signal=9, signal_adjusted=true, hist_mode="diff"))]
+pub fn pvo<'py>(
+    py: Python<'py>,
+    volume: FloatArray<'py>,
+    n_fast: usize,
+    n_slow: usize,
+    n_signal: usize,
+    signal_adjusted: bool,
+    hist_mode: &str,
+) -> PyResult<(Bound<'py, PyArray1<f64>>, Bound<'py, PyArray1<f64>>, Bound<'py, PyArray1<f64>>)> {
+    let volume = volume.to_f64()?;
+    let volume_slice: &[f64] = &volume;
+    let hist_mode = HistMode::parse(hist_mode).map_err(PyValueError::new_err)?;
+    let (pvo_line, signal, histogram) = percentage_oscillator_kernel(
+        volume_slice, n_fast, n_slow, n_signal, signal_adjusted, hist_mode,
+    );
 
     Ok((
         PyArray1::from_vec(py, pvo_line),
@@ -631,23 +838,724 @@ pub fn pvo<'py>(
 /// # Arguments
 /// * `data` - Price data series
 /// * `n` - Momentum period (default: 10)
+/// * `price_source` - "close" (default), "hlc3", "hl2" or "ohlc4"; other sources are
+///   built from `high`, `low` and, for "ohlc4", `open`
 ///
 /// # Returns
 /// Numpy array with momentum values
 #[pyfunction]
-#[pyo3(name = "momentum_numba", signature = (close, n=10))]
+#[pyo3(name = "momentum_numba", signature = (close, n=10, price_source="close", open=None, high=None, low=None))]
 pub fn momentum<'py>(
     py: Python<'py>,
     close: PyReadonlyArray1<'py, f64>,
     n: usize,
+    price_source: &str,
+    open: Option<PyReadonlyArray1<'py, f64>>,
+    high: Option<PyReadonlyArray1<'py, f64>>,
+    low: Option<PyReadonlyArray1<'py, f64>>,
 ) -> PyResult<Bound<'py, PyArray1<f64>>> {
     let close_slice = close.as_slice()?;
-    let len = close_slice.len();
+    let open_slice = open.as_ref().map(|a| a.as_slice()).transpose()?;
+    let high_slice = high.as_ref().map(|a| a.as_slice()).transpose()?;
+    let low_slice = low.as_ref().map(|a| a.as_slice()).transpose()?;
+    let source = select_source(price_source, close_slice, open_slice, high_slice, low_slice)
+        .map_err(PyValueError::new_err)?;
+    let len = source.len();
     let mut mom_values = vec![f64::NAN; len];
 
     for i in n..len {
-        mom_values[i] = close_slice[i] - close_slice[i - n];
+        mom_values[i] = source[i] - source[i - n];
     }
 
     Ok(PyArray1::from_vec(py, mom_values))
 }
+
+/// Divergence kernel between a price series and an oscillator.
+///
+/// At each bar the pivot is the lowest (highest) close of the previous
+/// `lookback` bars. Bullish (+1): close undercuts the pivot low while the
+/// oscillator stays above its value at that pivot. Bearish (-1): close exceeds
+/// the pivot high while the oscillator stays below its value there.
+pub fn divergence_kernel(close: &[f64], osc: &[f64], lookback: usize) -> Vec<f64> {
+    let len = close.len();
+    let mut flags = vec![0.0; len];
+
+    if lookback == 0 {
+        return flags;
+    }
+
+    for i in lookback..len {
+        if close[i].is_nan() || osc[i].is_nan() {
+            continue;
+        }
+
+        let mut low_idx = i - lookback;
+        let mut high_idx = i - lookback;
+        for j in (i - lookback)..i {
+            if close[j] < close[low_idx] {
+                low_idx = j;
+            }
+            if close[j] > close[high_idx] {
+                high_idx = j;
+            }
+        }
+
+        if close[i] < close[low_idx] && !osc[low_idx].is_nan() && osc[i] > osc[low_idx] {
+            flags[i] = 1.0;
+        } else if close[i] > close[high_idx] && !osc[high_idx].is_nan() && osc[i] < osc[high_idx] {
+            flags[i] = -1.0;
+        }
+    }
+
+    flags
+}
+
+/// RSI Divergence
+///
+/// Flags bars where price makes a new low (high) versus the pivot low (high)
+/// of the previous `lookback` bars while RSI does not confirm it.
+///
+/// # Arguments
+/// * `close` - Close price series
+/// * `n` - RSI period (default: 14)
+/// * `lookback` - Number of prior bars searched for the pivot (default: 5)
+///
+/// # Returns
+/// Numpy array with +1 (bullish), -1 (bearish) or 0
+#[pyfunction]
+#[pyo3(name = "rsi_divergence_numba", signature = (close, n=14, lookback=5))]
+pub fn rsi_divergence<'py>(
+    py: Python<'py>,
+    close: PyReadonlyArray1<'py, f64>,
+    n: usize,
+    lookback: usize,
+) -> PyResult<Bound<'py, PyArray1<f64>>> {
+    let close_slice = close.as_slice()?;
+    let rsi_values = rsi_kernel(close_slice, n, RsiMethod::Wilder);
+    let flags = divergence_kernel(close_slice, &rsi_values, lookback);
+    Ok(PyArray1::from_vec(py, flags))
+}
+
+/// Up/down streak length: +k after k consecutive up closes, -k after k down closes, 0 if unchanged
+pub fn streak_kernel(close: &[f64]) -> Vec<f64> {
+    let len = close.len();
+    let mut streak = vec![0.0; len];
+
+    for i in 1..len {
+        streak[i] = if close[i] > close[i - 1] {
+            if streak[i - 1] > 0.0 { streak[i - 1] + 1.0 } else { 1.0 }
+        } else if close[i] < close[i - 1] {
+            if streak[i - 1] < 0.0 { streak[i - 1] - 1.0 } else { -1.0 }
+        } else {
+            0.0
+        };
+    }
+
+    streak
+}
+
+/// Up/Down Streak
+///
+/// # Arguments
+/// * `close` - Close price series
+///
+/// # Returns
+/// Numpy array with signed streak lengths (+k up, -k down, 0 unchanged)
+#[pyfunction]
+#[pyo3(name = "up_down_streak_numba", signature = (close,))]
+pub fn up_down_streak<'py>(
+    py: Python<'py>,
+    close: PyReadonlyArray1<'py, f64>,
+) -> PyResult<Bound<'py, PyArray1<f64>>> {
+    let close_slice = close.as_slice()?;
+    let result = streak_kernel(close_slice);
+    Ok(PyArray1::from_vec(py, result))
+}
+
+/// Connors RSI kernel: mean of RSI(close), RSI(streak) and the percent rank of the
+/// one-period ROC among the previous `rank_n` ROC values
+pub fn connors_rsi_kernel(close: &[f64], rsi_n: usize, streak_n: usize, rank_n: usize) -> Vec<f64> {
+    let len = close.len();
+    let mut crsi = vec![f64::NAN; len];
+
+    let price_rsi = rsi_kernel(close, rsi_n, RsiMethod::Wilder);
+    let streak_rsi = rsi_kernel(&streak_kernel(close), streak_n, RsiMethod::Wilder);
+    let roc1 = roc_kernel(close, 1, true);
+
+    for i in (rank_n + 1)..len {
+        if price_rsi[i].is_nan() || streak_rsi[i].is_nan() || roc1[i].is_nan() {
+            continue;
+        }
+        let below = roc1[(i - rank_n)..i].iter().filter(|&&r| r < roc1[i]).count();
+        let rank = 100.0 * below as f64 / rank_n as f64;
+        crsi[i] = (price_rsi[i] + streak_rsi[i] + rank) / 3.0;
+    }
+
+    crsi
+}
+
+/// Connors RSI
+///
+/// # Arguments
+/// * `close` - Close price series
+/// * `rsi_n` - RSI period for price (default: 3)
+/// * `streak_n` - RSI period for the up/down streak (default: 2)
+/// * `rank_n` - Lookback for the percent rank of one-period ROC (default: 100)
+///
+/// # Returns
+/// Numpy array with Connors RSI values (0-100)
+#[pyfunction]
+#[pyo3(name = "connors_rsi_numba", signature = (close, rsi_n=3, streak_n=2, rank_n=100))]
+pub fn connors_rsi<'py>(
+    py: Python<'py>,
+    close: PyReadonlyArray1<'py, f64>,
+    rsi_n: usize,
+    streak_n: usize,
+    rank_n: usize,
+) -> PyResult<Bound<'py, PyArray1<f64>>> {
+    let close_slice = close.as_slice()?;
+    let result = connors_rsi_kernel(close_slice, rsi_n, streak_n, rank_n);
+    Ok(PyArray1::from_vec(py, result))
+}
+
+/// Smoothed RSI kernel: Wilder RSI of an adjusted EMA of price
+pub fn smoothed_rsi_kernel(close: &[f64], smooth_n: usize, rsi_n: usize) -> Vec<f64> {
+    let smoothed = ema_kernel(close, 2.0 / (smooth_n as f64 + 1.0), true);
+    rsi_kernel(&smoothed, rsi_n, RsiMethod::Wilder)
+}
+
+/// Smoothed RSI - RSI applied to EMA-smoothed price
+///
+/// # Arguments
+/// * `close` - Close price series
+/// * `smooth_n` - EMA period applied to price first (default: 3)
+/// * `rsi_n` - RSI period (default: 14)
+///
+/// # Returns
+/// Numpy array with RSI values (0-100)
+#[pyfunction]
+#[pyo3(name = "smoothed_rsi_numba", signature = (close, smooth_n=3, rsi_n=14))]
+pub fn smoothed_rsi<'py>(
+    py: Python<'py>,
+    close: PyReadonlyArray1<'py, f64>,
+    smooth_n: usize,
+    rsi_n: usize,
+) -> PyResult<Bound<'py, PyArray1<f64>>> {
+    let close_slice = close.as_slice()?;
+    let result = smoothed_rsi_kernel(close_slice, smooth_n, rsi_n);
+    Ok(PyArray1::from_vec(py, result))
+}
+
+/// Detrended RSI kernel: Wilder RSI minus its SMA over `sma_n` bars
+pub fn detrended_rsi_kernel(close: &[f64], rsi_n: usize, sma_n: usize) -> Vec<f64> {
+    let rsi_values = rsi_kernel(close, rsi_n, RsiMethod::Wilder);
+    let rsi_sma = sma_kernel_nan_aware(&rsi_values, sma_n);
+    rsi_values.iter().zip(&rsi_sma).map(|(r, m)| r - m).collect()
+}
+
+/// Detrended RSI - RSI centered on its own moving average
+///
+/// # Arguments
+/// * `close` - Close price series
+/// * `rsi_n` - RSI period (default: 14)
+/// * `sma_n` - SMA period applied to the RSI (default: 14)
+///
+/// # Returns
+/// Numpy array with RSI - SMA(RSI, sma_n), oscillating around zero
+#[pyfunction]
+#[pyo3(name = "detrended_rsi_numba", signature = (close, rsi_n=14, sma_n=14))]
+pub fn detrended_rsi<'py>(
+    py: Python<'py>,
+    close: PyReadonlyArray1<'py, f64>,
+    rsi_n: usize,
+    sma_n: usize,
+) -> PyResult<Bound<'py, PyArray1<f64>>> {
+    let close_slice = close.as_slice()?;
+    let result = detrended_rsi_kernel(close_slice, rsi_n, sma_n);
+    Ok(PyArray1::from_vec(py, result))
+}
+
+/// Chande Momentum Oscillator kernel: 100 * (sum_up - sum_down) / (sum_up + sum_down)
+/// over the last n close-to-close changes. NaN for the first n elements, 0.0 when
+/// the window has no movement.
+pub fn cmo_kernel(close: &[f64], n: usize) -> Vec<f64> {
+    let len = close.len();
+    let mut result = vec![f64::NAN; len];
+
+    if n == 0 || n >= len {
+        return result;
+    }
+
+    let mut gains = vec![0.0; len];
+    let mut losses = vec![0.0; len];
+    for i in 1..len {
+        let delta = close[i] - close[i - 1];
+        if delta > 0.0 {
+            gains[i] = delta;
+        } else {
+            losses[i] = -delta;
+        }
+    }
+
+    let sum_up = rolling_sum(&gains, n);
+    let sum_down = rolling_sum(&losses, n);
+    for i in n..len {
+        let total = sum_up[i] + sum_down[i];
+        result[i] = if total != 0.0 { 100.0 * (sum_up[i] - sum_down[i]) / total } else { 0.0 };
+    }
+
+    result
+}
+
+/// Chande Momentum Oscillator (CMO)
+///
+/// # Arguments
+/// * `close` - Close price series
+/// * `n` - Number of price changes in the window (default: 14)
+///
+/// # Returns
+/// Numpy array with CMO values (-100 to 100)
+#[pyfunction]
+#[pyo3(name = "cmo_numba", signature = (close, n=14))]
+pub fn cmo<'py>(
+    py: Python<'py>,
+    close: PyReadonlyArray1<'py, f64>,
+    n: usize,
+) -> PyResult<Bound<'py, PyArray1<f64>>> {
+    let close_slice = close.as_slice()?;
+    let result = cmo_kernel(close_slice, n);
+    Ok(PyArray1::from_vec(py, result))
+}
+
+/// FRAMA kernel (Ehlers' Fractal Adaptive Moving Average) over the median price
+/// (high + low) / 2.
+///
+/// The fractal dimension of each n-bar window is estimated from price ranges: with
+/// N1 and N2 the high-low range of the older and newer half-windows divided by n/2,
+/// and N3 the full-window range divided by n, D = (ln(N1 + N2) - ln(N3)) / ln(2).
+/// A straight line gives D near 1 and a random walk near 1.5, with choppier paths
+/// approaching 2. The EMA factor is alpha = exp(-4.6 * (D - 1)) clamped to
+/// [0.01, 1], so trends are followed closely and chop is smoothed heavily. Windows
+/// with a zero range keep the previous dimension. The recursion is seeded with the
+/// median price at index n-1; `n` must be even and at least 2.
+pub fn frama_kernel(high: &[f64], low: &[f64], n: usize) -> Result<Vec<f64>, String> {
+    if n < 2 || !n.is_multiple_of(2) {
+        return Err(format!("n must be an even number >= 2, got {}", n));
+    }
+
+    let len = high.len();
+    let mut result = vec![f64::NAN; len];
+    if len < n {
+        return Ok(result);
+    }
+
+    let half = n / 2;
+    let price: Vec<f64> = (0..len).map(|i| (high[i] + low[i]) / 2.0).collect();
+    let range = |start: usize, end: usize| -> f64 {
+        let hi = high[start..end].iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        let lo = low[start..end].iter().copied().fold(f64::INFINITY, f64::min);
+        hi - lo
+    };
+
+    let mut dimension = 1.0;
+    result[n - 1] = price[n - 1];
+    for i in n..len {
+        let start = i + 1 - n;
+        let n1 = range(start, start + half) / half as f64;
+        let n2 = range(start + half, i + 1) / half as f64;
+        let n3 = range(start, i + 1) / n as f64;
+
+        if n1 > 0.0 && n2 > 0.0 && n3 > 0.0 {
+            dimension = ((n1 + n2).ln() - n3.ln()) / std::f64::consts::LN_2;
+        }
+
+        let alpha = (-4.6 * (dimension - 1.0)).exp().clamp(0.01, 1.0);
+        result[i] = alpha * price[i] + (1.0 - alpha) * result[i - 1];
+    }
+
+    Ok(result)
+}
+
+/// FRAMA - Fractal Adaptive Moving Average
+///
+/// # Arguments
+/// * `high` - High price series
+/// * `low` - Low price series
+/// * `n` - Window for the fractal dimension estimate, must be even (default: 16)
+///
+/// # Returns
+/// Numpy array with FRAMA values of the median price, NaN for the first n-1 elements
+#[pyfunction]
+#[pyo3(name = "frama_numba", signature = (high, low, n=16))]
+pub fn frama<'py>(
+    py: Python<'py>,
+    high: PyReadonlyArray1<'py, f64>,
+    low: PyReadonlyArray1<'py, f64>,
+    n: usize,
+) -> PyResult<Bound<'py, PyArray1<f64>>> {
+    let high_slice = high.as_slice()?;
+    let low_slice = low.as_slice()?;
+    check_equal_lengths(&[("high", high_slice.len()), ("low", low_slice.len())])
+        .map_err(PyValueError::new_err)?;
+
+    let result = frama_kernel(high_slice, low_slice, n).map_err(PyValueError::new_err)?;
+    Ok(PyArray1::from_vec(py, result))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_close(len: usize) -> Vec<f64> {
+        (0..len).map(|i| 100.0 + (i as f64 * 0.37).sin() * 4.0 + i as f64 * 0.05).collect()
+    }
+
+    // Wilder RSI as previously inlined in `rsi` and `stochastic_rsi`
+    fn reference_rsi(close: &[f64], n: usize) -> Vec<f64> {
+        let len = close.len();
+        let mut out = vec![f64::NAN; len];
+        let (mut avg_gain, mut avg_loss) = (0.0, 0.0);
+        for i in 1..len {
+            let delta = close[i] - close[i - 1];
+            let (gain, loss) = if delta > 0.0 { (delta, 0.0) } else { (0.0, -delta) };
+            if i <= n {
+                avg_gain += gain / n as f64;
+                avg_loss += loss / n as f64;
+            } else {
+                avg_gain = gain / n as f64 + (1.0 - 1.0 / n as f64) * avg_gain;
+                avg_loss = loss / n as f64 + (1.0 - 1.0 / n as f64) * avg_loss;
+            }
+            if i >= n {
+                out[i] = if avg_loss == 0.0 { 100.0 } else { 100.0 - 100.0 / (1.0 + avg_gain / avg_loss) };
+            }
+        }
+        out
+    }
+
+    #[test]
+    fn test_rsi_kernel_matches_previous_rsi() {
+        let close = sample_close(120);
+        let expected = reference_rsi(&close, 14);
+        let actual = rsi_kernel(&close, 14, RsiMethod::Wilder);
+
+        for i in 0..close.len() {
+            if expected[i].is_nan() {
+                assert!(actual[i].is_nan());
+            } else {
+                assert!((actual[i] - expected[i]).abs() < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn test_stochastic_rsi_kernel_matches_previous_output() {
+        let close = sample_close(120);
+        let n = 14;
+        let rsi_values = reference_rsi(&close, n);
+        let (stoch_rsi, stoch_k, _) = stochastic_rsi_kernel(&close, n, 3, 3, StochRsiCompat::Native);
+
+        for i in (2 * n - 1)..close.len() {
+            let window: Vec<f64> = rsi_values[i + 1 - n..=i].iter().copied().filter(|x| !x.is_nan()).collect();
+            let low = window.iter().copied().fold(f64::INFINITY, f64::min);
+            let high = window.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+            let expected = if high > low { (rsi_values[i] - low) / (high - low) } else { 0.0 };
+            assert!((stoch_rsi[i] - expected).abs() < 1e-9);
+        }
+        assert!(!stoch_k[2 * n + 1].is_nan());
+    }
+
+    #[test]
+    fn test_stochastic_rsi_tradingview_compat() {
+        let close = sample_close(120);
+        let n = 14;
+        let (stoch, k, d) = stochastic_rsi_kernel(&close, n, 3, 3, StochRsiCompat::TradingView);
+
+        // first values: stoch at 2n-1, %K at 2n+1, %D at 2n+3
+        assert!(stoch[2 * n - 2].is_nan() && !stoch[2 * n - 1].is_nan());
+        assert!(k[2 * n].is_nan() && !k[2 * n + 1].is_nan());
+        assert!(d[2 * n + 2].is_nan() && !d[2 * n + 3].is_nan());
+
+        // same values as native once both are warm, on the 0..100 scale
+        let (native, _, _) = stochastic_rsi_kernel(&close, n, 3, 3, StochRsiCompat::Native);
+        assert!(!native[2 * n - 2].is_nan());
+        for i in (2 * n - 1)..close.len() {
+            assert!((stoch[i] - 100.0 * native[i]).abs() < 1e-9);
+        }
+
+        // a steady rise pins RSI at 100: the zero range is NaN through %K and %D
+        let rising: Vec<f64> = (0..60).map(|i| 100.0 + i as f64).collect();
+        let (flat_stoch, flat_k, flat_d) = stochastic_rsi_kernel(&rising, n, 3, 3, StochRsiCompat::TradingView);
+        assert!(flat_stoch.iter().chain(&flat_k).chain(&flat_d).all(|v| v.is_nan()));
+        let (flat_native, _, _) = stochastic_rsi_kernel(&rising, n, 3, 3, StochRsiCompat::Native);
+        assert_eq!(flat_native[2 * n - 2], 0.0);
+
+        assert!(StochRsiCompat::parse("ta").is_err());
+    }
+
+    #[test]
+    fn test_roc_fraction_is_percentage_over_100() {
+        let close = sample_close(60);
+        let pct = roc_kernel(&close, 12, true);
+        let frac = roc_kernel(&close, 12, false);
+
+        assert!(frac[11].is_nan());
+        for i in 12..close.len() {
+            assert!((frac[i] - pct[i] / 100.0).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_divergence_flags_bullish_and_bearish_bars() {
+        // Lower low in price at bar 6 with a higher oscillator low (bullish),
+        // then a higher high at bar 12 with a lower oscillator high (bearish)
+        let close = [10.0, 9.0, 8.0, 9.0, 9.5, 9.0, 7.5, 8.0, 9.0, 11.0, 12.0, 11.0, 12.5];
+        let osc = [50.0, 40.0, 30.0, 45.0, 50.0, 45.0, 35.0, 45.0, 55.0, 70.0, 80.0, 70.0, 75.0];
+
+        let flags = divergence_kernel(&close, &osc, 5);
+
+        assert_eq!(flags[6], 1.0);
+        assert_eq!(flags[12], -1.0);
+        let fired: usize = flags.iter().filter(|&&f| f != 0.0).count();
+        assert_eq!(fired, 2);
+    }
+
+    #[test]
+    fn test_streak_resets_and_accumulates() {
+        let close = [10.0, 11.0, 10.0, 11.0, 11.0, 12.0, 13.0, 14.0, 13.0, 12.0, 11.0];
+        let expected = [0.0, 1.0, -1.0, 1.0, 0.0, 1.0, 2.0, 3.0, -1.0, -2.0, -3.0];
+        assert_eq!(streak_kernel(&close), expected.to_vec());
+    }
+
+    #[test]
+    fn test_detrended_rsi_near_zero_when_rsi_flat() {
+        // steady gains pin RSI at 100, so it sits on its own average
+        let rising: Vec<f64> = (0..60).map(|i| 100.0 + i as f64).collect();
+        let flat = detrended_rsi_kernel(&rising, 14, 14);
+        assert!(flat[..27].iter().all(|x| x.is_nan()));
+        assert!(flat[27..].iter().all(|x| x.abs() < 1e-9));
+
+        let close = sample_close(120);
+        let rsi_values = rsi_kernel(&close, 14, RsiMethod::Wilder);
+        let detrended = detrended_rsi_kernel(&close, 14, 14);
+        let i = close.len() - 1;
+        let mean = rsi_values[i - 13..=i].iter().sum::<f64>() / 14.0;
+        assert!((detrended[i] - (rsi_values[i] - mean)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_awesome_oscillator_close_source_and_color() {
+        let close = sample_close(80);
+        let high: Vec<f64> = close.iter().map(|c| c + 2.0).collect();
+        let low: Vec<f64> = close.iter().map(|c| c - 1.0).collect();
+
+        let on_close = awesome_oscillator_kernel(&close, 5, 34);
+        let fast = sma_kernel(&close, 5);
+        let slow = sma_kernel(&close, 34);
+        assert!(on_close[..33].iter().all(|x| x.is_nan()));
+        for i in 33..close.len() {
+            assert!((on_close[i] - (fast[i] - slow[i])).abs() < 1e-12);
+        }
+        // a constant offset between sources cancels in the SMA difference, hl2 = close + 0.5
+        let hl2: Vec<f64> = high.iter().zip(&low).map(|(h, l)| (h + l) / 2.0).collect();
+        let on_hl2 = awesome_oscillator_kernel(&hl2, 5, 34);
+        assert!((on_hl2[50] - on_close[50]).abs() < 1e-9);
+
+        let color = awesome_oscillator_color_kernel(&on_close);
+        assert!(color[33].is_nan() && !color[34].is_nan());
+        let mut flips = 0;
+        for i in 34..close.len() {
+            let expected = if on_close[i] > on_close[i - 1] { 1.0 } else { -1.0 };
+            assert_eq!(color[i], expected);
+            if i > 34 && color[i] != color[i - 1] {
+                flips += 1;
+            }
+        }
+        assert!(flips > 0);
+    }
+
+    #[test]
+    fn test_smoothed_rsi_with_passthrough_ema_is_plain_rsi() {
+        let close = sample_close(80);
+        let smoothed = smoothed_rsi_kernel(&close, 1, 14);
+        let plain = rsi_kernel(&close, 14, RsiMethod::Wilder);
+
+        for i in 0..close.len() {
+            assert!(smoothed[i] == plain[i] || (smoothed[i].is_nan() && plain[i].is_nan()));
+        }
+    }
+
+    #[test]
+    fn test_efficiency_ratio_trend_vs_zigzag() {
+        let monotone: Vec<f64> = (0..30).map(|i| 100.0 + i as f64 * 0.5).collect();
+        let er = efficiency_ratio_kernel(&monotone, 10);
+        assert!(er[..10].iter().all(|v| v.is_nan()));
+        assert!(er[10..].iter().all(|v| (v - 1.0).abs() < 1e-12));
+
+        let zigzag: Vec<f64> = (0..30).map(|i| if i % 2 == 0 { 100.0 } else { 101.0 }).collect();
+        let er = efficiency_ratio_kernel(&zigzag, 10);
+        assert!(er[10..].iter().all(|v| v.abs() < 1e-12));
+
+        let flat = vec![100.0; 20];
+        assert_eq!(efficiency_ratio_kernel(&flat, 10)[15], 0.0);
+    }
+
+    #[test]
+    fn test_stochastic_ta_compat_flat_window() {
+        let high = [10.0, 10.0, 10.0, 12.0];
+        let low = [10.0, 10.0, 10.0, 9.0];
+        let close = [10.0, 10.0, 10.0, 11.0];
+
+        let (native_k, native_d) = stochastic_kernel(&high, &low, &close, 3, 2, 1, "sma", Compat::Native).unwrap();
+        let (ta_k, ta_d) = stochastic_kernel(&high, &low, &close, 3, 2, 1, "sma", Compat::Ta).unwrap();
+
+        assert!(native_k[1].is_nan() && ta_k[1].is_nan());
+        assert_eq!(native_k[2], 50.0);
+        assert!(ta_k[2].is_nan());
+        assert!((ta_k[3] - 100.0 * 2.0 / 3.0).abs() < 1e-12);
+        assert_eq!(ta_k[3], native_k[3]);
+        assert!(!native_d[3].is_nan() && ta_d[3].is_nan());
+    }
+
+    #[test]
+    fn test_percentage_oscillator_hist_modes() {
+        let volume = sample_close(60);
+        let (line, signal, diff) =
+            percentage_oscillator_kernel(&volume, 12, 26, 9, true, HistMode::Diff);
+        let (_, _, pct) = percentage_oscillator_kernel(&volume, 12, 26, 9, true, HistMode::Pct);
+
+        // Both EMAs start from the first value, so line and signal are 0 at bar 0
+        assert_eq!(signal[0], 0.0);
+        assert!(pct[0].is_nan());
+        for i in 1..volume.len() {
+            assert_eq!(diff[i], line[i] - signal[i]);
+            assert!((pct[i] - diff[i] / signal[i].abs() * 100.0).abs() < 1e-9);
+        }
+
+        // NaN in either input (and a zero signal for "pct") yields NaN
+        let line = [f64::NAN, 1.0, 2.0, 3.0];
+        let signal = [0.5, f64::NAN, 0.0, 2.0];
+        let diff = histogram_mode_kernel(&line, &signal, HistMode::Diff);
+        let pct = histogram_mode_kernel(&line, &signal, HistMode::Pct);
+        assert!(diff[0].is_nan() && diff[1].is_nan());
+        assert_eq!(&diff[2..], &[2.0, 1.0]);
+        assert!(pct[..3].iter().all(|h| h.is_nan()));
+        assert_eq!(pct[3], 50.0);
+    }
+
+    #[test]
+    fn test_williams_r_smoothing() {
+        let close = sample_close(40);
+        let high: Vec<f64> = close.iter().map(|c| c + 1.0).collect();
+        let low: Vec<f64> = close.iter().map(|c| c - 1.0).collect();
+
+        let raw = williams_r_kernel(&high, &low, &close, 14, 1).unwrap();
+        let smoothed = williams_r_kernel(&high, &low, &close, 14, 3).unwrap();
+
+        assert!(!raw[13].is_nan());
+        assert!(smoothed[13].is_nan() && smoothed[14].is_nan());
+        for i in 15..close.len() {
+            let expected = (raw[i - 2] + raw[i - 1] + raw[i]) / 3.0;
+            assert!((smoothed[i] - expected).abs() < 1e-12);
+        }
+        assert!(williams_r_kernel(&high, &low, &close, 14, 0).is_err());
+    }
+
+    #[test]
+    fn test_snr_monotone_and_lag() {
+        let monotone: Vec<f64> = (0..40).map(|i| 100.0 + i as f64 * 0.5).collect();
+        let snr = snr_kernel(&monotone, 20, 20);
+        assert!(snr[19].is_nan());
+        assert!(snr[20..].iter().all(|&v| (v - 1.0).abs() < 1e-12));
+
+        // A shorter lag covers only part of the path
+        let short = snr_kernel(&monotone, 20, 5);
+        assert!(short[20..].iter().all(|&v| (v - 0.25).abs() < 1e-12));
+
+        let close = sample_close(60);
+        assert_eq!(snr_kernel(&close, 10, 10)[10..], efficiency_ratio_kernel(&close, 10)[10..]);
+    }
+
+    #[test]
+    fn test_tsi_ma_type() {
+        let close = sample_close(120);
+
+        // Pre-`ma_type` EMA implementation
+        let reference = |adjusted: bool| -> Vec<f64> {
+            let mut out = vec![f64::NAN; close.len()];
+            let start = if adjusted { 0 } else { 1 };
+            let pc: Vec<f64> = (start..close.len())
+                .map(|i| if i == 0 { 0.0 } else { close[i] - close[i - 1] })
+                .collect();
+            let apc: Vec<f64> = pc.iter().map(|x| x.abs()).collect();
+            let (al, as_) = (2.0 / 26.0, 2.0 / 14.0);
+            let num = ema_kernel(&ema_kernel(&pc, al, adjusted), as_, adjusted);
+            let den = ema_kernel(&ema_kernel(&apc, al, adjusted), as_, adjusted);
+            for i in 0..den.len() {
+                if den[i] != 0.0 {
+                    out[i + start] = 100.0 * (num[i] / den[i]);
+                }
+            }
+            out
+        };
+        for adjusted in [true, false] {
+            let tsi = tsi_kernel(&close, 25, 13, adjusted, "ema").unwrap();
+            let expected = reference(adjusted);
+            for i in 1..close.len() {
+                assert_eq!(tsi[i], expected[i]);
+            }
+        }
+
+        // Manual double SMA of momentum and absolute momentum
+        let (r, s) = (10, 5);
+        let tsi = tsi_kernel(&close, r, s, true, "sma").unwrap();
+        let change = |j: usize| close[j] - close[j - 1];
+        let first = r + s - 1;
+        assert!(tsi[first - 1].is_nan());
+        for i in first..close.len() {
+            let (mut num, mut den) = (0.0, 0.0);
+            for k in (i + 1 - s)..=i {
+                let window = (k + 1 - r)..=k;
+                num += window.clone().map(change).sum::<f64>() / r as f64;
+                den += window.map(|j| change(j).abs()).sum::<f64>() / r as f64;
+            }
+            let expected = 100.0 * (num / s as f64) / (den / s as f64);
+            assert!((tsi[i] - expected).abs() < 1e-9, "bar {}", i);
+        }
+        assert!(tsi_kernel(&close, r, s, true, "wma").is_err());
+    }
+
+    #[test]
+    fn test_cmo_bounds() {
+        let rising: Vec<f64> = (0..30).map(|i| i as f64).collect();
+        let cmo = cmo_kernel(&rising, 14);
+        assert!(cmo[13].is_nan());
+        assert!(cmo[14..].iter().all(|&v| v == 100.0));
+
+        assert!(cmo_kernel(&[5.0; 20], 14)[14..].iter().all(|&v| v == 0.0));
+
+        let close = sample_close(100);
+        assert!(cmo_kernel(&close, 14)[14..].iter().all(|v| (-100.0..=100.0).contains(v)));
+    }
+
+    #[test]
+    fn test_frama_tracks_trend_and_smooths_chop() {
+        // Steady trend: the dimension is near 1, so FRAMA follows the median price
+        let high: Vec<f64> = (0..60).map(|i| 100.25 + i as f64).collect();
+        let low: Vec<f64> = (0..60).map(|i| 99.75 + i as f64).collect();
+        let trend = frama_kernel(&high, &low, 16).unwrap();
+        assert!(trend[14].is_nan());
+        for i in 15..60 {
+            assert!((trend[i] - (100.0 + i as f64)).abs() < 1e-9);
+        }
+
+        // Sideways zigzag: the dimension is near 2, so FRAMA barely moves
+        let mid: Vec<f64> = (0..60).map(|i| if i % 2 == 0 { 105.0 } else { 95.0 }).collect();
+        let high: Vec<f64> = mid.iter().map(|m| m + 0.5).collect();
+        let low: Vec<f64> = mid.iter().map(|m| m - 0.5).collect();
+        let chop = frama_kernel(&high, &low, 16).unwrap();
+        // Price swings by 10 every bar; FRAMA steps by alpha = 0.01 of that
+        assert!(chop[15..].windows(2).all(|w| (w[1] - w[0]).abs() <= 0.11));
+
+        assert!(frama_kernel(&high, &low, 15).is_err());
+    }
+}
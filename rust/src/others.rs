@@ -1,87 +1,124 @@
-/// Other utility indicators: Daily Returns, Log Returns, Cumulative Returns,
-/// Rolling Z-Score, Linear Regression Slope, Rolling Percentile
+//! Other utility indicators: Daily Returns, Log Returns, Cumulative Returns,
+//! Rolling Z-Score, Linear Regression Slope, Regression Forecast, Rolling Percentile, Rolling Sum, Rolling RMS,
+//! Percent Above MA, Warmup NaN Fill, Drawdown Duration, Max Drawdown, Rolling Entropy,
+//! Rolling VaR / CVaR, Rolling Valid Count, Up Bar Ratio, Win Rate, Profit Factor, Rolling OLS Residual,
+//! Percent From High / Low, New High/Low Oscillator, Round to Tick, Min Move Filter,
+//! Spread Z-Score, Max Up / Down Streak, Rolling Reduce, Gap, Gap Fill, Downside Beta, Rolling Spearman,
+//! Calmar Ratio, Martin Ratio, Rolling Trimmed Mean, Standardize, Rolling Information Ratio
 
+use std::collections::VecDeque;
 use numpy::{PyArray1, PyReadonlyArray1};
+use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
+use pyo3::types::PyTuple;
+use crate::volatility::ulcer_index_kernel;
+use crate::helpers::{self, sma_kernel, rolling_linreg, rolling_ols, rolling_sum, rolling_max, rolling_min, rolling_std, rolling_median, return_scale, mask_warmup};
+
+/// Daily return kernel: simple one-period returns, first value NaN
+pub fn daily_return_kernel(close: &[f64], pct: bool) -> Vec<f64> {
+    let len = close.len();
+    let scale = return_scale(pct);
+
+    let mut dr = vec![f64::NAN; len];
+
+    for i in 1..len {
+        dr[i] = (close[i] - close[i - 1]) / close[i - 1] * scale;
+    }
+
+    dr
+}
 
 /// Daily Return
 ///
 /// # Arguments
 /// * `data` - Price series (typically close)
+/// * `pct` - Return percentages instead of raw fractions (default: true)
 ///
 /// # Returns
-/// Numpy array with daily return values (percentage)
+/// Numpy array with daily return values
 #[pyfunction]
-#[pyo3(name = "daily_return_numba", signature = (close,))]
+#[pyo3(name = "daily_return_numba", signature = (close, pct=true))]
 pub fn daily_return<'py>(
     py: Python<'py>,
     close: PyReadonlyArray1<'py, f64>,
+    pct: bool,
 ) -> PyResult<Bound<'py, PyArray1<f64>>> {
     let close_slice = close.as_slice()?;
-    let len = close_slice.len();
+    let dr = daily_return_kernel(close_slice, pct);
+    Ok(PyArray1::from_vec(py, dr))
+}
 
-    let mut dr = vec![f64::NAN; len];
+/// Daily log return kernel: one-period log returns, first value NaN
+pub fn daily_log_return_kernel(close: &[f64], pct: bool) -> Vec<f64> {
+    let len = close.len();
+    let scale = return_scale(pct);
+
+    let mut dlr = vec![f64::NAN; len];
 
     for i in 1..len {
-        dr[i] = (close_slice[i] - close_slice[i - 1]) / close_slice[i - 1] * 100.0;
+        let ratio: f64 = close[i] / close[i - 1];
+        dlr[i] = ratio.ln() * scale;
     }
 
-    Ok(PyArray1::from_vec(py, dr))
+    dlr
 }
 
 /// Daily Log Return
 ///
 /// # Arguments
 /// * `data` - Price series (typically close)
+/// * `pct` - Return percentages instead of raw fractions (default: true)
 ///
 /// # Returns
-/// Numpy array with daily log return values (percentage)
+/// Numpy array with daily log return values
 #[pyfunction]
-#[pyo3(name = "daily_log_return_numba", signature = (close,))]
+#[pyo3(name = "daily_log_return_numba", signature = (close, pct=true))]
 pub fn daily_log_return<'py>(
     py: Python<'py>,
     close: PyReadonlyArray1<'py, f64>,
+    pct: bool,
 ) -> PyResult<Bound<'py, PyArray1<f64>>> {
     let close_slice = close.as_slice()?;
-    let len = close_slice.len();
+    let dlr = daily_log_return_kernel(close_slice, pct);
+    Ok(PyArray1::from_vec(py, dlr))
+}
 
-    let mut dlr = vec![f64::NAN; len];
+/// Cumulative return kernel: return relative to the first price
+pub fn cumulative_return_kernel(close: &[f64], pct: bool) -> Vec<f64> {
+    let len = close.len();
+    let scale = return_scale(pct);
 
-    for i in 1..len {
-        let ratio: f64 = close_slice[i] / close_slice[i - 1];
-        dlr[i] = ratio.ln() * 100.0;
+    let mut cr = vec![f64::NAN; len];
+
+    if len > 0 {
+        let initial_price = close[0];
+        if initial_price != 0.0 {
+            for i in 0..len {
+                cr[i] = ((close[i] / initial_price) - 1.0) * scale;
+            }
+        }
     }
 
-    Ok(PyArray1::from_vec(py, dlr))
+    cr
 }
 
 /// Cumulative Return
 ///
 /// # Arguments
 /// * `data` - Price series (typically close)
+/// * `pct` - Return percentages instead of raw fractions (default: true)
 ///
 /// # Returns
-/// Numpy array with cumulative return values (percentage)
+/// Numpy array with cumulative return values
 #[pyfunction]
-#[pyo3(name = "cumulative_return_numba", signature = (close,))]
+#[pyo3(name = "cumulative_return_numba", signature = (close, pct=true))]
 pub fn cumulative_return<'py>(
     py: Python<'py>,
     close: PyReadonlyArray1<'py, f64>,
+    pct: bool,
 ) -> PyResult<Bound<'py, PyArray1<f64>>> {
     let close_slice = close.as_slice()?;
-    let len = close_slice.len();
-
-    let mut cr = vec![f64::NAN; len];
-
-    if len > 0 {
-        let initial_price = close_slice[0];
-        if initial_price != 0.0 {
-            for i in 0..len {
-                cr[i] = ((close_slice[i] / initial_price) - 1.0) * 100.0;
-            }
-        }
-    }
-
+    let cr = cumulative_return_kernel(close_slice, pct);
     Ok(PyArray1::from_vec(py, cr))
 }
 
@@ -122,6 +159,45 @@ pub fn compound_log_return<'py>(
     Ok(PyArray1::from_vec(py, clr))
 }
 
+/// Rolling z-score kernel. With `expanding`, each index uses data[0..=i] and
+/// `window` only sets the minimum number of periods; the expanding mean and
+/// variance are updated with Welford's method.
+pub fn rolling_zscore_kernel(data: &[f64], window: usize, expanding: bool) -> Vec<f64> {
+    let len = data.len();
+    let mut result = vec![f64::NAN; len];
+
+    if window == 0 || window > len {
+        return result;
+    }
+
+    if expanding {
+        let (mut mean, mut m2) = (0.0, 0.0);
+        for (i, &x) in data.iter().enumerate() {
+            let count = (i + 1) as f64;
+            let delta = x - mean;
+            mean += delta / count;
+            m2 += delta * (x - mean);
+            if i + 1 >= window {
+                result[i] = standardize_value(x, mean, (m2 / count).sqrt());
+            }
+        }
+        return result;
+    }
+
+    for i in (window - 1)..len {
+        result[i] = zscore_of_last(&data[(i + 1 - window)..=i]);
+    }
+
+    result
+}
+
+/// Z-score of the last value of `slice` against the slice's mean and population
+/// std; 0 when the std is zero
+fn zscore_of_last(slice: &[f64]) -> f64 {
+    let (mean, std) = standardize_params(slice, StandardizeMethod::ZScore);
+    standardize_value(slice[slice.len() - 1], mean, std)
+}
+
 /// Rolling Z-Score
 ///
 /// (x - rolling_mean(x, w)) / rolling_std(x, w)
@@ -129,41 +205,21 @@ pub fn compound_log_return<'py>(
 /// # Arguments
 /// * `data` - Data series
 /// * `window` - Rolling window size (default: 20)
+/// * `expanding` - Use all data up to each index instead of the last `window`;
+///   `window` still acts as the minimum number of periods (default: false)
 ///
 /// # Returns
 /// Numpy array with z-score values
 #[pyfunction]
-#[pyo3(name = "rolling_zscore_numba", signature = (data, window=20))]
+#[pyo3(name = "rolling_zscore_numba", signature = (data, window=20, expanding=false))]
 pub fn rolling_zscore<'py>(
     py: Python<'py>,
     data: PyReadonlyArray1<'py, f64>,
     window: usize,
+    expanding: bool,
 ) -> PyResult<Bound<'py, PyArray1<f64>>> {
     let data_slice = data.as_slice()?;
-    let len = data_slice.len();
-    let mut result = vec![f64::NAN; len];
-
-    if window == 0 || window > len {
-        return Ok(PyArray1::from_vec(py, result));
-    }
-
-    for i in (window - 1)..len {
-        let start = i + 1 - window;
-        let slice = &data_slice[start..=i];
-
-        let mean: f64 = slice.iter().sum::<f64>() / window as f64;
-        let variance: f64 = slice.iter()
-            .map(|x| (x - mean).powi(2))
-            .sum::<f64>() / window as f64;
-        let std = variance.sqrt();
-
-        if std != 0.0 {
-            result[i] = (data_slice[i] - mean) / std;
-        } else {
-            result[i] = 0.0;
-        }
-    }
-
+    let result = rolling_zscore_kernel(data_slice, window, expanding);
     Ok(PyArray1::from_vec(py, result))
 }
 
@@ -186,37 +242,59 @@ pub fn linear_regression_slope<'py>(
     window: usize,
 ) -> PyResult<Bound<'py, PyArray1<f64>>> {
     let data_slice = data.as_slice()?;
-    let len = data_slice.len();
-    let mut result = vec![f64::NAN; len];
+    let (slope, _, _) = rolling_linreg(data_slice, window);
 
-    if window == 0 || window > len {
-        return Ok(PyArray1::from_vec(py, result));
-    }
+    Ok(PyArray1::from_vec(py, slope))
+}
 
-    let w = window as f64;
-    let sum_x = w * (w - 1.0) / 2.0;
-    let sum_x2 = w * (w - 1.0) * (2.0 * w - 1.0) / 6.0;
-    let denom = w * sum_x2 - sum_x * sum_x;
+/// Regression forecast kernel: each window's least-squares line evaluated at
+/// x = n - 1 + steps, i.e. `steps` bars past the window's last bar
+pub fn regression_forecast_kernel(data: &[f64], n: usize, steps: usize) -> Vec<f64> {
+    let (slope, intercept, _) = rolling_linreg(data, n);
+    let x = (n + steps) as f64 - 1.0;
+    slope.iter().zip(&intercept).map(|(b, a)| a + b * x).collect()
+}
 
-    if denom == 0.0 {
-        return Ok(PyArray1::from_vec(py, result));
-    }
+/// Regression Forecast
+///
+/// # Arguments
+/// * `data` - Data series
+/// * `n` - Rolling window size (default: 20)
+/// * `steps` - Bars beyond the window end to project (default: 1)
+///
+/// # Returns
+/// Numpy array with the projected regression line value (NaN for first n-1 elements)
+#[pyfunction]
+#[pyo3(name = "regression_forecast_numba", signature = (data, n=20, steps=1))]
+pub fn regression_forecast<'py>(
+    py: Python<'py>,
+    data: PyReadonlyArray1<'py, f64>,
+    n: usize,
+    steps: usize,
+) -> PyResult<Bound<'py, PyArray1<f64>>> {
+    let result = regression_forecast_kernel(data.as_slice()?, n, steps);
+    Ok(PyArray1::from_vec(py, result))
+}
 
-    for i in (window - 1)..len {
-        let start = i + 1 - window;
-        let mut sum_y = 0.0;
-        let mut sum_xy = 0.0;
+/// Rolling percentile kernel: fraction of the window's values that are <= the
+/// current value (ties count as <=, NaN compares false)
+pub fn rolling_percentile_kernel(data: &[f64], window: usize) -> Vec<f64> {
+    let len = data.len();
+    let mut result = vec![f64::NAN; len];
 
-        for j in 0..window {
-            let y = data_slice[start + j];
-            sum_y += y;
-            sum_xy += j as f64 * y;
-        }
+    if window == 0 || window > len {
+        return result;
+    }
 
-        result[i] = (w * sum_xy - sum_x * sum_y) / denom;
+    let mut values = helpers::SortedWindow::new(window);
+    for i in 0..len {
+        values.push(data[i]);
+        if values.is_full() {
+            result[i] = values.count_at_or_below(data[i]) as f64 / window as f64;
+        }
     }
 
-    Ok(PyArray1::from_vec(py, result))
+    result
 }
 
 /// Rolling Percentile
@@ -237,26 +315,2248 @@ pub fn rolling_percentile<'py>(
     window: usize,
 ) -> PyResult<Bound<'py, PyArray1<f64>>> {
     let data_slice = data.as_slice()?;
-    let len = data_slice.len();
+    let result = rolling_percentile_kernel(data_slice, window);
+    Ok(PyArray1::from_vec(py, result))
+}
+
+/// Rolling Sum
+///
+/// NaN inputs contribute nothing to the window sum.
+///
+/// # Arguments
+/// * `data` - Data series
+/// * `n` - Rolling window size (default: 20)
+///
+/// # Returns
+/// Numpy array with window sums (NaN for first n-1 elements)
+#[pyfunction]
+#[pyo3(name = "rolling_sum_numba", signature = (data, n=20))]
+pub fn rolling_sum_py<'py>(
+    py: Python<'py>,
+    data: PyReadonlyArray1<'py, f64>,
+    n: usize,
+) -> PyResult<Bound<'py, PyArray1<f64>>> {
+    let data_slice = data.as_slice()?;
+    let result = rolling_sum(data_slice, n);
+    Ok(PyArray1::from_vec(py, result))
+}
+
+/// Rolling RMS kernel: sqrt(rolling_sum(x^2) / window), O(n) via running sum of squares
+pub fn rolling_rms_kernel(data: &[f64], window: usize) -> Vec<f64> {
+    let squares: Vec<f64> = data.iter().map(|x| x * x).collect();
+    let sum_sq = rolling_sum(&squares, window);
+
+    // Clamp tiny negative drift from the running subtraction before sqrt
+    sum_sq.iter()
+        .map(|&s| if s.is_nan() { f64::NAN } else { (s / window as f64).max(0.0).sqrt() })
+        .collect()
+}
+
+/// Rolling RMS (root mean square)
+///
+/// sqrt(mean(x^2)) over each window. NaN inputs are skipped as in rolling_sum.
+///
+/// # Arguments
+/// * `data` - Data series
+/// * `n` - Rolling window size (default: 20)
+///
+/// # Returns
+/// Numpy array with RMS values
+#[pyfunction]
+#[pyo3(name = "rolling_rms_numba", signature = (data, n=20))]
+pub fn rolling_rms<'py>(
+    py: Python<'py>,
+    data: PyReadonlyArray1<'py, f64>,
+    n: usize,
+) -> PyResult<Bound<'py, PyArray1<f64>>> {
+    let data_slice = data.as_slice()?;
+    let result = rolling_rms_kernel(data_slice, n);
+
+    Ok(PyArray1::from_vec(py, result))
+}
+
+/// Above-MA kernel: 1.0 where close > SMA(close, n), 0.0 otherwise, NaN during warmup
+pub fn pct_above_ma_kernel(close: &[f64], n: usize) -> Vec<f64> {
+    let ma = sma_kernel(close, n);
+
+    close.iter()
+        .zip(&ma)
+        .map(|(&c, &m)| if m.is_nan() { f64::NAN } else if c > m { 1.0 } else { 0.0 })
+        .collect()
+}
+
+/// Rolling above-MA kernel: fraction of the last `window` bars with close above its SMA
+pub fn rolling_pct_above_ma_kernel(close: &[f64], ma_n: usize, window: usize) -> Vec<f64> {
+    let len = close.len();
     let mut result = vec![f64::NAN; len];
 
-    if window == 0 || window > len {
-        return Ok(PyArray1::from_vec(py, result));
+    if ma_n == 0 || window == 0 {
+        return result;
     }
 
-    for i in (window - 1)..len {
-        let start = i + 1 - window;
-        let current = data_slice[i];
-        let mut count = 0usize;
+    let above = pct_above_ma_kernel(close, ma_n);
+    let count = rolling_sum(&above, window);
+
+    // Only report once the window holds `window` post-warmup flags
+    for i in (ma_n + window - 2)..len {
+        result[i] = count[i] / window as f64;
+    }
+
+    result
+}
+
+/// Percent Above MA
+///
+/// # Arguments
+/// * `close` - Close price series
+/// * `n` - SMA period (default: 50)
+///
+/// # Returns
+/// Numpy array with 1.0 where close is above its SMA, 0.0 otherwise (NaN during warmup)
+#[pyfunction]
+#[pyo3(name = "pct_above_ma_numba", signature = (close, n=50))]
+pub fn pct_above_ma<'py>(
+    py: Python<'py>,
+    close: PyReadonlyArray1<'py, f64>,
+    n: usize,
+) -> PyResult<Bound<'py, PyArray1<f64>>> {
+    let close_slice = close.as_slice()?;
+    let result = pct_above_ma_kernel(close_slice, n);
+    Ok(PyArray1::from_vec(py, result))
+}
+
+/// Rolling Percent Above MA
+///
+/// # Arguments
+/// * `close` - Close price series
+/// * `ma_n` - SMA period (default: 50)
+/// * `window` - Number of bars counted (default: 200)
+///
+/// # Returns
+/// Numpy array with the fraction (0.0 to 1.0) of the last `window` bars above the SMA
+#[pyfunction]
+#[pyo3(name = "rolling_pct_above_ma_numba", signature = (close, ma_n=50, window=200))]
+pub fn rolling_pct_above_ma<'py>(
+    py: Python<'py>,
+    close: PyReadonlyArray1<'py, f64>,
+    ma_n: usize,
+    window: usize,
+) -> PyResult<Bound<'py, PyArray1<f64>>> {
+    let close_slice = close.as_slice()?;
+    let result = rolling_pct_above_ma_kernel(close_slice, ma_n, window);
+    Ok(PyArray1::from_vec(py, result))
+}
 
-        for j in start..=i {
-            if data_slice[j] <= current {
-                count += 1;
+/// NaN fill kernel with pandas-like semantics:
+/// "ffill" carries the last valid value forward (leading NaN stay NaN),
+/// "bfill" carries the next valid value backward (trailing NaN stay NaN),
+/// "zero" replaces every NaN with 0.0 and "none" returns the input unchanged.
+pub fn fill_warmup_kernel(data: &[f64], method: &str) -> Result<Vec<f64>, String> {
+    let mut result = data.to_vec();
+
+    match method {
+        "none" => {}
+        "zero" => {
+            for v in result.iter_mut() {
+                if v.is_nan() {
+                    *v = 0.0;
+                }
+            }
+        }
+        "ffill" => {
+            let mut last = f64::NAN;
+            for v in result.iter_mut() {
+                if v.is_nan() {
+                    *v = last;
+                } else {
+                    last = *v;
+                }
+            }
+        }
+        "bfill" => {
+            let mut next = f64::NAN;
+            for v in result.iter_mut().rev() {
+                if v.is_nan() {
+                    *v = next;
+                } else {
+                    next = *v;
+                }
             }
         }
+        other => {
+            return Err(format!("unknown fill method '{}', expected one of: none, ffill, bfill, zero", other));
+        }
+    }
+
+    Ok(result)
+}
+
+/// Fill Warmup NaN
+///
+/// Post-processes any indicator output for consumers that cannot handle NaN.
+///
+/// # Arguments
+/// * `data` - Indicator output
+/// * `method` - "none", "ffill", "bfill" or "zero" (default: "none")
+///
+/// # Returns
+/// Numpy array with NaN values filled according to `method`
+#[pyfunction]
+#[pyo3(name = "fill_warmup_numba", signature = (data, method="none"))]
+pub fn fill_warmup<'py>(
+    py: Python<'py>,
+    data: PyReadonlyArray1<'py, f64>,
+    method: &str,
+) -> PyResult<Bound<'py, PyArray1<f64>>> {
+    let data_slice = data.as_slice()?;
+    let result = fill_warmup_kernel(data_slice, method).map_err(PyValueError::new_err)?;
+    Ok(PyArray1::from_vec(py, result))
+}
+
+/// Round to Tick (preprocessor)
+///
+/// # Arguments
+/// * `data` - Input series
+/// * `tick_size` - Price grid spacing, must be positive
+///
+/// # Returns
+/// Numpy array with each value rounded to the nearest multiple of `tick_size`
+#[pyfunction]
+#[pyo3(name = "round_to_tick_numba", signature = (data, tick_size))]
+pub fn round_to_tick<'py>(
+    py: Python<'py>,
+    data: PyReadonlyArray1<'py, f64>,
+    tick_size: f64,
+) -> PyResult<Bound<'py, PyArray1<f64>>> {
+    let data_slice = data.as_slice()?;
+    let result = helpers::round_to_tick_kernel(data_slice, tick_size).map_err(PyValueError::new_err)?;
+    Ok(PyArray1::from_vec(py, result))
+}
+
+/// Minimum Move Filter (preprocessor)
+///
+/// # Arguments
+/// * `data` - Input series
+/// * `min_move` - Smallest absolute change that is passed through, must be non-negative
+///
+/// # Returns
+/// Numpy array holding the last accepted value until a move exceeds `min_move`
+#[pyfunction]
+#[pyo3(name = "min_move_filter_numba", signature = (data, min_move))]
+pub fn min_move_filter<'py>(
+    py: Python<'py>,
+    data: PyReadonlyArray1<'py, f64>,
+    min_move: f64,
+) -> PyResult<Bound<'py, PyArray1<f64>>> {
+    let data_slice = data.as_slice()?;
+    let result = helpers::min_move_filter_kernel(data_slice, min_move).map_err(PyValueError::new_err)?;
+    Ok(PyArray1::from_vec(py, result))
+}
 
-        result[i] = count as f64 / window as f64;
+/// Drawdown duration kernel: bars since the reference high, 0 on a new high.
+/// The reference is the all-time high, or the high of the last `window` bars.
+pub fn drawdown_duration_kernel(close: &[f64], window: Option<usize>) -> Vec<f64> {
+    let len = close.len();
+    let mut duration = vec![f64::NAN; len];
+
+    match window {
+        None => {
+            let mut peak = f64::NEG_INFINITY;
+            let mut peak_idx = 0;
+            for i in 0..len {
+                if close[i] >= peak {
+                    peak = close[i];
+                    peak_idx = i;
+                }
+                duration[i] = (i - peak_idx) as f64;
+            }
+        }
+        Some(w) => {
+            // Candidate peaks with strictly decreasing closes; the front is the
+            // latest bar holding the window high
+            let w = w.max(1);
+            let mut peaks: VecDeque<usize> = VecDeque::with_capacity(w);
+            for i in 0..len {
+                while peaks.back().is_some_and(|&j| close[j] <= close[i]) {
+                    peaks.pop_back();
+                }
+                peaks.push_back(i);
+                while peaks.front().is_some_and(|&j| j + w <= i) {
+                    peaks.pop_front();
+                }
+                duration[i] = (i - peaks[0]) as f64;
+            }
+        }
     }
 
+    duration
+}
+
+/// Drawdown Duration
+///
+/// # Arguments
+/// * `close` - Close price series
+/// * `window` - Rolling window for the reference high; None uses the all-time high (default: None)
+///
+/// # Returns
+/// Numpy array with the number of bars since the reference high
+#[pyfunction]
+#[pyo3(name = "drawdown_duration_numba", signature = (close, window=None))]
+pub fn drawdown_duration<'py>(
+    py: Python<'py>,
+    close: PyReadonlyArray1<'py, f64>,
+    window: Option<usize>,
+) -> PyResult<Bound<'py, PyArray1<f64>>> {
+    let close_slice = close.as_slice()?;
+    let result = drawdown_duration_kernel(close_slice, window);
     Ok(PyArray1::from_vec(py, result))
 }
+
+/// Peak, trough and worst drawdown of a run of consecutive prices. Runs combine
+/// in order: the worst drop across the join runs from the earlier peak to the
+/// later trough, so window summaries can be built from pieces.
+#[derive(Clone, Copy)]
+struct DrawdownSpan {
+    high: f64,
+    low: f64,
+    max_drawdown: f64,
+}
+
+impl DrawdownSpan {
+    fn of(price: f64) -> Self {
+        Self { high: price, low: price, max_drawdown: 0.0 }
+    }
+
+    /// This run followed by `later`
+    fn then(self, later: Self) -> Self {
+        Self {
+            high: self.high.max(later.high),
+            low: self.low.min(later.low),
+            max_drawdown: self.max_drawdown
+                .min(later.max_drawdown)
+                .min((later.low - self.high) / self.high),
+        }
+    }
+}
+
+fn join_spans(earlier: Option<DrawdownSpan>, later: Option<DrawdownSpan>) -> Option<DrawdownSpan> {
+    match (earlier, later) {
+        (Some(a), Some(b)) => Some(a.then(b)),
+        (a, b) => a.or(b),
+    }
+}
+
+/// Max drawdown kernel: worst peak-to-trough drop in percent (<= 0) over the last
+/// `n` closes, or with `expanding` over close[0..=i] once `n` closes are seen.
+///
+/// The rolling window is a two-stack queue of `DrawdownSpan`s: newer prices are
+/// folded onto one summary, and when the oldest price leaves, the newer stack is
+/// flipped into suffix summaries, so each close costs amortized O(1).
+pub fn max_drawdown_kernel(close: &[f64], n: usize, expanding: bool) -> Vec<f64> {
+    let len = close.len();
+    let mut result = vec![f64::NAN; len];
+
+    if n == 0 || n > len {
+        return result;
+    }
+
+    if expanding {
+        let mut span: Option<DrawdownSpan> = None;
+        for i in 0..len {
+            span = join_spans(span, Some(DrawdownSpan::of(close[i])));
+            if i + 1 >= n {
+                result[i] = span.map_or(f64::NAN, |s| s.max_drawdown * 100.0);
+            }
+        }
+        return result;
+    }
+
+    // `older` holds suffix summaries with the oldest price on top; `newer` holds
+    // the prices added since the last flip, summarized by `newer_span`
+    let mut older: Vec<DrawdownSpan> = Vec::with_capacity(n);
+    let mut newer: Vec<f64> = Vec::with_capacity(n);
+    let mut newer_span: Option<DrawdownSpan> = None;
+
+    for i in 0..len {
+        newer.push(close[i]);
+        newer_span = join_spans(newer_span, Some(DrawdownSpan::of(close[i])));
+
+        if i >= n {
+            if older.is_empty() {
+                for &price in newer.iter().rev() {
+                    let span = DrawdownSpan::of(price);
+                    older.push(older.last().map_or(span, |&suffix| span.then(suffix)));
+                }
+                newer.clear();
+                newer_span = None;
+            }
+            older.pop();
+        }
+
+        if i + 1 >= n {
+            let window = join_spans(older.last().copied(), newer_span);
+            result[i] = window.map_or(f64::NAN, |s| s.max_drawdown * 100.0);
+        }
+    }
+
+    result
+}
+
+/// Max Drawdown
+///
+/// # Arguments
+/// * `close` - Close price series
+/// * `n` - Rolling window size (default: 252)
+/// * `expanding` - Use all closes up to each index instead of the last `n`;
+///   `n` still acts as the minimum number of periods (default: false)
+///
+/// # Returns
+/// Numpy array with the max drawdown in percent (0 or negative), as the full-window
+/// values of `MaxDrawdownStreaming`
+#[pyfunction]
+#[pyo3(name = "max_drawdown_numba", signature = (close, n=252, expanding=false))]
+pub fn max_drawdown<'py>(
+    py: Python<'py>,
+    close: PyReadonlyArray1<'py, f64>,
+    n: usize,
+    expanding: bool,
+) -> PyResult<Bound<'py, PyArray1<f64>>> {
+    let result = max_drawdown_kernel(close.as_slice()?, n, expanding);
+    Ok(PyArray1::from_vec(py, result))
+}
+
+/// Rolling entropy kernel: normalized Shannon entropy of each window's values.
+///
+/// Each window is split into `bins` equal-width buckets spanning its own min..max;
+/// the maximum falls in the last bucket. Entropy is divided by ln(bins) so the
+/// result lies in [0, 1]. A window with identical values has entropy 0, and any
+/// NaN in the window yields NaN.
+pub fn rolling_entropy_kernel(data: &[f64], window: usize, bins: usize) -> Result<Vec<f64>, String> {
+    if bins < 2 {
+        return Err("bins must be at least 2".to_string());
+    }
+
+    let len = data.len();
+    let mut result = vec![f64::NAN; len];
+
+    if window == 0 || window > len {
+        return Ok(result);
+    }
+
+    let max_entropy = (bins as f64).ln();
+    let mut counts = vec![0usize; bins];
+
+    for i in (window - 1)..len {
+        let slice = &data[(i + 1 - window)..=i];
+        if slice.iter().any(|x| x.is_nan()) {
+            continue;
+        }
+
+        let min = slice.iter().fold(f64::INFINITY, |a, &b| a.min(b));
+        let max = slice.iter().fold(f64::NEG_INFINITY, |a, &b| a.max(b));
+        let range = max - min;
+        if range == 0.0 {
+            result[i] = 0.0;
+            continue;
+        }
+
+        counts.iter_mut().for_each(|c| *c = 0);
+        for &x in slice {
+            let bin = (((x - min) / range) * bins as f64) as usize;
+            counts[bin.min(bins - 1)] += 1;
+        }
+
+        let entropy: f64 = counts
+            .iter()
+            .filter(|&&c| c > 0)
+            .map(|&c| {
+                let p = c as f64 / window as f64;
+                -p * p.ln()
+            })
+            .sum();
+        result[i] = entropy / max_entropy;
+    }
+
+    Ok(result)
+}
+
+/// Rolling Entropy
+///
+/// # Arguments
+/// * `data` - Data series (typically returns)
+/// * `n` - Rolling window size (default: 50)
+/// * `bins` - Number of equal-width buckets per window, at least 2 (default: 10)
+///
+/// # Returns
+/// Numpy array with normalized Shannon entropy in [0, 1]
+#[pyfunction]
+#[pyo3(name = "rolling_entropy_numba", signature = (data, n=50, bins=10))]
+pub fn rolling_entropy<'py>(
+    py: Python<'py>,
+    data: PyReadonlyArray1<'py, f64>,
+    n: usize,
+    bins: usize,
+) -> PyResult<Bound<'py, PyArray1<f64>>> {
+    let data_slice = data.as_slice()?;
+    let result = rolling_entropy_kernel(data_slice, n, bins).map_err(PyValueError::new_err)?;
+    Ok(PyArray1::from_vec(py, result))
+}
+
+/// Rolling historical VaR / CVaR kernel over simple (fractional) returns.
+///
+/// Each window holds the last `window` returns, so values start once `window + 1`
+/// prices exist. VaR is the `alpha`-quantile of the sorted window (linear
+/// interpolation, as numpy's default); CVaR is the mean of returns at or below it.
+/// Both are returns, so losses are negative and CVaR <= VaR.
+pub fn rolling_var_cvar_kernel(close: &[f64], window: usize, alpha: f64) -> Result<(Vec<f64>, Vec<f64>), String> {
+    if !(alpha > 0.0 && alpha < 1.0) {
+        return Err(format!("alpha must be in (0, 1), got {}", alpha));
+    }
+
+    let len = close.len();
+    let mut var = vec![f64::NAN; len];
+    let mut cvar = vec![f64::NAN; len];
+
+    if window == 0 || window >= len {
+        return Ok((var, cvar));
+    }
+
+    let returns = daily_return_kernel(close, false);
+    let mut sorted = Vec::with_capacity(window);
+
+    for i in window..len {
+        sorted.clear();
+        sorted.extend_from_slice(&returns[(i + 1 - window)..=i]);
+        if sorted.iter().any(|r| r.is_nan()) {
+            continue;
+        }
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+        let quantile = helpers::quantile_of_sorted(&sorted, alpha);
+
+        let tail: Vec<f64> = sorted.iter().copied().take_while(|&r| r <= quantile).collect();
+        var[i] = quantile;
+        cvar[i] = tail.iter().sum::<f64>() / tail.len() as f64;
+    }
+
+    Ok((var, cvar))
+}
+
+/// Rolling Value-at-Risk (historical)
+///
+/// # Arguments
+/// * `close` - Close price series
+/// * `n` - Number of returns per window (default: 250)
+/// * `alpha` - Tail probability in (0, 1) (default: 0.05)
+///
+/// # Returns
+/// Numpy array with the `alpha`-quantile of each window's simple returns
+#[pyfunction]
+#[pyo3(name = "rolling_var_numba", signature = (close, n=250, alpha=0.05))]
+pub fn rolling_var<'py>(
+    py: Python<'py>,
+    close: PyReadonlyArray1<'py, f64>,
+    n: usize,
+    alpha: f64,
+) -> PyResult<Bound<'py, PyArray1<f64>>> {
+    let close_slice = close.as_slice()?;
+    let (var, _) = rolling_var_cvar_kernel(close_slice, n, alpha).map_err(PyValueError::new_err)?;
+    Ok(PyArray1::from_vec(py, var))
+}
+
+/// Rolling Conditional Value-at-Risk (historical expected shortfall)
+///
+/// # Arguments
+/// * `close` - Close price series
+/// * `n` - Number of returns per window (default: 250)
+/// * `alpha` - Tail probability in (0, 1) (default: 0.05)
+///
+/// # Returns
+/// Numpy array with the mean of each window's returns at or below its VaR
+#[pyfunction]
+#[pyo3(name = "rolling_cvar_numba", signature = (close, n=250, alpha=0.05))]
+pub fn rolling_cvar<'py>(
+    py: Python<'py>,
+    close: PyReadonlyArray1<'py, f64>,
+    n: usize,
+    alpha: f64,
+) -> PyResult<Bound<'py, PyArray1<f64>>> {
+    let close_slice = close.as_slice()?;
+    let (_, cvar) = rolling_var_cvar_kernel(close_slice, n, alpha).map_err(PyValueError::new_err)?;
+    Ok(PyArray1::from_vec(py, cvar))
+}
+
+/// Rolling valid-count kernel: number of non-NaN values in each window
+pub fn rolling_count_valid_kernel(data: &[f64], window: usize) -> Vec<f64> {
+    let valid: Vec<f64> = data.iter().map(|x| if x.is_nan() { 0.0 } else { 1.0 }).collect();
+    rolling_sum(&valid, window)
+}
+
+/// Rolling Count of Valid Values
+///
+/// Counts the non-NaN points in each window, e.g. to gate indicators computed on
+/// gappy data.
+///
+/// # Arguments
+/// * `data` - Data series
+/// * `n` - Rolling window size
+///
+/// # Returns
+/// Numpy array with counts in 0..=n (NaN for first n-1 elements)
+#[pyfunction]
+#[pyo3(name = "rolling_count_valid_numba", signature = (data, n))]
+pub fn rolling_count_valid<'py>(
+    py: Python<'py>,
+    data: PyReadonlyArray1<'py, f64>,
+    n: usize,
+) -> PyResult<Bound<'py, PyArray1<f64>>> {
+    let data_slice = data.as_slice()?;
+    let result = rolling_count_valid_kernel(data_slice, n);
+    Ok(PyArray1::from_vec(py, result))
+}
+
+/// Up-bar ratio kernel: fraction of the last `window` bars that closed above the
+/// previous close. Bar 0 has no prior close, so values start at index `window`.
+pub fn up_bar_ratio_kernel(close: &[f64], window: usize) -> Vec<f64> {
+    let len = close.len();
+    let mut result = vec![f64::NAN; len];
+
+    if window == 0 || window >= len {
+        return result;
+    }
+
+    let mut up = vec![0.0; len];
+    for i in 1..len {
+        if close[i] > close[i - 1] {
+            up[i] = 1.0;
+        }
+    }
+
+    let count = rolling_sum(&up, window);
+    for i in window..len {
+        result[i] = count[i] / window as f64;
+    }
+
+    result
+}
+
+/// Up Bar Ratio
+///
+/// # Arguments
+/// * `close` - Close price series
+/// * `n` - Rolling window size (default: 20)
+///
+/// # Returns
+/// Numpy array with the fraction of up closes in each window (0.0 to 1.0)
+#[pyfunction]
+#[pyo3(name = "up_bar_ratio_numba", signature = (close, n=20))]
+pub fn up_bar_ratio<'py>(
+    py: Python<'py>,
+    close: PyReadonlyArray1<'py, f64>,
+    n: usize,
+) -> PyResult<Bound<'py, PyArray1<f64>>> {
+    let close_slice = close.as_slice()?;
+    let result = up_bar_ratio_kernel(close_slice, n);
+    Ok(PyArray1::from_vec(py, result))
+}
+
+/// Win rate kernel: fraction of the last `window` simple returns that are positive.
+/// Bar 0 has no return, so values start at index `window`.
+pub fn win_rate_kernel(close: &[f64], window: usize) -> Vec<f64> {
+    let len = close.len();
+    let mut result = vec![f64::NAN; len];
+
+    if window == 0 || window >= len {
+        return result;
+    }
+
+    let wins: Vec<f64> = daily_return_kernel(close, false)
+        .into_iter()
+        .map(|r| if r > 0.0 { 1.0 } else { 0.0 })
+        .collect();
+
+    let count = rolling_sum(&wins, window);
+    for i in window..len {
+        result[i] = count[i] / window as f64;
+    }
+
+    result
+}
+
+/// Profit factor kernel: sum of positive returns over the absolute sum of negative
+/// returns in each window; NaN where the window has no losing return
+pub fn profit_factor_kernel(close: &[f64], window: usize) -> Vec<f64> {
+    let len = close.len();
+    let mut result = vec![f64::NAN; len];
+
+    if window == 0 || window >= len {
+        return result;
+    }
+
+    let returns = daily_return_kernel(close, false);
+    let gains: Vec<f64> = returns.iter().map(|&r| if r > 0.0 { r } else { 0.0 }).collect();
+    let losses: Vec<f64> = returns.iter().map(|&r| if r < 0.0 { -r } else { 0.0 }).collect();
+
+    let gain_sum = rolling_sum(&gains, window);
+    let loss_sum = rolling_sum(&losses, window);
+    for i in window..len {
+        if loss_sum[i] > 0.0 {
+            result[i] = gain_sum[i] / loss_sum[i];
+        }
+    }
+
+    result
+}
+
+/// Win Rate
+///
+/// # Arguments
+/// * `close` - Close price series
+/// * `n` - Rolling window size in returns (default: 60)
+///
+/// # Returns
+/// Numpy array with the fraction of positive returns in each window (0.0 to 1.0)
+#[pyfunction]
+#[pyo3(name = "win_rate_numba", signature = (close, n=60))]
+pub fn win_rate<'py>(
+    py: Python<'py>,
+    close: PyReadonlyArray1<'py, f64>,
+    n: usize,
+) -> PyResult<Bound<'py, PyArray1<f64>>> {
+    let close_slice = close.as_slice()?;
+    let result = win_rate_kernel(close_slice, n);
+    Ok(PyArray1::from_vec(py, result))
+}
+
+/// Profit Factor
+///
+/// # Arguments
+/// * `close` - Close price series
+/// * `n` - Rolling window size in returns (default: 60)
+///
+/// # Returns
+/// Numpy array with sum(positive returns) / |sum(negative returns)| per window;
+/// NaN where the window has no negative return
+#[pyfunction]
+#[pyo3(name = "profit_factor_numba", signature = (close, n=60))]
+pub fn profit_factor<'py>(
+    py: Python<'py>,
+    close: PyReadonlyArray1<'py, f64>,
+    n: usize,
+) -> PyResult<Bound<'py, PyArray1<f64>>> {
+    let close_slice = close.as_slice()?;
+    let result = profit_factor_kernel(close_slice, n);
+    Ok(PyArray1::from_vec(py, result))
+}
+
+/// Rolling OLS residual kernel: y - (alpha + beta * x) at the last bar of each
+/// window, with the window's (alpha, beta) from `rolling_ols`
+pub fn rolling_ols_residual_kernel(
+    y: &[f64],
+    x: &[f64],
+    window: usize,
+) -> (Vec<f64>, Vec<f64>, Vec<f64>) {
+    let (alpha, beta) = rolling_ols(y, x, window);
+
+    let mut residual = vec![f64::NAN; y.len()];
+    for i in 0..y.len() {
+        if !beta[i].is_nan() {
+            residual[i] = y[i] - (alpha[i] + beta[i] * x[i]);
+        }
+    }
+
+    (residual, alpha, beta)
+}
+
+/// Rolling OLS Residual (beta-adjusted spread)
+///
+/// # Arguments
+/// * `y` - Dependent series
+/// * `x` - Independent series
+/// * `n` - Rolling regression window (default: 60)
+/// * `return_coefficients` - Also return the rolling alpha and beta (default: false)
+///
+/// # Returns
+/// Numpy array with the latest-bar residual y - (alpha + beta * x), or a tuple of
+/// (residual, alpha, beta) when `return_coefficients` is true
+#[pyfunction]
+#[pyo3(name = "rolling_ols_residual_numba", signature = (y, x, n=60, return_coefficients=false))]
+pub fn rolling_ols_residual<'py>(
+    py: Python<'py>,
+    y: PyReadonlyArray1<'py, f64>,
+    x: PyReadonlyArray1<'py, f64>,
+    n: usize,
+    return_coefficients: bool,
+) -> PyResult<Bound<'py, PyAny>> {
+    let y_slice = y.as_slice()?;
+    let x_slice = x.as_slice()?;
+    helpers::check_equal_lengths(&[("y", y_slice.len()), ("x", x_slice.len())])
+        .map_err(PyValueError::new_err)?;
+
+    let (residual, alpha, beta) = rolling_ols_residual_kernel(y_slice, x_slice, n);
+
+    if return_coefficients {
+        let outputs = [
+            PyArray1::from_vec(py, residual),
+            PyArray1::from_vec(py, alpha),
+            PyArray1::from_vec(py, beta),
+        ];
+        Ok(PyTuple::new(py, outputs)?.into_any())
+    } else {
+        Ok(PyArray1::from_vec(py, residual).into_any())
+    }
+}
+
+/// Percent distance of close from a rolling extreme: (close - extreme) / extreme * 100.
+/// NaN during warmup or where the extreme is zero.
+fn pct_from_extreme(close: &[f64], extreme: &[f64]) -> Vec<f64> {
+    close
+        .iter()
+        .zip(extreme)
+        .map(|(&c, &e)| if e != 0.0 { (c - e) / e * 100.0 } else { f64::NAN })
+        .collect()
+}
+
+/// Percent from rolling high kernel: <= 0, and 0 when close is the window high
+pub fn pct_from_high_kernel(close: &[f64], window: usize) -> Vec<f64> {
+    pct_from_extreme(close, &rolling_max(close, window))
+}
+
+/// Percent from rolling low kernel: >= 0, and 0 when close is the window low
+pub fn pct_from_low_kernel(close: &[f64], window: usize) -> Vec<f64> {
+    pct_from_extreme(close, &rolling_min(close, window))
+}
+
+/// Percent From High (e.g. distance to the 52-week high)
+///
+/// # Arguments
+/// * `close` - Close price series
+/// * `n` - Rolling window size (default: 252)
+///
+/// # Returns
+/// Numpy array with (close - rolling max) / rolling max * 100, NaN for the first n-1 elements
+#[pyfunction]
+#[pyo3(name = "pct_from_high_numba", signature = (close, n=252))]
+pub fn pct_from_high<'py>(
+    py: Python<'py>,
+    close: PyReadonlyArray1<'py, f64>,
+    n: usize,
+) -> PyResult<Bound<'py, PyArray1<f64>>> {
+    let close_slice = close.as_slice()?;
+    let result = pct_from_high_kernel(close_slice, n);
+    Ok(PyArray1::from_vec(py, result))
+}
+
+/// Percent From Low (e.g. distance above the 52-week low)
+///
+/// # Arguments
+/// * `close` - Close price series
+/// * `n` - Rolling window size (default: 252)
+///
+/// # Returns
+/// Numpy array with (close - rolling min) / rolling min * 100, NaN for the first n-1 elements
+#[pyfunction]
+#[pyo3(name = "pct_from_low_numba", signature = (close, n=252))]
+pub fn pct_from_low<'py>(
+    py: Python<'py>,
+    close: PyReadonlyArray1<'py, f64>,
+    n: usize,
+) -> PyResult<Bound<'py, PyArray1<f64>>> {
+    let close_slice = close.as_slice()?;
+    let result = pct_from_low_kernel(close_slice, n);
+    Ok(PyArray1::from_vec(py, result))
+}
+
+/// New high/low kernel. The flag is +1 when high exceeds the prior `n` bars' high,
+/// -1 when low undercuts the prior `n` bars' low (0 for an outside bar doing both),
+/// else 0; NaN for the first `n` bars. The oscillator is the flags' rolling sum over
+/// `n_sum` bars, NaN until `n_sum` flags exist. Returns (flags, oscillator).
+pub fn new_high_low_kernel(high: &[f64], low: &[f64], n: usize, n_sum: usize) -> (Vec<f64>, Vec<f64>) {
+    let len = high.len();
+    let mut flags = vec![f64::NAN; len];
+
+    if n == 0 || n >= len {
+        return (flags.clone(), flags);
+    }
+
+    let prior_high = rolling_max(high, n);
+    let prior_low = rolling_min(low, n);
+    for i in n..len {
+        let up = if high[i] > prior_high[i - 1] { 1.0 } else { 0.0 };
+        let down = if low[i] < prior_low[i - 1] { 1.0 } else { 0.0 };
+        flags[i] = up - down;
+    }
+
+    let mut oscillator = rolling_sum(&flags, n_sum);
+    mask_warmup(&mut oscillator, n + n_sum - 1);
+
+    (flags, oscillator)
+}
+
+/// New High/Low Oscillator
+///
+/// # Arguments
+/// * `high` - High price series
+/// * `low` - Low price series
+/// * `n` - Lookback for a new high or low (default: 20)
+/// * `n_sum` - Window of the rolling sum of flags (default: 10)
+///
+/// # Returns
+/// Tuple of (flags, oscillator) as numpy arrays; flags are +1 new high, -1 new low, 0 otherwise
+#[pyfunction]
+#[pyo3(name = "new_high_low_oscillator_numba", signature = (high, low, n=20, n_sum=10))]
+pub fn new_high_low_oscillator<'py>(
+    py: Python<'py>,
+    high: PyReadonlyArray1<'py, f64>,
+    low: PyReadonlyArray1<'py, f64>,
+    n: usize,
+    n_sum: usize,
+) -> PyResult<(Bound<'py, PyArray1<f64>>, Bound<'py, PyArray1<f64>>)> {
+    let high_slice = high.as_slice()?;
+    let low_slice = low.as_slice()?;
+    helpers::check_equal_lengths(&[("high", high_slice.len()), ("low", low_slice.len())])
+        .map_err(PyValueError::new_err)?;
+
+    let (flags, oscillator) = new_high_low_kernel(high_slice, low_slice, n, n_sum);
+
+    Ok((
+        PyArray1::from_vec(py, flags),
+        PyArray1::from_vec(py, oscillator),
+    ))
+}
+
+/// Spread z-score kernel: rolling z-score of a - beta * b over `window` bars.
+/// A fixed `beta` gives one spread series; with `beta = None` each bar uses the
+/// rolling OLS beta of `a` on `b` over the same window, applied to the whole window.
+pub fn spread_zscore_kernel(a: &[f64], b: &[f64], window: usize, beta: Option<f64>) -> Vec<f64> {
+    if let Some(beta) = beta {
+        let spread: Vec<f64> = a.iter().zip(b).map(|(&x, &y)| x - beta * y).collect();
+        return rolling_zscore_kernel(&spread, window, false);
+    }
+
+    let len = a.len();
+    let mut result = vec![f64::NAN; len];
+    let (_, rolling_beta) = rolling_ols(a, b, window);
+
+    for i in 0..len {
+        let beta = rolling_beta[i];
+        if beta.is_nan() {
+            continue;
+        }
+        let start = i + 1 - window;
+        let spread: Vec<f64> = (start..=i).map(|j| a[j] - beta * b[j]).collect();
+        result[i] = zscore_of_last(&spread);
+    }
+
+    result
+}
+
+/// Spread Z-Score (pairs trading)
+///
+/// # Arguments
+/// * `a` - First series
+/// * `b` - Second series (the hedge leg)
+/// * `n` - Rolling window size (default: 60)
+/// * `beta` - Fixed hedge ratio; None estimates a rolling OLS beta of `a` on `b` (default: None)
+///
+/// # Returns
+/// Numpy array with the z-score of the spread a - beta * b, NaN for the first n-1 elements
+#[pyfunction]
+#[pyo3(name = "spread_zscore_numba", signature = (a, b, n=60, beta=None))]
+pub fn spread_zscore<'py>(
+    py: Python<'py>,
+    a: PyReadonlyArray1<'py, f64>,
+    b: PyReadonlyArray1<'py, f64>,
+    n: usize,
+    beta: Option<f64>,
+) -> PyResult<Bound<'py, PyArray1<f64>>> {
+    let a_slice = a.as_slice()?;
+    let b_slice = b.as_slice()?;
+    helpers::check_equal_lengths(&[("a", a_slice.len()), ("b", b_slice.len())])
+        .map_err(PyValueError::new_err)?;
+
+    let result = spread_zscore_kernel(a_slice, b_slice, n, beta);
+    Ok(PyArray1::from_vec(py, result))
+}
+
+/// Max streak kernel: longest run of consecutive up (`up = true`) or down closes
+/// among the last `window` close-to-close changes, NaN for the first `window` elements
+pub fn max_streak_kernel(close: &[f64], window: usize, up: bool) -> Vec<f64> {
+    let len = close.len();
+    let mut result = vec![f64::NAN; len];
+
+    if window == 0 || window >= len {
+        return result;
+    }
+
+    for i in window..len {
+        let mut run = 0usize;
+        let mut best = 0usize;
+        for j in (i + 1 - window)..=i {
+            let moved = if up { close[j] > close[j - 1] } else { close[j] < close[j - 1] };
+            if moved {
+                run += 1;
+                best = best.max(run);
+            } else {
+                run = 0;
+            }
+        }
+        result[i] = best as f64;
+    }
+
+    result
+}
+
+/// Max Up Streak
+///
+/// # Arguments
+/// * `close` - Close price series
+/// * `n` - Rolling window size in bars (default: 60)
+///
+/// # Returns
+/// Numpy array with the longest run of consecutive up closes within each window
+#[pyfunction]
+#[pyo3(name = "max_up_streak_numba", signature = (close, n=60))]
+pub fn max_up_streak<'py>(
+    py: Python<'py>,
+    close: PyReadonlyArray1<'py, f64>,
+    n: usize,
+) -> PyResult<Bound<'py, PyArray1<f64>>> {
+    let close_slice = close.as_slice()?;
+    let result = max_streak_kernel(close_slice, n, true);
+    Ok(PyArray1::from_vec(py, result))
+}
+
+/// Max Down Streak
+///
+/// # Arguments
+/// * `close` - Close price series
+/// * `n` - Rolling window size in bars (default: 60)
+///
+/// # Returns
+/// Numpy array with the longest run of consecutive down closes within each window
+#[pyfunction]
+#[pyo3(name = "max_down_streak_numba", signature = (close, n=60))]
+pub fn max_down_streak<'py>(
+    py: Python<'py>,
+    close: PyReadonlyArray1<'py, f64>,
+    n: usize,
+) -> PyResult<Bound<'py, PyArray1<f64>>> {
+    let close_slice = close.as_slice()?;
+    let result = max_streak_kernel(close_slice, n, false);
+    Ok(PyArray1::from_vec(py, result))
+}
+
+/// Reduction applied by `rolling_reduce_kernel`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ReduceOp {
+    Min,
+    Max,
+    Mean,
+    Sum,
+    Std,
+    Median,
+}
+
+impl ReduceOp {
+    pub fn parse(op: &str) -> Result<Self, String> {
+        match op {
+            "min" => Ok(ReduceOp::Min),
+            "max" => Ok(ReduceOp::Max),
+            "mean" => Ok(ReduceOp::Mean),
+            "sum" => Ok(ReduceOp::Sum),
+            "std" => Ok(ReduceOp::Std),
+            "median" => Ok(ReduceOp::Median),
+            other => Err(format!(
+                "unknown op '{}', expected one of: min, max, mean, sum, std, median",
+                other
+            )),
+        }
+    }
+}
+
+/// Rolling reduce kernel: routes `op` to its dedicated rolling kernel. Windows
+/// that contain a NaN are NaN for every op, as are the first window-1 elements.
+pub fn rolling_reduce_kernel(data: &[f64], window: usize, op: ReduceOp) -> Vec<f64> {
+    let mut result = match op {
+        ReduceOp::Min => rolling_min(data, window),
+        ReduceOp::Max => rolling_max(data, window),
+        ReduceOp::Mean => sma_kernel(data, window),
+        ReduceOp::Sum => rolling_sum(data, window),
+        ReduceOp::Std => rolling_std(data, window),
+        ReduceOp::Median => rolling_median(data, window),
+    };
+
+    let valid = rolling_count_valid_kernel(data, window);
+    for (r, &count) in result.iter_mut().zip(&valid) {
+        if count != window as f64 {
+            *r = f64::NAN;
+        }
+    }
+
+    result
+}
+
+/// Rolling Reduce
+///
+/// Single entry point for the common rolling reductions.
+///
+/// # Arguments
+/// * `data` - Data series
+/// * `n` - Rolling window size
+/// * `op` - "min", "max", "mean", "sum", "std" (population) or "median"
+///
+/// # Returns
+/// Numpy array with the reduced value of each window, NaN for the first n-1
+/// elements and for windows containing NaN
+#[pyfunction]
+#[pyo3(name = "rolling_reduce_numba", signature = (data, n, op))]
+pub fn rolling_reduce<'py>(
+    py: Python<'py>,
+    data: PyReadonlyArray1<'py, f64>,
+    n: usize,
+    op: &str,
+) -> PyResult<Bound<'py, PyArray1<f64>>> {
+    let data_slice = data.as_slice()?;
+    let op = ReduceOp::parse(op).map_err(PyValueError::new_err)?;
+    let result = rolling_reduce_kernel(data_slice, n, op);
+    Ok(PyArray1::from_vec(py, result))
+}
+
+/// Gap kernel: open-to-prior-close gap in percent, first value NaN
+pub fn gap_kernel(open: &[f64], close: &[f64]) -> Vec<f64> {
+    let len = open.len();
+    let mut result = vec![f64::NAN; len];
+
+    for i in 1..len {
+        if close[i - 1] != 0.0 {
+            result[i] = (open[i] - close[i - 1]) / close[i - 1] * 100.0;
+        }
+    }
+
+    result
+}
+
+/// Gap fill kernel: 1.0 when the bar traded back to the prior close after gapping
+/// away from it (low <= prior close for up-gaps, high >= prior close for down-gaps),
+/// 0.0 for unfilled gaps and bars without a gap, first value NaN
+pub fn gap_fill_kernel(open: &[f64], high: &[f64], low: &[f64], close: &[f64]) -> Vec<f64> {
+    let len = open.len();
+    let mut result = vec![f64::NAN; len];
+
+    for i in 1..len {
+        let prev_close = close[i - 1];
+        let filled = (open[i] > prev_close && low[i] <= prev_close)
+            || (open[i] < prev_close && high[i] >= prev_close);
+        result[i] = if filled { 1.0 } else { 0.0 };
+    }
+
+    result
+}
+
+/// Gap
+///
+/// # Arguments
+/// * `open` - Open price series
+/// * `close` - Close price series
+///
+/// # Returns
+/// Numpy array with (open - prior close) / prior close * 100, NaN for the first element
+#[pyfunction]
+#[pyo3(name = "gap_numba", signature = (open, close))]
+pub fn gap<'py>(
+    py: Python<'py>,
+    open: PyReadonlyArray1<'py, f64>,
+    close: PyReadonlyArray1<'py, f64>,
+) -> PyResult<Bound<'py, PyArray1<f64>>> {
+    let open_slice = open.as_slice()?;
+    let close_slice = close.as_slice()?;
+    helpers::check_equal_lengths(&[("open", open_slice.len()), ("close", close_slice.len())])
+        .map_err(PyValueError::new_err)?;
+
+    let result = gap_kernel(open_slice, close_slice);
+    Ok(PyArray1::from_vec(py, result))
+}
+
+/// Gap Fill
+///
+/// # Arguments
+/// * `open` - Open price series
+/// * `high` - High price series
+/// * `low` - Low price series
+/// * `close` - Close price series
+///
+/// # Returns
+/// Numpy array of flags: 1.0 where the bar's gap was filled intraday, 0.0 otherwise
+#[pyfunction]
+#[pyo3(name = "gap_fill_numba", signature = (open, high, low, close))]
+pub fn gap_fill<'py>(
+    py: Python<'py>,
+    open: PyReadonlyArray1<'py, f64>,
+    high: PyReadonlyArray1<'py, f64>,
+    low: PyReadonlyArray1<'py, f64>,
+    close: PyReadonlyArray1<'py, f64>,
+) -> PyResult<Bound<'py, PyArray1<f64>>> {
+    let open_slice = open.as_slice()?;
+    let high_slice = high.as_slice()?;
+    let low_slice = low.as_slice()?;
+    let close_slice = close.as_slice()?;
+    helpers::check_equal_lengths(&[
+        ("open", open_slice.len()),
+        ("high", high_slice.len()),
+        ("low", low_slice.len()),
+        ("close", close_slice.len()),
+    ])
+    .map_err(PyValueError::new_err)?;
+
+    let result = gap_fill_kernel(open_slice, high_slice, low_slice, close_slice);
+    Ok(PyArray1::from_vec(py, result))
+}
+
+/// Downside beta kernel: OLS slope of asset returns on benchmark returns using only
+/// the bars of each `window`-return window where the benchmark return is negative.
+/// NaN for the first `window` elements, and where fewer than two down bars remain
+/// or the benchmark's down returns have no variance.
+pub fn downside_beta_kernel(asset: &[f64], benchmark: &[f64], window: usize) -> Vec<f64> {
+    let len = asset.len();
+    let mut result = vec![f64::NAN; len];
+
+    if window == 0 || window >= len {
+        return result;
+    }
+
+    let asset_ret = daily_return_kernel(asset, false);
+    let bench_ret = daily_return_kernel(benchmark, false);
+
+    for i in window..len {
+        let down: Vec<(f64, f64)> = ((i + 1 - window)..=i)
+            .filter(|&j| bench_ret[j] < 0.0 && !asset_ret[j].is_nan())
+            .map(|j| (asset_ret[j], bench_ret[j]))
+            .collect();
+        if down.len() < 2 {
+            continue;
+        }
+
+        let count = down.len() as f64;
+        let mean_a = down.iter().map(|d| d.0).sum::<f64>() / count;
+        let mean_b = down.iter().map(|d| d.1).sum::<f64>() / count;
+        let cov: f64 = down.iter().map(|(a, b)| (a - mean_a) * (b - mean_b)).sum();
+        let var: f64 = down.iter().map(|(_, b)| (b - mean_b).powi(2)).sum();
+
+        if var > 0.0 {
+            result[i] = cov / var;
+        }
+    }
+
+    result
+}
+
+/// Downside Beta
+///
+/// # Arguments
+/// * `asset` - Asset price series
+/// * `benchmark` - Benchmark price series
+/// * `n` - Rolling window size in returns (default: 60)
+///
+/// # Returns
+/// Numpy array with the regression beta of asset on benchmark returns over the
+/// benchmark's down days in each window
+#[pyfunction]
+#[pyo3(name = "downside_beta_numba", signature = (asset, benchmark, n=60))]
+pub fn downside_beta<'py>(
+    py: Python<'py>,
+    asset: PyReadonlyArray1<'py, f64>,
+    benchmark: PyReadonlyArray1<'py, f64>,
+    n: usize,
+) -> PyResult<Bound<'py, PyArray1<f64>>> {
+    let asset_slice = asset.as_slice()?;
+    let benchmark_slice = benchmark.as_slice()?;
+    helpers::check_equal_lengths(&[("asset", asset_slice.len()), ("benchmark", benchmark_slice.len())])
+        .map_err(PyValueError::new_err)?;
+
+    let result = downside_beta_kernel(asset_slice, benchmark_slice, n);
+    Ok(PyArray1::from_vec(py, result))
+}
+
+/// Pearson correlation of two equal-length samples; NaN when either is constant
+fn pearson(x: &[f64], y: &[f64]) -> f64 {
+    let count = x.len() as f64;
+    let mean_x = x.iter().sum::<f64>() / count;
+    let mean_y = y.iter().sum::<f64>() / count;
+    let (mut cov, mut var_x, mut var_y) = (0.0, 0.0, 0.0);
+    for (a, b) in x.iter().zip(y) {
+        cov += (a - mean_x) * (b - mean_y);
+        var_x += (a - mean_x).powi(2);
+        var_y += (b - mean_y).powi(2);
+    }
+    if var_x == 0.0 || var_y == 0.0 {
+        f64::NAN
+    } else {
+        cov / (var_x * var_y).sqrt()
+    }
+}
+
+/// Ranks (1-based) of `values` written into `ranks`, ties sharing their average rank.
+/// `order` is scratch space for the sorted indices.
+fn average_ranks(values: &[f64], order: &mut Vec<usize>, ranks: &mut [f64]) {
+    order.clear();
+    order.extend(0..values.len());
+    order.sort_by(|&a, &b| values[a].total_cmp(&values[b]));
+
+    let mut start = 0;
+    while start < order.len() {
+        let mut end = start + 1;
+        while end < order.len() && values[order[end]] == values[order[start]] {
+            end += 1;
+        }
+        // positions start..end hold ranks start+1..=end
+        let rank = (start + end + 1) as f64 / 2.0;
+        for &idx in &order[start..end] {
+            ranks[idx] = rank;
+        }
+        start = end;
+    }
+}
+
+/// Rolling Spearman rank correlation kernel: Pearson correlation of the average
+/// ranks within each window. NaN for the first window-1 elements, for windows
+/// containing NaN and where either side is constant.
+pub fn rolling_spearman_kernel(x: &[f64], y: &[f64], window: usize) -> Vec<f64> {
+    let len = x.len();
+    let mut result = vec![f64::NAN; len];
+
+    if window == 0 || window > len {
+        return result;
+    }
+
+    let mut order = Vec::with_capacity(window);
+    let mut rank_x = vec![0.0; window];
+    let mut rank_y = vec![0.0; window];
+    for i in (window - 1)..len {
+        let start = i + 1 - window;
+        let (wx, wy) = (&x[start..=i], &y[start..=i]);
+        if wx.iter().chain(wy).any(|v| v.is_nan()) {
+            continue;
+        }
+        average_ranks(wx, &mut order, &mut rank_x);
+        average_ranks(wy, &mut order, &mut rank_y);
+        result[i] = pearson(&rank_x, &rank_y);
+    }
+
+    result
+}
+
+/// Rolling Spearman Rank Correlation
+///
+/// # Arguments
+/// * `x` - First series
+/// * `y` - Second series
+/// * `n` - Rolling window size (default: 30)
+///
+/// # Returns
+/// Numpy array with Spearman's rho (-1 to 1) per window; ties get average ranks
+#[pyfunction]
+#[pyo3(name = "rolling_spearman_numba", signature = (x, y, n=30))]
+pub fn rolling_spearman<'py>(
+    py: Python<'py>,
+    x: PyReadonlyArray1<'py, f64>,
+    y: PyReadonlyArray1<'py, f64>,
+    n: usize,
+) -> PyResult<Bound<'py, PyArray1<f64>>> {
+    let x_slice = x.as_slice()?;
+    let y_slice = y.as_slice()?;
+    helpers::check_equal_lengths(&[("x", x_slice.len()), ("y", y_slice.len())])
+        .map_err(PyValueError::new_err)?;
+
+    let result = rolling_spearman_kernel(x_slice, y_slice, n);
+    Ok(PyArray1::from_vec(py, result))
+}
+
+/// Calmar ratio of one window of prices: linearly annualized return over the
+/// absolute max drawdown inside the window; 0 when there is no drawdown
+pub fn calmar_ratio_of_window(close: &[f64], periods_per_year: f64) -> f64 {
+    let annual_return = helpers::annualized_return(close[0], close[close.len() - 1], close.len(), periods_per_year);
+
+    let mut running_max = close[0];
+    let mut max_drawdown: f64 = 0.0;
+    for &price in close.iter().skip(1) {
+        running_max = running_max.max(price);
+        max_drawdown = max_drawdown.min((price - running_max) / running_max);
+    }
+
+    let max_drawdown = max_drawdown.abs();
+    if max_drawdown > 0.0 {
+        annual_return / max_drawdown
+    } else {
+        0.0
+    }
+}
+
+/// Rolling Calmar ratio kernel, NaN for the first n-1 elements
+pub fn calmar_ratio_kernel(close: &[f64], n: usize, periods_per_year: f64) -> Vec<f64> {
+    let len = close.len();
+    let mut result = vec![f64::NAN; len];
+
+    if n == 0 || n > len {
+        return result;
+    }
+
+    for i in (n - 1)..len {
+        result[i] = calmar_ratio_of_window(&close[i + 1 - n..=i], periods_per_year);
+    }
+
+    result
+}
+
+/// Calmar Ratio
+///
+/// # Arguments
+/// * `close` - Close price series
+/// * `n` - Rolling window size (default: 252)
+/// * `bar_frequency` - "daily" (252 bars/year), "hourly" (252 * 6.5), "minute"
+///   (252 * 390) or "crypto_daily" (365) (default: "daily")
+/// * `periods_per_year` - Overrides the `bar_frequency` preset (default: None)
+///
+/// # Returns
+/// Numpy array with annualized return / max drawdown per window, as `CalmarRatioStreaming`
+#[pyfunction]
+#[pyo3(name = "calmar_ratio_numba", signature = (close, n=252, bar_frequency="daily", periods_per_year=None))]
+pub fn calmar_ratio<'py>(
+    py: Python<'py>,
+    close: PyReadonlyArray1<'py, f64>,
+    n: usize,
+    bar_frequency: &str,
+    periods_per_year: Option<f64>,
+) -> PyResult<Bound<'py, PyArray1<f64>>> {
+    let periods_per_year = helpers::resolve_periods_per_year(bar_frequency, periods_per_year)
+        .map_err(PyValueError::new_err)?;
+    let result = calmar_ratio_kernel(close.as_slice()?, n, periods_per_year);
+    Ok(PyArray1::from_vec(py, result))
+}
+
+/// Martin ratio kernel: annualized excess return over each n-bar window, in percent,
+/// divided by the window's Ulcer Index. NaN for the first n-1 elements and where the
+/// Ulcer Index is zero.
+pub fn martin_ratio_kernel(close: &[f64], n: usize, risk_free: f64, periods_per_year: f64) -> Vec<f64> {
+    let len = close.len();
+    let ui = ulcer_index_kernel(close, n);
+    let mut result = vec![f64::NAN; len];
+
+    for i in 0..len {
+        if ui[i].is_nan() || ui[i] == 0.0 {
+            continue;
+        }
+        let annual_return = helpers::annualized_return(close[i + 1 - n], close[i], n, periods_per_year);
+        result[i] = (annual_return - risk_free) * 100.0 / ui[i];
+    }
+
+    result
+}
+
+/// Martin Ratio (Ulcer Performance Index)
+///
+/// # Arguments
+/// * `close` - Close price series
+/// * `n` - Rolling window size (default: 252)
+/// * `risk_free` - Annual risk-free rate as a fraction (default: 0.0)
+/// * `bar_frequency` - "daily" (252 bars/year), "hourly" (252 * 6.5), "minute"
+///   (252 * 390) or "crypto_daily" (365) (default: "daily")
+/// * `periods_per_year` - Overrides the `bar_frequency` preset (default: None)
+///
+/// # Returns
+/// Numpy array with (annualized return - risk_free) in percent divided by the Ulcer Index
+#[pyfunction]
+#[pyo3(name = "martin_ratio_numba", signature = (close, n=252, risk_free=0.0, bar_frequency="daily", periods_per_year=None))]
+pub fn martin_ratio<'py>(
+    py: Python<'py>,
+    close: PyReadonlyArray1<'py, f64>,
+    n: usize,
+    risk_free: f64,
+    bar_frequency: &str,
+    periods_per_year: Option<f64>,
+) -> PyResult<Bound<'py, PyArray1<f64>>> {
+    let close_slice = close.as_slice()?;
+    let periods_per_year = helpers::resolve_periods_per_year(bar_frequency, periods_per_year)
+        .map_err(PyValueError::new_err)?;
+    let result = martin_ratio_kernel(close_slice, n, risk_free, periods_per_year);
+    Ok(PyArray1::from_vec(py, result))
+}
+
+/// Rolling trimmed mean kernel: mean of each window after dropping floor(window * trim)
+/// values from each end of its sorted order. NaN for the first window-1 elements and
+/// for windows containing NaN.
+pub fn rolling_trimmed_mean_kernel(data: &[f64], window: usize, trim: f64) -> Result<Vec<f64>, String> {
+    let k = helpers::trim_count(window, trim)?;
+    let len = data.len();
+    let mut result = vec![f64::NAN; len];
+
+    if window == 0 || window > len {
+        return Ok(result);
+    }
+
+    let mut values = helpers::SortedWindow::new(window);
+    for i in 0..len {
+        values.push(data[i]);
+        if values.is_full() && values.nan_count() == 0 {
+            result[i] = helpers::trimmed_mean_of_sorted(values.sorted(), k);
+        }
+    }
+
+    Ok(result)
+}
+
+/// Rolling Trimmed Mean
+///
+/// # Arguments
+/// * `data` - Data series
+/// * `n` - Rolling window size (default: 20)
+/// * `trim` - Fraction dropped from each tail of the window, in [0, 0.5) (default: 0.1)
+///
+/// # Returns
+/// Numpy array with trimmed means, NaN for the first n-1 elements
+#[pyfunction]
+#[pyo3(name = "rolling_trimmed_mean_numba", signature = (data, n=20, trim=0.1))]
+pub fn rolling_trimmed_mean<'py>(
+    py: Python<'py>,
+    data: PyReadonlyArray1<'py, f64>,
+    n: usize,
+    trim: f64,
+) -> PyResult<Bound<'py, PyArray1<f64>>> {
+    let data_slice = data.as_slice()?;
+    let result = rolling_trimmed_mean_kernel(data_slice, n, trim).map_err(PyValueError::new_err)?;
+    Ok(PyArray1::from_vec(py, result))
+}
+
+/// Centering and scaling applied by `standardize_kernel`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum StandardizeMethod {
+    /// (x - mean) / population std
+    ZScore,
+    /// (x - min) / (max - min)
+    MinMax,
+    /// (x - median) / (q75 - q25)
+    Robust,
+}
+
+impl StandardizeMethod {
+    pub fn parse(method: &str) -> Result<Self, String> {
+        match method {
+            "zscore" => Ok(StandardizeMethod::ZScore),
+            "minmax" => Ok(StandardizeMethod::MinMax),
+            "robust" => Ok(StandardizeMethod::Robust),
+            other => Err(format!("unknown method '{}', expected one of: zscore, minmax, robust", other)),
+        }
+    }
+}
+
+/// (center, scale) of `sample` (non-empty, NaN-free) for `method`
+fn standardize_params(sample: &[f64], method: StandardizeMethod) -> (f64, f64) {
+    match method {
+        StandardizeMethod::ZScore => {
+            let count = sample.len() as f64;
+            let mean = sample.iter().sum::<f64>() / count;
+            let variance = sample.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / count;
+            (mean, variance.sqrt())
+        }
+        StandardizeMethod::MinMax => {
+            let min = sample.iter().copied().fold(f64::INFINITY, f64::min);
+            let max = sample.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+            (min, max - min)
+        }
+        StandardizeMethod::Robust => {
+            let mut sorted = sample.to_vec();
+            sorted.sort_by(|a, b| a.total_cmp(b));
+            let q25 = helpers::quantile_of_sorted(&sorted, 0.25);
+            let q75 = helpers::quantile_of_sorted(&sorted, 0.75);
+            (helpers::quantile_of_sorted(&sorted, 0.5), q75 - q25)
+        }
+    }
+}
+
+/// Standardize `x` by `center` and `scale`; 0 when the scale is zero
+fn standardize_value(x: f64, center: f64, scale: f64) -> f64 {
+    if scale != 0.0 {
+        (x - center) / scale
+    } else {
+        0.0
+    }
+}
+
+/// Standardize kernel. With `window = None`, statistics come from all non-NaN
+/// values and NaN inputs stay NaN. With a window, each value is standardized
+/// against its trailing window; the first window-1 elements and windows
+/// containing NaN are NaN. A zero scale yields 0.
+pub fn standardize_kernel(data: &[f64], method: StandardizeMethod, window: Option<usize>) -> Vec<f64> {
+    let len = data.len();
+    let mut result = vec![f64::NAN; len];
+
+    match window {
+        None => {
+            let valid: Vec<f64> = data.iter().copied().filter(|x| !x.is_nan()).collect();
+            if valid.is_empty() {
+                return result;
+            }
+            let (center, scale) = standardize_params(&valid, method);
+            for (r, &x) in result.iter_mut().zip(data) {
+                if !x.is_nan() {
+                    *r = standardize_value(x, center, scale);
+                }
+            }
+        }
+        Some(window) => {
+            if window == 0 || window > len {
+                return result;
+            }
+            for i in (window - 1)..len {
+                let slice = &data[(i + 1 - window)..=i];
+                if slice.iter().any(|x| x.is_nan()) {
+                    continue;
+                }
+                let (center, scale) = standardize_params(slice, method);
+                result[i] = standardize_value(data[i], center, scale);
+            }
+        }
+    }
+
+    result
+}
+
+/// Standardize
+///
+/// Centers and scales any series, e.g. indicator outputs used as model features.
+///
+/// # Arguments
+/// * `data` - Data series
+/// * `method` - "zscore" (population std), "minmax" or "robust" (median / IQR)
+///   (default: "zscore")
+/// * `n` - Rolling window; None standardizes against the whole series (default: None)
+///
+/// # Returns
+/// Numpy array with standardized values
+#[pyfunction]
+#[pyo3(name = "standardize_numba", signature = (data, method="zscore", n=None))]
+pub fn standardize<'py>(
+    py: Python<'py>,
+    data: PyReadonlyArray1<'py, f64>,
+    method: &str,
+    n: Option<usize>,
+) -> PyResult<Bound<'py, PyArray1<f64>>> {
+    let data_slice = data.as_slice()?;
+    let method = StandardizeMethod::parse(method).map_err(PyValueError::new_err)?;
+    let result = standardize_kernel(data_slice, method, n);
+    Ok(PyArray1::from_vec(py, result))
+}
+
+/// Rolling information ratio kernel: annualized mean active return (strategy -
+/// benchmark) over the annualized tracking error (sample std of active returns)
+/// of each `window`. NaN for the first window-1 elements and where the tracking
+/// error is zero or undefined; a std within rounding noise of the active returns'
+/// magnitude (e.g. a constant outperformance) counts as zero.
+pub fn rolling_information_ratio_kernel(
+    strategy: &[f64],
+    benchmark: &[f64],
+    window: usize,
+    periods_per_year: f64,
+) -> Vec<f64> {
+    let len = strategy.len();
+    let mut result = vec![f64::NAN; len];
+
+    if window < 2 || window > len {
+        return result;
+    }
+
+    let active: Vec<f64> = strategy.iter().zip(benchmark).map(|(s, b)| s - b).collect();
+
+    for i in (window - 1)..len {
+        let slice = &active[(i + 1 - window)..=i];
+        let mean = slice.iter().sum::<f64>() / window as f64;
+        let variance = slice.iter().map(|a| (a - mean).powi(2)).sum::<f64>() / (window - 1) as f64;
+        let std = variance.sqrt();
+        let magnitude = slice.iter().fold(0.0_f64, |m, a| m.max(a.abs()));
+
+        if std > magnitude * 1e-10 {
+            let tracking_error = std * periods_per_year.sqrt();
+            result[i] = mean * periods_per_year / tracking_error;
+        }
+    }
+
+    result
+}
+
+/// Rolling Information Ratio
+///
+/// # Arguments
+/// * `strategy_returns` - Strategy return series
+/// * `benchmark_returns` - Benchmark return series
+/// * `n` - Rolling window size (default: 60)
+/// * `bar_frequency` - "daily" (252 bars/year), "hourly" (252 * 6.5), "minute"
+///   (252 * 390) or "crypto_daily" (365) (default: "daily")
+/// * `periods_per_year` - Overrides the `bar_frequency` preset (default: None)
+///
+/// # Returns
+/// Numpy array with information ratio values
+#[pyfunction]
+#[pyo3(name = "rolling_information_ratio_numba", signature = (strategy_returns, benchmark_returns, n=60, bar_frequency="daily", periods_per_year=None))]
+pub fn rolling_information_ratio<'py>(
+    py: Python<'py>,
+    strategy_returns: PyReadonlyArray1<'py, f64>,
+    benchmark_returns: PyReadonlyArray1<'py, f64>,
+    n: usize,
+    bar_frequency: &str,
+    periods_per_year: Option<f64>,
+) -> PyResult<Bound<'py, PyArray1<f64>>> {
+    let periods_per_year = helpers::resolve_periods_per_year(bar_frequency, periods_per_year)
+        .map_err(PyValueError::new_err)?;
+    let strategy_slice = strategy_returns.as_slice()?;
+    let benchmark_slice = benchmark_returns.as_slice()?;
+    helpers::check_equal_lengths(&[
+        ("strategy_returns", strategy_slice.len()),
+        ("benchmark_returns", benchmark_slice.len()),
+    ])
+    .map_err(PyValueError::new_err)?;
+
+    let result = rolling_information_ratio_kernel(strategy_slice, benchmark_slice, n, periods_per_year);
+    Ok(PyArray1::from_vec(py, result))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_regression_forecast_extrapolates_linear_series() {
+        let data: Vec<f64> = (0..40).map(|i| 3.0 + 0.5 * i as f64).collect();
+        let forecast = regression_forecast_kernel(&data, 20, 3);
+
+        assert!(forecast[..19].iter().all(|x| x.is_nan()));
+        for i in 19..data.len() {
+            let expected = 3.0 + 0.5 * (i + 3) as f64;
+            assert!((forecast[i] - expected).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_win_rate_and_profit_factor_on_mostly_up_series() {
+        // two up days of +1% for every down day of -1%
+        let mut close = vec![100.0];
+        for i in 1..120 {
+            let r = if i % 3 == 0 { -0.01 } else { 0.01 };
+            close.push(close[i - 1] * (1.0 + r));
+        }
+
+        let win = win_rate_kernel(&close, 60);
+        let pf = profit_factor_kernel(&close, 60);
+        assert!(win[..60].iter().all(|x| x.is_nan()));
+        assert!(pf[..60].iter().all(|x| x.is_nan()));
+        for i in 60..close.len() {
+            assert!((win[i] - 2.0 / 3.0).abs() < 1e-12);
+            assert!(pf[i] > 1.0);
+        }
+
+        // no losing return leaves the profit factor undefined
+        let rising: Vec<f64> = (0..30).map(|i| 100.0 + i as f64).collect();
+        assert!(profit_factor_kernel(&rising, 10)[20].is_nan());
+        assert_eq!(win_rate_kernel(&rising, 10)[20], 1.0);
+    }
+
+    #[test]
+    fn test_rolling_spearman_monotone_nonlinear() {
+        let x: Vec<f64> = (0..50).map(|i| i as f64 * 0.2).collect();
+        let y: Vec<f64> = x.iter().map(|v| v.exp()).collect();
+
+        let rho = rolling_spearman_kernel(&x, &y, 30);
+        assert!(rho[..29].iter().all(|v| v.is_nan()));
+        for i in 29..x.len() {
+            assert!((rho[i] - 1.0).abs() < 1e-12);
+            assert!(pearson(&x[i - 29..=i], &y[i - 29..=i]) < 0.95);
+        }
+
+        // ties share the average rank: [1, 2, 2, 3] ranks as [1, 2.5, 2.5, 4]
+        let tied = rolling_spearman_kernel(&[1.0, 2.0, 2.0, 3.0], &[1.0, 2.0, 3.0, 4.0], 4);
+        let expected = pearson(&[1.0, 2.5, 2.5, 4.0], &[1.0, 2.0, 3.0, 4.0]);
+        assert!((tied[3] - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_rolling_rms_matches_naive() {
+        let data: Vec<f64> = (0..50).map(|i| ((i as f64) * 0.7).sin() * 3.0 - 1.0).collect();
+        let window = 7;
+        let rms = rolling_rms_kernel(&data, window);
+
+        for i in 0..(window - 1) {
+            assert!(rms[i].is_nan());
+        }
+        for i in (window - 1)..data.len() {
+            let slice = &data[i + 1 - window..=i];
+            let naive = (slice.iter().map(|x| x * x).sum::<f64>() / window as f64).sqrt();
+            assert!((rms[i] - naive).abs() < 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_return_fraction_is_percentage_over_100() {
+        let close: Vec<f64> = (0..30).map(|i| 100.0 + ((i as f64) * 0.5).sin() * 5.0).collect();
+        let pairs = [
+            (daily_return_kernel(&close, true), daily_return_kernel(&close, false)),
+            (daily_log_return_kernel(&close, true), daily_log_return_kernel(&close, false)),
+            (cumulative_return_kernel(&close, true), cumulative_return_kernel(&close, false)),
+        ];
+
+        for (pct, frac) in pairs.iter() {
+            for i in 1..close.len() {
+                assert!((frac[i] - pct[i] / 100.0).abs() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_expanding_zscore_uses_full_history() {
+        let data: Vec<f64> = (0..20).map(|i| (i * i) as f64).collect();
+        let expanding = rolling_zscore_kernel(&data, 5, true);
+        let rolling = rolling_zscore_kernel(&data, 5, false);
+
+        assert!(expanding[3].is_nan());
+        assert!((expanding[4] - rolling[4]).abs() < 1e-12);
+
+        let mean = data.iter().sum::<f64>() / 20.0;
+        let std = (data.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / 20.0).sqrt();
+        assert!((expanding[19] - (data[19] - mean) / std).abs() < 1e-12);
+
+        assert_eq!(rolling_zscore_kernel(&[3.0; 10], 2, true)[9], 0.0);
+        let mut gap = data.clone();
+        gap[10] = f64::NAN;
+        assert!(rolling_zscore_kernel(&gap, 5, true)[10..].iter().all(|z| z.is_nan()));
+    }
+
+    #[test]
+    fn test_rolling_pct_above_ma_rising_series() {
+        let close: Vec<f64> = (0..300).map(|i| 100.0 + i as f64 * 0.5).collect();
+        let above = pct_above_ma_kernel(&close, 50);
+        let fraction = rolling_pct_above_ma_kernel(&close, 50, 200);
+
+        assert!(above[48].is_nan());
+        assert_eq!(above[49], 1.0);
+        assert!(fraction[247].is_nan());
+        assert!(fraction[248] > 0.99);
+        assert!(fraction[299] > 0.99);
+    }
+
+    #[test]
+    fn test_fill_warmup_methods() {
+        let nan = f64::NAN;
+        let data = [nan, nan, 1.0, 2.0, nan, 4.0];
+
+        let same = |a: &[f64], b: &[f64]| a.iter().zip(b).all(|(x, y)| x == y || (x.is_nan() && y.is_nan()));
+
+        assert!(same(&fill_warmup_kernel(&data, "none").unwrap(), &data));
+        assert!(same(&fill_warmup_kernel(&data, "ffill").unwrap(), &[nan, nan, 1.0, 2.0, 2.0, 4.0]));
+        assert!(same(&fill_warmup_kernel(&data, "bfill").unwrap(), &[1.0, 1.0, 1.0, 2.0, 4.0, 4.0]));
+        assert!(same(&fill_warmup_kernel(&data, "zero").unwrap(), &[0.0, 0.0, 1.0, 2.0, 0.0, 4.0]));
+        assert!(fill_warmup_kernel(&data, "mean").is_err());
+    }
+
+    #[test]
+    fn test_max_drawdown_rolling_and_expanding() {
+        let close: Vec<f64> = (0..150)
+            .map(|i| 100.0 + (i as f64 * 0.17).sin() * 8.0 + (i as f64 * 0.05).cos() * 5.0)
+            .collect();
+        let window_drawdown = |prices: &[f64]| {
+            let mut peak = prices[0];
+            let mut worst: f64 = 0.0;
+            for &price in prices {
+                peak = peak.max(price);
+                worst = worst.min((price - peak) / peak);
+            }
+            worst * 100.0
+        };
+
+        let rolling = max_drawdown_kernel(&close, 20, false);
+        let mut streaming = crate::streaming::MaxDrawdownStreaming::new(20);
+        for i in 0..close.len() {
+            let full_window = streaming.update(close[i]);
+            if i < 19 {
+                assert!(rolling[i].is_nan());
+            } else {
+                assert!((rolling[i] - window_drawdown(&close[i - 19..=i])).abs() < 1e-9, "rolling at {}", i);
+                assert!((rolling[i] - full_window).abs() < 1e-9);
+            }
+        }
+
+        let expanding = max_drawdown_kernel(&close, 20, true);
+        assert!(expanding[18].is_nan());
+        for i in 19..close.len() {
+            assert!((expanding[i] - window_drawdown(&close[..=i])).abs() < 1e-9, "expanding at {}", i);
+        }
+    }
+
+    #[test]
+    fn test_drawdown_duration_counts_and_resets() {
+        let close = [100.0, 105.0, 103.0, 101.0, 102.0, 104.0, 106.0, 105.0];
+        let all_time = drawdown_duration_kernel(&close, None);
+        assert_eq!(all_time, vec![0.0, 0.0, 1.0, 2.0, 3.0, 4.0, 0.0, 1.0]);
+
+        // With a 3-bar reference the old high drops out of the window
+        let rolling = drawdown_duration_kernel(&close, Some(3));
+        assert_eq!(rolling, vec![0.0, 0.0, 1.0, 2.0, 2.0, 0.0, 0.0, 1.0]);
+
+        // Matches a rescan of each window, including tied highs
+        let wave: Vec<f64> = (0..200).map(|i| ((i as f64 * 0.3).sin() * 10.0).round()).collect();
+        let rolling = drawdown_duration_kernel(&wave, Some(20));
+        for i in 0..wave.len() {
+            let start = (i + 1).saturating_sub(20);
+            let high = wave[start..=i].iter().copied().fold(f64::NEG_INFINITY, f64::max);
+            let peak_idx = (start..=i).rev().find(|&j| wave[j] == high).unwrap();
+            assert_eq!(rolling[i], (i - peak_idx) as f64, "bar {}", i);
+        }
+    }
+
+    #[test]
+    fn test_rolling_entropy_uniform_and_constant() {
+        // One value per bucket is maximal entropy
+        let spread: Vec<f64> = (0..10).map(|i| i as f64).collect();
+        let entropy = rolling_entropy_kernel(&spread, 10, 10).unwrap();
+        assert!(entropy[8].is_nan());
+        assert!((entropy[9] - 1.0).abs() < 1e-12);
+
+        let constant = vec![0.01; 20];
+        let entropy = rolling_entropy_kernel(&constant, 10, 10).unwrap();
+        assert!(entropy[9..].iter().all(|&v| v == 0.0));
+
+        assert!(rolling_entropy_kernel(&constant, 10, 1).is_err());
+    }
+
+    #[test]
+    fn test_rolling_var_cvar_known_tail() {
+        // 20 returns: two large losses, the rest small gains
+        let mut returns = vec![0.01; 20];
+        returns[5] = -0.10;
+        returns[12] = -0.05;
+        let mut close = vec![100.0];
+        for r in &returns {
+            close.push(close.last().unwrap() * (1.0 + r));
+        }
+
+        let (var, cvar) = rolling_var_cvar_kernel(&close, 20, 0.05).unwrap();
+        assert!(var[19].is_nan() && cvar[19].is_nan());
+
+        // pos = 0.05 * 19 = 0.95 -> interpolate between -0.10 and -0.05
+        let expected_var = -0.10 + 0.05 * 0.95;
+        assert!((var[20] - expected_var).abs() < 1e-9);
+        assert!((cvar[20] + 0.10).abs() < 1e-9);
+        assert!(cvar[20] <= var[20]);
+
+        assert!(rolling_var_cvar_kernel(&close, 20, 1.0).is_err());
+    }
+
+    #[test]
+    fn test_rolling_count_valid_drops_as_nan_enters() {
+        let nan = f64::NAN;
+        let data = [1.0, 2.0, 3.0, nan, 5.0, nan, 7.0, 8.0, 9.0, 10.0];
+        let counts = rolling_count_valid_kernel(&data, 3);
+
+        assert!(counts[1].is_nan());
+        assert_eq!(&counts[2..], &[3.0, 2.0, 2.0, 1.0, 2.0, 2.0, 3.0, 3.0]);
+    }
+
+    #[test]
+    fn test_up_bar_ratio_mostly_up() {
+        // Up every bar except each fifth
+        let close: Vec<f64> = (0..30)
+            .scan(100.0, |c, i| {
+                *c += if i % 5 == 4 { -1.0 } else { 1.0 };
+                Some(*c)
+            })
+            .collect();
+        let ratio = up_bar_ratio_kernel(&close, 10);
+
+        assert!(ratio[9].is_nan());
+        assert!(ratio[10..].iter().all(|&r| (r - 0.8).abs() < 1e-12));
+    }
+
+    #[test]
+    fn test_rolling_ols_residual_exact_linear_relation() {
+        let x: Vec<f64> = (0..100).map(|i| 50.0 + (i as f64 * 0.3).sin() * 5.0).collect();
+        let y: Vec<f64> = x.iter().map(|v| 2.0 * v + 3.0).collect();
+        let (residual, alpha, beta) = rolling_ols_residual_kernel(&y, &x, 20);
+
+        assert!(residual[18].is_nan() && beta[18].is_nan());
+        for i in 19..100 {
+            assert!(residual[i].abs() < 1e-9);
+            assert!((beta[i] - 2.0).abs() < 1e-9);
+            assert!((alpha[i] - 3.0).abs() < 1e-6);
+        }
+
+        // Constant x over the window leaves beta undefined
+        let (residual, _, beta) = rolling_ols_residual_kernel(&[1.0, 2.0, 3.0], &[5.0; 3], 3);
+        assert!(residual[2].is_nan() && beta[2].is_nan());
+    }
+
+    #[test]
+    fn test_rolling_ols_residual_recovers_after_nan() {
+        // y = 1 + 2x with the last bar off the line by 0.5
+        let x = [0.0, 1.0, f64::NAN, 3.0, 4.0, 5.0, 6.0];
+        let y = [1.0, 3.0, 5.0, 7.0, 9.0, 11.0, 13.5];
+        let (residual, alpha, beta) = rolling_ols_residual_kernel(&y, &x, 3);
+
+        assert!(beta[2..5].iter().all(|v| v.is_nan()));
+        assert!((beta[5] - 2.0).abs() < 1e-12);
+        assert!((alpha[5] - 1.0).abs() < 1e-12);
+        assert!(residual[5].abs() < 1e-12);
+
+        // Window (4, 9), (5, 11), (6, 13.5): beta = 2.25, alpha = -1/12, residual = 1/12
+        assert!((beta[6] - 2.25).abs() < 1e-12);
+        assert!((alpha[6] + 1.0 / 12.0).abs() < 1e-9);
+        assert!((residual[6] - 1.0 / 12.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_pct_from_high_low() {
+        let close = [10.0, 12.0, 11.0, 9.0, 13.0, 12.0];
+        let from_high = pct_from_high_kernel(&close, 3);
+        let from_low = pct_from_low_kernel(&close, 3);
+
+        assert!(from_high[1].is_nan() && from_low[1].is_nan());
+        // Close is the window max at bar 4
+        assert_eq!(from_high[4], 0.0);
+        assert!((from_high[3] - (9.0 - 12.0) / 12.0 * 100.0).abs() < 1e-12);
+        assert_eq!(from_low[3], 0.0);
+        assert!((from_low[5] - (12.0 - 9.0) / 9.0 * 100.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_new_high_low_on_rising_series() {
+        // Rising with a pullback every sixth bar
+        let high: Vec<f64> = (0..60)
+            .map(|i| 100.0 + i as f64 - if i % 6 == 5 { 3.0 } else { 0.0 })
+            .collect();
+        let low: Vec<f64> = high.iter().map(|h| h - 2.0).collect();
+        let (flags, oscillator) = new_high_low_kernel(&high, &low, 20, 10);
+
+        assert!(flags[19].is_nan() && !flags[20].is_nan());
+        assert!(oscillator[28].is_nan() && !oscillator[29].is_nan());
+
+        let new_highs = flags[20..].iter().filter(|&&f| f == 1.0).count();
+        assert!(new_highs >= 30);
+        assert!(flags[20..].iter().all(|&f| f >= 0.0));
+        assert!(oscillator[29..].iter().all(|&o| o > 0.0));
+    }
+
+    #[test]
+    fn test_spread_zscore_fixed_beta_on_ramps() {
+        // a tracks 2 * b plus a small oscillation, so the spread is the oscillation
+        let b: Vec<f64> = (0..80).map(|i| 50.0 + i as f64 * 0.5).collect();
+        let wiggle: Vec<f64> = (0..80).map(|i| (i as f64 * 0.9).sin()).collect();
+        let a: Vec<f64> = b.iter().zip(&wiggle).map(|(y, w)| 2.0 * y + 1.0 + w).collect();
+
+        let z = spread_zscore_kernel(&a, &b, 20, Some(2.0));
+        let expected = rolling_zscore_kernel(&wiggle, 20, false);
+        assert!(z[18].is_nan());
+        for i in 19..80 {
+            assert!((z[i] - expected[i]).abs() < 1e-9);
+        }
+
+        // Rolling beta: each bar applies that bar's OLS beta to its whole window
+        let z = spread_zscore_kernel(&a, &b, 20, None);
+        let (_, beta) = rolling_ols(&a, &b, 20);
+        assert!(z[18].is_nan());
+        let spread: Vec<f64> = (40..60).map(|j| a[j] - beta[59] * b[j]).collect();
+        let mean = spread.iter().sum::<f64>() / 20.0;
+        let std = (spread.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / 20.0).sqrt();
+        assert!((z[59] - (spread[19] - mean) / std).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_max_streak_known_up_run() {
+        // Changes: down, up x4, down, up, down, up x2
+        let close = [10.0, 9.0, 10.0, 11.0, 12.0, 13.0, 12.0, 13.0, 12.0, 13.0, 14.0];
+        let up = max_streak_kernel(&close, 10, true);
+        let down = max_streak_kernel(&close, 10, false);
+
+        assert!(up[9].is_nan() && down[9].is_nan());
+        assert_eq!(up[10], 4.0);
+        assert_eq!(down[10], 1.0);
+
+        // Shorter window slides past the 4-bar run
+        let up_short = max_streak_kernel(&close, 4, true);
+        assert_eq!(up_short[5], 4.0);
+        assert_eq!(up_short[6], 3.0);
+        assert_eq!(up_short[10], 2.0);
+    }
+
+    #[test]
+    fn test_rolling_reduce_matches_dedicated_kernels() {
+        let data: Vec<f64> = (0..40).map(|i| 50.0 + (i as f64 * 0.9).sin() * 7.0).collect();
+        let n = 6;
+        let cases = [
+            (ReduceOp::Min, rolling_min(&data, n)),
+            (ReduceOp::Max, rolling_max(&data, n)),
+            (ReduceOp::Mean, sma_kernel(&data, n)),
+            (ReduceOp::Sum, rolling_sum(&data, n)),
+            (ReduceOp::Std, rolling_std(&data, n)),
+            (ReduceOp::Median, rolling_median(&data, n)),
+        ];
+
+        for (op, expected) in cases {
+            let reduced = rolling_reduce_kernel(&data, n, op);
+            assert!(reduced[n - 2].is_nan());
+            for i in (n - 1)..data.len() {
+                assert_eq!(reduced[i], expected[i], "{:?} at {}", op, i);
+            }
+        }
+
+        assert!(ReduceOp::parse("mode").is_err());
+    }
+
+    #[test]
+    fn test_rolling_reduce_nan_window() {
+        let mut data = vec![1.0; 10];
+        data[4] = f64::NAN;
+        let sum = rolling_reduce_kernel(&data, 3, ReduceOp::Sum);
+        assert_eq!(sum[3], 3.0);
+        assert!(sum[4..7].iter().all(|v| v.is_nan()));
+        assert_eq!(sum[7], 3.0);
+    }
+
+    #[test]
+    fn test_gap_up_filled() {
+        // Bar 1 gaps up 5% and trades back to 100; bar 2 gaps up and holds;
+        // bar 3 gaps down and recovers to the prior close
+        let open = [99.0, 105.0, 106.0, 100.0];
+        let high = [101.0, 106.0, 108.0, 107.0];
+        let low = [98.0, 99.5, 105.5, 99.0];
+        let close = [100.0, 104.0, 107.0, 106.0];
+
+        let g = gap_kernel(&open, &close);
+        assert!(g[0].is_nan());
+        assert!((g[1] - 5.0).abs() < 1e-12);
+
+        let filled = gap_fill_kernel(&open, &high, &low, &close);
+        assert!(filled[0].is_nan());
+        assert_eq!(filled[1..], [1.0, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn test_downside_beta_over_reacts_on_down_days() {
+        // Asset tracks the benchmark on up days and falls twice as hard on down days
+        let bench_ret: Vec<f64> = (0..80).map(|i| (i as f64 * 0.9).sin() * 0.01).collect();
+        let mut benchmark = vec![100.0];
+        let mut asset = vec![100.0];
+        for &r in &bench_ret {
+            benchmark.push(benchmark.last().unwrap() * (1.0 + r));
+            let ra = if r < 0.0 { 2.0 * r } else { r };
+            asset.push(asset.last().unwrap() * (1.0 + ra));
+        }
+
+        let beta = downside_beta_kernel(&asset, &benchmark, 30);
+        assert!(beta[29].is_nan());
+        assert!(beta[30..].iter().all(|&b| (b - 2.0).abs() < 1e-9));
+
+        // A benchmark that never falls leaves no down days
+        let rising: Vec<f64> = (0..50).map(|i| 100.0 + i as f64).collect();
+        assert!(downside_beta_kernel(&rising, &rising, 20).iter().all(|b| b.is_nan()));
+    }
+
+    #[test]
+    fn test_martin_ratio_smooth_vs_choppy_uptrend() {
+        // Same trend and end points: small 0.5% pullbacks versus 5% ones
+        let trend: Vec<f64> = (0..120).map(|i| 100.0 * 1.002_f64.powi(i)).collect();
+        let with_pullbacks = |depth: f64| -> Vec<f64> {
+            trend
+                .iter()
+                .enumerate()
+                .map(|(i, &p)| if i % 10 == 5 { p * (1.0 - depth) } else { p })
+                .collect()
+        };
+        let smooth_mr = martin_ratio_kernel(&with_pullbacks(0.005), 60, 0.0, 252.0);
+        let choppy_mr = martin_ratio_kernel(&with_pullbacks(0.05), 60, 0.0, 252.0);
+
+        assert!(smooth_mr[58].is_nan());
+        assert!(smooth_mr[119].is_finite() && choppy_mr[119].is_finite());
+        assert!(choppy_mr[119] > 0.0);
+        assert!(smooth_mr[119] > choppy_mr[119]);
+
+        // A monotonic rise never draws down, so the Ulcer Index is zero
+        let monotonic_mr = martin_ratio_kernel(&trend, 60, 0.0, 252.0);
+        assert!(monotonic_mr[59..].iter().all(|v| v.is_nan()));
+    }
+
+    #[test]
+    fn test_rolling_trimmed_mean_ignores_outlier() {
+        let mut data = vec![10.0; 30];
+        data[25] = 1000.0;
+
+        let trimmed = rolling_trimmed_mean_kernel(&data, 10, 0.1).unwrap();
+        let sma = sma_kernel(&data, 10);
+
+        assert!(trimmed[8].is_nan());
+        assert!(trimmed[9..].iter().all(|&v| (v - 10.0).abs() < 1e-12));
+        assert!(sma[29] > 100.0);
+
+        // trim = 0 is the plain mean
+        let untrimmed = rolling_trimmed_mean_kernel(&data, 10, 0.0).unwrap();
+        assert!((untrimmed[29] - sma[29]).abs() < 1e-9);
+
+        assert!(rolling_trimmed_mean_kernel(&data, 10, 0.5).is_err());
+        assert!(rolling_trimmed_mean_kernel(&data, 10, -0.1).is_err());
+    }
+
+    #[test]
+    fn test_standardize_global_methods() {
+        let data = [1.0, 2.0, 3.0, 4.0, f64::NAN, 5.0];
+
+        let z = standardize_kernel(&data, StandardizeMethod::ZScore, None);
+        let std = 2.0_f64.sqrt();
+        assert!((z[0] + 2.0 / std).abs() < 1e-12);
+        assert_eq!(z[2], 0.0);
+        assert!(z[4].is_nan());
+
+        let mm = standardize_kernel(&data, StandardizeMethod::MinMax, None);
+        assert_eq!(mm[0], 0.0);
+        assert_eq!(mm[3], 0.75);
+        assert_eq!(mm[5], 1.0);
+
+        // Median 3, quartiles 2 and 4
+        let robust = standardize_kernel(&data, StandardizeMethod::Robust, None);
+        assert_eq!(robust[0], -1.0);
+        assert_eq!(robust[5], 1.0);
+
+        assert!(standardize_kernel(&[7.0; 4], StandardizeMethod::MinMax, None).iter().all(|&v| v == 0.0));
+        assert!(StandardizeMethod::parse("l2").is_err());
+    }
+
+    #[test]
+    fn test_standardize_rolling_zscore_matches_rolling_zscore() {
+        let data: Vec<f64> = (0..50).map(|i| (i as f64 * 0.4).sin() * 10.0 + i as f64).collect();
+        let standardized = standardize_kernel(&data, StandardizeMethod::ZScore, Some(12));
+        let zscore = rolling_zscore_kernel(&data, 12, false);
+
+        assert!(standardized[10].is_nan());
+        for i in 11..data.len() {
+            assert!((standardized[i] - zscore[i]).abs() < 1e-12);
+        }
+
+        let mm = standardize_kernel(&data, StandardizeMethod::MinMax, Some(12));
+        assert!(mm[11..].iter().all(|v| (0.0..=1.0).contains(v)));
+    }
+
+    #[test]
+    fn test_rolling_information_ratio() {
+        let benchmark: Vec<f64> = (0..100).map(|i| (i as f64 * 0.7).sin() * 0.01).collect();
+
+        // Constant outperformance has zero tracking error
+        let constant: Vec<f64> = benchmark.iter().map(|b| b + 0.001).collect();
+        let ir = rolling_information_ratio_kernel(&constant, &benchmark, 20, 252.0);
+        assert!(ir.iter().all(|v| v.is_nan()));
+
+        let noise: Vec<f64> = (0..100).map(|i| (i as f64 * 2.3).cos() * 0.002).collect();
+        let noisy: Vec<f64> = benchmark.iter().zip(&noise).map(|(b, e)| b + 0.001 + e).collect();
+        let ir = rolling_information_ratio_kernel(&noisy, &benchmark, 20, 252.0);
+        assert!(ir[18].is_nan());
+
+        let active = &noise[80..100].iter().map(|e| 0.001 + e).collect::<Vec<f64>>();
+        let mean = active.iter().sum::<f64>() / 20.0;
+        let std = (active.iter().map(|a| (a - mean).powi(2)).sum::<f64>() / 19.0).sqrt();
+        assert!((ir[99] - mean / std * 252.0_f64.sqrt()).abs() < 1e-9);
+        assert!(ir[99] > 0.0);
+    }
+}
@@ -0,0 +1,131 @@
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyType};
+
+use super::*;
+
+// Maps config names to streaming classes; constructor kwargs are parsed by
+// each class's own `#[new]` signature.
+macro_rules! streaming_registry {
+    ($($name:literal => $class:ty),* $(,)?) => {
+        const STREAMING_NAMES: &[&str] = &[$($name),*];
+
+        fn streaming_type<'py>(py: Python<'py>, name: &str) -> Option<Bound<'py, PyType>> {
+            match name {
+                $($name => Some(py.get_type::<$class>()),)*
+                _ => None,
+            }
+        }
+    };
+}
+
+streaming_registry! {
+    // Trend
+    "sma" => SMAStreaming,
+    "ema" => EMAStreaming,
+    "wma" => WMAStreaming,
+    "macd" => MACDStreaming,
+    "ma_distance" => MADistanceStreaming,
+    "price_oscillator" => PriceOscillatorStreaming,
+    "disparity_index" => DisparityIndexStreaming,
+    "adx" => ADXStreaming,
+    "cci" => CCIStreaming,
+    "dpo" => DPOStreaming,
+    "vortex" => VortexStreaming,
+    "trix" => TRIXStreaming,
+    "aroon" => AroonStreaming,
+    "psar" => PSARStreaming,
+    // Momentum
+    "rsi" => RSIStreaming,
+    "connors_rsi" => ConnorsRSIStreaming,
+    "up_down_streak" => UpDownStreakStreaming,
+    "smoothed_rsi" => SmoothedRSIStreaming,
+    "detrended_rsi" => DetrendedRSIStreaming,
+    "stochastic" => StochasticStreaming,
+    "williams_r" => WilliamsRStreaming,
+    "roc" => ROCStreaming,
+    "ppo" => PPOStreaming,
+    "pvo" => PVOStreaming,
+    "ultimate_oscillator" => UltimateOscillatorStreaming,
+    "stochastic_rsi" => StochasticRSIStreaming,
+    "tsi" => TSIStreaming,
+    "awesome_oscillator" => AwesomeOscillatorStreaming,
+    "kama" => KAMAStreaming,
+    "efficiency_ratio" => EfficiencyRatioStreaming,
+    "momentum" => MomentumStreaming,
+    "cmo" => CMOStreaming,
+    // Volatility
+    "atr" => ATRStreaming,
+    "atr_trailing_stop" => ATRTrailingStopStreaming,
+    "bollinger_bands" => BollingerBandsStreaming,
+    "keltner_channel" => KeltnerChannelStreaming,
+    "ma_envelope" => MAEnvelopeStreaming,
+    "squeeze" => SqueezeStreaming,
+    "donchian_channel" => DonchianChannelStreaming,
+    "ulcer_index" => UlcerIndexStreaming,
+    "standard_deviation" => StandardDeviationStreaming,
+    "variance" => VarianceStreaming,
+    "range" => RangeStreaming,
+    "historical_volatility" => HistoricalVolatilityStreaming,
+    "chaikin_volatility" => ChaikinVolatilityStreaming,
+    // Volume
+    "mfi" => MFIStreaming,
+    "acc_dist" => AccDistStreaming,
+    "obv" => OBVStreaming,
+    "cmf" => CMFStreaming,
+    "force_index" => ForceIndexStreaming,
+    "eom" => EOMStreaming,
+    "vpt" => VPTStreaming,
+    "nvi" => NVIStreaming,
+    "vwap" => VWAPStreaming,
+    "vwap_bands" => VWAPBandsStreaming,
+    "vwema" => VWEMAStreaming,
+    "volume_ratio" => VolumeRatioStreaming,
+    "volume_zscore" => VolumeZScoreStreaming,
+    // Others
+    "daily_return" => DailyReturnStreaming,
+    "daily_log_return" => DailyLogReturnStreaming,
+    "cumulative_return" => CumulativeReturnStreaming,
+    "compound_log_return" => CompoundLogReturnStreaming,
+    "rolling_return" => RollingReturnStreaming,
+    "max_drawdown" => MaxDrawdownStreaming,
+    "drawdown" => DrawdownStreaming,
+    "sharpe_ratio" => SharpeRatioStreaming,
+    "calmar_ratio" => CalmarRatioStreaming,
+    "rolling_zscore" => RollingZScoreStreaming,
+    "linear_regression_slope" => LinearRegressionSlopeStreaming,
+    "rolling_percentile" => RollingPercentileStreaming,
+    "rolling_rms" => RollingRMSStreaming,
+    "rolling_count_valid" => RollingCountValidStreaming,
+    "drawdown_duration" => DrawdownDurationStreaming,
+    "pct_from_high" => PctFromHighStreaming,
+    "pct_from_low" => PctFromLowStreaming,
+    "rolling_trimmed_mean" => RollingTrimmedMeanStreaming,
+}
+
+/// Construct a streaming indicator by name
+///
+/// # Arguments
+/// * `name` - Indicator name, e.g. "rsi" or "macd" (case-insensitive)
+/// * `**kwargs` - Constructor parameters of the target class
+///
+/// # Returns
+/// New streaming indicator instance
+#[pyfunction]
+#[pyo3(signature = (name, **kwargs))]
+pub fn create_streaming<'py>(
+    py: Python<'py>,
+    name: &str,
+    kwargs: Option<&Bound<'py, PyDict>>,
+) -> PyResult<Bound<'py, PyAny>> {
+    let key = name.to_ascii_lowercase();
+    let class = streaming_type(py, &key).ok_or_else(|| {
+        PyValueError::new_err(format!(
+            "unknown streaming indicator '{}', expected one of: {}",
+            name,
+            STREAMING_NAMES.join(", ")
+        ))
+    })?;
+
+    class.call((), kwargs)
+}
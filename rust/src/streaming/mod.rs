@@ -6,6 +6,7 @@ pub mod momentum;
 pub mod volatility;
 pub mod volume;
 pub mod others;
+pub mod factory;
 
 // Re-export all streaming classes
 pub use trend::*;
@@ -13,3 +14,4 @@ pub use momentum::*;
 pub use volatility::*;
 pub use volume::*;
 pub use others::*;
+pub use factory::*;
@@ -1,11 +1,14 @@
+use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
 use std::collections::VecDeque;
-use super::trend::{SMAStreaming, EMAStreaming};
+use super::trend::{SMAStreaming, EMAStreaming, MovingAverageStreaming};
+use crate::helpers;
 
 // ============================================================================
 // RSI (Relative Strength Index)
 // ============================================================================
 #[pyclass]
+#[derive(Clone)]
 pub struct RSIStreaming {
     window: usize,
     alpha: f64,
@@ -68,6 +71,14 @@ impl RSIStreaming {
         }
     }
 
+    pub fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    pub fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> Self {
+        self.clone()
+    }
+
     pub fn reset(&mut self) {
         self.prev_close = f64::NAN;
         self.avg_gain = f64::NAN;
@@ -76,29 +87,252 @@ impl RSIStreaming {
     }
 }
 
+// ============================================================================
+// Smoothed RSI (RSI of EMA-smoothed price)
+// ============================================================================
+#[pyclass]
+#[derive(Clone)]
+pub struct SmoothedRSIStreaming {
+    ema: EMAStreaming,
+    rsi: RSIStreaming,
+}
+
+#[pymethods]
+impl SmoothedRSIStreaming {
+    #[new]
+    #[pyo3(signature = (smooth_n=3, rsi_n=14))]
+    pub fn new(smooth_n: usize, rsi_n: usize) -> Self {
+        Self {
+            ema: EMAStreaming::new(smooth_n),
+            rsi: RSIStreaming::new(rsi_n),
+        }
+    }
+
+    pub fn update(&mut self, close: f64) -> f64 {
+        let smoothed = self.ema.update(close);
+        self.rsi.update(smoothed)
+    }
+
+    pub fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    pub fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> Self {
+        self.clone()
+    }
+
+    pub fn reset(&mut self) {
+        self.ema.reset();
+        self.rsi.reset();
+    }
+}
+
+// ============================================================================
+// Detrended RSI (RSI minus its SMA)
+// ============================================================================
+#[pyclass]
+#[derive(Clone)]
+pub struct DetrendedRSIStreaming {
+    rsi: RSIStreaming,
+    sma: SMAStreaming,
+}
+
+#[pymethods]
+impl DetrendedRSIStreaming {
+    #[new]
+    #[pyo3(signature = (rsi_n=14, sma_n=14))]
+    pub fn new(rsi_n: usize, sma_n: usize) -> Self {
+        Self {
+            rsi: RSIStreaming::new(rsi_n),
+            sma: SMAStreaming::new(sma_n),
+        }
+    }
+
+    pub fn update(&mut self, close: f64) -> f64 {
+        let rsi_value = self.rsi.update(close);
+        if rsi_value.is_nan() {
+            return f64::NAN;
+        }
+        rsi_value - self.sma.update(rsi_value)
+    }
+
+    pub fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    pub fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> Self {
+        self.clone()
+    }
+
+    pub fn reset(&mut self) {
+        self.rsi.reset();
+        self.sma.reset();
+    }
+}
+
+// ============================================================================
+// Up/Down Streak
+// ============================================================================
+#[pyclass]
+#[derive(Clone)]
+pub struct UpDownStreakStreaming {
+    prev_close: f64,
+    streak: f64,
+}
+
+#[pymethods]
+impl UpDownStreakStreaming {
+    #[new]
+    pub fn new() -> Self {
+        Self {
+            prev_close: f64::NAN,
+            streak: 0.0,
+        }
+    }
+
+    pub fn update(&mut self, close: f64) -> f64 {
+        if !self.prev_close.is_nan() {
+            self.streak = if close > self.prev_close {
+                if self.streak > 0.0 { self.streak + 1.0 } else { 1.0 }
+            } else if close < self.prev_close {
+                if self.streak < 0.0 { self.streak - 1.0 } else { -1.0 }
+            } else {
+                0.0
+            };
+        }
+
+        self.prev_close = close;
+        self.streak
+    }
+
+    pub fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    pub fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> Self {
+        self.clone()
+    }
+
+    pub fn reset(&mut self) {
+        self.prev_close = f64::NAN;
+        self.streak = 0.0;
+    }
+}
+
+// ============================================================================
+// Connors RSI
+// ============================================================================
+#[pyclass]
+#[derive(Clone)]
+pub struct ConnorsRSIStreaming {
+    rank_n: usize,
+    price_rsi: RSIStreaming,
+    streak_rsi: RSIStreaming,
+    streak: UpDownStreakStreaming,
+    prev_close: f64,
+    // The rank_n one-bar ROCs before the current one
+    prior_rocs: helpers::SortedWindow,
+}
+
+#[pymethods]
+impl ConnorsRSIStreaming {
+    #[new]
+    #[pyo3(signature = (rsi_n=3, streak_n=2, rank_n=100))]
+    pub fn new(rsi_n: usize, streak_n: usize, rank_n: usize) -> Self {
+        Self {
+            rank_n,
+            price_rsi: RSIStreaming::new(rsi_n),
+            streak_rsi: RSIStreaming::new(streak_n),
+            streak: UpDownStreakStreaming::new(),
+            prev_close: f64::NAN,
+            prior_rocs: helpers::SortedWindow::new(rank_n),
+        }
+    }
+
+    pub fn update(&mut self, close: f64) -> f64 {
+        let rsi = self.price_rsi.update(close);
+
+        let streak = self.streak.update(close);
+        let streak_rsi = self.streak_rsi.update(streak);
+
+        // Percent rank of the current ROC among the rank_n prior values; NaN
+        // ROCs take a slot in the window but never count as below
+        let mut rank = f64::NAN;
+        if !self.prev_close.is_nan() {
+            let roc = if self.prev_close != 0.0 {
+                (close - self.prev_close) / self.prev_close * 100.0
+            } else {
+                f64::NAN
+            };
+            if self.prior_rocs.is_full() && !roc.is_nan() {
+                rank = 100.0 * self.prior_rocs.count_below(roc) as f64 / self.rank_n as f64;
+            }
+            self.prior_rocs.push(roc);
+        }
+        self.prev_close = close;
+
+        if rsi.is_nan() || streak_rsi.is_nan() || rank.is_nan() {
+            return f64::NAN;
+        }
+
+        (rsi + streak_rsi + rank) / 3.0
+    }
+
+    pub fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    pub fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> Self {
+        self.clone()
+    }
+
+    pub fn reset(&mut self) {
+        self.price_rsi.reset();
+        self.streak_rsi.reset();
+        self.streak.reset();
+        self.prev_close = f64::NAN;
+        self.prior_rocs.clear();
+    }
+}
+
 // ============================================================================
 // Stochastic Oscillator
 // ============================================================================
+/// Stochastic Oscillator. Raw %K is averaged over `slowing` bars before `d_ma`
+/// smooths it into %D, matching bulk `stochastic` with the same parameters.
 #[pyclass]
+#[derive(Clone)]
 pub struct StochasticStreaming {
     k_period: usize,
-    d_period: usize,
+    slowing: usize,
     high_buffer: VecDeque<f64>,
     low_buffer: VecDeque<f64>,
-    percent_k_buffer: VecDeque<f64>,
+    raw_k_buffer: VecDeque<f64>,
+    d_ma: MovingAverageStreaming,
 }
 
-#[pymethods]
 impl StochasticStreaming {
-    #[new]
-    pub fn new(k_period: usize, d_period: usize) -> Self {
-        Self {
+    fn try_new(k_period: usize, d_period: usize, slowing: usize, d_ma: &str) -> Result<Self, String> {
+        if slowing == 0 {
+            return Err("slowing must be at least 1".to_string());
+        }
+        Ok(Self {
             k_period,
-            d_period,
+            slowing,
             high_buffer: VecDeque::with_capacity(k_period),
             low_buffer: VecDeque::with_capacity(k_period),
-            percent_k_buffer: VecDeque::with_capacity(d_period),
-        }
+            raw_k_buffer: VecDeque::with_capacity(slowing),
+            d_ma: MovingAverageStreaming::from_type(d_ma, d_period)?,
+        })
+    }
+}
+
+#[pymethods]
+impl StochasticStreaming {
+    #[new]
+    #[pyo3(signature = (k_period, d_period, slowing=1, d_ma="sma"))]
+    pub fn new(k_period: usize, d_period: usize, slowing: usize, d_ma: &str) -> PyResult<Self> {
+        Self::try_new(k_period, d_period, slowing, d_ma).map_err(PyValueError::new_err)
     }
 
     /// Returns (percent_k, percent_d)
@@ -118,52 +352,77 @@ impl StochasticStreaming {
         let highest_high = self.high_buffer.iter().fold(f64::NEG_INFINITY, |a, &b| a.max(b));
         let lowest_low = self.low_buffer.iter().fold(f64::INFINITY, |a, &b| a.min(b));
 
-        let percent_k = if highest_high != lowest_low {
+        let raw_k = if highest_high != lowest_low {
             100.0 * (close - lowest_low) / (highest_high - lowest_low)
         } else {
-            0.0
+            50.0
         };
 
-        self.percent_k_buffer.push_back(percent_k);
-        if self.percent_k_buffer.len() > self.d_period {
-            self.percent_k_buffer.pop_front();
+        self.raw_k_buffer.push_back(raw_k);
+        if self.raw_k_buffer.len() > self.slowing {
+            self.raw_k_buffer.pop_front();
         }
 
-        let percent_d = if self.percent_k_buffer.len() >= self.d_period {
-            self.percent_k_buffer.iter().sum::<f64>() / self.percent_k_buffer.len() as f64
-        } else {
-            f64::NAN
-        };
+        if self.raw_k_buffer.len() < self.slowing {
+            return (f64::NAN, f64::NAN);
+        }
+
+        let percent_k = self.raw_k_buffer.iter().sum::<f64>() / self.slowing as f64;
+        let percent_d = self.d_ma.update(percent_k);
 
         (percent_k, percent_d)
     }
 
+    pub fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    pub fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> Self {
+        self.clone()
+    }
+
     pub fn reset(&mut self) {
         self.high_buffer.clear();
         self.low_buffer.clear();
-        self.percent_k_buffer.clear();
+        self.raw_k_buffer.clear();
+        self.d_ma.reset();
     }
 }
 
 // ============================================================================
 // Williams %R
 // ============================================================================
+/// Williams %R. With `smooth > 1` the raw %R is averaged by an SMA of that
+/// length, matching bulk `williams_r`.
 #[pyclass]
+#[derive(Clone)]
 pub struct WilliamsRStreaming {
     window: usize,
     high_buffer: VecDeque<f64>,
     low_buffer: VecDeque<f64>,
+    smoother: Option<SMAStreaming>,
 }
 
-#[pymethods]
 impl WilliamsRStreaming {
-    #[new]
-    pub fn new(window: usize) -> Self {
-        Self {
+    fn try_new(window: usize, smooth: usize) -> Result<Self, String> {
+        if smooth == 0 {
+            return Err("smooth must be at least 1".to_string());
+        }
+        Ok(Self {
             window,
             high_buffer: VecDeque::with_capacity(window),
             low_buffer: VecDeque::with_capacity(window),
-        }
+            smoother: (smooth > 1).then(|| SMAStreaming::new(smooth)),
+        })
+    }
+}
+
+#[pymethods]
+impl WilliamsRStreaming {
+    #[new]
+    #[pyo3(signature = (window, smooth=1))]
+    pub fn new(window: usize, smooth: usize) -> PyResult<Self> {
+        Self::try_new(window, smooth).map_err(PyValueError::new_err)
     }
 
     pub fn update(&mut self, high: f64, low: f64, close: f64) -> f64 {
@@ -176,22 +435,38 @@ impl WilliamsRStreaming {
         }
 
         if self.high_buffer.len() < self.window {
-            f64::NAN
+            return f64::NAN;
+        }
+
+        let highest_high = self.high_buffer.iter().fold(f64::NEG_INFINITY, |a, &b| a.max(b));
+        let lowest_low = self.low_buffer.iter().fold(f64::INFINITY, |a, &b| a.min(b));
+
+        let wr = if highest_high != lowest_low {
+            -100.0 * (highest_high - close) / (highest_high - lowest_low)
         } else {
-            let highest_high = self.high_buffer.iter().fold(f64::NEG_INFINITY, |a, &b| a.max(b));
-            let lowest_low = self.low_buffer.iter().fold(f64::INFINITY, |a, &b| a.min(b));
+            -100.0
+        };
 
-            if highest_high != lowest_low {
-                -100.0 * (highest_high - close) / (highest_high - lowest_low)
-            } else {
-                -100.0
-            }
+        match &mut self.smoother {
+            Some(sma) => sma.update(wr),
+            None => wr,
         }
     }
 
+    pub fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    pub fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> Self {
+        self.clone()
+    }
+
     pub fn reset(&mut self) {
         self.high_buffer.clear();
         self.low_buffer.clear();
+        if let Some(sma) = &mut self.smoother {
+            sma.reset();
+        }
     }
 }
 
@@ -199,18 +474,22 @@ impl WilliamsRStreaming {
 // ROC (Rate of Change)
 // ============================================================================
 #[pyclass]
+#[derive(Clone)]
 pub struct ROCStreaming {
     window: usize,
     buffer: VecDeque<f64>,
+    scale: f64,
 }
 
 #[pymethods]
 impl ROCStreaming {
     #[new]
-    pub fn new(window: usize) -> Self {
+    #[pyo3(signature = (window, pct=true))]
+    pub fn new(window: usize, pct: bool) -> Self {
         Self {
             window,
             buffer: VecDeque::with_capacity(window),
+            scale: helpers::return_scale(pct),
         }
     }
 
@@ -226,13 +505,21 @@ impl ROCStreaming {
         } else {
             let old_value = self.buffer[0];
             if old_value != 0.0 {
-                (value - old_value) / old_value * 100.0
+                (value - old_value) / old_value * self.scale
             } else {
                 0.0
             }
         }
     }
 
+    pub fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    pub fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> Self {
+        self.clone()
+    }
+
     pub fn reset(&mut self) {
         self.buffer.clear();
     }
@@ -241,7 +528,9 @@ impl ROCStreaming {
 // ============================================================================
 // PPO (Percentage Price Oscillator)
 // ============================================================================
+/// Unadjusted, first-value-seeded EMAs throughout; matches `percentage_price_oscillator_numba`.
 #[pyclass]
+#[derive(Clone)]
 pub struct PPOStreaming {
     fast_ema: EMAStreaming,
     slow_ema: EMAStreaming,
@@ -279,6 +568,14 @@ impl PPOStreaming {
         (ppo_line, signal_line, histogram)
     }
 
+    pub fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    pub fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> Self {
+        self.clone()
+    }
+
     pub fn reset(&mut self) {
         self.fast_ema.reset();
         self.slow_ema.reset();
@@ -289,7 +586,9 @@ impl PPOStreaming {
 // ============================================================================
 // PVO (Percentage Volume Oscillator) - same as PPO but for volume
 // ============================================================================
+/// Uses an unadjusted signal EMA; matches `percentage_volume_oscillator_numba(signal_adjusted=False)`.
 #[pyclass]
+#[derive(Clone)]
 pub struct PVOStreaming {
     fast_ema: EMAStreaming,
     slow_ema: EMAStreaming,
@@ -327,6 +626,14 @@ impl PVOStreaming {
         (pvo_line, signal_line, histogram)
     }
 
+    pub fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    pub fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> Self {
+        self.clone()
+    }
+
     pub fn reset(&mut self) {
         self.fast_ema.reset();
         self.slow_ema.reset();
@@ -338,6 +645,7 @@ impl PVOStreaming {
 // Ultimate Oscillator
 // ============================================================================
 #[pyclass]
+#[derive(Clone)]
 pub struct UltimateOscillatorStreaming {
     period1: usize,
     period2: usize,
@@ -400,6 +708,14 @@ impl UltimateOscillatorStreaming {
         }
     }
 
+    pub fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    pub fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> Self {
+        self.clone()
+    }
+
     pub fn reset(&mut self) {
         self.bp_buffer.clear();
         self.tr_buffer.clear();
@@ -412,6 +728,7 @@ impl UltimateOscillatorStreaming {
 // Stochastic RSI
 // ============================================================================
 #[pyclass]
+#[derive(Clone)]
 pub struct StochasticRSIStreaming {
     rsi_stream: RSIStreaming,
     rsi_buffer: VecDeque<f64>,
@@ -465,6 +782,14 @@ impl StochasticRSIStreaming {
         (stoch_rsi, k_value, d_value)
     }
 
+    pub fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    pub fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> Self {
+        self.clone()
+    }
+
     pub fn reset(&mut self) {
         self.rsi_stream.reset();
         self.rsi_buffer.clear();
@@ -476,28 +801,41 @@ impl StochasticRSIStreaming {
 // ============================================================================
 // TSI (True Strength Index)
 // ============================================================================
+/// Uses unadjusted EMAs (or SMAs with `ma_type="sma"`); matches
+/// `true_strength_index_numba(adjusted=False)` with the same `ma_type`.
 #[pyclass]
+#[derive(Clone)]
 pub struct TSIStreaming {
-    momentum_ema1: EMAStreaming,
-    momentum_ema2: EMAStreaming,
-    abs_momentum_ema1: EMAStreaming,
-    abs_momentum_ema2: EMAStreaming,
+    momentum_ma1: MovingAverageStreaming,
+    momentum_ma2: MovingAverageStreaming,
+    abs_momentum_ma1: MovingAverageStreaming,
+    abs_momentum_ma2: MovingAverageStreaming,
     prev_close: f64,
     update_count: usize,
 }
 
-#[pymethods]
 impl TSIStreaming {
-    #[new]
-    pub fn new(first_smooth: usize, second_smooth: usize) -> Self {
-        Self {
-            momentum_ema1: EMAStreaming::new(first_smooth),
-            momentum_ema2: EMAStreaming::new(second_smooth),
-            abs_momentum_ema1: EMAStreaming::new(first_smooth),
-            abs_momentum_ema2: EMAStreaming::new(second_smooth),
+    fn try_new(first_smooth: usize, second_smooth: usize, ma_type: &str) -> Result<Self, String> {
+        if ma_type != "ema" && ma_type != "sma" {
+            return Err(format!("unknown ma_type '{}', expected one of: ema, sma", ma_type));
+        }
+        Ok(Self {
+            momentum_ma1: MovingAverageStreaming::from_type(ma_type, first_smooth)?,
+            momentum_ma2: MovingAverageStreaming::from_type(ma_type, second_smooth)?,
+            abs_momentum_ma1: MovingAverageStreaming::from_type(ma_type, first_smooth)?,
+            abs_momentum_ma2: MovingAverageStreaming::from_type(ma_type, second_smooth)?,
             prev_close: f64::NAN,
             update_count: 0,
-        }
+        })
+    }
+}
+
+#[pymethods]
+impl TSIStreaming {
+    #[new]
+    #[pyo3(signature = (first_smooth, second_smooth, ma_type="ema"))]
+    pub fn new(first_smooth: usize, second_smooth: usize, ma_type: &str) -> PyResult<Self> {
+        Self::try_new(first_smooth, second_smooth, ma_type).map_err(PyValueError::new_err)
     }
 
     pub fn update(&mut self, value: f64) -> f64 {
@@ -510,14 +848,18 @@ impl TSIStreaming {
 
         let momentum = value - self.prev_close;
         let abs_momentum = momentum.abs();
+        self.prev_close = value;
 
-        let smooth1_momentum = self.momentum_ema1.update(momentum);
-        let smooth2_momentum = self.momentum_ema2.update(smooth1_momentum);
+        let smooth1_momentum = self.momentum_ma1.update(momentum);
+        let smooth1_abs = self.abs_momentum_ma1.update(abs_momentum);
 
-        let smooth1_abs = self.abs_momentum_ema1.update(abs_momentum);
-        let smooth2_abs = self.abs_momentum_ema2.update(smooth1_abs);
+        // An SMA first pass is NaN until full; keep it out of the second pass
+        if smooth1_momentum.is_nan() || smooth1_abs.is_nan() {
+            return f64::NAN;
+        }
 
-        self.prev_close = value;
+        let smooth2_momentum = self.momentum_ma2.update(smooth1_momentum);
+        let smooth2_abs = self.abs_momentum_ma2.update(smooth1_abs);
 
         if !smooth2_momentum.is_nan() && !smooth2_abs.is_nan() && smooth2_abs != 0.0 {
             100.0 * (smooth2_momentum / smooth2_abs)
@@ -526,11 +868,19 @@ impl TSIStreaming {
         }
     }
 
+    pub fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    pub fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> Self {
+        self.clone()
+    }
+
     pub fn reset(&mut self) {
-        self.momentum_ema1.reset();
-        self.momentum_ema2.reset();
-        self.abs_momentum_ema1.reset();
-        self.abs_momentum_ema2.reset();
+        self.momentum_ma1.reset();
+        self.momentum_ma2.reset();
+        self.abs_momentum_ma1.reset();
+        self.abs_momentum_ma2.reset();
         self.prev_close = f64::NAN;
         self.update_count = 0;
     }
@@ -540,9 +890,26 @@ impl TSIStreaming {
 // Awesome Oscillator
 // ============================================================================
 #[pyclass]
+#[derive(Clone)]
 pub struct AwesomeOscillatorStreaming {
     fast_sma: SMAStreaming,
     slow_sma: SMAStreaming,
+    // Last three AO bars plus the bar before them, which sets the first bar's colour
+    history: VecDeque<f64>,
+}
+
+impl AwesomeOscillatorStreaming {
+    const HISTORY_LEN: usize = 4;
+
+    fn record(&mut self, ao: f64) {
+        if ao.is_nan() {
+            return;
+        }
+        self.history.push_back(ao);
+        if self.history.len() > Self::HISTORY_LEN {
+            self.history.pop_front();
+        }
+    }
 }
 
 #[pymethods]
@@ -552,6 +919,7 @@ impl AwesomeOscillatorStreaming {
         Self {
             fast_sma: SMAStreaming::new(fast_period),
             slow_sma: SMAStreaming::new(slow_period),
+            history: VecDeque::with_capacity(Self::HISTORY_LEN),
         }
     }
 
@@ -560,44 +928,75 @@ impl AwesomeOscillatorStreaming {
         let fast = self.fast_sma.update(midpoint);
         let slow = self.slow_sma.update(midpoint);
 
-        if fast.is_nan() || slow.is_nan() {
+        let ao = if fast.is_nan() || slow.is_nan() {
             f64::NAN
         } else {
             fast - slow
+        };
+
+        self.record(ao);
+        ao
+    }
+
+    /// Bullish saucer: two consecutive red bars followed by a green bar, all above zero.
+    /// A bar is red when it is lower than the previous bar and green when higher.
+    pub fn is_bullish_saucer(&self) -> bool {
+        if self.history.len() < Self::HISTORY_LEN {
+            return false;
+        }
+        let (prev, red1, red2, green) = (self.history[0], self.history[1], self.history[2], self.history[3]);
+        red1 < prev && red2 < red1 && green > red2 && red1 > 0.0 && red2 > 0.0 && green > 0.0
+    }
+
+    /// Zero-line cross on the latest bar: 1.0 crossing up, -1.0 crossing down, 0.0 otherwise
+    pub fn zero_cross(&self) -> f64 {
+        let len = self.history.len();
+        if len < 2 {
+            return 0.0;
+        }
+        let (prev, curr) = (self.history[len - 2], self.history[len - 1]);
+        if prev <= 0.0 && curr > 0.0 {
+            1.0
+        } else if prev >= 0.0 && curr < 0.0 {
+            -1.0
+        } else {
+            0.0
         }
     }
 
+    pub fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    pub fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> Self {
+        self.clone()
+    }
+
     pub fn reset(&mut self) {
         self.fast_sma.reset();
         self.slow_sma.reset();
+        self.history.clear();
     }
 }
 
 // ============================================================================
-// KAMA (Kaufman's Adaptive Moving Average)
+// Efficiency Ratio (Kaufman)
 // ============================================================================
 #[pyclass]
-pub struct KAMAStreaming {
+#[derive(Clone)]
+pub struct EfficiencyRatioStreaming {
     window: usize,
-    fast_sc: f64,
-    slow_sc: f64,
     price_buffer: VecDeque<f64>,
-    prev_kama: f64,
 }
 
 #[pymethods]
-impl KAMAStreaming {
+impl EfficiencyRatioStreaming {
     #[new]
-    pub fn new(window: usize, fast_period: usize, slow_period: usize) -> Self {
-        let fast_sc = 2.0 / (fast_period as f64 + 1.0);
-        let slow_sc = 2.0 / (slow_period as f64 + 1.0);
-
+    #[pyo3(signature = (window=10))]
+    pub fn new(window: usize) -> Self {
         Self {
             window,
-            fast_sc,
-            slow_sc,
             price_buffer: VecDeque::with_capacity(window + 1),
-            prev_kama: f64::NAN,
         }
     }
 
@@ -608,22 +1007,70 @@ impl KAMAStreaming {
             self.price_buffer.pop_front();
         }
 
-        if self.price_buffer.len() < self.window + 1 {
+        if self.window == 0 || self.price_buffer.len() < self.window + 1 {
             return f64::NAN;
         }
 
-        let price_vec: Vec<f64> = self.price_buffer.iter().copied().collect();
-        let direction = (price_vec[price_vec.len() - 1] - price_vec[0]).abs();
+        let direction = (self.price_buffer[self.window] - self.price_buffer[0]).abs();
 
-        let volatility: f64 = price_vec.windows(2)
-            .map(|w| (w[1] - w[0]).abs())
+        let volatility: f64 = self.price_buffer.iter()
+            .zip(self.price_buffer.iter().skip(1))
+            .map(|(prev, cur)| (cur - prev).abs())
             .sum();
 
-        let er = if volatility > 0.0 {
+        if volatility > 0.0 {
             direction / volatility
         } else {
             0.0
-        };
+        }
+    }
+
+    pub fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    pub fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> Self {
+        self.clone()
+    }
+
+    pub fn reset(&mut self) {
+        self.price_buffer.clear();
+    }
+}
+
+// ============================================================================
+// KAMA (Kaufman's Adaptive Moving Average)
+// ============================================================================
+#[pyclass]
+#[derive(Clone)]
+pub struct KAMAStreaming {
+    fast_sc: f64,
+    slow_sc: f64,
+    efficiency_ratio: EfficiencyRatioStreaming,
+    prev_kama: f64,
+}
+
+#[pymethods]
+impl KAMAStreaming {
+    #[new]
+    pub fn new(window: usize, fast_period: usize, slow_period: usize) -> Self {
+        let fast_sc = 2.0 / (fast_period as f64 + 1.0);
+        let slow_sc = 2.0 / (slow_period as f64 + 1.0);
+
+        Self {
+            fast_sc,
+            slow_sc,
+            efficiency_ratio: EfficiencyRatioStreaming::new(window),
+            prev_kama: f64::NAN,
+        }
+    }
+
+    pub fn update(&mut self, value: f64) -> f64 {
+        let er = self.efficiency_ratio.update(value);
+
+        if er.is_nan() {
+            return f64::NAN;
+        }
 
         let sc = (er * (self.fast_sc - self.slow_sc) + self.slow_sc).powi(2);
 
@@ -637,8 +1084,16 @@ impl KAMAStreaming {
         result
     }
 
+    pub fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    pub fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> Self {
+        self.clone()
+    }
+
     pub fn reset(&mut self) {
-        self.price_buffer.clear();
+        self.efficiency_ratio.reset();
         self.prev_kama = f64::NAN;
     }
 }
@@ -647,6 +1102,7 @@ impl KAMAStreaming {
 // Momentum (simple)
 // ============================================================================
 #[pyclass]
+#[derive(Clone)]
 pub struct MomentumStreaming {
     window: usize,
     buffer: VecDeque<f64>,
@@ -676,7 +1132,334 @@ impl MomentumStreaming {
         }
     }
 
+    pub fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    pub fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> Self {
+        self.clone()
+    }
+
     pub fn reset(&mut self) {
         self.buffer.clear();
     }
 }
+
+// ============================================================================
+// Chande Momentum Oscillator (CMO)
+// ============================================================================
+#[pyclass]
+#[derive(Clone)]
+pub struct CMOStreaming {
+    window: usize,
+    prev_value: f64,
+    gains: VecDeque<f64>,
+    losses: VecDeque<f64>,
+    sum_up: f64,
+    sum_down: f64,
+}
+
+#[pymethods]
+impl CMOStreaming {
+    #[new]
+    #[pyo3(signature = (window=14))]
+    pub fn new(window: usize) -> Self {
+        Self {
+            window,
+            prev_value: f64::NAN,
+            gains: VecDeque::with_capacity(window + 1),
+            losses: VecDeque::with_capacity(window + 1),
+            sum_up: 0.0,
+            sum_down: 0.0,
+        }
+    }
+
+    pub fn update(&mut self, value: f64) -> f64 {
+        if self.prev_value.is_nan() {
+            self.prev_value = value;
+            return f64::NAN;
+        }
+
+        let delta = value - self.prev_value;
+        self.prev_value = value;
+        let (gain, loss) = if delta > 0.0 { (delta, 0.0) } else { (0.0, -delta) };
+
+        self.gains.push_back(gain);
+        self.losses.push_back(loss);
+        self.sum_up += gain;
+        self.sum_down += loss;
+
+        if self.gains.len() > self.window {
+            self.sum_up -= self.gains.pop_front().unwrap();
+            self.sum_down -= self.losses.pop_front().unwrap();
+        }
+
+        if self.gains.len() < self.window {
+            return f64::NAN;
+        }
+
+        let total = self.sum_up + self.sum_down;
+        if total != 0.0 {
+            100.0 * (self.sum_up - self.sum_down) / total
+        } else {
+            0.0
+        }
+    }
+
+    pub fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    pub fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> Self {
+        self.clone()
+    }
+
+    pub fn reset(&mut self) {
+        self.prev_value = f64::NAN;
+        self.gains.clear();
+        self.losses.clear();
+        self.sum_up = 0.0;
+        self.sum_down = 0.0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::momentum::connors_rsi_kernel;
+
+    #[test]
+    fn test_connors_rsi_streaming_converges_to_bulk() {
+        let close: Vec<f64> = (0..200)
+            .map(|i| 100.0 + (i as f64 * 0.45).sin() * 3.0 + (i as f64 * 0.13).cos() * 2.0)
+            .collect();
+        let bulk = connors_rsi_kernel(&close, 3, 2, 20);
+
+        let mut crsi = ConnorsRSIStreaming::new(3, 2, 20);
+        let streamed: Vec<f64> = close.iter().map(|&c| crsi.update(c)).collect();
+
+        assert!(streamed[20].is_nan());
+        for i in 100..close.len() {
+            assert!((streamed[i] - bulk[i]).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_detrended_rsi_streaming_is_rsi_minus_sma() {
+        let close: Vec<f64> = (0..60).map(|i| 100.0 + (i as f64 * 0.4).sin() * 3.0).collect();
+        let mut rsi = RSIStreaming::new(14);
+        let mut detrended = DetrendedRSIStreaming::new(14, 5);
+        let mut recent = VecDeque::new();
+
+        for &c in &close {
+            let r = rsi.update(c);
+            let value = detrended.update(c);
+            if r.is_nan() {
+                assert!(value.is_nan());
+                continue;
+            }
+            recent.push_back(r);
+            if recent.len() > 5 {
+                recent.pop_front();
+            }
+            if recent.len() < 5 {
+                assert!(value.is_nan());
+            } else {
+                let mean = recent.iter().sum::<f64>() / 5.0;
+                assert!((value - (r - mean)).abs() < 1e-9);
+            }
+        }
+
+        // a steady climb pins RSI at 100, leaving nothing above its average
+        let mut flat = DetrendedRSIStreaming::new(14, 14);
+        let last = (0..60).map(|i| flat.update(100.0 + i as f64)).last().unwrap();
+        assert!(last.abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_up_down_streak_streaming() {
+        let mut streak = UpDownStreakStreaming::new();
+        let close = [10.0, 11.0, 10.0, 10.0, 9.0, 8.0, 9.0, 10.0];
+        let expected = [0.0, 1.0, -1.0, 0.0, -1.0, -2.0, 1.0, 2.0];
+        for (&c, &want) in close.iter().zip(expected.iter()) {
+            assert_eq!(streak.update(c), want);
+        }
+    }
+
+    #[test]
+    fn test_tsi_streaming_matches_unadjusted_bulk() {
+        let close: Vec<f64> = (0..200)
+            .map(|i| 100.0 + (i as f64 * 0.37).sin() * 4.0 + i as f64 * 0.05)
+            .collect();
+        let bulk = crate::momentum::tsi_kernel(&close, 25, 13, false, "ema").unwrap();
+
+        let mut tsi = TSIStreaming::try_new(25, 13, "ema").unwrap();
+        for (i, &c) in close.iter().enumerate() {
+            let value = tsi.update(c);
+            if i == 0 {
+                assert!(value.is_nan() && bulk[i].is_nan());
+            } else {
+                assert!((value - bulk[i]).abs() < 1e-9, "bar {}: {} vs {}", i, value, bulk[i]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_efficiency_ratio_streaming_matches_bulk() {
+        let close: Vec<f64> = (0..60)
+            .map(|i| 100.0 + (i as f64 * 0.37).sin() * 4.0 + i as f64 * 0.05)
+            .collect();
+        let bulk = crate::momentum::efficiency_ratio_kernel(&close, 10);
+
+        let mut er = EfficiencyRatioStreaming::new(10);
+        for (i, &c) in close.iter().enumerate() {
+            let value = er.update(c);
+            if bulk[i].is_nan() {
+                assert!(value.is_nan());
+            } else {
+                assert!((value - bulk[i]).abs() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_awesome_oscillator_saucer_and_zero_cross() {
+        // With fast=1 and slow=2 on flat bars, AO is half the change in midpoint
+        let mut ao = AwesomeOscillatorStreaming::new(1, 2);
+        let outputs: Vec<f64> = [0.0, 6.0, 11.0, 15.0, 19.8].iter().map(|&m| ao.update(m, m)).collect();
+        assert!(outputs[0].is_nan());
+        assert!((outputs[4] - 2.4).abs() < 1e-9);
+        assert!(ao.is_bullish_saucer());
+        assert_eq!(ao.zero_cross(), 0.0);
+
+        // The same shape straddling zero is not a saucer
+        ao.reset();
+        for m in [0.0, 2.0, 1.0, -1.0, 0.0] {
+            ao.update(m, m);
+        }
+        assert!(!ao.is_bullish_saucer());
+        assert_eq!(ao.zero_cross(), 1.0);
+
+        ao.update(-0.4, -0.4);
+        assert_eq!(ao.zero_cross(), -1.0);
+    }
+
+    #[test]
+    fn test_pvo_streaming_matches_unadjusted_bulk() {
+        let volume: Vec<f64> = (0..120)
+            .map(|i| 1_000.0 + (i as f64 * 0.7).sin() * 300.0 + (i % 7) as f64 * 50.0)
+            .collect();
+        let (line, signal, hist) = crate::momentum::percentage_oscillator_kernel(
+            &volume, 12, 26, 9, false, crate::helpers::HistMode::Diff,
+        );
+
+        let mut pvo = PVOStreaming::new(12, 26, 9);
+        for (i, &v) in volume.iter().enumerate() {
+            let (l, s, h) = pvo.update(v);
+            assert!((l - line[i]).abs() < 1e-9, "bar {}", i);
+            assert!((s - signal[i]).abs() < 1e-9, "bar {}", i);
+            assert!((h - hist[i]).abs() < 1e-9, "bar {}", i);
+        }
+    }
+
+    #[test]
+    fn test_ppo_streaming_matches_bulk() {
+        let close: Vec<f64> = (0..120)
+            .map(|i| 100.0 + (i as f64 * 0.37).sin() * 4.0 + i as f64 * 0.05)
+            .collect();
+        let (line, signal, hist) = crate::momentum::percentage_oscillator_kernel(
+            &close, 12, 26, 9, false, crate::helpers::HistMode::Diff,
+        );
+
+        let mut ppo = PPOStreaming::new(12, 26, 9);
+        for (i, &c) in close.iter().enumerate() {
+            let (l, s, h) = ppo.update(c);
+            assert!((l - line[i]).abs() < 1e-9, "bar {}", i);
+            assert!((s - signal[i]).abs() < 1e-9, "bar {}", i);
+            assert!((h - hist[i]).abs() < 1e-9, "bar {}", i);
+        }
+    }
+
+    #[test]
+    fn test_slow_stochastic_streaming_matches_bulk() {
+        let close: Vec<f64> = (0..120)
+            .map(|i| 100.0 + (i as f64 * 0.37).sin() * 4.0 + i as f64 * 0.05)
+            .collect();
+        let high: Vec<f64> = close.iter().map(|c| c + 1.5).collect();
+        let low: Vec<f64> = close.iter().map(|c| c - 1.5).collect();
+
+        for d_ma in ["sma", "ema"] {
+            let (bulk_k, bulk_d) = crate::momentum::stochastic_kernel(
+                &high, &low, &close, 14, 3, 3, d_ma, crate::helpers::Compat::Native,
+            )
+            .unwrap();
+
+            let mut stoch = StochasticStreaming::try_new(14, 3, 3, d_ma).unwrap();
+            for i in 0..close.len() {
+                let (k, d) = stoch.update(high[i], low[i], close[i]);
+                assert_eq!(k.is_nan(), bulk_k[i].is_nan(), "{} %K warmup at bar {}", d_ma, i);
+                assert_eq!(d.is_nan(), bulk_d[i].is_nan(), "{} %D warmup at bar {}", d_ma, i);
+                if !d.is_nan() {
+                    assert!((k - bulk_k[i]).abs() < 1e-9);
+                    assert!((d - bulk_d[i]).abs() < 1e-9);
+                }
+            }
+        }
+        assert!(StochasticStreaming::try_new(14, 3, 0, "sma").is_err());
+    }
+
+    #[test]
+    fn test_smoothed_williams_r_streaming_matches_bulk() {
+        let close: Vec<f64> = (0..80)
+            .map(|i| 100.0 + (i as f64 * 0.37).sin() * 4.0 + i as f64 * 0.05)
+            .collect();
+        let high: Vec<f64> = close.iter().map(|c| c + 1.0).collect();
+        let low: Vec<f64> = close.iter().map(|c| c - 1.0).collect();
+        let bulk = crate::momentum::williams_r_kernel(&high, &low, &close, 14, 3).unwrap();
+
+        let mut wr = WilliamsRStreaming::try_new(14, 3).unwrap();
+        for i in 0..close.len() {
+            let value = wr.update(high[i], low[i], close[i]);
+            assert_eq!(value.is_nan(), bulk[i].is_nan(), "warmup at bar {}", i);
+            if !value.is_nan() {
+                assert!((value - bulk[i]).abs() < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn test_tsi_streaming_sma_matches_bulk() {
+        let close: Vec<f64> = (0..120)
+            .map(|i| 100.0 + (i as f64 * 0.37).sin() * 4.0 + i as f64 * 0.05)
+            .collect();
+        let bulk = crate::momentum::tsi_kernel(&close, 10, 5, false, "sma").unwrap();
+
+        let mut tsi = TSIStreaming::try_new(10, 5, "sma").unwrap();
+        for (i, &c) in close.iter().enumerate() {
+            let value = tsi.update(c);
+            assert_eq!(value.is_nan(), bulk[i].is_nan(), "warmup at bar {}", i);
+            if !value.is_nan() {
+                assert!((value - bulk[i]).abs() < 1e-9, "bar {}: {} vs {}", i, value, bulk[i]);
+            }
+        }
+        assert!(TSIStreaming::try_new(10, 5, "wma").is_err());
+    }
+
+    #[test]
+    fn test_cmo_streaming_converges_to_bulk() {
+        let close: Vec<f64> = (0..150)
+            .map(|i| 100.0 + (i as f64 * 0.45).sin() * 3.0 + (i as f64 * 0.13).cos() * 2.0)
+            .collect();
+        let bulk = crate::momentum::cmo_kernel(&close, 14);
+        let mut stream = CMOStreaming::new(14);
+
+        for (i, &c) in close.iter().enumerate() {
+            let v = stream.update(c);
+            if bulk[i].is_nan() {
+                assert!(v.is_nan());
+            } else {
+                assert!((v - bulk[i]).abs() < 1e-9, "bar {}", i);
+            }
+        }
+    }
+}
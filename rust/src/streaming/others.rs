@@ -1,22 +1,29 @@
+use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
 use std::collections::VecDeque;
+use crate::helpers;
+use crate::others::calmar_ratio_of_window;
 
 // ============================================================================
 // Daily Return
 // ============================================================================
 #[pyclass]
+#[derive(Clone)]
 pub struct DailyReturnStreaming {
     prev_close: f64,
     update_count: usize,
+    scale: f64,
 }
 
 #[pymethods]
 impl DailyReturnStreaming {
     #[new]
-    pub fn new() -> Self {
+    #[pyo3(signature = (pct=true))]
+    pub fn new(pct: bool) -> Self {
         Self {
             prev_close: f64::NAN,
             update_count: 0,
+            scale: helpers::return_scale(pct),
         }
     }
 
@@ -29,7 +36,7 @@ impl DailyReturnStreaming {
         }
 
         let result = if self.prev_close != 0.0 {
-            (close - self.prev_close) / self.prev_close * 100.0
+            (close - self.prev_close) / self.prev_close * self.scale
         } else {
             f64::NAN
         };
@@ -38,6 +45,14 @@ impl DailyReturnStreaming {
         result
     }
 
+    pub fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    pub fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> Self {
+        self.clone()
+    }
+
     pub fn reset(&mut self) {
         self.prev_close = f64::NAN;
         self.update_count = 0;
@@ -48,18 +63,22 @@ impl DailyReturnStreaming {
 // Daily Log Return
 // ============================================================================
 #[pyclass]
+#[derive(Clone)]
 pub struct DailyLogReturnStreaming {
     prev_close: f64,
     update_count: usize,
+    scale: f64,
 }
 
 #[pymethods]
 impl DailyLogReturnStreaming {
     #[new]
-    pub fn new() -> Self {
+    #[pyo3(signature = (pct=true))]
+    pub fn new(pct: bool) -> Self {
         Self {
             prev_close: f64::NAN,
             update_count: 0,
+            scale: helpers::return_scale(pct),
         }
     }
 
@@ -72,7 +91,7 @@ impl DailyLogReturnStreaming {
         }
 
         let result = if self.prev_close > 0.0 && close > 0.0 {
-            (close / self.prev_close).ln() * 100.0
+            (close / self.prev_close).ln() * self.scale
         } else {
             f64::NAN
         };
@@ -81,6 +100,14 @@ impl DailyLogReturnStreaming {
         result
     }
 
+    pub fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    pub fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> Self {
+        self.clone()
+    }
+
     pub fn reset(&mut self) {
         self.prev_close = f64::NAN;
         self.update_count = 0;
@@ -91,18 +118,22 @@ impl DailyLogReturnStreaming {
 // Cumulative Return
 // ============================================================================
 #[pyclass]
+#[derive(Clone)]
 pub struct CumulativeReturnStreaming {
     initial_price: f64,
     update_count: usize,
+    scale: f64,
 }
 
 #[pymethods]
 impl CumulativeReturnStreaming {
     #[new]
-    pub fn new() -> Self {
+    #[pyo3(signature = (pct=true))]
+    pub fn new(pct: bool) -> Self {
         Self {
             initial_price: f64::NAN,
             update_count: 0,
+            scale: helpers::return_scale(pct),
         }
     }
 
@@ -115,12 +146,20 @@ impl CumulativeReturnStreaming {
         }
 
         if self.initial_price != 0.0 {
-            ((close / self.initial_price) - 1.0) * 100.0
+            ((close / self.initial_price) - 1.0) * self.scale
         } else {
             f64::NAN
         }
     }
 
+    pub fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    pub fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> Self {
+        self.clone()
+    }
+
     pub fn reset(&mut self) {
         self.initial_price = f64::NAN;
         self.update_count = 0;
@@ -131,6 +170,7 @@ impl CumulativeReturnStreaming {
 // Rolling Return
 // ============================================================================
 #[pyclass]
+#[derive(Clone)]
 pub struct RollingReturnStreaming {
     window: usize,
     close_buffer: VecDeque<f64>,
@@ -167,6 +207,14 @@ impl RollingReturnStreaming {
         }
     }
 
+    pub fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    pub fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> Self {
+        self.clone()
+    }
+
     pub fn reset(&mut self) {
         self.close_buffer.clear();
     }
@@ -176,6 +224,7 @@ impl RollingReturnStreaming {
 // Maximum Drawdown
 // ============================================================================
 #[pyclass]
+#[derive(Clone)]
 pub struct MaxDrawdownStreaming {
     window: usize,
     close_buffer: VecDeque<f64>,
@@ -215,36 +264,167 @@ impl MaxDrawdownStreaming {
         }
     }
 
+    pub fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    pub fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> Self {
+        self.clone()
+    }
+
     pub fn reset(&mut self) {
         self.close_buffer.clear();
     }
 }
 
+// ============================================================================
+// Drawdown (current drawdown, max drawdown, bars in drawdown)
+// ============================================================================
+#[pyclass]
+#[derive(Clone)]
+pub struct DrawdownStreaming {
+    window: Option<usize>,
+    // windowed mode: the window high from a monotonic deque, with max drawdown
+    // from MaxDrawdownStreaming
+    rolling_high: helpers::RollingExtreme,
+    max_drawdown: MaxDrawdownStreaming,
+    // unwindowed mode: running peak since the first bar
+    peak: f64,
+    max_drawdown_pct: f64,
+    bars_in_drawdown: usize,
+}
+
+impl DrawdownStreaming {
+    fn try_new(window: Option<usize>) -> Result<Self, String> {
+        if window == Some(0) {
+            return Err("window must be >= 1".to_string());
+        }
+
+        Ok(Self {
+            window,
+            rolling_high: helpers::RollingExtreme::max(window.unwrap_or(0)),
+            max_drawdown: MaxDrawdownStreaming::new(window.unwrap_or(0)),
+            peak: f64::NAN,
+            max_drawdown_pct: 0.0,
+            bars_in_drawdown: 0,
+        })
+    }
+}
+
+#[pymethods]
+impl DrawdownStreaming {
+    /// Without `window` the peak and max drawdown run over the whole series;
+    /// with it, both are measured over the last `window` closes.
+    #[new]
+    #[pyo3(signature = (window=None))]
+    pub fn new(window: Option<usize>) -> PyResult<Self> {
+        Self::try_new(window).map_err(PyValueError::new_err)
+    }
+
+    /// Returns (current_drawdown_pct, max_drawdown_pct, bars_in_drawdown); drawdowns
+    /// are negative percentages from the peak, as in `MaxDrawdownStreaming`, and
+    /// bars_in_drawdown counts bars since the peak (0 at a new high)
+    pub fn update(&mut self, close: f64) -> (f64, f64, f64) {
+        match self.window {
+            Some(_) => {
+                // the deque keeps the latest bar holding the window high, so an
+                // equal high counts as a new peak
+                self.rolling_high.update(close);
+                let peak = self.rolling_high.extreme();
+                let current = (close - peak) / peak * 100.0;
+                let bars = self.rolling_high.bars_since();
+                let max_drawdown = self.max_drawdown.update(close);
+                let max_drawdown = if max_drawdown.is_nan() { 0.0 } else { max_drawdown };
+                (current, max_drawdown, bars as f64)
+            }
+            None => {
+                if self.peak.is_nan() || close >= self.peak {
+                    self.peak = close;
+                    self.bars_in_drawdown = 0;
+                } else {
+                    self.bars_in_drawdown += 1;
+                }
+                let current = (close - self.peak) / self.peak * 100.0;
+                self.max_drawdown_pct = self.max_drawdown_pct.min(current);
+                (current, self.max_drawdown_pct, self.bars_in_drawdown as f64)
+            }
+        }
+    }
+
+    pub fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    pub fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> Self {
+        self.clone()
+    }
+
+    pub fn reset(&mut self) {
+        self.rolling_high.reset();
+        self.max_drawdown.reset();
+        self.peak = f64::NAN;
+        self.max_drawdown_pct = 0.0;
+        self.bars_in_drawdown = 0;
+    }
+}
+
 // ============================================================================
 // Sharpe Ratio
 // ============================================================================
 #[pyclass]
+#[derive(Clone)]
 pub struct SharpeRatioStreaming {
     window: usize,
+    min_periods: usize,
     risk_free_rate: f64,
     annualization_factor: f64,
+    return_type: helpers::ReturnType,
     prev_close: f64,
     returns_buffer: VecDeque<f64>,
     update_count: usize,
 }
 
-#[pymethods]
 impl SharpeRatioStreaming {
-    #[new]
-    pub fn new(window: usize, risk_free_rate: f64, annualization_factor: f64) -> Self {
-        Self {
+    fn try_new(
+        window: usize,
+        risk_free_rate: f64,
+        annualization_factor: Option<f64>,
+        min_periods: Option<usize>,
+        return_type: &str,
+        bar_frequency: &str,
+    ) -> Result<Self, String> {
+        Ok(Self {
             window,
+            min_periods: helpers::resolve_min_periods(window, min_periods)?,
             risk_free_rate,
-            annualization_factor,
+            annualization_factor: helpers::resolve_periods_per_year(bar_frequency, annualization_factor)?,
+            return_type: helpers::ReturnType::parse(return_type)?,
             prev_close: f64::NAN,
             returns_buffer: VecDeque::with_capacity(window),
             update_count: 0,
-        }
+        })
+    }
+}
+
+#[pymethods]
+impl SharpeRatioStreaming {
+    /// `min_periods` (default: `window`) is the number of returns needed before the
+    /// first estimate; until the window fills, the available returns are used.
+    /// `return_type` is "log" (default) or "simple". `annualization_factor`
+    /// (periods per year) defaults to the one implied by `bar_frequency`
+    /// ("daily", "hourly", "minute" or "crypto_daily"; default: "daily" = 252).
+    #[new]
+    #[pyo3(signature = (window, risk_free_rate, annualization_factor=None, min_periods=None, return_type="log", bar_frequency="daily"))]
+    pub fn new(
+        window: usize,
+        risk_free_rate: f64,
+        annualization_factor: Option<f64>,
+        min_periods: Option<usize>,
+        return_type: &str,
+        bar_frequency: &str,
+    ) -> PyResult<Self> {
+        Self::try_new(window, risk_free_rate, annualization_factor, min_periods, return_type, bar_frequency)
+            .map_err(PyValueError::new_err)
     }
 
     pub fn update(&mut self, close: f64) -> f64 {
@@ -256,8 +436,8 @@ impl SharpeRatioStreaming {
         }
 
         if self.prev_close > 0.0 && close > 0.0 {
-            let log_return = (close / self.prev_close).ln();
-            self.returns_buffer.push_back(log_return);
+            let period_return = self.return_type.compute(self.prev_close, close);
+            self.returns_buffer.push_back(period_return);
 
             if self.returns_buffer.len() > self.window {
                 self.returns_buffer.pop_front();
@@ -266,7 +446,7 @@ impl SharpeRatioStreaming {
 
         self.prev_close = close;
 
-        if self.returns_buffer.len() < self.window {
+        if self.returns_buffer.len() < self.min_periods {
             f64::NAN
         } else {
             let returns_vec: Vec<f64> = self.returns_buffer.iter().copied().collect();
@@ -287,6 +467,14 @@ impl SharpeRatioStreaming {
         }
     }
 
+    pub fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    pub fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> Self {
+        self.clone()
+    }
+
     pub fn reset(&mut self) {
         self.prev_close = f64::NAN;
         self.returns_buffer.clear();
@@ -298,6 +486,7 @@ impl SharpeRatioStreaming {
 // Compound Log Return
 // ============================================================================
 #[pyclass]
+#[derive(Clone)]
 pub struct CompoundLogReturnStreaming {
     cumulative_log_return: f64,
     prev_close: f64,
@@ -332,6 +521,14 @@ impl CompoundLogReturnStreaming {
         (self.cumulative_log_return.exp() - 1.0) * 100.0
     }
 
+    pub fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    pub fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> Self {
+        self.clone()
+    }
+
     pub fn reset(&mut self) {
         self.cumulative_log_return = 0.0;
         self.prev_close = f64::NAN;
@@ -343,6 +540,7 @@ impl CompoundLogReturnStreaming {
 // Rolling Z-Score
 // ============================================================================
 #[pyclass]
+#[derive(Clone)]
 pub struct RollingZScoreStreaming {
     window: usize,
     buffer: VecDeque<f64>,
@@ -382,6 +580,14 @@ impl RollingZScoreStreaming {
         }
     }
 
+    pub fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    pub fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> Self {
+        self.clone()
+    }
+
     pub fn reset(&mut self) {
         self.buffer.clear();
     }
@@ -391,6 +597,7 @@ impl RollingZScoreStreaming {
 // Linear Regression Slope
 // ============================================================================
 #[pyclass]
+#[derive(Clone)]
 pub struct LinearRegressionSlopeStreaming {
     window: usize,
     sum_x: f64,
@@ -440,6 +647,14 @@ impl LinearRegressionSlopeStreaming {
         }
     }
 
+    pub fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    pub fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> Self {
+        self.clone()
+    }
+
     pub fn reset(&mut self) {
         self.buffer.clear();
     }
@@ -448,10 +663,13 @@ impl LinearRegressionSlopeStreaming {
 // ============================================================================
 // Rolling Percentile
 // ============================================================================
+/// Rolling percent rank of the latest value, read off a `helpers::SortedWindow`
+/// with a binary search instead of a scan of the window.
 #[pyclass]
+#[derive(Clone)]
 pub struct RollingPercentileStreaming {
     window: usize,
-    buffer: VecDeque<f64>,
+    values: helpers::SortedWindow,
 }
 
 #[pymethods]
@@ -460,86 +678,829 @@ impl RollingPercentileStreaming {
     pub fn new(window: usize) -> Self {
         Self {
             window,
+            values: helpers::SortedWindow::new(window),
+        }
+    }
+
+    pub fn update(&mut self, value: f64) -> f64 {
+        self.values.push(value);
+
+        if !self.values.is_full() {
+            f64::NAN
+        } else {
+            // NaN is never <= anything, matching the bulk comparison
+            self.values.count_at_or_below(value) as f64 / self.window as f64
+        }
+    }
+
+    pub fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    pub fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> Self {
+        self.clone()
+    }
+
+    pub fn reset(&mut self) {
+        self.values.clear();
+    }
+}
+
+// ============================================================================
+// Calmar Ratio
+// ============================================================================
+#[pyclass]
+#[derive(Clone)]
+pub struct CalmarRatioStreaming {
+    window: usize,
+    periods_per_year: f64,
+    close_buffer: VecDeque<f64>,
+}
+
+impl CalmarRatioStreaming {
+    fn try_new(window: usize, bar_frequency: &str, periods_per_year: Option<f64>) -> Result<Self, String> {
+        Ok(Self {
+            window,
+            periods_per_year: helpers::resolve_periods_per_year(bar_frequency, periods_per_year)?,
+            close_buffer: VecDeque::with_capacity(window),
+        })
+    }
+}
+
+#[pymethods]
+impl CalmarRatioStreaming {
+    /// `bar_frequency` sets the annualization of the window return: "daily"
+    /// (default, 252), "hourly", "minute" or "crypto_daily" (365).
+    /// `periods_per_year` overrides the preset.
+    #[new]
+    #[pyo3(signature = (window=252, bar_frequency="daily", periods_per_year=None))]
+    pub fn new(window: usize, bar_frequency: &str, periods_per_year: Option<f64>) -> PyResult<Self> {
+        Self::try_new(window, bar_frequency, periods_per_year).map_err(PyValueError::new_err)
+    }
+
+    pub fn update(&mut self, close: f64) -> f64 {
+        self.close_buffer.push_back(close);
+
+        if self.close_buffer.len() > self.window {
+            self.close_buffer.pop_front();
+        }
+
+        if self.close_buffer.len() < self.window {
+            f64::NAN
+        } else {
+            let close_vec: Vec<f64> = self.close_buffer.iter().copied().collect();
+            calmar_ratio_of_window(&close_vec, self.periods_per_year)
+        }
+    }
+
+    pub fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    pub fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> Self {
+        self.clone()
+    }
+
+    pub fn reset(&mut self) {
+        self.close_buffer.clear();
+    }
+}
+
+// ============================================================================
+// Rolling RMS
+// ============================================================================
+#[pyclass]
+#[derive(Clone)]
+pub struct RollingRMSStreaming {
+    window: usize,
+    sum: f64,
+    sum_sq: f64,
+    buffer: VecDeque<f64>,
+}
+
+#[pymethods]
+impl RollingRMSStreaming {
+    #[new]
+    pub fn new(window: usize) -> Self {
+        Self {
+            window,
+            sum: 0.0,
+            sum_sq: 0.0,
             buffer: VecDeque::with_capacity(window),
         }
     }
 
     pub fn update(&mut self, value: f64) -> f64 {
+        // NaN values contribute nothing, matching the bulk rolling_sum convention
+        let value = if value.is_nan() { 0.0 } else { value };
         self.buffer.push_back(value);
+        self.sum += value;
+        self.sum_sq += value * value;
 
         if self.buffer.len() > self.window {
-            self.buffer.pop_front();
+            if let Some(old) = self.buffer.pop_front() {
+                self.sum -= old;
+                self.sum_sq -= old * old;
+            }
         }
 
         if self.buffer.len() < self.window {
             f64::NAN
         } else {
-            let count = self.buffer.iter().filter(|&&x| x <= value).count();
-            count as f64 / self.window as f64
+            (self.sum_sq / self.window as f64).max(0.0).sqrt()
+        }
+    }
+
+    /// Population variance of the current window from the running sums, in O(1);
+    /// NaN until the window is full. RMS² is this plus the squared window mean.
+    pub fn variance(&self) -> f64 {
+        if self.buffer.len() < self.window {
+            return f64::NAN;
         }
+        let w = self.window as f64;
+        let mean = self.sum / w;
+        (self.sum_sq / w - mean * mean).max(0.0)
+    }
+
+    pub fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    pub fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> Self {
+        self.clone()
     }
 
     pub fn reset(&mut self) {
+        self.sum = 0.0;
+        self.sum_sq = 0.0;
         self.buffer.clear();
     }
 }
 
 // ============================================================================
-// Calmar Ratio
+// Percent From High / Low
 // ============================================================================
 #[pyclass]
-pub struct CalmarRatioStreaming {
+#[derive(Clone)]
+pub struct PctFromHighStreaming {
+    high: helpers::RollingExtreme,
+}
+
+#[pymethods]
+impl PctFromHighStreaming {
+    #[new]
+    #[pyo3(signature = (window=252))]
+    pub fn new(window: usize) -> Self {
+        Self {
+            high: helpers::RollingExtreme::max(window),
+        }
+    }
+
+    pub fn update(&mut self, close: f64) -> f64 {
+        let high = self.high.update(close);
+        if high != 0.0 {
+            (close - high) / high * 100.0
+        } else {
+            f64::NAN
+        }
+    }
+
+    pub fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    pub fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> Self {
+        self.clone()
+    }
+
+    pub fn reset(&mut self) {
+        self.high.reset();
+    }
+}
+
+#[pyclass]
+#[derive(Clone)]
+pub struct PctFromLowStreaming {
+    low: helpers::RollingExtreme,
+}
+
+#[pymethods]
+impl PctFromLowStreaming {
+    #[new]
+    #[pyo3(signature = (window=252))]
+    pub fn new(window: usize) -> Self {
+        Self {
+            low: helpers::RollingExtreme::min(window),
+        }
+    }
+
+    pub fn update(&mut self, close: f64) -> f64 {
+        let low = self.low.update(close);
+        if low != 0.0 {
+            (close - low) / low * 100.0
+        } else {
+            f64::NAN
+        }
+    }
+
+    pub fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    pub fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> Self {
+        self.clone()
+    }
+
+    pub fn reset(&mut self) {
+        self.low.reset();
+    }
+}
+
+// ============================================================================
+// Rolling Valid Count
+// ============================================================================
+#[pyclass]
+#[derive(Clone)]
+pub struct RollingCountValidStreaming {
     window: usize,
-    close_buffer: VecDeque<f64>,
+    count: usize,
+    buffer: VecDeque<bool>,
 }
 
 #[pymethods]
-impl CalmarRatioStreaming {
+impl RollingCountValidStreaming {
     #[new]
     pub fn new(window: usize) -> Self {
         Self {
             window,
-            close_buffer: VecDeque::with_capacity(window),
+            count: 0,
+            buffer: VecDeque::with_capacity(window),
+        }
+    }
+
+    pub fn update(&mut self, value: f64) -> f64 {
+        let valid = !value.is_nan();
+        self.buffer.push_back(valid);
+        if valid {
+            self.count += 1;
+        }
+
+        if self.buffer.len() > self.window {
+            if let Some(true) = self.buffer.pop_front() {
+                self.count -= 1;
+            }
+        }
+
+        if self.buffer.len() < self.window {
+            f64::NAN
+        } else {
+            self.count as f64
+        }
+    }
+
+    pub fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    pub fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> Self {
+        self.clone()
+    }
+
+    pub fn reset(&mut self) {
+        self.count = 0;
+        self.buffer.clear();
+    }
+}
+
+// ============================================================================
+// Drawdown Duration
+// ============================================================================
+#[pyclass]
+#[derive(Clone)]
+pub struct DrawdownDurationStreaming {
+    peak: f64,
+    duration: usize,
+    // rolling mode: window high, with the latest bar of an equal high as the peak
+    rolling_high: Option<helpers::RollingExtreme>,
+}
+
+#[pymethods]
+impl DrawdownDurationStreaming {
+    #[new]
+    #[pyo3(signature = (window=None))]
+    pub fn new(window: Option<usize>) -> Self {
+        Self {
+            peak: f64::NEG_INFINITY,
+            duration: 0,
+            rolling_high: window.map(|w| helpers::RollingExtreme::max(w.max(1))),
         }
     }
 
     pub fn update(&mut self, close: f64) -> f64 {
-        self.close_buffer.push_back(close);
+        match self.rolling_high.as_mut() {
+            None => {
+                if close >= self.peak {
+                    self.peak = close;
+                    self.duration = 0;
+                } else {
+                    self.duration += 1;
+                }
+            }
+            Some(high) => {
+                high.update(close);
+                self.duration = high.bars_since();
+            }
+        }
 
-        if self.close_buffer.len() > self.window {
-            self.close_buffer.pop_front();
+        self.duration as f64
+    }
+
+    pub fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    pub fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> Self {
+        self.clone()
+    }
+
+    pub fn reset(&mut self) {
+        self.peak = f64::NEG_INFINITY;
+        self.duration = 0;
+        if let Some(high) = self.rolling_high.as_mut() {
+            high.reset();
         }
+    }
+}
 
-        if self.close_buffer.len() < self.window {
+// ============================================================================
+// OHLC Bar Aggregator
+// ============================================================================
+/// Builds OHLCV bars from ticks, closing a bar either every `bar_ticks` ticks or
+/// when a tick falls into the next `bar_seconds` time bucket (buckets aligned to
+/// multiples of `bar_seconds`). In time mode a bar is only known to be complete
+/// once the first tick of the following bar arrives.
+#[pyclass]
+#[derive(Clone)]
+pub struct OHLCAggregator {
+    bar_ticks: Option<usize>,
+    bar_seconds: Option<f64>,
+    open: f64,
+    high: f64,
+    low: f64,
+    close: f64,
+    volume: f64,
+    ticks: usize,
+    bucket: f64,
+}
+
+impl OHLCAggregator {
+    fn try_new(bar_ticks: Option<usize>, bar_seconds: Option<f64>) -> Result<Self, String> {
+        match (bar_ticks, bar_seconds) {
+            (Some(0), None) => return Err("bar_ticks must be >= 1".to_string()),
+            (None, Some(secs)) if secs.is_nan() || secs <= 0.0 => {
+                return Err("bar_seconds must be > 0".to_string())
+            }
+            (Some(_), None) | (None, Some(_)) => {}
+            _ => return Err("exactly one of bar_ticks or bar_seconds must be given".to_string()),
+        }
+
+        Ok(Self {
+            bar_ticks,
+            bar_seconds,
+            open: f64::NAN,
+            high: f64::NAN,
+            low: f64::NAN,
+            close: f64::NAN,
+            volume: 0.0,
+            ticks: 0,
+            bucket: f64::NAN,
+        })
+    }
+
+    fn start_bar(&mut self, price: f64, volume: f64) {
+        self.open = price;
+        self.high = price;
+        self.low = price;
+        self.close = price;
+        self.volume = volume;
+        self.ticks = 1;
+    }
+
+    fn current_bar(&self) -> (f64, f64, f64, f64, f64) {
+        (self.open, self.high, self.low, self.close, self.volume)
+    }
+}
+
+#[pymethods]
+impl OHLCAggregator {
+    #[new]
+    #[pyo3(signature = (bar_ticks=None, bar_seconds=None))]
+    pub fn new(bar_ticks: Option<usize>, bar_seconds: Option<f64>) -> PyResult<Self> {
+        Self::try_new(bar_ticks, bar_seconds).map_err(PyValueError::new_err)
+    }
+
+    /// Returns (open, high, low, close, volume, completed). The OHLCV values are
+    /// those of the bar that just completed, or NaN when `completed` is false.
+    /// `timestamp` is in seconds and ignored for tick-count bars.
+    pub fn update(&mut self, price: f64, volume: f64, timestamp: f64) -> (f64, f64, f64, f64, f64, bool) {
+        let mut completed = None;
+
+        if let Some(secs) = self.bar_seconds {
+            let bucket = (timestamp / secs).floor();
+            if self.ticks > 0 && bucket != self.bucket {
+                completed = Some(self.current_bar());
+                self.ticks = 0;
+            }
+            self.bucket = bucket;
+        }
+
+        if self.ticks == 0 {
+            self.start_bar(price, volume);
+        } else {
+            self.high = self.high.max(price);
+            self.low = self.low.min(price);
+            self.close = price;
+            self.volume += volume;
+            self.ticks += 1;
+        }
+
+        if let Some(n) = self.bar_ticks {
+            if self.ticks == n {
+                completed = Some(self.current_bar());
+                self.ticks = 0;
+            }
+        }
+
+        match completed {
+            Some((o, h, l, c, v)) => (o, h, l, c, v, true),
+            None => (f64::NAN, f64::NAN, f64::NAN, f64::NAN, f64::NAN, false),
+        }
+    }
+
+    /// Close out the in-progress bar, e.g. at the end of a session. Returns
+    /// (open, high, low, close, volume), all NaN when no ticks are pending.
+    pub fn flush(&mut self) -> (f64, f64, f64, f64, f64) {
+        if self.ticks == 0 {
+            return (f64::NAN, f64::NAN, f64::NAN, f64::NAN, f64::NAN);
+        }
+        self.ticks = 0;
+        self.current_bar()
+    }
+
+    pub fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    pub fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> Self {
+        self.clone()
+    }
+
+    pub fn reset(&mut self) {
+        self.open = f64::NAN;
+        self.high = f64::NAN;
+        self.low = f64::NAN;
+        self.close = f64::NAN;
+        self.volume = 0.0;
+        self.ticks = 0;
+        self.bucket = f64::NAN;
+    }
+}
+
+// ============================================================================
+// Rolling Trimmed Mean
+// ============================================================================
+#[pyclass]
+#[derive(Clone)]
+pub struct RollingTrimmedMeanStreaming {
+    trim_count: usize,
+    values: helpers::SortedWindow,
+}
+
+impl RollingTrimmedMeanStreaming {
+    fn try_new(window: usize, trim: f64) -> Result<Self, String> {
+        Ok(Self {
+            trim_count: helpers::trim_count(window, trim)?,
+            values: helpers::SortedWindow::new(window),
+        })
+    }
+}
+
+#[pymethods]
+impl RollingTrimmedMeanStreaming {
+    #[new]
+    #[pyo3(signature = (window=20, trim=0.1))]
+    pub fn new(window: usize, trim: f64) -> PyResult<Self> {
+        Self::try_new(window, trim).map_err(PyValueError::new_err)
+    }
+
+    pub fn update(&mut self, value: f64) -> f64 {
+        self.values.push(value);
+
+        if !self.values.is_full() || self.values.nan_count() > 0 {
             f64::NAN
         } else {
-            let close_vec: Vec<f64> = self.close_buffer.iter().copied().collect();
+            helpers::trimmed_mean_of_sorted(self.values.sorted(), self.trim_count)
+        }
+    }
 
-            // Calculate annualized return
-            let total_return = (close_vec[close_vec.len() - 1] / close_vec[0]) - 1.0;
-            let annual_return = total_return * (252.0 / close_vec.len() as f64);
+    pub fn __copy__(&self) -> Self {
+        self.clone()
+    }
 
-            // Calculate maximum drawdown
-            let mut running_max = close_vec[0];
-            let mut max_drawdown: f64 = 0.0;
+    pub fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> Self {
+        self.clone()
+    }
 
-            for &price in close_vec.iter().skip(1) {
-                running_max = running_max.max(price);
-                let drawdown = (price - running_max) / running_max;
-                max_drawdown = max_drawdown.min(drawdown);
+    pub fn reset(&mut self) {
+        self.values.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_return_streaming_fraction_is_percentage_over_100() {
+        let prices = [100.0, 102.0, 99.0, 105.0];
+        let mut dr_pct = DailyReturnStreaming::new(true);
+        let mut dr_frac = DailyReturnStreaming::new(false);
+        let mut cr_pct = CumulativeReturnStreaming::new(true);
+        let mut cr_frac = CumulativeReturnStreaming::new(false);
+
+        for &price in prices.iter() {
+            let (a, b) = (dr_pct.update(price), dr_frac.update(price));
+            assert!(a.is_nan() && b.is_nan() || (b - a / 100.0).abs() < 1e-12);
+            assert!((cr_frac.update(price) - cr_pct.update(price) / 100.0).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_rolling_rms_variance_matches_window() {
+        let data = [3.0, 4.0, 1.0, 7.0, 2.0];
+        let mut rms = RollingRMSStreaming::new(3);
+        rms.update(data[0]);
+        assert!(rms.variance().is_nan());
+
+        for i in 1..data.len() {
+            let value = rms.update(data[i]);
+            if i < 2 {
+                continue;
+            }
+            let window = &data[i - 2..=i];
+            let mean = window.iter().sum::<f64>() / 3.0;
+            let var = window.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / 3.0;
+            assert!((rms.variance() - var).abs() < 1e-12);
+            assert!((value * value - (var + mean * mean)).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_drawdown_duration_streaming() {
+        let close = [100.0, 105.0, 103.0, 101.0, 102.0, 104.0, 106.0, 105.0];
+        let mut all_time = DrawdownDurationStreaming::new(None);
+        let mut rolling = DrawdownDurationStreaming::new(Some(3));
+
+        let a: Vec<f64> = close.iter().map(|&c| all_time.update(c)).collect();
+        let r: Vec<f64> = close.iter().map(|&c| rolling.update(c)).collect();
+        assert_eq!(a, vec![0.0, 0.0, 1.0, 2.0, 3.0, 4.0, 0.0, 1.0]);
+        assert_eq!(r, vec![0.0, 0.0, 1.0, 2.0, 2.0, 0.0, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn test_drawdown_duration_streaming_matches_bulk_with_equal_highs() {
+        // Rounded wave repeats its highs, so the latest equal high must win
+        let close: Vec<f64> = (0..200).map(|i| ((i as f64 * 0.45).sin() * 3.0).round()).collect();
+        for window in [None, Some(1), Some(7), Some(25)] {
+            let bulk = crate::others::drawdown_duration_kernel(&close, window);
+            let mut stream = DrawdownDurationStreaming::new(window);
+            for i in 0..close.len() {
+                assert_eq!(stream.update(close[i]), bulk[i], "window {:?} at {}", window, i);
+            }
+        }
+    }
+
+    #[test]
+    fn test_rolling_count_valid_streaming() {
+        let nan = f64::NAN;
+        let data = [1.0, 2.0, 3.0, nan, 5.0, nan, 7.0, 8.0, 9.0, 10.0];
+        let mut counter = RollingCountValidStreaming::new(3);
+        let counts: Vec<f64> = data.iter().map(|&x| counter.update(x)).collect();
+
+        assert!(counts[1].is_nan());
+        assert_eq!(&counts[2..], &[3.0, 2.0, 2.0, 1.0, 2.0, 2.0, 3.0, 3.0]);
+    }
+
+    #[test]
+    fn test_pct_from_high_low_streaming_matches_bulk() {
+        let close: Vec<f64> = (0..60)
+            .map(|i| 100.0 + (i as f64 * 0.37).sin() * 4.0 + i as f64 * 0.05)
+            .collect();
+        let bulk_high = crate::others::pct_from_high_kernel(&close, 10);
+        let bulk_low = crate::others::pct_from_low_kernel(&close, 10);
+
+        let mut from_high = PctFromHighStreaming::new(10);
+        let mut from_low = PctFromLowStreaming::new(10);
+        for i in 0..close.len() {
+            let (h, l) = (from_high.update(close[i]), from_low.update(close[i]));
+            assert_eq!(h.is_nan(), bulk_high[i].is_nan());
+            if !h.is_nan() {
+                assert_eq!(h, bulk_high[i]);
+                assert_eq!(l, bulk_low[i]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_rolling_percentile_streaming_matches_bulk_with_ties() {
+        // Small-range LCG values give many duplicates; a few NaN mixed in
+        let mut state: u64 = 12345;
+        let data: Vec<f64> = (0..300)
+            .map(|i| {
+                state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+                if i % 37 == 0 { f64::NAN } else { ((state >> 33) % 12) as f64 }
+            })
+            .collect();
+        let bulk = crate::others::rolling_percentile_kernel(&data, 20);
+
+        let mut pct = RollingPercentileStreaming::new(20);
+        for i in 0..data.len() {
+            let value = pct.update(data[i]);
+            assert_eq!(value.is_nan(), bulk[i].is_nan(), "bar {}", i);
+            if !value.is_nan() {
+                assert_eq!(value, bulk[i], "bar {}", i);
+            }
+        }
+    }
+
+    #[test]
+    fn test_sharpe_min_periods_emits_early() {
+        let close: Vec<f64> = (0..30).map(|i| 100.0 * (1.0 + 0.01 * (i as f64 * 0.8).sin()) + i as f64).collect();
+        let mut full = SharpeRatioStreaming::try_new(20, 0.0, Some(252.0), None, "log", "daily").unwrap();
+        let mut early = SharpeRatioStreaming::try_new(20, 0.0, Some(252.0), Some(5), "log", "daily").unwrap();
+
+        for (i, &c) in close.iter().enumerate() {
+            let (f, e) = (full.update(c), early.update(c));
+            // Bar i has i returns
+            assert_eq!(e.is_nan(), i < 5, "bar {}", i);
+            assert_eq!(f.is_nan(), i < 20, "bar {}", i);
+            if i >= 20 {
+                assert_eq!(f, e);
+            }
+        }
+        assert!(SharpeRatioStreaming::try_new(20, 0.0, Some(252.0), Some(1), "log", "daily").is_err());
+        assert!(SharpeRatioStreaming::try_new(20, 0.0, Some(252.0), Some(21), "log", "daily").is_err());
+    }
+
+    #[test]
+    fn test_ohlc_aggregator_tick_bars() {
+        let mut agg = OHLCAggregator::try_new(Some(3), None).unwrap();
+        let ticks = [(10.0, 1.0), (12.0, 2.0), (9.0, 1.0), (11.0, 5.0), (11.5, 1.0), (10.5, 1.0)];
+
+        let out: Vec<_> = ticks.iter().map(|&(p, v)| agg.update(p, v, 0.0)).collect();
+        assert!(!out[0].5 && !out[1].5 && out[0].0.is_nan());
+        assert_eq!(out[2], (10.0, 12.0, 9.0, 9.0, 4.0, true));
+        assert!(!out[3].5 && !out[4].5);
+        assert_eq!(out[5], (11.0, 11.5, 10.5, 10.5, 7.0, true));
+        assert!(agg.flush().0.is_nan());
+    }
+
+    #[test]
+    fn test_ohlc_aggregator_time_bars() {
+        let mut agg = OHLCAggregator::try_new(None, Some(60.0)).unwrap();
+        assert!(!agg.update(100.0, 1.0, 0.0).5);
+        assert!(!agg.update(101.0, 1.0, 30.0).5);
+        assert!(!agg.update(99.5, 2.0, 59.9).5);
+
+        // First tick of the next minute completes the previous bar
+        assert_eq!(agg.update(100.5, 3.0, 60.0), (100.0, 101.0, 99.5, 99.5, 4.0, true));
+        assert!(!agg.update(102.0, 1.0, 90.0).5);
+        // Gap over an empty minute still closes just the one pending bar
+        assert_eq!(agg.update(98.0, 1.0, 200.0), (100.5, 102.0, 100.5, 102.0, 4.0, true));
+        assert_eq!(agg.flush(), (98.0, 98.0, 98.0, 98.0, 1.0));
+
+        assert!(OHLCAggregator::try_new(None, None).is_err());
+        assert!(OHLCAggregator::try_new(Some(5), Some(60.0)).is_err());
+        assert!(OHLCAggregator::try_new(Some(0), None).is_err());
+    }
+
+    #[test]
+    fn test_sharpe_streaming_simple_vs_log_returns() {
+        let run = |step: f64, return_type: &str| {
+            let mut sharpe = SharpeRatioStreaming::try_new(20, 0.0, Some(252.0), None, return_type, "daily").unwrap();
+            let mut price = 100.0;
+            let mut last = f64::NAN;
+            for i in 0..40 {
+                price *= 1.0 + step * (0.3 + (i as f64 * 1.7).sin());
+                last = sharpe.update(price);
             }
+            last
+        };
+
+        // Tiny moves: ln(1 + r) ~ r
+        let (log_small, simple_small) = (run(0.0005, "log"), run(0.0005, "simple"));
+        assert!((log_small - simple_small).abs() / log_small.abs() < 1e-3);
 
-            let max_drawdown: f64 = max_drawdown.abs();
+        // Large moves: the log transform compresses gains and stretches losses
+        let (log_large, simple_large) = (run(0.1, "log"), run(0.1, "simple"));
+        assert!((log_large - simple_large).abs() / log_large.abs() > 0.05);
 
-            if max_drawdown > 0.0 {
-                annual_return / max_drawdown
+        assert!(SharpeRatioStreaming::try_new(20, 0.0, Some(252.0), None, "pct", "daily").is_err());
+    }
+
+    #[test]
+    fn test_calmar_streaming_matches_bulk_with_bar_frequency() {
+        let close: Vec<f64> = (0..80).map(|i| 100.0 + (i as f64 * 0.4).sin() * 5.0 + i as f64 * 0.2).collect();
+        let daily = crate::others::calmar_ratio_kernel(&close, 30, 252.0);
+        let crypto = crate::others::calmar_ratio_kernel(&close, 30, 365.0);
+        let mut stream = CalmarRatioStreaming::try_new(30, "crypto_daily", None).unwrap();
+
+        for i in 0..close.len() {
+            let value = stream.update(close[i]);
+            if i < 29 {
+                assert!(value.is_nan());
             } else {
-                0.0
+                assert!((value - crypto[i]).abs() < 1e-12);
+                assert!((crypto[i] - daily[i] * 365.0 / 252.0).abs() < 1e-9);
             }
         }
+
+        // Sharpe picks up the same periods per year unless a factor is given
+        let mut by_frequency = SharpeRatioStreaming::try_new(20, 0.0, None, None, "log", "crypto_daily").unwrap();
+        let mut explicit = SharpeRatioStreaming::try_new(20, 0.0, Some(365.0), None, "log", "daily").unwrap();
+        for &c in &close {
+            let (a, b) = (by_frequency.update(c), explicit.update(c));
+            assert!(a == b || (a.is_nan() && b.is_nan()));
+        }
     }
 
-    pub fn reset(&mut self) {
-        self.close_buffer.clear();
+    #[test]
+    fn test_drawdown_streaming_drop_then_recover() {
+        let close = [100.0, 110.0, 99.0, 88.0, 95.0, 105.0, 112.0, 111.0];
+        let mut dd = DrawdownStreaming::try_new(None).unwrap();
+        let out: Vec<(f64, f64, f64)> = close.iter().map(|&c| dd.update(c)).collect();
+
+        assert_eq!(out[0], (0.0, 0.0, 0.0));
+        assert_eq!(out[1], (0.0, 0.0, 0.0));
+        // trough at 88 is 20% below the 110 peak
+        assert!((out[3].0 + 20.0).abs() < 1e-12);
+        assert_eq!(out[3].2, 2.0);
+        assert!((out[5].1 + 20.0).abs() < 1e-12);
+        assert_eq!(out[5].2, 4.0);
+        // new high clears the current drawdown, the max sticks at the trough
+        assert_eq!(out[6].0, 0.0);
+        assert_eq!(out[6].2, 0.0);
+        assert!((out[6].1 + 20.0).abs() < 1e-12);
+        assert!((out[7].0 - (111.0 / 112.0 - 1.0) * 100.0).abs() < 1e-12);
+        assert_eq!(out[7].2, 1.0);
+
+        // windowed max drawdown matches MaxDrawdownStreaming once the trough rolls out
+        let mut windowed = DrawdownStreaming::try_new(Some(4)).unwrap();
+        let mut reference = MaxDrawdownStreaming::new(4);
+        for &c in &close[1..] {
+            let (current, max, bars) = windowed.update(c);
+            let expected = reference.update(c);
+            assert!((expected.is_nan() && max == 0.0) || (max - expected).abs() < 1e-12);
+            assert!(current <= 0.0 && bars < 4.0);
+        }
+        assert_eq!(windowed.update(90.0).2, 2.0);
+
+        assert!(DrawdownStreaming::try_new(Some(0)).is_err());
+
+        // windowed peak and bars since it, against a rescan of the last 5 closes
+        let series: Vec<f64> = (0..60).map(|i| 100.0 + ((i % 9) as f64 - 4.0).abs().round()).collect();
+        let mut windowed = DrawdownStreaming::try_new(Some(5)).unwrap();
+        for i in 0..series.len() {
+            let (current, _, bars) = windowed.update(series[i]);
+            let start = i.saturating_sub(4);
+            let (peak_idx, peak) = (start..=i).fold((start, f64::NEG_INFINITY), |best, j| {
+                if series[j] >= best.1 { (j, series[j]) } else { best }
+            });
+            assert_eq!(current, (series[i] - peak) / peak * 100.0, "bar {}", i);
+            assert_eq!(bars, (i - peak_idx) as f64, "bar {}", i);
+        }
+        let mut single = DrawdownStreaming::try_new(Some(1)).unwrap();
+        assert_eq!(single.update(100.0), (0.0, 0.0, 0.0));
+        assert_eq!(single.update(90.0), (0.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_rolling_trimmed_mean_streaming_matches_bulk() {
+        let mut data: Vec<f64> = (0..60).map(|i| 50.0 + (i as f64 * 0.7).sin() * 5.0).collect();
+        data[20] = 500.0;
+        data[40] = f64::NAN;
+
+        let bulk = crate::others::rolling_trimmed_mean_kernel(&data, 10, 0.2).unwrap();
+        let mut stream = RollingTrimmedMeanStreaming::try_new(10, 0.2).unwrap();
+        for (i, &x) in data.iter().enumerate() {
+            let v = stream.update(x);
+            if bulk[i].is_nan() {
+                assert!(v.is_nan(), "bar {}", i);
+            } else {
+                assert!((v - bulk[i]).abs() < 1e-9, "bar {}", i);
+            }
+        }
+
+        assert!(RollingTrimmedMeanStreaming::try_new(10, 0.5).is_err());
     }
 }
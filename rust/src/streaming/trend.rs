@@ -1,44 +1,84 @@
+use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
 use std::collections::VecDeque;
+use crate::helpers::{AfSchedule, NanPolicy};
 
 // ============================================================================
 // Simple Moving Average (SMA)
 // ============================================================================
 #[pyclass]
+#[derive(Clone)]
 pub struct SMAStreaming {
     window: usize,
     buffer: VecDeque<f64>,
     sum: f64,
+    nan_policy: NanPolicy,
+    last_value: f64,
 }
 
-#[pymethods]
 impl SMAStreaming {
-    #[new]
     pub fn new(window: usize) -> Self {
         Self {
             window,
             buffer: VecDeque::with_capacity(window),
             sum: 0.0,
+            nan_policy: NanPolicy::Propagate,
+            last_value: f64::NAN,
         }
     }
 
+    fn try_new(window: usize, nan_policy: &str) -> Result<Self, String> {
+        let nan_policy = NanPolicy::parse(nan_policy)?;
+        Ok(Self { nan_policy, ..Self::new(window) })
+    }
+}
+
+#[pymethods]
+impl SMAStreaming {
+    #[new]
+    #[pyo3(signature = (window, nan_policy="propagate"))]
+    fn py_new(window: usize, nan_policy: &str) -> PyResult<Self> {
+        Self::try_new(window, nan_policy).map_err(PyValueError::new_err)
+    }
+
     pub fn update(&mut self, value: f64) -> f64 {
+        if value.is_nan() {
+            match self.nan_policy {
+                NanPolicy::Propagate => {}
+                NanPolicy::Skip => return self.last_value,
+                NanPolicy::Reset => {
+                    self.reset();
+                    return f64::NAN;
+                }
+            }
+        }
+
         if self.buffer.len() >= self.window {
             self.sum -= self.buffer.pop_front().unwrap();
         }
         self.buffer.push_back(value);
         self.sum += value;
 
-        if self.buffer.len() < self.window {
+        self.last_value = if self.buffer.len() < self.window {
             f64::NAN
         } else {
             self.sum / self.window as f64
-        }
+        };
+        self.last_value
+    }
+
+    pub fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    pub fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> Self {
+        self.clone()
     }
 
     pub fn reset(&mut self) {
         self.buffer.clear();
         self.sum = 0.0;
+        self.last_value = f64::NAN;
     }
 }
 
@@ -46,35 +86,89 @@ impl SMAStreaming {
 // Exponential Moving Average (EMA)
 // ============================================================================
 #[pyclass]
+#[derive(Clone)]
 pub struct EMAStreaming {
-    window: usize,
     alpha: f64,
     current_value: f64,
+    nan_policy: NanPolicy,
+    min_periods: usize,
+    update_count: usize,
 }
 
-#[pymethods]
 impl EMAStreaming {
-    #[new]
     pub fn new(window: usize) -> Self {
         let alpha = 2.0 / (window as f64 + 1.0);
         Self {
-            window,
             alpha,
             current_value: f64::NAN,
+            nan_policy: NanPolicy::Propagate,
+            min_periods: 0,
+            update_count: 0,
         }
     }
 
+    fn try_new(window: usize, nan_policy: &str, min_periods: Option<usize>) -> Result<Self, String> {
+        let nan_policy = NanPolicy::parse(nan_policy)?;
+        Ok(Self {
+            nan_policy,
+            min_periods: min_periods.unwrap_or(0),
+            ..Self::new(window)
+        })
+    }
+
+    /// Current EMA, hidden as NaN until `min_periods` updates have been applied
+    fn output(&self) -> f64 {
+        if self.update_count < self.min_periods {
+            f64::NAN
+        } else {
+            self.current_value
+        }
+    }
+}
+
+#[pymethods]
+impl EMAStreaming {
+    /// `min_periods` (default: None) returns NaN until that many updates have been
+    /// applied, e.g. `window` to line up with an SMA-seeded EMA; the recursion
+    /// itself still starts from the first value.
+    #[new]
+    #[pyo3(signature = (window, nan_policy="propagate", min_periods=None))]
+    fn py_new(window: usize, nan_policy: &str, min_periods: Option<usize>) -> PyResult<Self> {
+        Self::try_new(window, nan_policy, min_periods).map_err(PyValueError::new_err)
+    }
+
     pub fn update(&mut self, value: f64) -> f64 {
+        if value.is_nan() {
+            match self.nan_policy {
+                NanPolicy::Propagate => {}
+                NanPolicy::Skip => return self.output(),
+                NanPolicy::Reset => {
+                    self.reset();
+                    return f64::NAN;
+                }
+            }
+        }
+
+        self.update_count += 1;
         if self.current_value.is_nan() {
             self.current_value = value;
         } else {
             self.current_value = self.alpha * value + (1.0 - self.alpha) * self.current_value;
         }
-        self.current_value
+        self.output()
+    }
+
+    pub fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    pub fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> Self {
+        self.clone()
     }
 
     pub fn reset(&mut self) {
         self.current_value = f64::NAN;
+        self.update_count = 0;
     }
 }
 
@@ -82,16 +176,17 @@ impl EMAStreaming {
 // Weighted Moving Average (WMA)
 // ============================================================================
 #[pyclass]
+#[derive(Clone)]
 pub struct WMAStreaming {
     window: usize,
     buffer: VecDeque<f64>,
     weights: Vec<f64>,
     sum_weights: f64,
+    nan_policy: NanPolicy,
+    last_value: f64,
 }
 
-#[pymethods]
 impl WMAStreaming {
-    #[new]
     pub fn new(window: usize) -> Self {
         let weights: Vec<f64> = (1..=window).map(|i| i as f64).collect();
         let sum_weights: f64 = weights.iter().sum();
@@ -101,16 +196,43 @@ impl WMAStreaming {
             buffer: VecDeque::with_capacity(window),
             weights,
             sum_weights,
+            nan_policy: NanPolicy::Propagate,
+            last_value: f64::NAN,
         }
     }
 
+    fn try_new(window: usize, nan_policy: &str) -> Result<Self, String> {
+        let nan_policy = NanPolicy::parse(nan_policy)?;
+        Ok(Self { nan_policy, ..Self::new(window) })
+    }
+}
+
+#[pymethods]
+impl WMAStreaming {
+    #[new]
+    #[pyo3(signature = (window, nan_policy="propagate"))]
+    fn py_new(window: usize, nan_policy: &str) -> PyResult<Self> {
+        Self::try_new(window, nan_policy).map_err(PyValueError::new_err)
+    }
+
     pub fn update(&mut self, value: f64) -> f64 {
+        if value.is_nan() {
+            match self.nan_policy {
+                NanPolicy::Propagate => {}
+                NanPolicy::Skip => return self.last_value,
+                NanPolicy::Reset => {
+                    self.reset();
+                    return f64::NAN;
+                }
+            }
+        }
+
         self.buffer.push_back(value);
         if self.buffer.len() > self.window {
             self.buffer.pop_front();
         }
 
-        if self.buffer.len() < self.window {
+        self.last_value = if self.buffer.len() < self.window {
             f64::NAN
         } else {
             let weighted_sum: f64 = self.buffer.iter()
@@ -118,11 +240,21 @@ impl WMAStreaming {
                 .map(|(v, w)| v * w)
                 .sum();
             weighted_sum / self.sum_weights
-        }
+        };
+        self.last_value
+    }
+
+    pub fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    pub fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> Self {
+        self.clone()
     }
 
     pub fn reset(&mut self) {
         self.buffer.clear();
+        self.last_value = f64::NAN;
     }
 }
 
@@ -130,6 +262,7 @@ impl WMAStreaming {
 // MACD (Moving Average Convergence Divergence)
 // ============================================================================
 #[pyclass]
+#[derive(Clone)]
 pub struct MACDStreaming {
     fast_ema: EMAStreaming,
     slow_ema: EMAStreaming,
@@ -167,6 +300,29 @@ impl MACDStreaming {
         (macd_line, signal_line, histogram)
     }
 
+    /// Latest fast EMA value (NaN before the first update)
+    pub fn fast_ema(&self) -> f64 {
+        self.fast_ema.current_value
+    }
+
+    /// Latest slow EMA value (NaN before the first update)
+    pub fn slow_ema(&self) -> f64 {
+        self.slow_ema.current_value
+    }
+
+    /// Latest signal line value (NaN before the first update)
+    pub fn signal_value(&self) -> f64 {
+        self.signal_ema.current_value
+    }
+
+    pub fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    pub fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> Self {
+        self.clone()
+    }
+
     pub fn reset(&mut self) {
         self.fast_ema.reset();
         self.slow_ema.reset();
@@ -174,10 +330,202 @@ impl MACDStreaming {
     }
 }
 
+// ============================================================================
+// MA Distance Oscillator
+// ============================================================================
+#[derive(Clone)]
+pub(crate) enum MovingAverageStreaming {
+    Sma(SMAStreaming),
+    Ema(EMAStreaming),
+    Wma(WMAStreaming),
+}
+
+impl MovingAverageStreaming {
+    pub(crate) fn from_type(ma_type: &str, window: usize) -> Result<Self, String> {
+        match ma_type {
+            "sma" => Ok(Self::Sma(SMAStreaming::new(window))),
+            "ema" => Ok(Self::Ema(EMAStreaming::new(window))),
+            "wma" => Ok(Self::Wma(WMAStreaming::new(window))),
+            other => Err(format!("unknown ma_type '{}', expected one of: sma, ema, wma", other)),
+        }
+    }
+
+    pub(crate) fn update(&mut self, value: f64) -> f64 {
+        match self {
+            Self::Sma(ma) => ma.update(value),
+            Self::Ema(ma) => ma.update(value),
+            Self::Wma(ma) => ma.update(value),
+        }
+    }
+
+    pub(crate) fn reset(&mut self) {
+        match self {
+            Self::Sma(ma) => ma.reset(),
+            Self::Ema(ma) => ma.reset(),
+            Self::Wma(ma) => ma.reset(),
+        }
+    }
+}
+
+#[pyclass]
+#[derive(Clone)]
+pub struct MADistanceStreaming {
+    fast_ma: MovingAverageStreaming,
+    slow_ma: MovingAverageStreaming,
+    percent: bool,
+}
+
+impl MADistanceStreaming {
+    fn try_new(fast_period: usize, slow_period: usize, ma_type: &str, percent: bool) -> Result<Self, String> {
+        Ok(Self {
+            fast_ma: MovingAverageStreaming::from_type(ma_type, fast_period)?,
+            slow_ma: MovingAverageStreaming::from_type(ma_type, slow_period)?,
+            percent,
+        })
+    }
+}
+
+#[pymethods]
+impl MADistanceStreaming {
+    #[new]
+    #[pyo3(signature = (fast_period=20, slow_period=50, ma_type="ema", percent=false))]
+    pub fn new(fast_period: usize, slow_period: usize, ma_type: &str, percent: bool) -> PyResult<Self> {
+        Self::try_new(fast_period, slow_period, ma_type, percent).map_err(PyValueError::new_err)
+    }
+
+    pub fn update(&mut self, value: f64) -> f64 {
+        let fast = self.fast_ma.update(value);
+        let slow = self.slow_ma.update(value);
+
+        if fast.is_nan() || slow.is_nan() {
+            return f64::NAN;
+        }
+
+        if !self.percent {
+            fast - slow
+        } else if slow != 0.0 {
+            100.0 * (fast - slow) / slow
+        } else {
+            f64::NAN
+        }
+    }
+
+    pub fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    pub fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> Self {
+        self.clone()
+    }
+
+    pub fn reset(&mut self) {
+        self.fast_ma.reset();
+        self.slow_ma.reset();
+    }
+}
+
+// ============================================================================
+// Price Oscillator
+// ============================================================================
+/// Generalized APO / PPO: fast MA minus slow MA, optionally as a percentage of the slow MA
+#[pyclass]
+#[derive(Clone)]
+pub struct PriceOscillatorStreaming {
+    distance: MADistanceStreaming,
+}
+
+impl PriceOscillatorStreaming {
+    fn try_new(fast: usize, slow: usize, ma_type: &str, pct: bool) -> Result<Self, String> {
+        Ok(Self {
+            distance: MADistanceStreaming::try_new(fast, slow, ma_type, pct)?,
+        })
+    }
+}
+
+#[pymethods]
+impl PriceOscillatorStreaming {
+    #[new]
+    #[pyo3(signature = (fast=10, slow=20, ma_type="sma", pct=false))]
+    pub fn new(fast: usize, slow: usize, ma_type: &str, pct: bool) -> PyResult<Self> {
+        Self::try_new(fast, slow, ma_type, pct).map_err(PyValueError::new_err)
+    }
+
+    pub fn update(&mut self, value: f64) -> f64 {
+        self.distance.update(value)
+    }
+
+    pub fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    pub fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> Self {
+        self.clone()
+    }
+
+    pub fn reset(&mut self) {
+        self.distance.reset();
+    }
+}
+
+// ============================================================================
+// Disparity Index
+// ============================================================================
+#[pyclass]
+#[derive(Clone)]
+pub struct DisparityIndexStreaming {
+    window: usize,
+    ma: MovingAverageStreaming,
+    update_count: usize,
+}
+
+impl DisparityIndexStreaming {
+    fn try_new(window: usize, ma_type: &str) -> Result<Self, String> {
+        Ok(Self {
+            window,
+            ma: MovingAverageStreaming::from_type(ma_type, window)?,
+            update_count: 0,
+        })
+    }
+}
+
+#[pymethods]
+impl DisparityIndexStreaming {
+    #[new]
+    #[pyo3(signature = (window=14, ma_type="ema"))]
+    pub fn new(window: usize, ma_type: &str) -> PyResult<Self> {
+        Self::try_new(window, ma_type).map_err(PyValueError::new_err)
+    }
+
+    pub fn update(&mut self, close: f64) -> f64 {
+        self.update_count += 1;
+        let ma = self.ma.update(close);
+
+        if self.update_count < self.window || ma.is_nan() || ma == 0.0 {
+            f64::NAN
+        } else {
+            100.0 * (close - ma) / ma
+        }
+    }
+
+    pub fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    pub fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> Self {
+        self.clone()
+    }
+
+    pub fn reset(&mut self) {
+        self.ma.reset();
+        self.update_count = 0;
+    }
+}
+
 // ============================================================================
 // ADX (Average Directional Index)
 // ============================================================================
 #[pyclass]
+#[derive(Clone)]
 pub struct ADXStreaming {
     window: usize,
     alpha: f64,
@@ -189,6 +537,7 @@ pub struct ADXStreaming {
     smoothed_tr: f64,
     smoothed_dx: f64,
     update_count: usize,
+    adx_history: VecDeque<f64>,
 }
 
 #[pymethods]
@@ -206,6 +555,7 @@ impl ADXStreaming {
             smoothed_tr: f64::NAN,
             smoothed_dx: f64::NAN,
             update_count: 0,
+            adx_history: VecDeque::with_capacity(window + 1),
         }
     }
 
@@ -217,6 +567,7 @@ impl ADXStreaming {
             self.prev_high = high;
             self.prev_low = low;
             self.prev_close = close;
+            self.record_adx(f64::NAN);
             return (f64::NAN, f64::NAN, f64::NAN);
         }
 
@@ -268,10 +619,27 @@ impl ADXStreaming {
         self.prev_high = high;
         self.prev_low = low;
         self.prev_close = close;
+        self.record_adx(adx);
 
         (adx, plus_di, minus_di)
     }
 
+    /// ADXR from the latest ADX and the ADX `window` updates ago (NaN until both exist)
+    pub fn adxr(&self) -> f64 {
+        match (self.adx_history.front(), self.adx_history.back()) {
+            (Some(&old), Some(&current)) if self.adx_history.len() > self.window => (current + old) / 2.0,
+            _ => f64::NAN,
+        }
+    }
+
+    pub fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    pub fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> Self {
+        self.clone()
+    }
+
     pub fn reset(&mut self) {
         self.prev_high = f64::NAN;
         self.prev_low = f64::NAN;
@@ -280,14 +648,25 @@ impl ADXStreaming {
         self.smoothed_minus_dm = f64::NAN;
         self.smoothed_tr = f64::NAN;
         self.smoothed_dx = f64::NAN;
+        self.adx_history.clear();
         self.update_count = 0;
     }
 }
 
+impl ADXStreaming {
+    fn record_adx(&mut self, adx: f64) {
+        self.adx_history.push_back(adx);
+        if self.adx_history.len() > self.window + 1 {
+            self.adx_history.pop_front();
+        }
+    }
+}
+
 // ============================================================================
 // CCI (Commodity Channel Index)
 // ============================================================================
 #[pyclass]
+#[derive(Clone)]
 pub struct CCIStreaming {
     window: usize,
     constant: f64,
@@ -327,6 +706,14 @@ impl CCIStreaming {
         }
     }
 
+    pub fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    pub fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> Self {
+        self.clone()
+    }
+
     pub fn reset(&mut self) {
         self.tp_buffer.clear();
     }
@@ -336,23 +723,27 @@ impl CCIStreaming {
 // DPO (Detrended Price Oscillator)
 // ============================================================================
 #[pyclass]
+#[derive(Clone)]
 pub struct DPOStreaming {
     window: usize,
     displacement: usize,
     sma_stream: SMAStreaming,
     price_buffer: VecDeque<f64>,
+    pct: bool,
 }
 
 #[pymethods]
 impl DPOStreaming {
     #[new]
-    pub fn new(window: usize) -> Self {
+    #[pyo3(signature = (window, pct=false))]
+    pub fn new(window: usize, pct: bool) -> Self {
         let displacement = window / 2 + 1;
         Self {
             window,
             displacement,
             sma_stream: SMAStreaming::new(window),
             price_buffer: VecDeque::with_capacity(window),
+            pct,
         }
     }
 
@@ -366,12 +757,25 @@ impl DPOStreaming {
 
         if self.price_buffer.len() >= self.displacement && !sma_value.is_nan() {
             let displaced_price = self.price_buffer[self.price_buffer.len() - self.displacement];
-            displaced_price - sma_value
+            let diff = displaced_price - sma_value;
+            if self.pct {
+                diff / sma_value * 100.0
+            } else {
+                diff
+            }
         } else {
             f64::NAN
         }
     }
 
+    pub fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    pub fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> Self {
+        self.clone()
+    }
+
     pub fn reset(&mut self) {
         self.sma_stream.reset();
         self.price_buffer.clear();
@@ -382,6 +786,7 @@ impl DPOStreaming {
 // Vortex Indicator
 // ============================================================================
 #[pyclass]
+#[derive(Clone)]
 #[pyo3(name = "VortexIndicatorStreaming")]
 pub struct VortexStreaming {
     window: usize,
@@ -456,6 +861,14 @@ impl VortexStreaming {
         result
     }
 
+    pub fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    pub fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> Self {
+        self.clone()
+    }
+
     pub fn reset(&mut self) {
         self.vm_plus_buffer.clear();
         self.vm_minus_buffer.clear();
@@ -471,6 +884,7 @@ impl VortexStreaming {
 // TRIX
 // ============================================================================
 #[pyclass]
+#[derive(Clone)]
 pub struct TRIXStreaming {
     ema1: EMAStreaming,
     ema2: EMAStreaming,
@@ -505,6 +919,14 @@ impl TRIXStreaming {
         result
     }
 
+    pub fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    pub fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> Self {
+        self.clone()
+    }
+
     pub fn reset(&mut self) {
         self.ema1.reset();
         self.ema2.reset();
@@ -517,6 +939,7 @@ impl TRIXStreaming {
 // Aroon Indicator
 // ============================================================================
 #[pyclass]
+#[derive(Clone)]
 pub struct AroonStreaming {
     window: usize,
     high_buffer: VecDeque<f64>,
@@ -563,6 +986,14 @@ impl AroonStreaming {
         }
     }
 
+    pub fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    pub fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> Self {
+        self.clone()
+    }
+
     pub fn reset(&mut self) {
         self.high_buffer.clear();
         self.low_buffer.clear();
@@ -573,13 +1004,13 @@ impl AroonStreaming {
 // Parabolic SAR
 // ============================================================================
 #[pyclass]
+#[derive(Clone)]
 #[pyo3(name = "ParabolicSARStreaming")]
 pub struct PSARStreaming {
-    af_start: f64,
-    af_inc: f64,
-    af_max: f64,
+    schedule: AfSchedule,
     up_trend: bool,
     acceleration_factor: f64,
+    advances: usize,
     up_trend_high: f64,
     down_trend_low: f64,
     prev_sar: f64,
@@ -589,16 +1020,14 @@ pub struct PSARStreaming {
     update_count: usize,
 }
 
-#[pymethods]
 impl PSARStreaming {
-    #[new]
-    pub fn new(af_start: f64, af_inc: f64, af_max: f64) -> Self {
-        Self {
-            af_start,
-            af_inc,
-            af_max,
+    fn try_new(af_start: f64, af_inc: f64, af_max: f64, af_schedule: Option<Vec<f64>>) -> Result<Self, String> {
+        let schedule = AfSchedule::new(af_start, af_inc, af_max, af_schedule)?;
+        Ok(Self {
             up_trend: true,
-            acceleration_factor: af_start,
+            acceleration_factor: schedule.initial(),
+            advances: 0,
+            schedule,
             up_trend_high: f64::NAN,
             down_trend_low: f64::NAN,
             prev_sar: f64::NAN,
@@ -606,7 +1035,16 @@ impl PSARStreaming {
             prev_low: f64::NAN,
             buffer: VecDeque::with_capacity(2),
             update_count: 0,
-        }
+        })
+    }
+}
+
+#[pymethods]
+impl PSARStreaming {
+    #[new]
+    #[pyo3(signature = (af_start=0.02, af_inc=0.02, af_max=0.2, af_schedule=None))]
+    pub fn new(af_start: f64, af_inc: f64, af_max: f64, af_schedule: Option<Vec<f64>>) -> PyResult<Self> {
+        Self::try_new(af_start, af_inc, af_max, af_schedule).map_err(PyValueError::new_err)
     }
 
     pub fn update(&mut self, high: f64, low: f64, close: f64) -> f64 {
@@ -637,11 +1075,13 @@ impl PSARStreaming {
                 reversal = true;
                 current_sar = self.up_trend_high;
                 self.down_trend_low = low;
-                self.acceleration_factor = self.af_start;
+                self.acceleration_factor = self.schedule.initial();
+                self.advances = 0;
             } else {
                 if high > self.up_trend_high {
                     self.up_trend_high = high;
-                    self.acceleration_factor = (self.acceleration_factor + self.af_inc).min(self.af_max);
+                    self.advances += 1;
+                    self.acceleration_factor = self.schedule.advance(self.acceleration_factor, self.advances);
                 }
 
                 if self.prev_low < current_sar {
@@ -655,11 +1095,13 @@ impl PSARStreaming {
                 reversal = true;
                 current_sar = self.down_trend_low;
                 self.up_trend_high = high;
-                self.acceleration_factor = self.af_start;
+                self.acceleration_factor = self.schedule.initial();
+                self.advances = 0;
             } else {
                 if low < self.down_trend_low {
                     self.down_trend_low = low;
-                    self.acceleration_factor = (self.acceleration_factor + self.af_inc).min(self.af_max);
+                    self.advances += 1;
+                    self.acceleration_factor = self.schedule.advance(self.acceleration_factor, self.advances);
                 }
 
                 if self.prev_high > current_sar {
@@ -682,9 +1124,18 @@ impl PSARStreaming {
         current_sar
     }
 
+    pub fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    pub fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> Self {
+        self.clone()
+    }
+
     pub fn reset(&mut self) {
         self.up_trend = true;
-        self.acceleration_factor = self.af_start;
+        self.acceleration_factor = self.schedule.initial();
+        self.advances = 0;
         self.up_trend_high = f64::NAN;
         self.down_trend_low = f64::NAN;
         self.prev_sar = f64::NAN;
@@ -694,3 +1145,181 @@ impl PSARStreaming {
         self.update_count = 0;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sma_copy_diverges_independently() {
+        let mut original = SMAStreaming::new(3);
+        for value in [1.0, 2.0, 3.0] {
+            original.update(value);
+        }
+
+        let mut fork = original.__copy__();
+        assert_eq!(original.update(4.0), 3.0);
+        assert_eq!(fork.update(10.0), 5.0);
+
+        // Each instance keeps evolving from its own buffer
+        assert_eq!(original.update(5.0), 4.0);
+        assert_eq!(fork.update(10.0), 23.0 / 3.0);
+    }
+
+    #[test]
+    fn test_adx_streaming_adxr() {
+        let mut adx = ADXStreaming::new(3);
+        let mut history = Vec::new();
+
+        for i in 0..30 {
+            let close = 100.0 + (i as f64 * 0.6).sin() * 3.0 + i as f64 * 0.2;
+            let (value, _, _) = adx.update(close + 1.0, close - 1.0, close);
+            history.push(value);
+
+            let adxr = adx.adxr();
+            if i >= 3 && !history[i - 3].is_nan() {
+                assert!((adxr - (value + history[i - 3]) / 2.0).abs() < 1e-12);
+            } else {
+                assert!(adxr.is_nan());
+            }
+        }
+    }
+
+    #[test]
+    fn test_macd_exposes_internal_emas() {
+        let mut macd = MACDStreaming::new(12, 26, 9);
+        assert!(macd.fast_ema().is_nan() && macd.signal_value().is_nan());
+
+        let mut last = (f64::NAN, f64::NAN, f64::NAN);
+        for i in 0..60 {
+            last = macd.update(100.0 + (i as f64 * 0.3).sin() * 5.0);
+        }
+        let (macd_line, signal_line, _) = last;
+        assert!((macd.fast_ema() - macd.slow_ema() - macd_line).abs() < 1e-12);
+        assert_eq!(macd.signal_value(), signal_line);
+
+        macd.reset();
+        assert!(macd.slow_ema().is_nan());
+    }
+
+    #[test]
+    fn test_dpo_streaming_pct() {
+        let mut abs = DPOStreaming::new(10, false);
+        let mut pct = DPOStreaming::new(10, true);
+        let mut sma = SMAStreaming::new(10);
+
+        for i in 0..40 {
+            let price = 100.0 + (i as f64 * 0.3).sin() * 5.0;
+            let a = abs.update(price);
+            let p = pct.update(price);
+            let m = sma.update(price);
+            if a.is_nan() {
+                assert!(p.is_nan());
+            } else {
+                assert!((p - a / m * 100.0).abs() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_nan_policy_on_moving_averages() {
+        let prices = [10.0, 11.0, 12.0, 13.0, f64::NAN, 14.0, 15.0];
+
+        let mut skip = SMAStreaming::try_new(3, "skip").unwrap();
+        let out: Vec<f64> = prices.iter().map(|&p| skip.update(p)).collect();
+        assert_eq!(out[3], 12.0);
+        assert_eq!(out[4], 12.0);
+        // The NaN never entered the window: mean of 12, 13, 14
+        assert_eq!(out[5], 13.0);
+        assert_eq!(out[6], 14.0);
+
+        let mut propagate = SMAStreaming::new(3);
+        let out: Vec<f64> = prices.iter().map(|&p| propagate.update(p)).collect();
+        assert!(out[4..].iter().all(|v| v.is_nan()));
+
+        // Reset restarts warmup from the next valid tick
+        let mut reset = SMAStreaming::try_new(3, "reset").unwrap();
+        let out: Vec<f64> = prices.iter().map(|&p| reset.update(p)).collect();
+        assert!(out[4..].iter().all(|v| v.is_nan()));
+        assert_eq!(reset.update(16.0), 15.0);
+
+        let mut ema = EMAStreaming::try_new(3, "skip", None).unwrap();
+        let before = prices[..4].iter().map(|&p| ema.update(p)).last().unwrap();
+        assert_eq!(ema.update(f64::NAN), before);
+
+        let mut wma = WMAStreaming::try_new(2, "skip").unwrap();
+        wma.update(1.0);
+        assert!(wma.update(f64::NAN).is_nan());
+        assert!((wma.update(4.0) - 3.0).abs() < 1e-12);
+
+        assert!(SMAStreaming::try_new(3, "drop").is_err());
+    }
+
+    #[test]
+    fn test_disparity_index_streaming_matches_bulk() {
+        let close: Vec<f64> = (0..60).map(|i| 100.0 + (i as f64 * 0.25).sin() * 6.0).collect();
+        for ma_type in ["sma", "ema", "wma"] {
+            let bulk = crate::trend::disparity_index_kernel(&close, 10, ma_type).unwrap();
+            let mut stream = DisparityIndexStreaming::try_new(10, ma_type).unwrap();
+            for (i, &c) in close.iter().enumerate() {
+                let v = stream.update(c);
+                if bulk[i].is_nan() {
+                    assert!(v.is_nan());
+                } else {
+                    assert!((v - bulk[i]).abs() < 1e-9, "{} at {}", ma_type, i);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_ema_streaming_min_periods() {
+        let prices: Vec<f64> = (0..20).map(|i| 100.0 + (i as f64 * 0.6).sin() * 3.0).collect();
+        let mut plain = EMAStreaming::new(5);
+        let mut gated = EMAStreaming::try_new(5, "propagate", Some(5)).unwrap();
+
+        for (i, &p) in prices.iter().enumerate() {
+            let (a, b) = (plain.update(p), gated.update(p));
+            if i < 4 {
+                assert!(!a.is_nan() && b.is_nan());
+            } else {
+                assert_eq!(a, b);
+            }
+        }
+
+        gated.reset();
+        assert!(gated.update(100.0).is_nan());
+    }
+
+    #[test]
+    fn test_price_oscillator_streaming_matches_ppo_streaming() {
+        let mut osc = PriceOscillatorStreaming::try_new(12, 26, "ema", true).unwrap();
+        let mut ppo = crate::streaming::PPOStreaming::new(12, 26, 9);
+        for i in 0..80 {
+            let price = 100.0 + (i as f64 * 0.3).sin() * 4.0;
+            let (line, _, _) = ppo.update(price);
+            assert!((osc.update(price) - line).abs() < 1e-9);
+        }
+        assert!(PriceOscillatorStreaming::try_new(12, 26, "hma", false).is_err());
+    }
+
+    #[test]
+    fn test_ma_distance_streaming_matches_bulk() {
+        let close: Vec<f64> = (0..120).map(|i| 100.0 + (i as f64 * 0.21).sin() * 6.0 + i as f64 * 0.05).collect();
+
+        for ma_type in ["sma", "ema", "wma"] {
+            for percent in [false, true] {
+                let bulk = crate::trend::ma_distance_kernel(&close, 5, 20, ma_type, false, percent).unwrap();
+                let mut stream = MADistanceStreaming::try_new(5, 20, ma_type, percent).unwrap();
+                for (i, &price) in close.iter().enumerate() {
+                    let value = stream.update(price);
+                    if bulk[i].is_nan() {
+                        assert!(value.is_nan(), "{} warmup at {}", ma_type, i);
+                    } else {
+                        assert!((value - bulk[i]).abs() < 1e-9, "{} at {}", ma_type, i);
+                    }
+                }
+            }
+        }
+    }
+}
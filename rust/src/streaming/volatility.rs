@@ -1,11 +1,14 @@
+use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
 use std::collections::VecDeque;
-use super::trend::EMAStreaming;
+use super::trend::{EMAStreaming, MovingAverageStreaming};
+use crate::helpers;
 
 // ============================================================================
 // ATR (Average True Range)
 // ============================================================================
 #[pyclass]
+#[derive(Clone)]
 pub struct ATRStreaming {
     window: usize,
     alpha: f64,
@@ -54,6 +57,14 @@ impl ATRStreaming {
         }
     }
 
+    pub fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    pub fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> Self {
+        self.clone()
+    }
+
     pub fn reset(&mut self) {
         self.prev_close = f64::NAN;
         self.current_value = f64::NAN;
@@ -65,6 +76,7 @@ impl ATRStreaming {
 // Bollinger Bands
 // ============================================================================
 #[pyclass]
+#[derive(Clone)]
 #[pyo3(name = "BBandsStreaming")]
 pub struct BollingerBandsStreaming {
     window: usize,
@@ -107,6 +119,14 @@ impl BollingerBandsStreaming {
         }
     }
 
+    pub fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    pub fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> Self {
+        self.clone()
+    }
+
     pub fn reset(&mut self) {
         self.buffer.clear();
     }
@@ -116,10 +136,15 @@ impl BollingerBandsStreaming {
 // Keltner Channel
 // ============================================================================
 #[pyclass]
+#[derive(Clone)]
 pub struct KeltnerChannelStreaming {
     multiplier: f64,
     ema: EMAStreaming,
     atr: ATRStreaming,
+    ema_value: f64,
+    atr_value: f64,
+    upper: f64,
+    lower: f64,
 }
 
 #[pymethods]
@@ -130,6 +155,10 @@ impl KeltnerChannelStreaming {
             multiplier,
             ema: EMAStreaming::new(window),
             atr: ATRStreaming::new(atr_period),
+            ema_value: f64::NAN,
+            atr_value: f64::NAN,
+            upper: f64::NAN,
+            lower: f64::NAN,
         }
     }
 
@@ -137,19 +166,158 @@ impl KeltnerChannelStreaming {
     pub fn update(&mut self, high: f64, low: f64, close: f64) -> (f64, f64, f64) {
         let ema_value = self.ema.update(close);
         let atr_value = self.atr.update(high, low, close);
+        self.ema_value = ema_value;
+        self.atr_value = atr_value;
 
         if ema_value.is_nan() || atr_value.is_nan() {
             (f64::NAN, f64::NAN, f64::NAN)
         } else {
             let upper = ema_value + self.multiplier * atr_value;
             let lower = ema_value - self.multiplier * atr_value;
+            self.upper = upper;
+            self.lower = lower;
             (upper, ema_value, lower)
         }
     }
 
+    /// Position of `close` within the current channel (0 at lower, 0.5 at the
+    /// EMA midline, 1 at upper). NaN before warmup or when the channel collapses.
+    pub fn position(&self, close: f64) -> f64 {
+        let width = self.upper - self.lower;
+        if width.is_nan() || width == 0.0 {
+            f64::NAN
+        } else {
+            (close - self.lower) / width
+        }
+    }
+
+    /// Latest EMA midline value
+    pub fn ema(&self) -> f64 {
+        self.ema_value
+    }
+
+    /// Latest ATR value
+    pub fn atr(&self) -> f64 {
+        self.atr_value
+    }
+
+    /// Latest band width (upper - lower), NaN before warmup
+    pub fn width(&self) -> f64 {
+        self.upper - self.lower
+    }
+
+    pub fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    pub fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> Self {
+        self.clone()
+    }
+
     pub fn reset(&mut self) {
         self.ema.reset();
         self.atr.reset();
+        self.ema_value = f64::NAN;
+        self.atr_value = f64::NAN;
+        self.upper = f64::NAN;
+        self.lower = f64::NAN;
+    }
+}
+
+// ============================================================================
+// Moving Average Envelope
+// ============================================================================
+#[pyclass]
+#[derive(Clone)]
+pub struct MAEnvelopeStreaming {
+    ma: MovingAverageStreaming,
+    pct: f64,
+}
+
+impl MAEnvelopeStreaming {
+    fn try_new(window: usize, pct: f64, ma_type: &str) -> Result<Self, String> {
+        Ok(Self {
+            ma: MovingAverageStreaming::from_type(ma_type, window)?,
+            pct,
+        })
+    }
+}
+
+#[pymethods]
+impl MAEnvelopeStreaming {
+    #[new]
+    #[pyo3(signature = (window=20, pct=2.5, ma_type="sma"))]
+    pub fn new(window: usize, pct: f64, ma_type: &str) -> PyResult<Self> {
+        Self::try_new(window, pct, ma_type).map_err(PyValueError::new_err)
+    }
+
+    /// Returns (upper, middle, lower)
+    pub fn update(&mut self, value: f64) -> (f64, f64, f64) {
+        let middle = self.ma.update(value);
+        (
+            middle * (1.0 + self.pct / 100.0),
+            middle,
+            middle * (1.0 - self.pct / 100.0),
+        )
+    }
+
+    pub fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    pub fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> Self {
+        self.clone()
+    }
+
+    pub fn reset(&mut self) {
+        self.ma.reset();
+    }
+}
+
+// ============================================================================
+// Bollinger Squeeze (TTM squeeze)
+// ============================================================================
+#[pyclass]
+#[derive(Clone)]
+pub struct SqueezeStreaming {
+    bands: BollingerBandsStreaming,
+    keltner: KeltnerChannelStreaming,
+}
+
+#[pymethods]
+impl SqueezeStreaming {
+    #[new]
+    #[pyo3(signature = (window=20, std_dev=2.0, kc_mult=1.5, kc_window=20))]
+    pub fn new(window: usize, std_dev: f64, kc_mult: f64, kc_window: usize) -> Self {
+        Self {
+            bands: BollingerBandsStreaming::new(window, std_dev),
+            keltner: KeltnerChannelStreaming::new(kc_window, kc_window, kc_mult),
+        }
+    }
+
+    /// Returns 1.0 while the Bollinger Bands are inside the Keltner Channel, else 0.0
+    pub fn update(&mut self, high: f64, low: f64, close: f64) -> f64 {
+        let (bb_upper, _, bb_lower) = self.bands.update(close);
+        let (kc_upper, _, kc_lower) = self.keltner.update(high, low, close);
+
+        if bb_upper < kc_upper && bb_lower > kc_lower {
+            1.0
+        } else {
+            0.0
+        }
+    }
+
+    pub fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    pub fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> Self {
+        self.clone()
+    }
+
+    pub fn reset(&mut self) {
+        self.bands.reset();
+        self.keltner.reset();
     }
 }
 
@@ -157,10 +325,13 @@ impl KeltnerChannelStreaming {
 // Donchian Channel
 // ============================================================================
 #[pyclass]
+#[derive(Clone)]
 pub struct DonchianChannelStreaming {
     window: usize,
     high_buffer: VecDeque<f64>,
     low_buffer: VecDeque<f64>,
+    upper: f64,
+    lower: f64,
 }
 
 #[pymethods]
@@ -171,6 +342,8 @@ impl DonchianChannelStreaming {
             window,
             high_buffer: VecDeque::with_capacity(window),
             low_buffer: VecDeque::with_capacity(window),
+            upper: f64::NAN,
+            lower: f64::NAN,
         }
     }
 
@@ -190,14 +363,37 @@ impl DonchianChannelStreaming {
             let upper = self.high_buffer.iter().fold(f64::NEG_INFINITY, |a, &b| a.max(b));
             let lower = self.low_buffer.iter().fold(f64::INFINITY, |a, &b| a.min(b));
             let middle = (upper + lower) / 2.0;
+            self.upper = upper;
+            self.lower = lower;
 
             (upper, middle, lower)
         }
     }
 
+    /// Position of `close` within the current channel (0 at lower, 1 at upper).
+    /// NaN before warmup or when the channel has zero width.
+    pub fn position(&self, close: f64) -> f64 {
+        let width = self.upper - self.lower;
+        if width.is_nan() || width == 0.0 {
+            f64::NAN
+        } else {
+            (close - self.lower) / width
+        }
+    }
+
+    pub fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    pub fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> Self {
+        self.clone()
+    }
+
     pub fn reset(&mut self) {
         self.high_buffer.clear();
         self.low_buffer.clear();
+        self.upper = f64::NAN;
+        self.lower = f64::NAN;
     }
 }
 
@@ -205,6 +401,7 @@ impl DonchianChannelStreaming {
 // Ulcer Index
 // ============================================================================
 #[pyclass]
+#[derive(Clone)]
 pub struct UlcerIndexStreaming {
     window: usize,
     close_buffer: VecDeque<f64>,
@@ -246,6 +443,14 @@ impl UlcerIndexStreaming {
         }
     }
 
+    pub fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    pub fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> Self {
+        self.clone()
+    }
+
     pub fn reset(&mut self) {
         self.close_buffer.clear();
     }
@@ -255,6 +460,7 @@ impl UlcerIndexStreaming {
 // Standard Deviation (rolling, population)
 // ============================================================================
 #[pyclass]
+#[derive(Clone)]
 pub struct StandardDeviationStreaming {
     window: usize,
     buffer: VecDeque<f64>,
@@ -288,6 +494,14 @@ impl StandardDeviationStreaming {
         }
     }
 
+    pub fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    pub fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> Self {
+        self.clone()
+    }
+
     pub fn reset(&mut self) {
         self.buffer.clear();
     }
@@ -297,6 +511,7 @@ impl StandardDeviationStreaming {
 // Variance (rolling, population)
 // ============================================================================
 #[pyclass]
+#[derive(Clone)]
 pub struct VarianceStreaming {
     window: usize,
     buffer: VecDeque<f64>,
@@ -329,6 +544,14 @@ impl VarianceStreaming {
         }
     }
 
+    pub fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    pub fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> Self {
+        self.clone()
+    }
+
     pub fn reset(&mut self) {
         self.buffer.clear();
     }
@@ -338,6 +561,7 @@ impl VarianceStreaming {
 // Range (rolling High - Low range)
 // ============================================================================
 #[pyclass]
+#[derive(Clone)]
 pub struct RangeStreaming {
     window: usize,
     high_buffer: VecDeque<f64>,
@@ -373,6 +597,14 @@ impl RangeStreaming {
         }
     }
 
+    pub fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    pub fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> Self {
+        self.clone()
+    }
+
     pub fn reset(&mut self) {
         self.high_buffer.clear();
         self.low_buffer.clear();
@@ -383,26 +615,59 @@ impl RangeStreaming {
 // Historical Volatility (annualized rolling std of log returns)
 // ============================================================================
 #[pyclass]
+#[derive(Clone)]
 pub struct HistoricalVolatilityStreaming {
     window: usize,
+    min_periods: usize,
     annualize: bool,
+    periods_per_year: f64,
+    return_type: helpers::ReturnType,
     prev_value: f64,
     returns_buffer: VecDeque<f64>,
     update_count: usize,
 }
 
-#[pymethods]
 impl HistoricalVolatilityStreaming {
-    #[new]
-    #[pyo3(signature = (window=20, annualize=true))]
-    pub fn new(window: usize, annualize: bool) -> Self {
-        Self {
+    fn try_new(
+        window: usize,
+        annualize: bool,
+        min_periods: Option<usize>,
+        return_type: &str,
+        bar_frequency: &str,
+        periods_per_year: Option<f64>,
+    ) -> Result<Self, String> {
+        Ok(Self {
             window,
+            min_periods: helpers::resolve_min_periods(window, min_periods)?,
             annualize,
+            periods_per_year: helpers::resolve_periods_per_year(bar_frequency, periods_per_year)?,
+            return_type: helpers::ReturnType::parse(return_type)?,
             prev_value: f64::NAN,
             returns_buffer: VecDeque::with_capacity(window),
             update_count: 0,
-        }
+        })
+    }
+}
+
+#[pymethods]
+impl HistoricalVolatilityStreaming {
+    /// `min_periods` (default: `window`) is the number of returns needed before the
+    /// first estimate; until the window fills, the available returns are used.
+    /// `return_type` is "log" (default) or "simple". `bar_frequency` sets the
+    /// annualization: "daily" (default, sqrt(252)), "hourly", "minute" or
+    /// "crypto_daily" (sqrt(365)); `periods_per_year` overrides the preset.
+    #[new]
+    #[pyo3(signature = (window=20, annualize=true, min_periods=None, return_type="log", bar_frequency="daily", periods_per_year=None))]
+    pub fn new(
+        window: usize,
+        annualize: bool,
+        min_periods: Option<usize>,
+        return_type: &str,
+        bar_frequency: &str,
+        periods_per_year: Option<f64>,
+    ) -> PyResult<Self> {
+        Self::try_new(window, annualize, min_periods, return_type, bar_frequency, periods_per_year)
+            .map_err(PyValueError::new_err)
     }
 
     pub fn update(&mut self, value: f64) -> f64 {
@@ -414,8 +679,8 @@ impl HistoricalVolatilityStreaming {
         }
 
         if self.prev_value > 0.0 && value > 0.0 {
-            let log_return = (value / self.prev_value).ln();
-            self.returns_buffer.push_back(log_return);
+            let period_return = self.return_type.compute(self.prev_value, value);
+            self.returns_buffer.push_back(period_return);
 
             if self.returns_buffer.len() > self.window {
                 self.returns_buffer.pop_front();
@@ -424,7 +689,7 @@ impl HistoricalVolatilityStreaming {
 
         self.prev_value = value;
 
-        if self.returns_buffer.len() < self.window {
+        if self.returns_buffer.len() < self.min_periods {
             f64::NAN
         } else {
             let n = self.returns_buffer.len() as f64;
@@ -435,16 +700,308 @@ impl HistoricalVolatilityStreaming {
             let mut volatility = variance.sqrt();
 
             if self.annualize {
-                volatility *= (252.0_f64).sqrt();
+                volatility *= self.periods_per_year.sqrt();
             }
 
             volatility
         }
     }
 
+    pub fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    pub fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> Self {
+        self.clone()
+    }
+
     pub fn reset(&mut self) {
         self.prev_value = f64::NAN;
         self.returns_buffer.clear();
         self.update_count = 0;
     }
 }
+
+// ============================================================================
+// Chaikin Volatility
+// ============================================================================
+#[pyclass]
+#[derive(Clone)]
+pub struct ChaikinVolatilityStreaming {
+    ema: EMAStreaming,
+    roc_period: usize,
+    range_emas: VecDeque<f64>,
+}
+
+#[pymethods]
+impl ChaikinVolatilityStreaming {
+    #[new]
+    #[pyo3(signature = (ema_period=10, roc_period=10))]
+    pub fn new(ema_period: usize, roc_period: usize) -> Self {
+        Self {
+            ema: EMAStreaming::new(ema_period),
+            roc_period,
+            range_emas: VecDeque::with_capacity(roc_period + 1),
+        }
+    }
+
+    /// NaN for the first `roc_period` updates and wherever the range EMA
+    /// `roc_period` bars back is zero, like the bulk version (`ROCStreaming`
+    /// reports 0.0 there instead)
+    pub fn update(&mut self, high: f64, low: f64) -> f64 {
+        let range_ema = self.ema.update(high - low);
+        self.range_emas.push_back(range_ema);
+        if self.range_emas.len() > self.roc_period + 1 {
+            self.range_emas.pop_front();
+        }
+
+        let base = self.range_emas[0];
+        if self.range_emas.len() <= self.roc_period || base == 0.0 {
+            f64::NAN
+        } else {
+            (range_ema - base) / base * 100.0
+        }
+    }
+
+    pub fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    pub fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> Self {
+        self.clone()
+    }
+
+    pub fn reset(&mut self) {
+        self.ema.reset();
+        self.range_emas.clear();
+    }
+}
+
+// ============================================================================
+// ATR Trailing Stop
+// ============================================================================
+#[pyclass]
+#[derive(Clone)]
+pub struct ATRTrailingStopStreaming {
+    multiplier: f64,
+    atr: ATRStreaming,
+    stop: f64,
+    direction: f64,
+}
+
+#[pymethods]
+impl ATRTrailingStopStreaming {
+    #[new]
+    #[pyo3(signature = (atr_n=14, multiplier=3.0))]
+    pub fn new(atr_n: usize, multiplier: f64) -> Self {
+        Self {
+            multiplier,
+            atr: ATRStreaming::new(atr_n),
+            stop: f64::NAN,
+            direction: f64::NAN,
+        }
+    }
+
+    /// Returns (stop, direction) with direction 1.0 long / -1.0 short.
+    /// Starts long on the first bar with an ATR; NaN before that.
+    pub fn update(&mut self, high: f64, low: f64, close: f64) -> (f64, f64) {
+        let atr = self.atr.update(high, low, close);
+        if atr.is_nan() {
+            return (f64::NAN, f64::NAN);
+        }
+
+        let offset = self.multiplier * atr;
+        (self.stop, self.direction) = if self.stop.is_nan() {
+            (close - offset, 1.0)
+        } else {
+            helpers::trailing_stop_step(self.stop, self.direction, close, offset)
+        };
+
+        (self.stop, self.direction)
+    }
+
+    pub fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    pub fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> Self {
+        self.clone()
+    }
+
+    pub fn reset(&mut self) {
+        self.atr.reset();
+        self.stop = f64::NAN;
+        self.direction = f64::NAN;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chaikin_volatility_streaming_matches_bulk() {
+        // Flat bars first leave a zero range EMA, which the bulk ROC reports as NaN
+        let high: Vec<f64> = (0..60).map(|i| if i < 5 { 100.0 } else { 101.0 + (i as f64 * 0.4).sin() }).collect();
+        let low: Vec<f64> = (0..60).map(|i| if i < 5 { 100.0 } else { 99.0 - (i as f64 * 0.3).cos() }).collect();
+        let bulk = crate::volatility::chaikin_volatility_kernel(&high, &low, 10, 10);
+
+        let mut stream = ChaikinVolatilityStreaming::new(10, 10);
+        for i in 0..high.len() {
+            let value = stream.update(high[i], low[i]);
+            if bulk[i].is_nan() {
+                assert!(value.is_nan(), "expected NaN at {}", i);
+            } else {
+                assert!((value - bulk[i]).abs() < 1e-9, "mismatch at {}", i);
+            }
+        }
+        assert!(bulk[12].is_nan() && !bulk[20].is_nan());
+    }
+
+    #[test]
+    fn test_donchian_position_streaming() {
+        let mut donchian = DonchianChannelStreaming::new(3);
+        assert!(donchian.position(10.0).is_nan());
+
+        donchian.update(10.0, 8.0);
+        donchian.update(12.0, 9.0);
+        let (upper, _, lower) = donchian.update(11.0, 9.5);
+        assert_eq!((upper, lower), (12.0, 8.0));
+        assert!((donchian.position(12.0) - 1.0).abs() < 1e-12);
+        assert!(donchian.position(8.0).abs() < 1e-12);
+        assert!((donchian.position(10.0) - 0.5).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_atr_trailing_stop_ratchets_and_flips() {
+        // 30 bars up 1.0 per bar, then 30 bars down 1.0 per bar, range 1.0
+        let closes: Vec<f64> = (0..60)
+            .map(|i| if i < 30 { 100.0 + i as f64 } else { 129.0 - (i - 29) as f64 })
+            .collect();
+        let mut stop = ATRTrailingStopStreaming::new(5, 2.0);
+
+        let mut prev: Option<(f64, f64)> = None;
+        let mut flip_bar = None;
+        for (i, &c) in closes.iter().enumerate() {
+            let (s, d) = stop.update(c + 0.5, c - 0.5, c);
+            if let Some((ps, pd)) = prev {
+                if d == pd {
+                    // Never loosens within a trend, and holds only while unbreached
+                    if d > 0.0 {
+                        assert!(s >= ps && c >= ps);
+                    } else {
+                        assert!(s <= ps && c <= ps);
+                    }
+                } else {
+                    assert!(d < 0.0 && c < ps, "flip must be a breach of the long stop");
+                    flip_bar = Some(i);
+                }
+            }
+            if !s.is_nan() {
+                prev = Some((s, d));
+            }
+        }
+
+        // Stop trails 2 ATR (TR ≈ 1.5) below the top close of 129, so the flip
+        // lands within the first few falling bars
+        assert!(matches!(flip_bar, Some(i) if (30..=33).contains(&i)));
+    }
+
+    #[test]
+    fn test_ma_envelope_streaming_matches_bulk() {
+        let close: Vec<f64> = (0..40).map(|i| 100.0 + (i as f64 * 0.3).sin() * 5.0).collect();
+        let (upper, middle, lower) =
+            crate::volatility::ma_envelope_kernel(&close, 10, 2.5, "ema").unwrap();
+
+        let mut envelope = MAEnvelopeStreaming::try_new(10, 2.5, "ema").unwrap();
+        for i in 0..close.len() {
+            let (u, m, l) = envelope.update(close[i]);
+            assert!((m - middle[i]).abs() < 1e-9);
+            assert!((u - upper[i]).abs() < 1e-9 && (l - lower[i]).abs() < 1e-9);
+        }
+        assert!(MAEnvelopeStreaming::try_new(10, 2.5, "hma").is_err());
+    }
+
+    #[test]
+    fn test_historical_volatility_min_periods_emits_early() {
+        let close: Vec<f64> = (0..30).map(|i| 100.0 + (i as f64 * 0.8).sin() * 3.0).collect();
+        let mut full = HistoricalVolatilityStreaming::try_new(20, true, None, "log", "daily", None).unwrap();
+        let mut early = HistoricalVolatilityStreaming::try_new(20, true, Some(5), "log", "daily", None).unwrap();
+
+        for (i, &c) in close.iter().enumerate() {
+            let (f, e) = (full.update(c), early.update(c));
+            assert_eq!(e.is_nan(), i < 5, "bar {}", i);
+            assert_eq!(f.is_nan(), i < 20, "bar {}", i);
+            if i >= 20 {
+                assert_eq!(f, e);
+            }
+        }
+
+        // Bar 5 uses the 5 returns seen so far with ddof=1
+        let mut hv = HistoricalVolatilityStreaming::try_new(20, false, Some(5), "log", "daily", None).unwrap();
+        let value = close[..6].iter().map(|&c| hv.update(c)).last().unwrap();
+        let returns: Vec<f64> = close[..6].windows(2).map(|w| (w[1] / w[0]).ln()).collect();
+        let mean = returns.iter().sum::<f64>() / 5.0;
+        let var = returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / 4.0;
+        assert!((value - var.sqrt()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_historical_volatility_crypto_daily_uses_365() {
+        let close: Vec<f64> = (0..30).map(|i| 100.0 + (i as f64 * 0.8).sin() * 3.0).collect();
+        let mut raw = HistoricalVolatilityStreaming::try_new(20, false, None, "log", "daily", None).unwrap();
+        let mut crypto = HistoricalVolatilityStreaming::try_new(20, true, None, "log", "crypto_daily", None).unwrap();
+
+        let (r, c) = close.iter().fold((0.0, 0.0), |_, &v| (raw.update(v), crypto.update(v)));
+        assert!((c - r * 365.0_f64.sqrt()).abs() < 1e-12);
+        assert!(HistoricalVolatilityStreaming::try_new(20, true, None, "log", "weekly", None).is_err());
+
+        // An explicit periods_per_year replaces the preset
+        let mut custom = HistoricalVolatilityStreaming::try_new(20, true, None, "log", "daily", Some(365.0)).unwrap();
+        let v = close.iter().fold(0.0, |_, &v| custom.update(v));
+        assert!((v - c).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_keltner_position_streaming() {
+        let mut keltner = KeltnerChannelStreaming::new(5, 3, 2.0);
+        assert!(keltner.position(100.0).is_nan());
+
+        let mut bands = (f64::NAN, f64::NAN, f64::NAN);
+        for i in 0..10 {
+            let c = 100.0 + i as f64;
+            bands = keltner.update(c + 1.0, c - 1.0, c);
+        }
+        let (upper, middle, lower) = bands;
+        assert!((keltner.position(middle) - 0.5).abs() < 1e-12);
+        assert!((keltner.position(upper) - 1.0).abs() < 1e-12);
+        assert!(keltner.position(lower).abs() < 1e-12);
+
+        // Zero ATR collapses the channel
+        let mut flat = KeltnerChannelStreaming::new(5, 3, 2.0);
+        for _ in 0..10 {
+            flat.update(100.0, 100.0, 100.0);
+        }
+        assert!(flat.position(100.0).is_nan());
+    }
+
+    #[test]
+    fn test_keltner_component_accessors() {
+        let mut keltner = KeltnerChannelStreaming::new(5, 3, 2.0);
+        assert!(keltner.ema().is_nan() && keltner.atr().is_nan() && keltner.width().is_nan());
+
+        let mut bands = (f64::NAN, f64::NAN, f64::NAN);
+        for i in 0..12 {
+            let c = 100.0 + (i as f64 * 0.7).sin() * 3.0;
+            bands = keltner.update(c + 1.5, c - 1.0, c);
+        }
+        let (upper, middle, lower) = bands;
+        assert_eq!(keltner.ema(), middle);
+        assert!((keltner.ema() + 2.0 * keltner.atr() - upper).abs() < 1e-12);
+        assert!((keltner.ema() - 2.0 * keltner.atr() - lower).abs() < 1e-12);
+        assert!((keltner.width() - (upper - lower)).abs() < 1e-12);
+
+        keltner.reset();
+        assert!(keltner.ema().is_nan() && keltner.atr().is_nan() && keltner.width().is_nan());
+    }
+}
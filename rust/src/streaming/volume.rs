@@ -1,11 +1,13 @@
 use pyo3::prelude::*;
 use std::collections::VecDeque;
+use super::others::RollingZScoreStreaming;
 use super::trend::{EMAStreaming, SMAStreaming};
 
 // ============================================================================
 // MFI (Money Flow Index)
 // ============================================================================
 #[pyclass]
+#[derive(Clone)]
 #[pyo3(name = "MoneyFlowIndexStreaming")]
 pub struct MFIStreaming {
     window: usize,
@@ -65,6 +67,14 @@ impl MFIStreaming {
         }
     }
 
+    pub fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    pub fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> Self {
+        self.clone()
+    }
+
     pub fn reset(&mut self) {
         self.positive_mf_buffer.clear();
         self.negative_mf_buffer.clear();
@@ -76,6 +86,7 @@ impl MFIStreaming {
 // Accumulation/Distribution Index
 // ============================================================================
 #[pyclass]
+#[derive(Clone)]
 #[pyo3(name = "AccDistIndexStreaming")]
 pub struct AccDistStreaming {
     ad_line: f64,
@@ -103,6 +114,20 @@ impl AccDistStreaming {
         self.ad_line
     }
 
+    pub fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    pub fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> Self {
+        self.clone()
+    }
+
+    /// Restart the A/D line from 0, e.g. at a session open. Each bar's flow
+    /// depends only on that bar, so unlike OBV there is no `keep_prev`.
+    pub fn reset_session(&mut self) {
+        self.ad_line = 0.0;
+    }
+
     pub fn reset(&mut self) {
         self.ad_line = 0.0;
     }
@@ -112,6 +137,7 @@ impl AccDistStreaming {
 // OBV (On-Balance Volume)
 // ============================================================================
 #[pyclass]
+#[derive(Clone)]
 #[pyo3(name = "OnBalanceVolumeStreaming")]
 pub struct OBVStreaming {
     obv_line: f64,
@@ -147,6 +173,25 @@ impl OBVStreaming {
         self.obv_line
     }
 
+    pub fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    pub fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> Self {
+        self.clone()
+    }
+
+    /// Restart the OBV line from 0, e.g. at a session open. With `keep_prev`,
+    /// the next bar is still compared against the last close seen.
+    #[pyo3(signature = (keep_prev=true))]
+    pub fn reset_session(&mut self, keep_prev: bool) {
+        if keep_prev {
+            self.obv_line = 0.0;
+        } else {
+            self.reset();
+        }
+    }
+
     pub fn reset(&mut self) {
         self.obv_line = 0.0;
         self.prev_close = f64::NAN;
@@ -158,6 +203,7 @@ impl OBVStreaming {
 // CMF (Chaikin Money Flow)
 // ============================================================================
 #[pyclass]
+#[derive(Clone)]
 #[pyo3(name = "ChaikinMoneyFlowStreaming")]
 pub struct CMFStreaming {
     window: usize,
@@ -207,6 +253,14 @@ impl CMFStreaming {
         }
     }
 
+    pub fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    pub fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> Self {
+        self.clone()
+    }
+
     pub fn reset(&mut self) {
         self.mfv_buffer.clear();
         self.volume_buffer.clear();
@@ -217,6 +271,7 @@ impl CMFStreaming {
 // Force Index
 // ============================================================================
 #[pyclass]
+#[derive(Clone)]
 pub struct ForceIndexStreaming {
     window: usize,
     alpha: f64,
@@ -263,6 +318,14 @@ impl ForceIndexStreaming {
         }
     }
 
+    pub fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    pub fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> Self {
+        self.clone()
+    }
+
     pub fn reset(&mut self) {
         self.prev_close = f64::NAN;
         self.current_value = f64::NAN;
@@ -274,6 +337,7 @@ impl ForceIndexStreaming {
 // EOM (Ease of Movement)
 // ============================================================================
 #[pyclass]
+#[derive(Clone)]
 #[pyo3(name = "EaseOfMovementStreaming")]
 pub struct EOMStreaming {
     prev_high: f64,
@@ -315,6 +379,14 @@ impl EOMStreaming {
         result
     }
 
+    pub fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    pub fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> Self {
+        self.clone()
+    }
+
     pub fn reset(&mut self) {
         self.prev_high = f64::NAN;
         self.prev_low = f64::NAN;
@@ -326,6 +398,7 @@ impl EOMStreaming {
 // VPT (Volume Price Trend)
 // ============================================================================
 #[pyclass]
+#[derive(Clone)]
 #[pyo3(name = "VolumePriceTrendStreaming")]
 pub struct VPTStreaming {
     vpt_line: f64,
@@ -359,6 +432,25 @@ impl VPTStreaming {
         self.vpt_line
     }
 
+    pub fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    pub fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> Self {
+        self.clone()
+    }
+
+    /// Restart the VPT line from 0, e.g. at a session open. With `keep_prev`,
+    /// the next bar's change is measured from the last close seen.
+    #[pyo3(signature = (keep_prev=true))]
+    pub fn reset_session(&mut self, keep_prev: bool) {
+        if keep_prev {
+            self.vpt_line = 0.0;
+        } else {
+            self.reset();
+        }
+    }
+
     pub fn reset(&mut self) {
         self.vpt_line = 0.0;
         self.prev_close = f64::NAN;
@@ -370,6 +462,7 @@ impl VPTStreaming {
 // NVI (Negative Volume Index)
 // ============================================================================
 #[pyclass]
+#[derive(Clone)]
 #[pyo3(name = "NegativeVolumeIndexStreaming")]
 pub struct NVIStreaming {
     nvi_line: f64,
@@ -406,6 +499,25 @@ impl NVIStreaming {
         self.nvi_line
     }
 
+    pub fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    pub fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> Self {
+        self.clone()
+    }
+
+    /// Restart the NVI line from 1000, e.g. at a session open. With `keep_prev`,
+    /// the next bar is still compared against the last close and volume seen.
+    #[pyo3(signature = (keep_prev=true))]
+    pub fn reset_session(&mut self, keep_prev: bool) {
+        if keep_prev {
+            self.nvi_line = 1000.0;
+        } else {
+            self.reset();
+        }
+    }
+
     pub fn reset(&mut self) {
         self.nvi_line = 1000.0;
         self.prev_close = f64::NAN;
@@ -418,6 +530,7 @@ impl NVIStreaming {
 // VWAP (Volume Weighted Average Price)
 // ============================================================================
 #[pyclass]
+#[derive(Clone)]
 pub struct VWAPStreaming {
     window: usize,
     tpv_buffer: VecDeque<f64>,
@@ -461,16 +574,117 @@ impl VWAPStreaming {
         }
     }
 
+    pub fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    pub fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> Self {
+        self.clone()
+    }
+
     pub fn reset(&mut self) {
         self.tpv_buffer.clear();
         self.volume_buffer.clear();
     }
 }
 
+// ============================================================================
+// VWAP Bands
+// ============================================================================
+#[pyclass]
+#[derive(Clone)]
+pub struct VWAPBandsStreaming {
+    window: usize,
+    k: f64,
+    // (typical price, volume) pairs in the window
+    buffer: VecDeque<(f64, f64)>,
+    // Weighted Welford state: total weight, weighted mean, weighted sum of squared deviations
+    weight: f64,
+    mean: f64,
+    m2: f64,
+}
+
+impl VWAPBandsStreaming {
+    fn add(&mut self, x: f64, w: f64) {
+        self.weight += w;
+        if self.weight == 0.0 {
+            return;
+        }
+        let delta = x - self.mean;
+        self.mean += w / self.weight * delta;
+        self.m2 += w * delta * (x - self.mean);
+    }
+
+    fn remove(&mut self, x: f64, w: f64) {
+        self.weight -= w;
+        if self.weight <= 0.0 {
+            self.weight = 0.0;
+            self.mean = 0.0;
+            self.m2 = 0.0;
+            return;
+        }
+        let delta = x - self.mean;
+        self.mean -= w / self.weight * delta;
+        self.m2 -= w * delta * (x - self.mean);
+    }
+}
+
+#[pymethods]
+impl VWAPBandsStreaming {
+    #[new]
+    #[pyo3(signature = (window=20, k=2.0))]
+    pub fn new(window: usize, k: f64) -> Self {
+        Self {
+            window,
+            k,
+            buffer: VecDeque::with_capacity(window + 1),
+            weight: 0.0,
+            mean: 0.0,
+            m2: 0.0,
+        }
+    }
+
+    /// Returns (upper, vwap, lower)
+    pub fn update(&mut self, high: f64, low: f64, close: f64, volume: f64) -> (f64, f64, f64) {
+        let typical_price = (high + low + close) / 3.0;
+
+        self.buffer.push_back((typical_price, volume));
+        self.add(typical_price, volume);
+        if self.buffer.len() > self.window {
+            if let Some((x, w)) = self.buffer.pop_front() {
+                self.remove(x, w);
+            }
+        }
+
+        if self.buffer.len() < self.window || self.weight == 0.0 {
+            return (f64::NAN, f64::NAN, f64::NAN);
+        }
+
+        let std = (self.m2.max(0.0) / self.weight).sqrt();
+        (self.mean + self.k * std, self.mean, self.mean - self.k * std)
+    }
+
+    pub fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    pub fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> Self {
+        self.clone()
+    }
+
+    pub fn reset(&mut self) {
+        self.buffer.clear();
+        self.weight = 0.0;
+        self.mean = 0.0;
+        self.m2 = 0.0;
+    }
+}
+
 // ============================================================================
 // VWEMA (Volume Weighted EMA)
 // ============================================================================
 #[pyclass]
+#[derive(Clone)]
 pub struct VWEMAStreaming {
     vwap_stream: VWAPStreaming,
     ema_stream: EMAStreaming,
@@ -496,6 +710,14 @@ impl VWEMAStreaming {
         }
     }
 
+    pub fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    pub fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> Self {
+        self.clone()
+    }
+
     pub fn reset(&mut self) {
         self.vwap_stream.reset();
         self.ema_stream.reset();
@@ -506,6 +728,7 @@ impl VWEMAStreaming {
 // Volume Ratio: volume / SMA(volume, window)
 // ============================================================================
 #[pyclass]
+#[derive(Clone)]
 pub struct VolumeRatioStreaming {
     sma: SMAStreaming,
     #[allow(dead_code)]
@@ -535,8 +758,127 @@ impl VolumeRatioStreaming {
         }
     }
 
+    pub fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    pub fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> Self {
+        self.clone()
+    }
+
     pub fn reset(&mut self) {
         self.sma.reset();
         self.update_count = 0;
     }
 }
+
+// ============================================================================
+// Volume Z-Score
+// ============================================================================
+#[pyclass]
+#[derive(Clone)]
+pub struct VolumeZScoreStreaming {
+    zscore: RollingZScoreStreaming,
+}
+
+#[pymethods]
+impl VolumeZScoreStreaming {
+    #[new]
+    #[pyo3(signature = (window=20))]
+    pub fn new(window: usize) -> Self {
+        Self {
+            zscore: RollingZScoreStreaming::new(window),
+        }
+    }
+
+    pub fn update(&mut self, volume: f64) -> f64 {
+        self.zscore.update(volume)
+    }
+
+    pub fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    pub fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> Self {
+        self.clone()
+    }
+
+    pub fn reset(&mut self) {
+        self.zscore.reset();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reset_session_restarts_cumulative_lines() {
+        let mut obv = OBVStreaming::new();
+        let mut vpt = VPTStreaming::new();
+        let mut nvi = NVIStreaming::new();
+        let mut ad = AccDistStreaming::new();
+
+        for (close, volume) in [(10.0, 100.0), (11.0, 80.0), (12.0, 60.0)] {
+            obv.update(close, volume);
+            vpt.update(close, volume);
+            nvi.update(close, volume);
+            ad.update(close + 1.0, close - 1.0, close + 0.5, volume);
+        }
+
+        obv.reset_session(true);
+        vpt.reset_session(true);
+        nvi.reset_session(true);
+        ad.reset_session();
+
+        // Next bar accumulates from the base, measured against the kept prev close
+        assert_eq!(obv.update(13.0, 50.0), 50.0);
+        assert!((vpt.update(13.2, 50.0) - 50.0 * 0.1).abs() < 1e-12);
+        assert!((nvi.update(13.2, 40.0) - 1100.0).abs() < 1e-9);
+        assert_eq!(ad.update(14.0, 12.0, 13.0, 20.0), 0.0);
+
+        // Without keep_prev, the first bar after the reset seeds the line again
+        obv.reset_session(false);
+        assert_eq!(obv.update(9.0, 70.0), 70.0);
+    }
+
+    #[test]
+    fn test_volume_zscore_streaming_matches_bulk() {
+        let mut volume: Vec<f64> = (0..40).map(|i| 1000.0 + ((i * 7) % 5) as f64 * 20.0).collect();
+        volume[30] *= 5.0;
+        let expected = crate::others::rolling_zscore_kernel(&volume, 20, false);
+        let mut stream = VolumeZScoreStreaming::new(20);
+
+        for i in 0..volume.len() {
+            let z = stream.update(volume[i]);
+            if expected[i].is_nan() {
+                assert!(z.is_nan());
+            } else {
+                assert!((z - expected[i]).abs() < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn test_vwap_bands_streaming_matches_bulk() {
+        let len = 80;
+        let close: Vec<f64> = (0..len).map(|i| 100.0 + (i as f64 * 0.3).sin() * 4.0 + i as f64 * 0.05).collect();
+        let high: Vec<f64> = close.iter().map(|c| c + 1.0).collect();
+        let low: Vec<f64> = close.iter().map(|c| c - 1.5).collect();
+        let volume: Vec<f64> = (0..len).map(|i| 1000.0 + ((i * 37) % 11) as f64 * 150.0).collect();
+
+        let (upper, middle, lower) = crate::volume::vwap_bands_kernel(&high, &low, &close, &volume, 20, 2.0);
+        let mut stream = VWAPBandsStreaming::new(20, 2.0);
+
+        for i in 0..len {
+            let (u, m, l) = stream.update(high[i], low[i], close[i], volume[i]);
+            if i < 19 {
+                assert!(u.is_nan() && m.is_nan() && l.is_nan());
+            } else {
+                assert!((m - middle[i]).abs() < 1e-9);
+                assert!((u - upper[i]).abs() < 1e-9);
+                assert!((l - lower[i]).abs() < 1e-9);
+            }
+        }
+    }
+}
@@ -1,26 +1,37 @@
-/// Trend indicators: SMA, EMA, MACD, ADX, CCI, DPO, Vortex, Parabolic SAR
+//! Trend indicators: SMA, EMA, Multi-period SMA/EMA/Std, GMMA Compression, MACD, MA Distance, Price Oscillator, MA Cross Signal, Disparity Index, ADX, ADXR, CCI, DPO, Vortex, Parabolic SAR,
+//! Vertical Horizontal Filter, Signal Line, TRIX of Series, Weighted ROC
 
-use numpy::{PyArray1, PyReadonlyArray1};
+use numpy::ndarray::Array2;
+use numpy::{PyArray1, PyArray2, PyReadonlyArray1};
+use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
-use crate::helpers::{sma_kernel, sma_kernel_nan_aware, ema_kernel, ema_kernel_nan_aware, wilders_ema_kernel, true_range, rolling_sum};
+use pyo3::types::PyTuple;
+use crate::helpers::{self, mask_warmup, sma_kernel, sma_kernel_nan_aware, ema_kernel, ema_kernel_nan_aware, wilders_ema_kernel, true_range, rolling_sum, rolling_max, rolling_min, histogram_kernel, FloatArray};
 
 /// Simple Moving Average
 ///
 /// # Arguments
 /// * `data` - Input price series
 /// * `n` - Period for moving average
+/// * `expanding` - Average over all data up to each index instead of the last n;
+///   n still acts as the minimum number of periods (default: false)
 ///
 /// # Returns
 /// Numpy array with SMA values (NaN for first n-1 elements)
 #[pyfunction]
-#[pyo3(name = "sma_numba", signature = (data, n=20))]
+#[pyo3(name = "sma_numba", signature = (data, n=20, expanding=false))]
 pub fn sma<'py>(
     py: Python<'py>,
     data: PyReadonlyArray1<'py, f64>,
     n: usize,
+    expanding: bool,
 ) -> PyResult<Bound<'py, PyArray1<f64>>> {
     let data_slice = data.as_slice()?;
-    let result = sma_kernel(data_slice, n);
+    let result = if expanding {
+        helpers::expanding_mean_kernel(data_slice, n)
+    } else {
+        sma_kernel(data_slice, n)
+    };
     Ok(PyArray1::from_vec(py, result))
 }
 
@@ -47,6 +58,209 @@ pub fn ema<'py>(
     Ok(PyArray1::from_vec(py, result))
 }
 
+fn validate_periods(periods: &[usize]) -> Result<(), String> {
+    if periods.is_empty() {
+        return Err("periods must not be empty".to_string());
+    }
+    if periods.contains(&0) {
+        return Err("periods must be positive".to_string());
+    }
+    Ok(())
+}
+
+/// Multi-period SMA kernel: one prefix-sum pass shared by every window.
+/// Returns a row-major (len × periods.len()) buffer.
+pub fn sma_multi_kernel(data: &[f64], periods: &[usize]) -> Result<Vec<f64>, String> {
+    validate_periods(periods)?;
+    let len = data.len();
+    let cols = periods.len();
+    let mut result = vec![f64::NAN; len * cols];
+
+    let prefix = helpers::PrefixSum::new(data);
+
+    for (j, &window) in periods.iter().enumerate() {
+        for i in (window - 1).min(len)..len {
+            result[i * cols + j] = prefix.mean(i + 1 - window, i + 1);
+        }
+    }
+
+    Ok(result)
+}
+
+/// Multi-period population std kernel: window variances from the same shared
+/// prefix sums as `sma_multi_kernel`. Returns a row-major (len × periods.len()) buffer.
+pub fn std_multi_kernel(data: &[f64], periods: &[usize]) -> Result<Vec<f64>, String> {
+    validate_periods(periods)?;
+    let len = data.len();
+    let cols = periods.len();
+    let mut result = vec![f64::NAN; len * cols];
+
+    let prefix = helpers::PrefixSum::new(data);
+
+    for (j, &window) in periods.iter().enumerate() {
+        for i in (window - 1).min(len)..len {
+            result[i * cols + j] = prefix.variance(i + 1 - window, i + 1).sqrt();
+        }
+    }
+
+    Ok(result)
+}
+
+/// Multi-period EMA kernel: a single pass over the data updating every EMA.
+/// Returns a row-major (len × periods.len()) buffer.
+pub fn ema_multi_kernel(data: &[f64], periods: &[usize], adjusted: bool) -> Result<Vec<f64>, String> {
+    validate_periods(periods)?;
+    let len = data.len();
+    let cols = periods.len();
+    let mut result = vec![f64::NAN; len * cols];
+
+    if len == 0 {
+        return Ok(result);
+    }
+
+    let decay: Vec<f64> = periods.iter().map(|&p| 1.0 - 2.0 / (p as f64 + 1.0)).collect();
+    let mut state: Vec<f64> = vec![data[0]; cols];
+    let mut divisor = vec![1.0; cols];
+    result[..cols].copy_from_slice(&state);
+
+    for i in 1..len {
+        let row = &mut result[i * cols..(i + 1) * cols];
+        for j in 0..cols {
+            if adjusted {
+                // state holds the weighted sum, as in `ema_kernel`
+                state[j] = state[j] * decay[j] + data[i];
+                divisor[j] = divisor[j] * decay[j] + 1.0;
+                row[j] = state[j] / divisor[j];
+            } else {
+                state[j] = (1.0 - decay[j]) * data[i] + decay[j] * state[j];
+                row[j] = state[j];
+            }
+        }
+    }
+
+    Ok(result)
+}
+
+fn to_pyarray2<'py>(py: Python<'py>, flat: Vec<f64>, rows: usize, cols: usize) -> PyResult<Bound<'py, PyArray2<f64>>> {
+    let array = Array2::from_shape_vec((rows, cols), flat)
+        .map_err(|e| PyValueError::new_err(e.to_string()))?;
+    Ok(PyArray2::from_owned_array(py, array))
+}
+
+/// Simple Moving Average over several periods at once
+///
+/// # Arguments
+/// * `data` - Input price series
+/// * `periods` - List of SMA windows
+///
+/// # Returns
+/// 2D numpy array of shape (len, len(periods)); column j is `sma_numba(data, periods[j])`
+#[pyfunction]
+#[pyo3(name = "sma_multi_numba", signature = (data, periods))]
+pub fn sma_multi<'py>(
+    py: Python<'py>,
+    data: PyReadonlyArray1<'py, f64>,
+    periods: Vec<usize>,
+) -> PyResult<Bound<'py, PyArray2<f64>>> {
+    let data_slice = data.as_slice()?;
+    let result = sma_multi_kernel(data_slice, &periods).map_err(PyValueError::new_err)?;
+    to_pyarray2(py, result, data_slice.len(), periods.len())
+}
+
+/// Population Standard Deviation over several periods at once
+///
+/// Computed from prefix sums, so values carry the cancellation error of
+/// E[x²] - E[x]² on series whose mean is large relative to their spread.
+///
+/// # Arguments
+/// * `data` - Input price series
+/// * `periods` - List of rolling windows
+///
+/// # Returns
+/// 2D numpy array of shape (len, len(periods)); column j matches
+/// `standard_deviation_numba(data, periods[j])` to within that error
+#[pyfunction]
+#[pyo3(name = "std_multi_numba", signature = (data, periods))]
+pub fn std_multi<'py>(
+    py: Python<'py>,
+    data: PyReadonlyArray1<'py, f64>,
+    periods: Vec<usize>,
+) -> PyResult<Bound<'py, PyArray2<f64>>> {
+    let data_slice = data.as_slice()?;
+    let result = std_multi_kernel(data_slice, &periods).map_err(PyValueError::new_err)?;
+    to_pyarray2(py, result, data_slice.len(), periods.len())
+}
+
+/// Exponential Moving Average over several periods at once
+///
+/// # Arguments
+/// * `data` - Input price series
+/// * `periods` - List of EMA periods
+/// * `adjusted` - Use pandas-style adjusted EMA (default: true)
+///
+/// # Returns
+/// 2D numpy array of shape (len, len(periods)); column j is `ema_numba(data, periods[j], adjusted)`
+#[pyfunction]
+#[pyo3(name = "ema_multi_numba", signature = (data, periods, adjusted=true))]
+pub fn ema_multi<'py>(
+    py: Python<'py>,
+    data: PyReadonlyArray1<'py, f64>,
+    periods: Vec<usize>,
+    adjusted: bool,
+) -> PyResult<Bound<'py, PyArray2<f64>>> {
+    let data_slice = data.as_slice()?;
+    let result = ema_multi_kernel(data_slice, &periods, adjusted).map_err(PyValueError::new_err)?;
+    to_pyarray2(py, result, data_slice.len(), periods.len())
+}
+
+/// GMMA compression kernel: spread of the long-term Guppy EMAs relative to close.
+/// The long-term ribbon comes from one `ema_multi_kernel` pass; NaN until the
+/// longest EMA has warmed up.
+pub fn gmma_compression_kernel(close: &[f64], long_periods: &[usize], adjusted: bool) -> Result<Vec<f64>, String> {
+    let ribbon = ema_multi_kernel(close, long_periods, adjusted)?;
+
+    let cols = long_periods.len();
+    let mut result = vec![f64::NAN; close.len()];
+    for i in 0..close.len() {
+        let long = &ribbon[i * cols..(i + 1) * cols];
+        let max_long = long.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        let min_long = long.iter().copied().fold(f64::INFINITY, f64::min);
+        if close[i] != 0.0 {
+            result[i] = (max_long - min_long) / close[i];
+        }
+    }
+
+    let warmup = long_periods.iter().copied().max().unwrap_or(1) - 1;
+    mask_warmup(&mut result, warmup);
+    Ok(result)
+}
+
+/// GMMA Compression (Guppy ribbon compression)
+///
+/// # Arguments
+/// * `close` - Close price series
+/// * `long_periods` - Long-term (investor) EMA periods (default: [30, 35, 40, 45, 50, 60])
+/// * `adjusted` - Use pandas-style adjusted EMA (default: false)
+///
+/// # Returns
+/// Numpy array with `(max_long - min_long) / close`; small values mean the long
+/// EMAs are bunched together (NaN for the first max(long_periods)-1 elements)
+#[pyfunction]
+#[pyo3(
+    name = "gmma_compression_numba",
+    signature = (close, long_periods=vec![30, 35, 40, 45, 50, 60], adjusted=false)
+)]
+pub fn gmma_compression<'py>(
+    py: Python<'py>,
+    close: PyReadonlyArray1<'py, f64>,
+    long_periods: Vec<usize>,
+    adjusted: bool,
+) -> PyResult<Bound<'py, PyArray1<f64>>> {
+    let result = gmma_compression_kernel(close.as_slice()?, &long_periods, adjusted)
+        .map_err(PyValueError::new_err)?;
+    Ok(PyArray1::from_vec(py, result))
+}
+
 /// Weighted Moving Average
 ///
 /// # Arguments
@@ -63,26 +277,7 @@ pub fn wma<'py>(
     n: usize,
 ) -> PyResult<Bound<'py, PyArray1<f64>>> {
     let data_slice = data.as_slice()?;
-    let len = data_slice.len();
-    let mut result = vec![f64::NAN; len];
-
-    if len < n {
-        return Ok(PyArray1::from_vec(py, result));
-    }
-
-    let weights: Vec<f64> = (1..=n).map(|i| i as f64).collect();
-    let weight_sum: f64 = weights.iter().sum();
-
-    for i in (n - 1)..len {
-        let window_start = i + 1 - n;
-        let weighted_sum: f64 = data_slice[window_start..=i]
-            .iter()
-            .zip(&weights)
-            .map(|(price, weight)| price * weight)
-            .sum();
-        result[i] = weighted_sum / weight_sum;
-    }
-
+    let result = helpers::wma_kernel(data_slice, n);
     Ok(PyArray1::from_vec(py, result))
 }
 
@@ -140,28 +335,217 @@ pub fn macd<'py>(
     ))
 }
 
-/// ADX - Average Directional Index
+/// MA distance kernel: fast_ma - slow_ma, or 100*(fast-slow)/slow when `percent` is set
+pub fn ma_distance_kernel(
+    close: &[f64],
+    fast: usize,
+    slow: usize,
+    ma_type: &str,
+    adjusted: bool,
+    percent: bool,
+) -> Result<Vec<f64>, String> {
+    let fast_ma = helpers::moving_average_kernel(close, fast, ma_type, adjusted)?;
+    let slow_ma = helpers::moving_average_kernel(close, slow, ma_type, adjusted)?;
+
+    let mut result = vec![f64::NAN; close.len()];
+    for i in 0..close.len() {
+        if fast_ma[i].is_nan() || slow_ma[i].is_nan() {
+            continue;
+        }
+        let diff = fast_ma[i] - slow_ma[i];
+        result[i] = if !percent {
+            diff
+        } else if slow_ma[i] != 0.0 {
+            100.0 * diff / slow_ma[i]
+        } else {
+            f64::NAN
+        };
+    }
+
+    Ok(result)
+}
+
+/// MA Distance Oscillator
+///
+/// Generalizes the MACD line to arbitrary moving average types and periods.
 ///
 /// # Arguments
-/// * `high` - High price series
-/// * `low` - Low price series
 /// * `close` - Close price series
-/// * `n` - ADX period (default: 14)
+/// * `fast` - Fast MA period (default: 20)
+/// * `slow` - Slow MA period (default: 50)
+/// * `ma_type` - Moving average type: "sma", "ema" or "wma" (default: "ema")
+/// * `percent` - Return 100*(fast-slow)/slow instead of the raw difference (default: false)
+/// * `adjusted` - Use adjusted EMA when ma_type is "ema" (default: false)
 ///
 /// # Returns
-/// Tuple of (ADX, +DI, -DI) as numpy arrays
+/// Numpy array with MA distance values
 #[pyfunction]
-#[pyo3(name = "adx_numba", signature = (high, low, close, n=14))]
-pub fn adx<'py>(
+#[pyo3(name = "ma_distance_numba", signature = (close, fast=20, slow=50, ma_type="ema", percent=false, adjusted=false))]
+pub fn ma_distance<'py>(
+    py: Python<'py>,
+    close: PyReadonlyArray1<'py, f64>,
+    fast: usize,
+    slow: usize,
+    ma_type: &str,
+    percent: bool,
+    adjusted: bool,
+) -> PyResult<Bound<'py, PyArray1<f64>>> {
+    let close_slice = close.as_slice()?;
+    let result = ma_distance_kernel(close_slice, fast, slow, ma_type, adjusted, percent)
+        .map_err(PyValueError::new_err)?;
+    Ok(PyArray1::from_vec(py, result))
+}
+
+/// Price Oscillator (generalized APO / PPO)
+///
+/// # Arguments
+/// * `close` - Close price series
+/// * `fast` - Fast MA period (default: 10)
+/// * `slow` - Slow MA period (default: 20)
+/// * `ma_type` - Moving average type: "sma", "ema" or "wma" (default: "sma")
+/// * `pct` - Return 100*(fast-slow)/slow instead of the raw difference (default: false)
+///
+/// # Returns
+/// Numpy array with price oscillator values; `pct=True, ma_type="ema"` with
+/// fast=12, slow=26 is the PPO line
+#[pyfunction]
+#[pyo3(name = "price_oscillator_numba", signature = (close, fast=10, slow=20, ma_type="sma", pct=false))]
+pub fn price_oscillator<'py>(
+    py: Python<'py>,
+    close: PyReadonlyArray1<'py, f64>,
+    fast: usize,
+    slow: usize,
+    ma_type: &str,
+    pct: bool,
+) -> PyResult<Bound<'py, PyArray1<f64>>> {
+    let close_slice = close.as_slice()?;
+    let result = ma_distance_kernel(close_slice, fast, slow, ma_type, false, pct)
+        .map_err(PyValueError::new_err)?;
+    Ok(PyArray1::from_vec(py, result))
+}
+
+/// MA cross position kernel: +1 from a golden cross (fast MA crossing above the
+/// slow MA) and -1 from a death cross, held until the opposite cross. 0 before the
+/// first cross and NaN while either MA is warming up.
+pub fn ma_cross_signal_kernel(close: &[f64], fast: usize, slow: usize, ma_type: &str) -> Result<Vec<f64>, String> {
+    let fast_ma = helpers::moving_average_kernel(close, fast, ma_type, false)?;
+    let slow_ma = helpers::moving_average_kernel(close, slow, ma_type, false)?;
+    let crosses = helpers::crossover_kernel(&fast_ma, &slow_ma);
+
+    let mut position = vec![f64::NAN; close.len()];
+    let mut held = 0.0;
+    for i in 0..close.len() {
+        if crosses[i] != 0.0 {
+            held = crosses[i];
+        }
+        if !fast_ma[i].is_nan() && !slow_ma[i].is_nan() {
+            position[i] = held;
+        }
+    }
+
+    Ok(position)
+}
+
+/// MA Cross Signal
+///
+/// # Arguments
+/// * `close` - Close price series
+/// * `fast` - Fast MA period (default: 50)
+/// * `slow` - Slow MA period (default: 200)
+/// * `ma_type` - Moving average type: "sma", "ema" or "wma" (default: "sma")
+///
+/// # Returns
+/// Numpy array with the held position: 1.0 after a golden cross, -1.0 after a
+/// death cross, 0.0 before the first cross
+#[pyfunction]
+#[pyo3(name = "ma_cross_signal_numba", signature = (close, fast=50, slow=200, ma_type="sma"))]
+pub fn ma_cross_signal<'py>(
+    py: Python<'py>,
+    close: PyReadonlyArray1<'py, f64>,
+    fast: usize,
+    slow: usize,
+    ma_type: &str,
+) -> PyResult<Bound<'py, PyArray1<f64>>> {
+    let close_slice = close.as_slice()?;
+    let result = ma_cross_signal_kernel(close_slice, fast, slow, ma_type).map_err(PyValueError::new_err)?;
+    Ok(PyArray1::from_vec(py, result))
+}
+
+/// Disparity index kernel: 100 * (close - ma) / ma over the `ma_type` MA (unadjusted
+/// EMA). NaN for the first n-1 elements for every MA type, and where the MA is zero.
+pub fn disparity_index_kernel(close: &[f64], n: usize, ma_type: &str) -> Result<Vec<f64>, String> {
+    let ma = helpers::moving_average_kernel(close, n, ma_type, false)?;
+
+    let mut result: Vec<f64> = close
+        .iter()
+        .zip(&ma)
+        .map(|(&c, &m)| if m != 0.0 { 100.0 * (c - m) / m } else { f64::NAN })
+        .collect();
+    mask_warmup(&mut result, n.saturating_sub(1));
+
+    Ok(result)
+}
+
+/// Disparity Index
+///
+/// # Arguments
+/// * `close` - Close price series
+/// * `n` - Moving average period (default: 14)
+/// * `ma_type` - Moving average type: "sma", "ema" or "wma" (default: "ema")
+///
+/// # Returns
+/// Numpy array with the percent distance of close from its moving average
+#[pyfunction]
+#[pyo3(name = "disparity_index_numba", signature = (close, n=14, ma_type="ema"))]
+pub fn disparity_index<'py>(
     py: Python<'py>,
-    high: PyReadonlyArray1<'py, f64>,
-    low: PyReadonlyArray1<'py, f64>,
     close: PyReadonlyArray1<'py, f64>,
     n: usize,
-) -> PyResult<(Bound<'py, PyArray1<f64>>, Bound<'py, PyArray1<f64>>, Bound<'py, PyArray1<f64>>)> {
-    let high_slice = high.as_slice()?;
-    let low_slice = low.as_slice()?;
+    ma_type: &str,
+) -> PyResult<Bound<'py, PyArray1<f64>>> {
     let close_slice = close.as_slice()?;
+    let result = disparity_index_kernel(close_slice, n, ma_type).map_err(PyValueError::new_err)?;
+    Ok(PyArray1::from_vec(py, result))
+}
+
+/// Signal line kernel: NaN-aware smoothing of an arbitrary series
+pub fn signal_line_kernel(data: &[f64], n: usize, ma_type: &str, adjusted: bool) -> Result<Vec<f64>, String> {
+    match ma_type {
+        "sma" => Ok(sma_kernel_nan_aware(data, n)),
+        "ema" => Ok(ema_kernel_nan_aware(data, 2.0 / (n as f64 + 1.0), adjusted)),
+        "wma" => Ok(helpers::wma_kernel(data, n)),
+        other => Err(format!("unknown ma_type '{}', expected one of: sma, ema, wma", other)),
+    }
+}
+
+/// Signal Line
+///
+/// Smooths any indicator output (e.g. a custom oscillator) into a signal line.
+///
+/// # Arguments
+/// * `data` - Input series, may contain leading NaN
+/// * `n` - Smoothing period (default: 9)
+/// * `ma_type` - Moving average type: "sma", "ema" or "wma" (default: "ema")
+/// * `adjusted` - Use adjusted EMA when ma_type is "ema" (default: true)
+///
+/// # Returns
+/// Numpy array with signal line values
+#[pyfunction]
+#[pyo3(name = "signal_line_numba", signature = (data, n=9, ma_type="ema", adjusted=true))]
+pub fn signal_line<'py>(
+    py: Python<'py>,
+    data: PyReadonlyArray1<'py, f64>,
+    n: usize,
+    ma_type: &str,
+    adjusted: bool,
+) -> PyResult<Bound<'py, PyArray1<f64>>> {
+    let data_slice = data.as_slice()?;
+    let result = signal_line_kernel(data_slice, n, ma_type, adjusted).map_err(PyValueError::new_err)?;
+    Ok(PyArray1::from_vec(py, result))
+}
+
+/// ADX kernel returning (ADX, +DI, -DI)
+pub fn adx_kernel(high_slice: &[f64], low_slice: &[f64], close_slice: &[f64], n: usize) -> (Vec<f64>, Vec<f64>, Vec<f64>) {
     let len = high_slice.len();
 
     let mut plus_dm = vec![0.0; len];
@@ -207,6 +591,136 @@ pub fn adx<'py>(
 
     let adx_values = wilders_ema_kernel(&dx, n);
 
+    (adx_values, plus_di, minus_di)
+}
+
+/// ADX as computed by `ta.trend.ADXIndicator` with `fillna=False`. The Wilder
+/// sums are seeded with the totals over bars 1..=n and updated through the
+/// second-to-last bar. ADX starts at 2n-1 as the mean of the first n DX values,
+/// and +DI/-DI start at n+1. Warmup values are 0.0, not NaN, as in `ta`.
+pub fn adx_ta_kernel(high: &[f64], low: &[f64], close: &[f64], n: usize) -> (Vec<f64>, Vec<f64>, Vec<f64>) {
+    let len = close.len();
+    let mut adx_values = vec![0.0; len];
+    let mut plus_di = vec![0.0; len];
+    let mut minus_di = vec![0.0; len];
+
+    if n == 0 || len < n + 1 {
+        return (adx_values, plus_di, minus_di);
+    }
+
+    let mut tr = vec![0.0; len];
+    let mut pos = vec![0.0; len];
+    let mut neg = vec![0.0; len];
+    for i in 1..len {
+        tr[i] = high[i].max(close[i - 1]) - low[i].min(close[i - 1]);
+        let diff_up = high[i] - high[i - 1];
+        let diff_down = low[i - 1] - low[i];
+        if diff_up > diff_down && diff_up > 0.0 {
+            pos[i] = diff_up;
+        }
+        if diff_down > diff_up && diff_down > 0.0 {
+            neg[i] = diff_down;
+        }
+    }
+
+    // One slot per bar from n-1; `ta` leaves the last slot at zero
+    let slots = len - n + 1;
+    let wilder = |values: &[f64]| {
+        let mut sums = vec![0.0; slots];
+        sums[0] = values[1..=n].iter().sum::<f64>();
+        for i in 1..slots - 1 {
+            sums[i] = sums[i - 1] - sums[i - 1] / n as f64 + values[n + i];
+        }
+        sums
+    };
+    let trs = wilder(&tr);
+    let dip = wilder(&pos);
+    let din = wilder(&neg);
+
+    let dx: Vec<f64> = (0..slots)
+        .map(|i| {
+            let p = 100.0 * (dip[i] / trs[i]);
+            let m = 100.0 * (din[i] / trs[i]);
+            100.0 * ((p - m) / (p + m)).abs()
+        })
+        .collect();
+
+    for i in 1..slots - 1 {
+        plus_di[i + n] = 100.0 * (dip[i] / trs[i]);
+        minus_di[i + n] = 100.0 * (din[i] / trs[i]);
+    }
+
+    if slots > n {
+        let mut adx = dx[..n].iter().sum::<f64>() / n as f64;
+        adx_values[2 * n - 1] = adx;
+        for i in (n + 1)..slots {
+            adx = (adx * (n - 1) as f64 + dx[i - 1]) / n as f64;
+            adx_values[i + n - 1] = adx;
+        }
+    }
+
+    (adx_values, plus_di, minus_di)
+}
+
+/// `adx_kernel`, or `adx_ta_kernel` when `compat` is `Ta` (see `adx_numba`)
+pub fn adx_compat_kernel(
+    high: &[f64],
+    low: &[f64],
+    close: &[f64],
+    n: usize,
+    compat: helpers::Compat,
+) -> (Vec<f64>, Vec<f64>, Vec<f64>) {
+    match compat {
+        helpers::Compat::Native => adx_kernel(high, low, close, n),
+        helpers::Compat::Ta => adx_ta_kernel(high, low, close, n),
+    }
+}
+
+/// ADXR kernel: (adx[i] + adx[i-n]) / 2, NaN until adx[i-n] exists
+pub fn adxr_kernel(adx_values: &[f64], n: usize) -> Vec<f64> {
+    let len = adx_values.len();
+    let mut adxr = vec![f64::NAN; len];
+
+    for i in n..len {
+        if !adx_values[i].is_nan() && !adx_values[i - n].is_nan() {
+            adxr[i] = (adx_values[i] + adx_values[i - n]) / 2.0;
+        }
+    }
+
+    adxr
+}
+
+/// ADX - Average Directional Index
+///
+/// # Arguments
+/// * `high` - High price series
+/// * `low` - Low price series
+/// * `close` - Close price series
+/// * `n` - ADX period (default: 14)
+/// * `compat` - "native" or "ta" (default: "native").
+///   "native": +DI/-DI start at index n-1 and ADX at 2n-2, NaN before.
+///   "ta": reproduces `ta.trend.ADXIndicator(fillna=False)`. Its Wilder sums
+///   start at bar 1, +DI/-DI start at n+1 and ADX at 2n-1, and the warmup
+///   is 0.0 rather than NaN.
+///
+/// # Returns
+/// Tuple of (ADX, +DI, -DI) as numpy arrays
+#[pyfunction]
+#[pyo3(name = "adx_numba", signature = (high, low, close, n=14, compat="native"))]
+pub fn adx<'py>(
+    py: Python<'py>,
+    high: PyReadonlyArray1<'py, f64>,
+    low: PyReadonlyArray1<'py, f64>,
+    close: PyReadonlyArray1<'py, f64>,
+    n: usize,
+    compat: &str,
+) -> PyResult<(Bound<'py, PyArray1<f64>>, Bound<'py, PyArray1<f64>>, Bound<'py, PyArray1<f64>>)> {
+    let high_slice = high.as_slice()?;
+    let low_slice = low.as_slice()?;
+    let close_slice = close.as_slice()?;
+    let compat = helpers::Compat::parse(compat).map_err(PyValueError::new_err)?;
+    let (adx_values, plus_di, minus_di) = adx_compat_kernel(high_slice, low_slice, close_slice, n, compat);
+
     Ok((
         PyArray1::from_vec(py, adx_values),
         PyArray1::from_vec(py, plus_di),
@@ -214,6 +728,37 @@ pub fn adx<'py>(
     ))
 }
 
+/// ADXR - Average Directional Movement Rating
+///
+/// # Arguments
+/// * `high` - High price series
+/// * `low` - Low price series
+/// * `close` - Close price series
+/// * `n` - ADX period and ADXR lag (default: 14)
+///
+/// # Returns
+/// Tuple of (ADX, ADXR) as numpy arrays
+#[pyfunction]
+#[pyo3(name = "adxr_numba", signature = (high, low, close, n=14))]
+pub fn adxr<'py>(
+    py: Python<'py>,
+    high: PyReadonlyArray1<'py, f64>,
+    low: PyReadonlyArray1<'py, f64>,
+    close: PyReadonlyArray1<'py, f64>,
+    n: usize,
+) -> PyResult<(Bound<'py, PyArray1<f64>>, Bound<'py, PyArray1<f64>>)> {
+    let high_slice = high.as_slice()?;
+    let low_slice = low.as_slice()?;
+    let close_slice = close.as_slice()?;
+    let (adx_values, _, _) = adx_kernel(high_slice, low_slice, close_slice, n);
+    let adxr_values = adxr_kernel(&adx_values, n);
+
+    Ok((
+        PyArray1::from_vec(py, adx_values),
+        PyArray1::from_vec(py, adxr_values),
+    ))
+}
+
 /// CCI - Commodity Channel Index
 ///
 /// # Arguments
@@ -222,11 +767,13 @@ pub fn adx<'py>(
 /// * `close` - Close price series
 /// * `n` - CCI period (default: 20)
 /// * `constant` - Scaling constant (default: 0.015)
+/// * `price_source` - "hlc3" (default), "ohlc4", "hl2" or "close"
+/// * `open` - Open price series, required for "ohlc4"
 ///
 /// # Returns
 /// Numpy array with CCI values
 #[pyfunction]
-#[pyo3(name = "cci_numba", signature = (high, low, close, n=20, c=0.015))]
+#[pyo3(name = "cci_numba", signature = (high, low, close, n=20, c=0.015, price_source="hlc3", open=None))]
 pub fn cci<'py>(
     py: Python<'py>,
     high: PyReadonlyArray1<'py, f64>,
@@ -234,16 +781,17 @@ pub fn cci<'py>(
     close: PyReadonlyArray1<'py, f64>,
     n: usize,
     c: f64,
+    price_source: &str,
+    open: Option<PyReadonlyArray1<'py, f64>>,
 ) -> PyResult<Bound<'py, PyArray1<f64>>> {
     let high_slice = high.as_slice()?;
     let low_slice = low.as_slice()?;
     let close_slice = close.as_slice()?;
+    let open_slice = open.as_ref().map(|a| a.as_slice()).transpose()?;
     let len = high_slice.len();
 
-    let mut typical_price = vec![0.0; len];
-    for i in 0..len {
-        typical_price[i] = (high_slice[i] + low_slice[i] + close_slice[i]) / 3.0;
-    }
+    let typical_price = helpers::price_source(price_source, open_slice, high_slice, low_slice, close_slice)
+        .map_err(PyValueError::new_err)?;
 
     let sma_tp = sma_kernel(&typical_price, n);
 
@@ -264,36 +812,46 @@ pub fn cci<'py>(
     Ok(PyArray1::from_vec(py, cci_values))
 }
 
+/// DPO kernel: displaced close minus the SMA. With `pct`, the difference is
+/// expressed as a percentage of the SMA.
+pub fn dpo_kernel(close: &[f64], n: usize, pct: bool) -> Vec<f64> {
+    let len = close.len();
+
+    let sma_values = sma_kernel(close, n);
+    let shift = n / 2 + 1;
+
+    let mut dpo_values = vec![f64::NAN; len];
+    // DPO[i] = close[i - displacement] - sma[i]
+    for i in shift..len {
+        if !sma_values[i].is_nan() {
+            let diff = close[i - shift] - sma_values[i];
+            dpo_values[i] = if pct { diff / sma_values[i] * 100.0 } else { diff };
+        }
+    }
+
+    dpo_values
+}
+
 /// DPO - Detrended Price Oscillator
 ///
 /// # Arguments
 /// * `close` - Close price series
 /// * `n` - DPO period (default: 20)
+/// * `pct` - Return the difference as a percentage of the SMA (default: false)
 ///
 /// # Returns
 /// Numpy array with DPO values
 #[pyfunction]
-#[pyo3(name = "dpo_numba", signature = (close, n=20))]
+#[pyo3(name = "dpo_numba", signature = (close, n=20, pct=false))]
 pub fn dpo<'py>(
     py: Python<'py>,
     close: PyReadonlyArray1<'py, f64>,
     n: usize,
+    pct: bool,
 ) -> PyResult<Bound<'py, PyArray1<f64>>> {
     let close_slice = close.as_slice()?;
-    let len = close_slice.len();
-
-    let sma_values = sma_kernel(close_slice, n);
-    let shift = n / 2 + 1;
-
-    let mut dpo_values = vec![f64::NAN; len];
-    // DPO[i] = close[i - displacement] - sma[i]
-    for i in shift..len {
-        if !sma_values[i].is_nan() {
-            dpo_values[i] = close_slice[i - shift] - sma_values[i];
-        }
-    }
-
-    Ok(PyArray1::from_vec(py, dpo_values))
+    let result = dpo_kernel(close_slice, n, pct);
+    Ok(PyArray1::from_vec(py, result))
 }
 
 /// Vortex Indicator
@@ -350,37 +908,18 @@ pub fn vortex_indicator<'py>(
     ))
 }
 
-/// Parabolic SAR
-///
-/// # Arguments
-/// * `high` - High price series
-/// * `low` - Low price series
-/// * `close` - Close price series
-/// * `af_start` - Initial acceleration factor (default: 0.02)
-/// * `af_increment` - AF increment per extreme point (default: 0.02)
-/// * `af_max` - Maximum AF (default: 0.2)
-///
-/// # Returns
-/// Numpy array with SAR values
-#[pyfunction]
-#[pyo3(name = "parabolic_sar_numba", signature = (high, low, close, af_start=0.02, af_inc=0.02, af_max=0.2))]
-pub fn parabolic_sar<'py>(
-    py: Python<'py>,
-    high: PyReadonlyArray1<'py, f64>,
-    low: PyReadonlyArray1<'py, f64>,
-    close: PyReadonlyArray1<'py, f64>,
-    af_start: f64,
-    af_inc: f64,
-    af_max: f64,
-) -> PyResult<Bound<'py, PyArray1<f64>>> {
-    let high_slice = high.as_slice()?;
-    let low_slice = low.as_slice()?;
-    let close_slice = close.as_slice()?;
+/// Parabolic SAR kernel with a configurable acceleration-factor schedule
+pub fn parabolic_sar_kernel(
+    high_slice: &[f64],
+    low_slice: &[f64],
+    close_slice: &[f64],
+    schedule: &helpers::AfSchedule,
+) -> Vec<f64> {
     let len = high_slice.len();
 
     if len < 3 {
         // Match Python: sar = close.copy()
-        return Ok(PyArray1::from_vec(py, close_slice.to_vec()));
+        return close_slice.to_vec();
     }
 
     // Initialize with close values like TA library: sar = close.copy()
@@ -388,7 +927,8 @@ pub fn parabolic_sar<'py>(
 
     // Match Python exactly:
     let mut up_trend = true;
-    let mut af = af_start;
+    let mut af = schedule.initial();
+    let mut advances = 0;
     let mut up_trend_high = high_slice[0];
     let mut down_trend_low = low_slice[0];
 
@@ -407,12 +947,14 @@ pub fn parabolic_sar<'py>(
                 reversal = true;
                 sar[i] = up_trend_high;
                 down_trend_low = min_low;
-                af = af_start;
+                af = schedule.initial();
+                advances = 0;
             } else {
                 // No reversal: update EP and AF
                 if max_high > up_trend_high {
                     up_trend_high = max_high;
-                    af = (af + af_inc).min(af_max);
+                    advances += 1;
+                    af = schedule.advance(af, advances);
                 }
 
                 // Apply SAR constraints for uptrend (after EP update)
@@ -433,30 +975,101 @@ pub fn parabolic_sar<'py>(
                 reversal = true;
                 sar[i] = down_trend_low;
                 up_trend_high = max_high;
-                af = af_start;
+                af = schedule.initial();
+                advances = 0;
             } else {
                 // No reversal: update EP and AF
                 if min_low < down_trend_low {
                     down_trend_low = min_low;
-                    af = (af + af_inc).min(af_max);
+                    advances += 1;
+                    af = schedule.advance(af, advances);
+                }
+
+                // Apply SAR constraints for downtrend (after EP update)
+                let high1 = high_slice[i - 1];
+                let high2 = high_slice[i - 2];
+                if high2 > sar[i] {
+                    sar[i] = high2;
+                } else if high1 > sar[i] {
+                    sar[i] = high1;
                 }
+            }
+        }
+
+        // XOR logic: up_trend = up_trend != reversal
+        up_trend = up_trend != reversal;
+    }
+
+    sar
+}
+
+/// Parabolic SAR
+///
+/// # Arguments
+/// * `high` - High price series
+/// * `low` - Low price series
+/// * `close` - Close price series
+/// * `af_start` - Initial acceleration factor (default: 0.02)
+/// * `af_increment` - AF increment per extreme point (default: 0.02)
+/// * `af_max` - Maximum AF (default: 0.2)
+/// * `af_schedule` - Optional ascending list of AFs stepped through per new
+///   extreme point (last value held); overrides af_start/af_inc/af_max
+///
+/// # Returns
+/// Numpy array with SAR values
+#[pyfunction]
+#[pyo3(name = "parabolic_sar_numba", signature = (high, low, close, af_start=0.02, af_inc=0.02, af_max=0.2, af_schedule=None))]
+pub fn parabolic_sar<'py>(
+    py: Python<'py>,
+    high: PyReadonlyArray1<'py, f64>,
+    low: PyReadonlyArray1<'py, f64>,
+    close: PyReadonlyArray1<'py, f64>,
+    af_start: f64,
+    af_inc: f64,
+    af_max: f64,
+    af_schedule: Option<Vec<f64>>,
+) -> PyResult<Bound<'py, PyArray1<f64>>> {
+    let high_slice = high.as_slice()?;
+    let low_slice = low.as_slice()?;
+    let close_slice = close.as_slice()?;
+
+    let schedule = helpers::AfSchedule::new(af_start, af_inc, af_max, af_schedule)
+        .map_err(PyValueError::new_err)?;
+    let sar = parabolic_sar_kernel(high_slice, low_slice, close_slice, &schedule);
+
+    Ok(PyArray1::from_vec(py, sar))
+}
 
-                // Apply SAR constraints for downtrend (after EP update)
-                let high1 = high_slice[i - 1];
-                let high2 = high_slice[i - 2];
-                if high2 > sar[i] {
-                    sar[i] = high2;
-                } else if high1 > sar[i] {
-                    sar[i] = high1;
-                }
-            }
-        }
+/// Triple-smoothed EMA: ema(ema(ema(data))) with alpha = 2/(n+1)
+pub fn triple_ema_kernel(data: &[f64], n: usize, adjusted: bool) -> Vec<f64> {
+    let alpha = 2.0 / (n as f64 + 1.0);
 
-        // XOR logic: up_trend = up_trend != reversal
-        up_trend = up_trend != reversal;
+    let ema1 = ema_kernel(data, alpha, adjusted);
+    let ema2 = ema_kernel(&ema1, alpha, adjusted);
+    ema_kernel(&ema2, alpha, adjusted)
+}
+
+/// TRIX kernel: one-bar percent change of the adjusted triple EMA, first value NaN
+pub fn trix_kernel(data: &[f64], n: usize) -> Vec<f64> {
+    let len = data.len();
+    let ema3 = triple_ema_kernel(data, n, true);
+
+    let mut trix_values = vec![f64::NAN; len];
+    for i in 1..len {
+        if !ema3[i].is_nan() && !ema3[i - 1].is_nan() && ema3[i - 1] != 0.0 {
+            trix_values[i] = ((ema3[i] - ema3[i - 1]) / ema3[i - 1]) * 100.0;
+        }
     }
 
-    Ok(PyArray1::from_vec(py, sar))
+    trix_values
+}
+
+/// TRIX of an arbitrary series with an adjusted EMA signal line.
+/// Returns (trix, signal).
+pub fn trix_of_kernel(data: &[f64], n: usize, n_signal: usize) -> (Vec<f64>, Vec<f64>) {
+    let trix_values = trix_kernel(data, n);
+    let signal = ema_kernel_nan_aware(&trix_values, 2.0 / (n_signal as f64 + 1.0), true);
+    (trix_values, signal)
 }
 
 /// TRIX - Triple Exponential Average
@@ -475,22 +1088,88 @@ pub fn trix<'py>(
     n: usize,
 ) -> PyResult<Bound<'py, PyArray1<f64>>> {
     let close_slice = close.as_slice()?;
-    let len = close_slice.len();
+    let trix_values = trix_kernel(close_slice, n);
+    Ok(PyArray1::from_vec(py, trix_values))
+}
 
-    let alpha = 2.0 / (n as f64 + 1.0);
+/// TRIX of any series (volume, spreads, other indicators)
+///
+/// # Arguments
+/// * `data` - Input series
+/// * `n` - TRIX period (default: 15)
+/// * `n_signal` - Signal line EMA period (default: 9)
+///
+/// # Returns
+/// Tuple of (trix, signal) as numpy arrays
+#[pyfunction]
+#[pyo3(name = "trix_of_numba", signature = (data, n=15, n_signal=9))]
+pub fn trix_of<'py>(
+    py: Python<'py>,
+    data: FloatArray<'py>,
+    n: usize,
+    n_signal: usize,
+) -> PyResult<(Bound<'py, PyArray1<f64>>, Bound<'py, PyArray1<f64>>)> {
+    let data = data.to_f64()?;
+    let data_slice: &[f64] = &data;
+    let (trix_values, signal) = trix_of_kernel(data_slice, n, n_signal);
+
+    Ok((
+        PyArray1::from_vec(py, trix_values),
+        PyArray1::from_vec(py, signal),
+    ))
+}
+
+/// Mass Index kernel: n_sum-bar sum of EMA(range) / EMA(EMA(range))
+pub fn mass_index_kernel(high: &[f64], low: &[f64], n_ema: usize, n_sum: usize) -> Vec<f64> {
+    let len = high.len();
+
+    let mut range = vec![0.0; len];
+    for i in 0..len {
+        range[i] = high[i] - low[i];
+    }
 
-    let ema1 = ema_kernel(close_slice, alpha, true);
+    let alpha = 2.0 / (n_ema as f64 + 1.0);
+    let ema1 = ema_kernel(&range, alpha, true);
     let ema2 = ema_kernel(&ema1, alpha, true);
-    let ema3 = ema_kernel(&ema2, alpha, true);
 
-    let mut trix_values = vec![f64::NAN; len];
-    for i in 1..len {
-        if !ema3[i].is_nan() && !ema3[i - 1].is_nan() && ema3[i - 1] != 0.0 {
-            trix_values[i] = ((ema3[i] - ema3[i - 1]) / ema3[i - 1]) * 100.0;
+    let mut ratio = vec![f64::NAN; len];
+    for i in 0..len {
+        if !ema2[i].is_nan() && ema2[i] != 0.0 {
+            ratio[i] = ema1[i] / ema2[i];
         }
     }
 
-    Ok(PyArray1::from_vec(py, trix_values))
+    let mut mi = vec![f64::NAN; len];
+    if n_sum == 0 || n_sum > len {
+        return mi;
+    }
+    for i in (n_sum - 1)..len {
+        let sum: f64 = ratio[(i + 1 - n_sum)..=i].iter()
+            .filter(|x| !x.is_nan())
+            .sum();
+        mi[i] = sum;
+    }
+
+    mi
+}
+
+/// Reversal bulge kernel: 1.0 on the bar the Mass Index drops below `lower` after
+/// having risen above `upper`, 0.0 otherwise. The bulge stays armed across bars
+/// (including NaN bars) until it fires.
+pub fn mass_index_bulge_kernel(mi: &[f64], upper: f64, lower: f64) -> Vec<f64> {
+    let mut flags = vec![0.0; mi.len()];
+    let mut armed = false;
+
+    for (flag, &value) in flags.iter_mut().zip(mi) {
+        if value > upper {
+            armed = true;
+        } else if armed && value < lower {
+            *flag = 1.0;
+            armed = false;
+        }
+    }
+
+    flags
 }
 
 /// Mass Index
@@ -500,47 +1179,145 @@ pub fn trix<'py>(
 /// * `low` - Low price series
 /// * `n_ema` - EMA period for range (default: 9)
 /// * `n_sum` - Summation period (default: 25)
+/// * `signal` - Also return the reversal bulge flags (default: false)
+/// * `upper` - Bulge threshold the index must rise above (default: 27.0)
+/// * `lower` - Threshold whose downward cross completes the bulge (default: 26.5)
 ///
 /// # Returns
-/// Numpy array with Mass Index values
+/// Numpy array with Mass Index values, or a tuple of (mass_index, bulge) when
+/// `signal` is true, with bulge 1.0 on the bar the reversal bulge completes
 #[pyfunction]
-#[pyo3(name = "mass_index_numba", signature = (high, low, n_ema=9, n_sum=25))]
+#[pyo3(name = "mass_index_numba", signature = (high, low, n_ema=9, n_sum=25, signal=false, upper=27.0, lower=26.5))]
 pub fn mass_index<'py>(
     py: Python<'py>,
     high: PyReadonlyArray1<'py, f64>,
     low: PyReadonlyArray1<'py, f64>,
     n_ema: usize,
     n_sum: usize,
-) -> PyResult<Bound<'py, PyArray1<f64>>> {
+    signal: bool,
+    upper: f64,
+    lower: f64,
+) -> PyResult<Bound<'py, PyAny>> {
     let high_slice = high.as_slice()?;
     let low_slice = low.as_slice()?;
-    let len = high_slice.len();
 
-    let mut range = vec![0.0; len];
-    for i in 0..len {
-        range[i] = high_slice[i] - low_slice[i];
+    let mi = mass_index_kernel(high_slice, low_slice, n_ema, n_sum);
+
+    if signal {
+        let bulge = mass_index_bulge_kernel(&mi, upper, lower);
+        let outputs = [PyArray1::from_vec(py, mi), PyArray1::from_vec(py, bulge)];
+        Ok(PyTuple::new(py, outputs)?.into_any())
+    } else {
+        Ok(PyArray1::from_vec(py, mi).into_any())
     }
+}
 
-    let alpha = 2.0 / (n_ema as f64 + 1.0);
-    let ema1 = ema_kernel(&range, alpha, true);
-    let ema2 = ema_kernel(&ema1, alpha, true);
+/// Weighted sum of SMA-smoothed ROCs, one (roc period, sma length, weight) per
+/// component, scaled by 100. ROCs are raw ratios as in `ta`; a component that is
+/// still warming up counts as 0, and a bar is NaN only while every component is.
+fn weighted_roc_sum(close: &[f64], components: &[(usize, usize, f64)]) -> Vec<f64> {
+    let len = close.len();
+
+    let smoothed: Vec<Vec<f64>> = components
+        .iter()
+        .map(|&(period, smooth, _)| {
+            let mut roc = vec![f64::NAN; len];
+            for i in period..len {
+                if close[i - period] != 0.0 {
+                    roc[i] = (close[i] - close[i - period]) / close[i - period];
+                }
+            }
+            // NaN-aware SMA (requires all values in window to be non-NaN)
+            sma_kernel_nan_aware(&roc, smooth)
+        })
+        .collect();
 
-    let mut ratio = vec![f64::NAN; len];
+    let mut result = vec![f64::NAN; len];
     for i in 0..len {
-        if !ema2[i].is_nan() && ema2[i] != 0.0 {
-            ratio[i] = ema1[i] / ema2[i];
+        if smoothed.iter().all(|rcma| rcma[i].is_nan()) {
+            continue;
         }
+        let weighted: f64 = smoothed
+            .iter()
+            .zip(components)
+            .map(|(rcma, &(_, _, weight))| if rcma[i].is_nan() { 0.0 } else { rcma[i] * weight })
+            .sum();
+        result[i] = weighted * 100.0;
     }
 
-    let mut mi = vec![f64::NAN; len];
-    for i in (n_sum - 1)..len {
-        let sum: f64 = ratio[(i + 1 - n_sum)..=i].iter()
-            .filter(|x| !x.is_nan())
-            .sum();
-        mi[i] = sum;
+    result
+}
+
+/// Weighted multi-ROC kernel: `weighted_roc_sum` over parallel lists of ROC
+/// periods, SMA lengths and weights, which must be non-empty and equally long
+pub fn weighted_roc_kernel(
+    close: &[f64],
+    periods: &[usize],
+    smooths: &[usize],
+    weights: &[f64],
+) -> Result<Vec<f64>, String> {
+    if periods.is_empty() {
+        return Err("periods must not be empty".to_string());
     }
+    helpers::check_equal_lengths(&[
+        ("periods", periods.len()),
+        ("smooths", smooths.len()),
+        ("weights", weights.len()),
+    ])?;
+
+    let components: Vec<(usize, usize, f64)> = periods
+        .iter()
+        .zip(smooths)
+        .zip(weights)
+        .map(|((&p, &s), &w)| (p, s, w))
+        .collect();
+
+    Ok(weighted_roc_sum(close, &components))
+}
+
+/// KST kernel: weighted sum of four SMA-smoothed ROCs plus its SMA signal line
+pub fn kst_kernel(
+    close: &[f64],
+    r1: usize,
+    r2: usize,
+    r3: usize,
+    r4: usize,
+    s1: usize,
+    s2: usize,
+    s3: usize,
+    s4: usize,
+    n_sig: usize,
+) -> (Vec<f64>, Vec<f64>) {
+    let kst_values = weighted_roc_sum(close, &[(r1, s1, 1.0), (r2, s2, 2.0), (r3, s3, 3.0), (r4, s4, 4.0)]);
+    let signal = sma_kernel_nan_aware(&kst_values, n_sig);
+
+    (kst_values, signal)
+}
 
-    Ok(PyArray1::from_vec(py, mi))
+/// Weighted ROC composite (generalized KST / Coppock)
+///
+/// # Arguments
+/// * `close` - Close price series
+/// * `periods` - ROC periods, one per component
+/// * `smooths` - SMA smoothing length of each ROC
+/// * `weights` - Weight of each smoothed ROC
+///
+/// # Returns
+/// Numpy array with 100 * sum(weight * SMA(ROC)); `kst` uses periods [10, 15, 20, 30],
+/// smooths [10, 10, 10, 15] and weights [1, 2, 3, 4]
+#[pyfunction]
+#[pyo3(name = "weighted_roc_numba", signature = (close, periods, smooths, weights))]
+pub fn weighted_roc<'py>(
+    py: Python<'py>,
+    close: PyReadonlyArray1<'py, f64>,
+    periods: Vec<usize>,
+    smooths: Vec<usize>,
+    weights: Vec<f64>,
+) -> PyResult<Bound<'py, PyArray1<f64>>> {
+    let close_slice = close.as_slice()?;
+    let result = weighted_roc_kernel(close_slice, &periods, &smooths, &weights)
+        .map_err(PyValueError::new_err)?;
+    Ok(PyArray1::from_vec(py, result))
 }
 
 /// KST - Know Sure Thing
@@ -550,11 +1327,13 @@ pub fn mass_index<'py>(
 /// * `r1, r2, r3, r4` - ROC periods (defaults: 10, 15, 20, 30)
 /// * `s1, s2, s3, s4` - SMA smoothing periods (defaults: 10, 10, 10, 15)
 /// * `n_sig` - Signal line period (default: 9)
+/// * `return_hist` - Also return the (KST - signal) histogram (default: false)
 ///
 /// # Returns
-/// Tuple of (KST, signal) as numpy arrays
+/// Tuple of (KST, signal) as numpy arrays, or (KST, signal, histogram)
+/// when `return_hist` is true
 #[pyfunction]
-#[pyo3(name = "kst_numba", signature = (close, r1=10, r2=15, r3=20, r4=30, s1=10, s2=10, s3=10, s4=15, n_sig=9))]
+#[pyo3(name = "kst_numba", signature = (close, r1=10, r2=15, r3=20, r4=30, s1=10, s2=10, s3=10, s4=15, n_sig=9, return_hist=false))]
 pub fn kst<'py>(
     py: Python<'py>,
     close: PyReadonlyArray1<'py, f64>,
@@ -567,54 +1346,23 @@ pub fn kst<'py>(
     s3: usize,
     s4: usize,
     n_sig: usize,
-) -> PyResult<(Bound<'py, PyArray1<f64>>, Bound<'py, PyArray1<f64>>)> {
+    return_hist: bool,
+) -> PyResult<Bound<'py, PyTuple>> {
     let close_slice = close.as_slice()?;
-    let len = close_slice.len();
-
-    // ROC uses raw ratio (not * 100) to match ta library
-    let calc_roc = |window: usize| -> Vec<f64> {
-        let mut roc = vec![f64::NAN; len];
-        for i in window..len {
-            if close_slice[i - window] != 0.0 {
-                roc[i] = (close_slice[i] - close_slice[i - window]) / close_slice[i - window];
-            }
-        }
-        roc
-    };
-
-    let roc1 = calc_roc(r1);
-    let roc2 = calc_roc(r2);
-    let roc3 = calc_roc(r3);
-    let roc4 = calc_roc(r4);
-
-    // Use NaN-aware SMA (requires all values in window to be non-NaN)
-    let rcma1 = sma_kernel_nan_aware(&roc1, s1);
-    let rcma2 = sma_kernel_nan_aware(&roc2, s2);
-    let rcma3 = sma_kernel_nan_aware(&roc3, s3);
-    let rcma4 = sma_kernel_nan_aware(&roc4, s4);
-
-    // Match Python: nan_to_num(rcma, nan=0.0) then weighted sum * 100
-    let mut kst_values = vec![f64::NAN; len];
-    for i in 0..len {
-        let v1 = if rcma1[i].is_nan() { 0.0 } else { rcma1[i] };
-        let v2 = if rcma2[i].is_nan() { 0.0 } else { rcma2[i] };
-        let v3 = if rcma3[i].is_nan() { 0.0 } else { rcma3[i] };
-        let v4 = if rcma4[i].is_nan() { 0.0 } else { rcma4[i] };
-        let val = (v1 * 1.0 + v2 * 2.0 + v3 * 3.0 + v4 * 4.0) * 100.0;
-        // Only set NaN if ALL components are NaN
-        if rcma1[i].is_nan() && rcma2[i].is_nan() && rcma3[i].is_nan() && rcma4[i].is_nan() {
-            kst_values[i] = f64::NAN;
-        } else {
-            kst_values[i] = val;
-        }
+    let (kst_values, signal) = kst_kernel(close_slice, r1, r2, r3, r4, s1, s2, s3, s4, n_sig);
+
+    let mut outputs = Vec::with_capacity(3);
+    if return_hist {
+        let histogram = histogram_kernel(&kst_values, &signal);
+        outputs.push(PyArray1::from_vec(py, kst_values));
+        outputs.push(PyArray1::from_vec(py, signal));
+        outputs.push(PyArray1::from_vec(py, histogram));
+    } else {
+        outputs.push(PyArray1::from_vec(py, kst_values));
+        outputs.push(PyArray1::from_vec(py, signal));
     }
 
-    let signal = sma_kernel_nan_aware(&kst_values, n_sig);
-
-    Ok((
-        PyArray1::from_vec(py, kst_values),
-        PyArray1::from_vec(py, signal),
-    ))
+    PyTuple::new(py, outputs)
 }
 
 /// Ichimoku Cloud
@@ -673,8 +1421,8 @@ pub fn ichimoku<'py>(
     let senkou_b = calc_midpoint(n3);
 
     let mut chikou = vec![f64::NAN; len];
-    for i in n2..len {
-        chikou[i - n2] = close_slice[i];
+    if len > n2 {
+        chikou[..(len - n2)].copy_from_slice(&close_slice[n2..]);
     }
 
     Ok((
@@ -815,3 +1563,448 @@ pub fn aroon<'py>(
         PyArray1::from_vec(py, aroon_down),
     ))
 }
+
+/// Vertical Horizontal Filter kernel:
+/// (max(close, n) - min(close, n)) / sum(|close[i] - close[i-1]|, n)
+pub fn vertical_horizontal_filter_kernel(close: &[f64], n: usize) -> Vec<f64> {
+    let len = close.len();
+    let mut vhf = vec![f64::NAN; len];
+
+    if n == 0 || len <= n {
+        return vhf;
+    }
+
+    let mut abs_change = vec![f64::NAN; len];
+    for i in 1..len {
+        abs_change[i] = (close[i] - close[i - 1]).abs();
+    }
+
+    let highest = rolling_max(close, n);
+    let lowest = rolling_min(close, n);
+    let path = rolling_sum(&abs_change, n);
+
+    // Start once the change window holds n real differences
+    for i in n..len {
+        if path[i] != 0.0 {
+            vhf[i] = (highest[i] - lowest[i]) / path[i];
+        }
+    }
+
+    vhf
+}
+
+/// Vertical Horizontal Filter (VHF)
+///
+/// # Arguments
+/// * `close` - Close price series
+/// * `n` - Lookback period (default: 28)
+///
+/// # Returns
+/// Numpy array with VHF values (high when trending, low when choppy)
+#[pyfunction]
+#[pyo3(name = "vertical_horizontal_filter_numba", signature = (close, n=28))]
+pub fn vertical_horizontal_filter<'py>(
+    py: Python<'py>,
+    close: PyReadonlyArray1<'py, f64>,
+    n: usize,
+) -> PyResult<Bound<'py, PyArray1<f64>>> {
+    let close_slice = close.as_slice()?;
+    let result = vertical_horizontal_filter_kernel(close_slice, n);
+    Ok(PyArray1::from_vec(py, result))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_close(len: usize) -> Vec<f64> {
+        (0..len).map(|i| 100.0 + (i as f64 * 0.3).sin() * 5.0 + i as f64 * 0.1).collect()
+    }
+
+    #[test]
+    fn test_kst_histogram_is_line_minus_signal() {
+        let close = sample_close(120);
+        let (kst_values, signal) = kst_kernel(&close, 10, 15, 20, 30, 10, 10, 10, 15, 9);
+        let histogram = histogram_kernel(&kst_values, &signal);
+
+        for i in 0..close.len() {
+            if kst_values[i].is_nan() || signal[i].is_nan() {
+                assert!(histogram[i].is_nan());
+            } else {
+                assert!((histogram[i] - (kst_values[i] - signal[i])).abs() < 1e-12);
+            }
+        }
+        assert!(histogram[..19].iter().all(|x| x.is_nan()));
+        assert!(!histogram[close.len() - 1].is_nan());
+    }
+
+    #[test]
+    fn test_gmma_compression_tight_when_flat_wide_when_trending() {
+        let long = [30, 35, 40, 45, 50, 60];
+        // strong uptrend fans the long EMAs out, a long flat stretch lets them converge
+        let mut close: Vec<f64> = (0..150).map(|i| 100.0 + i as f64).collect();
+        close.extend(std::iter::repeat_n(249.0, 300));
+        let compression = gmma_compression_kernel(&close, &long, false).unwrap();
+
+        assert!(compression[..59].iter().all(|x| x.is_nan()));
+        let trending = compression[149];
+        let converged = compression[close.len() - 1];
+        assert!(trending > 0.05);
+        assert!(converged < 1e-3);
+        assert!(converged < trending);
+    }
+
+    #[test]
+    fn test_gmma_compression_rejects_empty_periods() {
+        assert!(gmma_compression_kernel(&[1.0, 2.0], &[], false).is_err());
+    }
+
+    #[test]
+    fn test_ma_distance_matches_macd_line() {
+        let close = sample_close(80);
+        let distance = ma_distance_kernel(&close, 12, 26, "ema", false, false).unwrap();
+
+        // MACD line as computed in `macd`
+        let ema_fast = ema_kernel(&close, 2.0 / 13.0, false);
+        let ema_slow = ema_kernel(&close, 2.0 / 27.0, false);
+        for i in 0..close.len() {
+            let expected = ema_fast[i] - ema_slow[i];
+            assert!((distance[i] - expected).abs() < 1e-12);
+        }
+
+        assert!(ma_distance_kernel(&close, 12, 26, "hma", false, false).is_err());
+    }
+
+    #[test]
+    fn test_vhf_trend_vs_oscillation() {
+        let trend: Vec<f64> = (0..60).map(|i| 100.0 + i as f64).collect();
+        let chop: Vec<f64> = (0..60).map(|i| if i % 2 == 0 { 100.0 } else { 101.0 }).collect();
+
+        let vhf_trend = vertical_horizontal_filter_kernel(&trend, 28);
+        let vhf_chop = vertical_horizontal_filter_kernel(&chop, 28);
+
+        assert!(vhf_trend[27].is_nan());
+        assert!(vhf_trend[59] > 0.9);
+        assert!(vhf_chop[59] < 0.1);
+
+        let flat = vec![100.0; 40];
+        assert!(vertical_horizontal_filter_kernel(&flat, 28)[39].is_nan());
+    }
+
+    #[test]
+    fn test_expanding_sma_is_cumulative_mean() {
+        let data = sample_close(30);
+        let expanding = helpers::expanding_mean_kernel(&data, 5);
+
+        assert!(expanding[3].is_nan());
+        let mut sum = 0.0;
+        for i in 0..data.len() {
+            sum += data[i];
+            if i >= 4 {
+                assert!((expanding[i] - sum / (i + 1) as f64).abs() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_parabolic_sar_af_schedule() {
+        let schedule = helpers::AfSchedule::new(0.02, 0.02, 0.2, Some(vec![0.01, 0.05, 0.1])).unwrap();
+        assert_eq!(schedule.initial(), 0.01);
+        let expected = [0.05, 0.1, 0.1, 0.1];
+        let mut af = schedule.initial();
+        for (k, &want) in expected.iter().enumerate() {
+            af = schedule.advance(af, k + 1);
+            assert_eq!(af, want);
+        }
+        assert!(helpers::AfSchedule::new(0.02, 0.02, 0.2, Some(vec![0.1, 0.05])).is_err());
+
+        // Steady uptrend: every bar is a new extreme, so SAR closes in at the scheduled pace
+        let high: Vec<f64> = (0..8).map(|i| 101.0 + i as f64).collect();
+        let low: Vec<f64> = (0..8).map(|i| 99.0 + i as f64).collect();
+        let close: Vec<f64> = (0..8).map(|i| 100.0 + i as f64).collect();
+        let sar = parabolic_sar_kernel(&high, &low, &close, &schedule);
+
+        let mut prev = close[1];
+        let mut ep = high[0];
+        let mut af = 0.01;
+        for i in 2..6 {
+            let expected = (prev + af * (ep - prev)).min(low[i - 2]);
+            assert!((sar[i] - expected).abs() < 1e-12);
+            ep = high[i];
+            af = schedule.advance(af, i - 1);
+            prev = sar[i];
+        }
+
+        // A list matching the linear progression reproduces the default behavior
+        let linear = helpers::AfSchedule::new(0.02, 0.02, 0.2, None).unwrap();
+        let stepped = helpers::AfSchedule::new(0.0, 0.0, 0.0, Some(vec![0.02, 0.04, 0.06, 0.08, 0.1, 0.12])).unwrap();
+        let a = parabolic_sar_kernel(&high, &low, &close, &linear);
+        let b = parabolic_sar_kernel(&high, &low, &close, &stepped);
+        for i in 0..a.len() {
+            assert!((a[i] - b[i]).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_signal_line_reproduces_macd_signal() {
+        let close = sample_close(80);
+        let macd_line = ma_distance_kernel(&close, 12, 26, "ema", false, false).unwrap();
+
+        // Signal line as computed in `macd`
+        let expected = ema_kernel(&macd_line, 2.0 / 10.0, true);
+        let signal = signal_line_kernel(&macd_line, 9, "ema", true).unwrap();
+
+        for i in 0..close.len() {
+            assert!((signal[i] - expected[i]).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_adxr_averages_current_and_lagged_adx() {
+        let close = sample_close(120);
+        let high: Vec<f64> = close.iter().enumerate().map(|(i, c)| c + 1.0 + (i % 3) as f64 * 0.2).collect();
+        let low: Vec<f64> = close.iter().enumerate().map(|(i, c)| c - 1.0 - (i % 4) as f64 * 0.1).collect();
+
+        let (adx_values, _, _) = adx_kernel(&high, &low, &close, 14);
+        let adxr_values = adxr_kernel(&adx_values, 14);
+
+        let first_adx = adx_values.iter().position(|v| !v.is_nan()).unwrap();
+        assert!(adxr_values[first_adx + 13].is_nan());
+        for i in (first_adx + 14)..close.len() {
+            assert!((adxr_values[i] - (adx_values[i] + adx_values[i - 14]) / 2.0).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_std_multi_matches_rolling_std() {
+        let close = sample_close(300);
+        let periods = [5, 20, 200];
+        let std = std_multi_kernel(&close, &periods).unwrap();
+
+        for (j, &p) in periods.iter().enumerate() {
+            let single = helpers::rolling_std(&close, p);
+            for i in 0..close.len() {
+                let got = std[i * periods.len() + j];
+                if single[i].is_nan() {
+                    assert!(got.is_nan());
+                } else {
+                    assert!((got - single[i]).abs() < 1e-6, "period {} at {}", p, i);
+                }
+            }
+        }
+        assert!(std_multi_kernel(&close, &[]).is_err());
+    }
+
+    #[test]
+    fn test_multi_period_columns_match_single_kernels() {
+        let close = sample_close(300);
+        let periods = [10, 20, 50, 100, 200];
+        let cols = periods.len();
+
+        let sma = sma_multi_kernel(&close, &periods).unwrap();
+        let ema_adj = ema_multi_kernel(&close, &periods, true).unwrap();
+        let ema_raw = ema_multi_kernel(&close, &periods, false).unwrap();
+
+        for (j, &p) in periods.iter().enumerate() {
+            let alpha = 2.0 / (p as f64 + 1.0);
+            let expected = [
+                (&sma, sma_kernel(&close, p)),
+                (&ema_adj, ema_kernel(&close, alpha, true)),
+                (&ema_raw, ema_kernel(&close, alpha, false)),
+            ];
+            for (multi, single) in expected {
+                for i in 0..close.len() {
+                    let got = multi[i * cols + j];
+                    if single[i].is_nan() {
+                        assert!(got.is_nan());
+                    } else {
+                        assert!((got - single[i]).abs() < 1e-9, "period {} bar {}", p, i);
+                    }
+                }
+            }
+        }
+
+        assert!(sma_multi_kernel(&close, &[10, 0]).is_err());
+        assert!(ema_multi_kernel(&close, &[], true).is_err());
+    }
+
+    #[test]
+    fn test_adx_ta_compat_warmup() {
+        let close = sample_close(400);
+        let high: Vec<f64> = close.iter().map(|c| c + 1.0).collect();
+        let low: Vec<f64> = close.iter().map(|c| c - 1.0).collect();
+        let first_valid = |v: &[f64]| v.iter().position(|x| !x.is_nan());
+
+        let (adx, plus, minus) = adx_compat_kernel(&high, &low, &close, 14, helpers::Compat::Native);
+        assert_eq!(first_valid(&adx), Some(26));
+        assert_eq!(first_valid(&plus), Some(13));
+
+        // ta fills its warmup with zeros: ADX before 2n-1, +DI/-DI before n+1
+        let (adx_ta, plus_ta, minus_ta) = adx_compat_kernel(&high, &low, &close, 14, helpers::Compat::Ta);
+        assert!(adx_ta[..27].iter().all(|&v| v == 0.0) && adx_ta[27] > 0.0);
+        assert!(plus_ta[..15].iter().all(|&v| v == 0.0) && plus_ta[15] > 0.0);
+        assert!(minus_ta[..15].iter().all(|&v| v == 0.0));
+        assert!(adx_ta.iter().chain(&plus_ta).chain(&minus_ta).all(|v| v.is_finite()));
+
+        // Same Wilder recursions from different seeds, so the two converge
+        for i in 300..400 {
+            assert!((adx_ta[i] - adx[i]).abs() < 1e-6, "adx bar {}", i);
+            assert!((plus_ta[i] - plus[i]).abs() < 1e-6, "+di bar {}", i);
+            assert!((minus_ta[i] - minus[i]).abs() < 1e-6, "-di bar {}", i);
+        }
+
+        let (short_adx, _, _) = adx_ta_kernel(&high[..20], &low[..20], &close[..20], 14);
+        assert!(short_adx.iter().all(|&v| v == 0.0));
+    }
+
+    #[test]
+    fn test_trix_of_known_values() {
+        // n = 1 makes every EMA the identity, so TRIX is the plain percent change
+        let data = [100.0, 110.0, 99.0, 99.0];
+        let (trix_values, signal) = trix_of_kernel(&data, 1, 3);
+        assert!(trix_values[0].is_nan() && signal[0].is_nan());
+        assert!((trix_values[1] - 10.0).abs() < 1e-12);
+        assert!((trix_values[2] + 10.0).abs() < 1e-12);
+        assert_eq!(trix_values[3], 0.0);
+        // Adjusted EMA(alpha = 0.5) of [10, -10, 0], starting at the first TRIX value
+        assert!((signal[1] - 10.0).abs() < 1e-12);
+        assert!((signal[2] + 10.0 / 3.0).abs() < 1e-12);
+        assert!((signal[3] + 10.0 / 7.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_trix_of_matches_weighted_average_reference() {
+        // Adjusted EMA written out as an explicit weighted average of the history
+        fn weighted_ema(data: &[f64], alpha: f64) -> Vec<f64> {
+            (0..data.len())
+                .map(|i| {
+                    let (mut num, mut den) = (0.0, 0.0);
+                    for (age, &x) in data[..=i].iter().rev().enumerate() {
+                        let w = (1.0 - alpha).powi(age as i32);
+                        num += w * x;
+                        den += w;
+                    }
+                    num / den
+                })
+                .collect()
+        }
+
+        let close = sample_close(80);
+        let ema3 = weighted_ema(&weighted_ema(&weighted_ema(&close, 2.0 / 16.0), 2.0 / 16.0), 2.0 / 16.0);
+        let expected: Vec<f64> = (1..close.len()).map(|i| (ema3[i] / ema3[i - 1] - 1.0) * 100.0).collect();
+        let expected_signal = weighted_ema(&expected, 2.0 / 10.0);
+
+        let (trix_values, signal) = trix_of_kernel(&close, 15, 9);
+        for i in 1..close.len() {
+            assert!((trix_values[i] - expected[i - 1]).abs() < 1e-9, "bar {}", i);
+            assert!((signal[i] - expected_signal[i - 1]).abs() < 1e-9, "bar {}", i);
+        }
+    }
+
+    #[test]
+    fn test_weighted_roc_reproduces_kst() {
+        let close = sample_close(120);
+        let (kst_values, _) = kst_kernel(&close, 10, 15, 20, 30, 10, 10, 10, 15, 9);
+        let composite = weighted_roc_kernel(
+            &close,
+            &[10, 15, 20, 30],
+            &[10, 10, 10, 15],
+            &[1.0, 2.0, 3.0, 4.0],
+        )
+        .unwrap();
+
+        assert!(kst_values[18].is_nan() && composite[18].is_nan());
+        for i in 19..close.len() {
+            assert_eq!(composite[i], kst_values[i]);
+        }
+
+        // First bar of the pre-refactor KST: only the 10-bar component is valid
+        let roc10: Vec<f64> = (10..20).map(|i| (close[i] - close[i - 10]) / close[i - 10]).collect();
+        let expected = roc10.iter().sum::<f64>() / 10.0 * 100.0;
+        assert!((kst_values[19] - expected).abs() < 1e-9);
+
+        assert!(weighted_roc_kernel(&close, &[10, 15], &[10], &[1.0, 2.0]).is_err());
+        assert!(weighted_roc_kernel(&close, &[], &[], &[]).is_err());
+    }
+
+    #[test]
+    fn test_dpo_pct_is_scaled_difference() {
+        let close: Vec<f64> = (0..80).map(|i| 100.0 + (i as f64 * 0.2).sin() * 4.0 + i as f64 * 0.1).collect();
+        let n = 20;
+        let abs = dpo_kernel(&close, n, false);
+        let pct = dpo_kernel(&close, n, true);
+        let sma = sma_kernel(&close, n);
+
+        assert!(abs[n - 2].is_nan() && pct[n - 2].is_nan());
+        for i in (n - 1)..close.len() {
+            assert!((pct[i] - abs[i] / sma[i] * 100.0).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_disparity_index_sign() {
+        let flat = [50.0; 30];
+        let di = disparity_index_kernel(&flat, 14, "ema").unwrap();
+        assert!(di[12].is_nan());
+        assert!(di[13..].iter().all(|&d| d == 0.0));
+
+        let rising: Vec<f64> = (0..30).map(|i| 100.0 + i as f64).collect();
+        for ma_type in ["sma", "ema", "wma"] {
+            let di = disparity_index_kernel(&rising, 14, ma_type).unwrap();
+            assert!(di[13..].iter().all(|&d| d > 0.0), "{}", ma_type);
+        }
+
+        assert!(disparity_index_kernel(&flat, 14, "hma").is_err());
+    }
+
+    #[test]
+    fn test_mass_index_bulge_requires_rise_then_drop() {
+        let nan = f64::NAN;
+        // Dips below 26.5 before any bulge, rises through 27, wobbles, then drops
+        let mi = [nan, 26.0, 26.8, 27.2, 26.9, 27.5, 26.7, 26.4, 26.0, 27.1, nan, 26.3];
+        let flags = mass_index_bulge_kernel(&mi, 27.0, 26.5);
+        assert_eq!(flags, vec![0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0]);
+
+        let high: Vec<f64> = (0..60).map(|i| 101.0 + (i as f64 * 0.3).sin()).collect();
+        let low: Vec<f64> = (0..60).map(|i| 99.0 - (i as f64 * 0.3).cos()).collect();
+        let mi = mass_index_kernel(&high, &low, 9, 25);
+        assert!(mi[23].is_nan() && !mi[24].is_nan());
+    }
+
+    #[test]
+    fn test_price_oscillator_pct_ema_is_ppo_line() {
+        let close: Vec<f64> = (0..120).map(|i| 100.0 + (i as f64 * 0.21).sin() * 5.0 + i as f64 * 0.1).collect();
+        let osc = ma_distance_kernel(&close, 12, 26, "ema", false, true).unwrap();
+        let (ppo_line, _, _) = crate::momentum::percentage_oscillator_kernel(
+            &close, 12, 26, 9, false, helpers::HistMode::Diff,
+        );
+
+        for i in 0..close.len() {
+            assert!((osc[i] - ppo_line[i]).abs() < 1e-9, "bar {}", i);
+        }
+    }
+
+    #[test]
+    fn test_ma_cross_signal_holds_between_crosses() {
+        // Falling, then rising (golden cross), then falling again (death cross)
+        let close: Vec<f64> = (0..60)
+            .map(|i| match i {
+                0..=19 => 100.0 - i as f64,
+                20..=39 => 81.0 + (i - 20) as f64 * 2.0,
+                _ => 119.0 - (i - 40) as f64 * 2.0,
+            })
+            .collect();
+        let position = ma_cross_signal_kernel(&close, 3, 8, "sma").unwrap();
+        let crosses = helpers::crossover_kernel(&sma_kernel(&close, 3), &sma_kernel(&close, 8));
+
+        assert!(position[6].is_nan());
+        assert_eq!(position[7], 0.0);
+        let golden = crosses.iter().position(|&c| c == 1.0).unwrap();
+        let death = crosses.iter().position(|&c| c == -1.0).unwrap();
+        assert!(20 < golden && golden < death && death > 40);
+
+        assert!(position[7..golden].iter().all(|&p| p == 0.0));
+        assert!(position[golden..death].iter().all(|&p| p == 1.0));
+        assert!(position[death..].iter().all(|&p| p == -1.0));
+    }
+}
@@ -1,8 +1,14 @@
-/// Volatility indicators: ATR, Bollinger Bands, Keltner Channel, Donchian Channel, Ulcer Index
+//! Volatility indicators: ATR, ATR Bands, Bollinger Bands, Standard Deviation, Keltner Channel, Donchian Channel, Ulcer Index,
+//! Standard Error Bands, Volatility-Scaled Signal, Bollinger Squeeze, Chaikin Volatility,
+//! Pivot Distance, Regression Channel, Channel Position, ATR Trailing Stop,
+//! Parkinson Volatility, Garman-Klass Volatility, MA Envelope, ATR Percentile, ATR Breakout
 
 use numpy::{PyArray1, PyReadonlyArray1};
+use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
-use crate::helpers::{sma_kernel, sma_kernel_min0, wilders_ema_kernel, true_range, rolling_std, rolling_min, rolling_max};
+use pyo3::types::PyTuple;
+use crate::helpers::{moving_average_kernel, sma_kernel, ema_kernel, sma_kernel_min0, wilders_ema_kernel, true_range, rolling_std, expanding_std_kernel, rolling_min, rolling_max, rolling_linreg, rolling_sum, roc_kernel, check_equal_lengths, trailing_stop_step, mask_warmup, resolve_periods_per_year, Compat};
+use crate::others::rolling_percentile_kernel;
 
 /// ATR - Average True Range (Wilder's method)
 ///
@@ -11,28 +17,63 @@ use crate::helpers::{sma_kernel, sma_kernel_min0, wilders_ema_kernel, true_range
 /// * `low` - Low price series
 /// * `close` - Close price series
 /// * `n` - ATR period (default: 14)
+/// * `compat` - "native" or "ta" (default: "native"). Both start at n-1 with the mean
+///   of the first n true ranges; "ta" fills the warmup with 0.0 instead of NaN, as
+///   `ta.volatility.AverageTrueRange` does.
 ///
 /// # Returns
 /// Numpy array with ATR values
 #[pyfunction]
-#[pyo3(name = "average_true_range_numba", signature = (high, low, close, n=14))]
+#[pyo3(name = "average_true_range_numba", signature = (high, low, close, n=14, compat="native"))]
 pub fn atr<'py>(
     py: Python<'py>,
     high: PyReadonlyArray1<'py, f64>,
     low: PyReadonlyArray1<'py, f64>,
     close: PyReadonlyArray1<'py, f64>,
     n: usize,
+    compat: &str,
 ) -> PyResult<Bound<'py, PyArray1<f64>>> {
     let high_slice = high.as_slice()?;
     let low_slice = low.as_slice()?;
     let close_slice = close.as_slice()?;
+    let compat = Compat::parse(compat).map_err(PyValueError::new_err)?;
 
-    let tr = true_range(high_slice, low_slice, close_slice);
-    let atr_values = wilders_ema_kernel(&tr, n);
+    let atr_values = atr_compat_kernel(high_slice, low_slice, close_slice, n, compat);
 
     Ok(PyArray1::from_vec(py, atr_values))
 }
 
+/// Wilder's ATR kernel; with `Compat::Ta` the first n-1 bars are 0.0 rather than NaN
+pub fn atr_compat_kernel(high: &[f64], low: &[f64], close: &[f64], n: usize, compat: Compat) -> Vec<f64> {
+    let mut atr_values = wilders_ema_kernel(&true_range(high, low, close), n);
+    if compat == Compat::Ta {
+        for value in atr_values.iter_mut().take(n.saturating_sub(1)) {
+            *value = 0.0;
+        }
+    }
+    atr_values
+}
+
+/// Bollinger Bands kernel: SMA ± k * population std
+pub fn bollinger_bands_kernel(close: &[f64], n: usize, k: f64) -> (Vec<f64>, Vec<f64>, Vec<f64>) {
+    let len = close.len();
+
+    let middle = sma_kernel(close, n);
+    let std = rolling_std(close, n);
+
+    let mut upper = vec![f64::NAN; len];
+    let mut lower = vec![f64::NAN; len];
+
+    for i in 0..len {
+        if !middle[i].is_nan() && !std[i].is_nan() {
+            upper[i] = middle[i] + k * std[i];
+            lower[i] = middle[i] - k * std[i];
+        }
+    }
+
+    (upper, middle, lower)
+}
+
 /// Bollinger Bands
 ///
 /// # Arguments
@@ -51,20 +92,7 @@ pub fn bollinger_bands<'py>(
     k: f64,
 ) -> PyResult<(Bound<'py, PyArray1<f64>>, Bound<'py, PyArray1<f64>>, Bound<'py, PyArray1<f64>>)> {
     let close_slice = close.as_slice()?;
-    let len = close_slice.len();
-
-    let middle = sma_kernel(close_slice, n);
-    let std = rolling_std(close_slice, n);
-
-    let mut upper = vec![f64::NAN; len];
-    let mut lower = vec![f64::NAN; len];
-
-    for i in 0..len {
-        if !middle[i].is_nan() && !std[i].is_nan() {
-            upper[i] = middle[i] + k * std[i];
-            lower[i] = middle[i] - k * std[i];
-        }
-    }
+    let (upper, middle, lower) = bollinger_bands_kernel(close_slice, n, k);
 
     Ok((
         PyArray1::from_vec(py, upper),
@@ -73,62 +101,199 @@ pub fn bollinger_bands<'py>(
     ))
 }
 
+/// Standard deviation kernel: population std over the last `n` values, or with
+/// `expanding` over data[0..=i] once `n` values are seen
+pub fn standard_deviation_kernel(data: &[f64], n: usize, expanding: bool) -> Vec<f64> {
+    if expanding {
+        expanding_std_kernel(data, n)
+    } else {
+        rolling_std(data, n)
+    }
+}
+
+/// Standard Deviation (population, ddof=0)
+///
+/// # Arguments
+/// * `data` - Data series
+/// * `n` - Rolling window size (default: 20)
+/// * `expanding` - Use all data up to each index instead of the last `n`;
+///   `n` still acts as the minimum number of periods (default: false)
+///
+/// # Returns
+/// Numpy array with standard deviation values, as `StandardDeviationStreaming`
+#[pyfunction]
+#[pyo3(name = "standard_deviation_numba", signature = (data, n=20, expanding=false))]
+pub fn standard_deviation<'py>(
+    py: Python<'py>,
+    data: PyReadonlyArray1<'py, f64>,
+    n: usize,
+    expanding: bool,
+) -> PyResult<Bound<'py, PyArray1<f64>>> {
+    let result = standard_deviation_kernel(data.as_slice()?, n, expanding);
+    Ok(PyArray1::from_vec(py, result))
+}
+
 /// Keltner Channel (matching ta library original_version=True)
 ///
 /// # Arguments
 /// * `high` - High price series
 /// * `low` - Low price series
 /// * `close` - Close price series
-/// * `n_ema` - Period for typical price moving average (default: 20)
-/// * `n_atr` - Unused (kept for API compatibility)
-/// * `k` - Unused (kept for API compatibility)
+/// * `n_ema` - Period for the middle line (default: 20)
+/// * `n_atr` - ATR period, used only when `original_version` is false (default: 10)
+/// * `k` - ATR multiplier, used only when `original_version` is false (default: 2.0)
+/// * `pct` - Return the bands as percent distance from the middle band (default: false)
+/// * `original_version` - Use the SMA-based formulas below; false selects the ATR
+///   version matching `ta.volatility.KeltnerChannel(original_version=False)` (default: true)
 ///
 /// # Returns
-/// Tuple of (upper_band, middle_band, lower_band) as numpy arrays
+/// Tuple of (upper_band, middle_band, lower_band) as numpy arrays. With `pct` the
+/// upper and lower bands are `(band - middle) / middle * 100`; the middle stays in price.
 ///
-/// Uses ta library original_version formulas:
+/// With `original_version` (ta library original_version formulas):
 /// - Middle: SMA(typical_price, n_ema) with min_periods=window
 /// - High band: SMA((4*H - 2*L + C)/3, n_ema) with min_periods=0
 /// - Low band: SMA((-2*H + 4*L + C)/3, n_ema) with min_periods=0
+///
+/// Without it, see `keltner_atr_kernel` with `Compat::Ta`.
 #[pyfunction]
-#[pyo3(name = "keltner_channel_numba", signature = (high, low, close, n_ema=20, n_atr=10, k=2.0))]
+#[pyo3(name = "keltner_channel_numba", signature = (high, low, close, n_ema=20, n_atr=10, k=2.0, pct=false, original_version=true))]
 pub fn keltner_channel<'py>(
     py: Python<'py>,
     high: PyReadonlyArray1<'py, f64>,
     low: PyReadonlyArray1<'py, f64>,
     close: PyReadonlyArray1<'py, f64>,
     n_ema: usize,
-    #[allow(unused_variables)]
     n_atr: usize,
-    #[allow(unused_variables)]
     k: f64,
+    pct: bool,
+    original_version: bool,
 ) -> PyResult<(Bound<'py, PyArray1<f64>>, Bound<'py, PyArray1<f64>>, Bound<'py, PyArray1<f64>>)> {
     let high_slice = high.as_slice()?;
     let low_slice = low.as_slice()?;
     let close_slice = close.as_slice()?;
-    let len = high_slice.len();
+
+    let (upper, middle, lower) = if original_version {
+        keltner_original_kernel(high_slice, low_slice, close_slice, n_ema)
+    } else {
+        keltner_atr_kernel(high_slice, low_slice, close_slice, n_ema, n_atr, k, Compat::Ta)
+    };
+    let (upper, lower) = if pct { band_pct_kernel(&upper, &middle, &lower) } else { (upper, lower) };
+
+    Ok((
+        PyArray1::from_vec(py, upper),
+        PyArray1::from_vec(py, middle),
+        PyArray1::from_vec(py, lower),
+    ))
+}
+
+/// Original (SMA-based) Keltner Channel kernel, as in `ta` with original_version=True
+pub fn keltner_original_kernel(
+    high: &[f64],
+    low: &[f64],
+    close: &[f64],
+    n_ema: usize,
+) -> (Vec<f64>, Vec<f64>, Vec<f64>) {
+    let len = high.len();
 
     // Middle: SMA(typical_price, n_ema) with standard min_periods=window
     let mut typical_price = vec![0.0; len];
     for i in 0..len {
-        typical_price[i] = (high_slice[i] + low_slice[i] + close_slice[i]) / 3.0;
+        typical_price[i] = (high[i] + low[i] + close[i]) / 3.0;
     }
     let middle = sma_kernel(&typical_price, n_ema);
 
     // High band: SMA((4*H - 2*L + C)/3, n_ema) with min_periods=0
     let mut high_tp = vec![0.0; len];
     for i in 0..len {
-        high_tp[i] = (4.0 * high_slice[i] - 2.0 * low_slice[i] + close_slice[i]) / 3.0;
+        high_tp[i] = (4.0 * high[i] - 2.0 * low[i] + close[i]) / 3.0;
     }
     let upper = sma_kernel_min0(&high_tp, n_ema);
 
     // Low band: SMA((-2*H + 4*L + C)/3, n_ema) with min_periods=0
     let mut low_tp = vec![0.0; len];
     for i in 0..len {
-        low_tp[i] = (-2.0 * high_slice[i] + 4.0 * low_slice[i] + close_slice[i]) / 3.0;
+        low_tp[i] = (-2.0 * high[i] + 4.0 * low[i] + close[i]) / 3.0;
     }
     let lower = sma_kernel_min0(&low_tp, n_ema);
 
+    (upper, middle, lower)
+}
+
+/// Express upper/lower bands as percent distance from the middle band:
+/// (band - middle) / middle * 100. NaN where the middle is NaN or zero.
+pub fn band_pct_kernel(upper: &[f64], middle: &[f64], lower: &[f64]) -> (Vec<f64>, Vec<f64>) {
+    let to_pct = |band: &[f64]| -> Vec<f64> {
+        band.iter()
+            .zip(middle)
+            .map(|(&b, &m)| if m != 0.0 { (b - m) / m * 100.0 } else { f64::NAN })
+            .collect()
+    };
+    (to_pct(upper), to_pct(lower))
+}
+
+/// ATR bands kernel: close ± multiplier * ATR (Wilder's)
+pub fn atr_bands_kernel(
+    high: &[f64],
+    low: &[f64],
+    close: &[f64],
+    n: usize,
+    multiplier: f64,
+) -> (Vec<f64>, Vec<f64>, Vec<f64>) {
+    let atr_values = wilders_ema_kernel(&true_range(high, low, close), n);
+
+    let mut upper = vec![f64::NAN; close.len()];
+    let mut middle = vec![f64::NAN; close.len()];
+    let mut lower = vec![f64::NAN; close.len()];
+
+    for i in 0..close.len() {
+        if !atr_values[i].is_nan() {
+            upper[i] = close[i] + multiplier * atr_values[i];
+            middle[i] = close[i];
+            lower[i] = close[i] - multiplier * atr_values[i];
+        }
+    }
+
+    (upper, middle, lower)
+}
+
+/// ATR Bands
+///
+/// # Arguments
+/// * `high` - High price series
+/// * `low` - Low price series
+/// * `close` - Close price series
+/// * `n` - ATR period (default: 14)
+/// * `multiplier` - Band distance in ATRs (default: 2.0)
+/// * `pct` - Return the bands as percent distance from the middle band (default: false)
+///
+/// # Returns
+/// Tuple of (upper_band, middle_band, lower_band) as numpy arrays; the middle band
+/// is the close. With `pct` the upper and lower bands are `(band - middle) / middle * 100`.
+#[pyfunction]
+#[pyo3(name = "atr_bands_numba", signature = (high, low, close, n=14, multiplier=2.0, pct=false))]
+pub fn atr_bands<'py>(
+    py: Python<'py>,
+    high: PyReadonlyArray1<'py, f64>,
+    low: PyReadonlyArray1<'py, f64>,
+    close: PyReadonlyArray1<'py, f64>,
+    n: usize,
+    multiplier: f64,
+    pct: bool,
+) -> PyResult<(Bound<'py, PyArray1<f64>>, Bound<'py, PyArray1<f64>>, Bound<'py, PyArray1<f64>>)> {
+    let high_slice = high.as_slice()?;
+    let low_slice = low.as_slice()?;
+    let close_slice = close.as_slice()?;
+    check_equal_lengths(&[
+        ("high", high_slice.len()),
+        ("low", low_slice.len()),
+        ("close", close_slice.len()),
+    ])
+    .map_err(PyValueError::new_err)?;
+
+    let (upper, middle, lower) = atr_bands_kernel(high_slice, low_slice, close_slice, n, multiplier);
+    let (upper, lower) = if pct { band_pct_kernel(&upper, &middle, &lower) } else { (upper, lower) };
+
     Ok((
         PyArray1::from_vec(py, upper),
         PyArray1::from_vec(py, middle),
@@ -136,23 +301,47 @@ pub fn keltner_channel<'py>(
     ))
 }
 
+/// Donchian position kernel: close's position within the channel (0..1) and the
+/// channel width. Position is NaN where the channel has zero width.
+pub fn donchian_position_kernel(close: &[f64], upper: &[f64], lower: &[f64]) -> (Vec<f64>, Vec<f64>) {
+    let len = close.len();
+    let mut position = vec![f64::NAN; len];
+    let mut width = vec![f64::NAN; len];
+
+    for i in 0..len {
+        if !upper[i].is_nan() && !lower[i].is_nan() {
+            width[i] = upper[i] - lower[i];
+            if width[i] != 0.0 {
+                position[i] = (close[i] - lower[i]) / width[i];
+            }
+        }
+    }
+
+    (position, width)
+}
+
 /// Donchian Channel
 ///
 /// # Arguments
 /// * `high` - High price series
 /// * `low` - Low price series
 /// * `n` - Period for channel calculation (default: 20)
+/// * `close` - Close price series, required when `return_position` is true (default: None)
+/// * `return_position` - Also return close's position in the channel and the channel width (default: false)
 ///
 /// # Returns
-/// Tuple of (upper_band, middle_band, lower_band) as numpy arrays
+/// Tuple of (upper_band, middle_band, lower_band) as numpy arrays, or
+/// (upper_band, middle_band, lower_band, position, width) when `return_position` is true
 #[pyfunction]
-#[pyo3(name = "donchian_channel_numba", signature = (high, low, n=20))]
+#[pyo3(name = "donchian_channel_numba", signature = (high, low, n=20, close=None, return_position=false))]
 pub fn donchian_channel<'py>(
     py: Python<'py>,
     high: PyReadonlyArray1<'py, f64>,
     low: PyReadonlyArray1<'py, f64>,
     n: usize,
-) -> PyResult<(Bound<'py, PyArray1<f64>>, Bound<'py, PyArray1<f64>>, Bound<'py, PyArray1<f64>>)> {
+    close: Option<PyReadonlyArray1<'py, f64>>,
+    return_position: bool,
+) -> PyResult<Bound<'py, PyTuple>> {
     let high_slice = high.as_slice()?;
     let low_slice = low.as_slice()?;
     let len = high_slice.len();
@@ -167,11 +356,62 @@ pub fn donchian_channel<'py>(
         }
     }
 
-    Ok((
-        PyArray1::from_vec(py, upper),
-        PyArray1::from_vec(py, middle),
-        PyArray1::from_vec(py, lower),
-    ))
+    let mut outputs = Vec::with_capacity(5);
+    if return_position {
+        let close = close.ok_or_else(|| {
+            PyValueError::new_err("close is required when return_position is true")
+        })?;
+        let close_slice = close.as_slice()?;
+        check_equal_lengths(&[
+            ("high", len),
+            ("low", low_slice.len()),
+            ("close", close_slice.len()),
+        ])
+        .map_err(PyValueError::new_err)?;
+
+        let (position, width) = donchian_position_kernel(close_slice, &upper, &lower);
+        outputs.push(PyArray1::from_vec(py, upper));
+        outputs.push(PyArray1::from_vec(py, middle));
+        outputs.push(PyArray1::from_vec(py, lower));
+        outputs.push(PyArray1::from_vec(py, position));
+        outputs.push(PyArray1::from_vec(py, width));
+    } else {
+        outputs.push(PyArray1::from_vec(py, upper));
+        outputs.push(PyArray1::from_vec(py, middle));
+        outputs.push(PyArray1::from_vec(py, lower));
+    }
+
+    PyTuple::new(py, outputs)
+}
+
+/// Ulcer Index kernel: RMS over n bars of the percent drawdown from the n-bar high
+pub fn ulcer_index_kernel(close: &[f64], n: usize) -> Vec<f64> {
+    let len = close.len();
+    let mut ui = vec![f64::NAN; len];
+
+    if n == 0 || n > len {
+        return ui;
+    }
+
+    let mut pct_drawdown_sq = vec![0.0; len];
+
+    for i in 1..len {
+        let start_idx = if i >= n { i - n + 1 } else { 0 };
+        let max_close = close[start_idx..=i]
+            .iter()
+            .copied()
+            .fold(f64::NEG_INFINITY, f64::max);
+
+        let pct_dd = ((close[i] - max_close) / max_close) * 100.0;
+        pct_drawdown_sq[i] = pct_dd * pct_dd;
+    }
+
+    for i in (n - 1)..len {
+        let mean_sq: f64 = pct_drawdown_sq[(i + 1 - n)..=i].iter().sum::<f64>() / n as f64;
+        ui[i] = mean_sq.sqrt();
+    }
+
+    ui
 }
 
 /// Ulcer Index
@@ -190,26 +430,1268 @@ pub fn ulcer_index<'py>(
     n: usize,
 ) -> PyResult<Bound<'py, PyArray1<f64>>> {
     let close_slice = close.as_slice()?;
-    let len = close_slice.len();
+    let ui = ulcer_index_kernel(close_slice, n);
+    Ok(PyArray1::from_vec(py, ui))
+}
+
+/// Standard Error Bands kernel: regression endpoint ± k * standard error.
+/// The standard error uses n - 2 degrees of freedom, so windows below 3 yield NaN.
+pub fn standard_error_bands_kernel(close: &[f64], n: usize, k: f64) -> (Vec<f64>, Vec<f64>, Vec<f64>) {
+    let len = close.len();
+    let mut upper = vec![f64::NAN; len];
+    let mut middle = vec![f64::NAN; len];
+    let mut lower = vec![f64::NAN; len];
+
+    if n < 3 {
+        return (upper, middle, lower);
+    }
+
+    let (slope, intercept, sse) = rolling_linreg(close, n);
+    let x_end = (n - 1) as f64;
+
+    for i in 0..len {
+        if !slope[i].is_nan() {
+            let endpoint = intercept[i] + slope[i] * x_end;
+            let std_error = (sse[i] / (n - 2) as f64).sqrt();
+            upper[i] = endpoint + k * std_error;
+            middle[i] = endpoint;
+            lower[i] = endpoint - k * std_error;
+        }
+    }
+
+    (upper, middle, lower)
+}
+
+/// Standard Error Bands
+///
+/// # Arguments
+/// * `close` - Close price series
+/// * `n` - Regression window (default: 21)
+/// * `k` - Number of standard errors (default: 2.0)
+///
+/// # Returns
+/// Tuple of (upper_band, middle_band, lower_band) as numpy arrays.
+/// Middle is the linear regression endpoint value of each window.
+#[pyfunction]
+#[pyo3(name = "standard_error_bands_numba", signature = (close, n=21, k=2.0))]
+pub fn standard_error_bands<'py>(
+    py: Python<'py>,
+    close: PyReadonlyArray1<'py, f64>,
+    n: usize,
+    k: f64,
+) -> PyResult<(Bound<'py, PyArray1<f64>>, Bound<'py, PyArray1<f64>>, Bound<'py, PyArray1<f64>>)> {
+    let close_slice = close.as_slice()?;
+    let (upper, middle, lower) = standard_error_bands_kernel(close_slice, n, k);
 
-    let mut pct_drawdown_sq = vec![0.0; len];
+    Ok((
+        PyArray1::from_vec(py, upper),
+        PyArray1::from_vec(py, middle),
+        PyArray1::from_vec(py, lower),
+    ))
+}
 
-    for i in 1..len {
-        let start_idx = if i >= n { i - n + 1 } else { 0 };
-        let max_close = close_slice[start_idx..=i]
-            .iter()
-            .copied()
-            .fold(f64::NEG_INFINITY, f64::max);
+/// Regression channel kernel: regression endpoint ± k * std of the window residuals.
+/// Unlike standard error bands the residual std uses the population divisor `n`.
+pub fn regression_channel_kernel(close: &[f64], n: usize, k: f64) -> (Vec<f64>, Vec<f64>, Vec<f64>) {
+    let len = close.len();
+    let mut upper = vec![f64::NAN; len];
+    let mut middle = vec![f64::NAN; len];
+    let mut lower = vec![f64::NAN; len];
 
-        let pct_dd = ((close_slice[i] - max_close) / max_close) * 100.0;
-        pct_drawdown_sq[i] = pct_dd * pct_dd;
+    if n < 2 {
+        return (upper, middle, lower);
     }
 
-    let mut ui = vec![f64::NAN; len];
-    for i in (n - 1)..len {
-        let mean_sq: f64 = pct_drawdown_sq[(i + 1 - n)..=i].iter().sum::<f64>() / n as f64;
-        ui[i] = mean_sq.sqrt();
+    let (slope, intercept, sse) = rolling_linreg(close, n);
+    let x_end = (n - 1) as f64;
+
+    for i in 0..len {
+        if !slope[i].is_nan() {
+            let endpoint = intercept[i] + slope[i] * x_end;
+            let residual_std = (sse[i] / n as f64).sqrt();
+            upper[i] = endpoint + k * residual_std;
+            middle[i] = endpoint;
+            lower[i] = endpoint - k * residual_std;
+        }
     }
 
-    Ok(PyArray1::from_vec(py, ui))
+    (upper, middle, lower)
+}
+
+/// Linear Regression Channel
+///
+/// # Arguments
+/// * `close` - Close price series
+/// * `n` - Regression window (default: 100)
+/// * `k` - Number of residual standard deviations (default: 2.0)
+///
+/// # Returns
+/// Tuple of (upper_band, middle_band, lower_band) as numpy arrays.
+/// Middle is the linear regression endpoint value of each window.
+#[pyfunction]
+#[pyo3(name = "regression_channel_numba", signature = (close, n=100, k=2.0))]
+pub fn regression_channel<'py>(
+    py: Python<'py>,
+    close: PyReadonlyArray1<'py, f64>,
+    n: usize,
+    k: f64,
+) -> PyResult<(Bound<'py, PyArray1<f64>>, Bound<'py, PyArray1<f64>>, Bound<'py, PyArray1<f64>>)> {
+    let close_slice = close.as_slice()?;
+    let (upper, middle, lower) = regression_channel_kernel(close_slice, n, k);
+
+    Ok((
+        PyArray1::from_vec(py, upper),
+        PyArray1::from_vec(py, middle),
+        PyArray1::from_vec(py, lower),
+    ))
+}
+
+/// ATR trailing stop kernel. Sequential by nature: starts long on the first bar with
+/// an ATR, then ratchets or flips via `trailing_stop_step`. Returns (stop, direction)
+/// with direction 1.0 long / -1.0 short, both NaN during ATR warmup.
+pub fn atr_trailing_stop_kernel(
+    high: &[f64],
+    low: &[f64],
+    close: &[f64],
+    atr_n: usize,
+    multiplier: f64,
+) -> (Vec<f64>, Vec<f64>) {
+    let len = close.len();
+    let mut stop = vec![f64::NAN; len];
+    let mut direction = vec![f64::NAN; len];
+
+    let atr_values = wilders_ema_kernel(&true_range(high, low, close), atr_n);
+
+    let mut prev: Option<(f64, f64)> = None;
+    for i in 0..len {
+        if atr_values[i].is_nan() {
+            continue;
+        }
+        let offset = multiplier * atr_values[i];
+        let next = match prev {
+            None => (close[i] - offset, 1.0),
+            Some((prev_stop, prev_dir)) => trailing_stop_step(prev_stop, prev_dir, close[i], offset),
+        };
+        (stop[i], direction[i]) = next;
+        prev = Some(next);
+    }
+
+    (stop, direction)
+}
+
+/// ATR Trailing Stop
+///
+/// # Arguments
+/// * `high` - High price series
+/// * `low` - Low price series
+/// * `close` - Close price series
+/// * `atr_n` - ATR period (default: 14)
+/// * `multiplier` - Stop distance in ATRs (default: 3.0)
+///
+/// # Returns
+/// Tuple of (stop, direction) as numpy arrays; direction is 1.0 long, -1.0 short
+#[pyfunction]
+#[pyo3(name = "atr_trailing_stop_numba", signature = (high, low, close, atr_n=14, multiplier=3.0))]
+pub fn atr_trailing_stop<'py>(
+    py: Python<'py>,
+    high: PyReadonlyArray1<'py, f64>,
+    low: PyReadonlyArray1<'py, f64>,
+    close: PyReadonlyArray1<'py, f64>,
+    atr_n: usize,
+    multiplier: f64,
+) -> PyResult<(Bound<'py, PyArray1<f64>>, Bound<'py, PyArray1<f64>>)> {
+    let high_slice = high.as_slice()?;
+    let low_slice = low.as_slice()?;
+    let close_slice = close.as_slice()?;
+    check_equal_lengths(&[
+        ("high", high_slice.len()),
+        ("low", low_slice.len()),
+        ("close", close_slice.len()),
+    ])
+    .map_err(PyValueError::new_err)?;
+
+    let (stop, direction) = atr_trailing_stop_kernel(high_slice, low_slice, close_slice, atr_n, multiplier);
+    Ok((PyArray1::from_vec(py, stop), PyArray1::from_vec(py, direction)))
+}
+
+/// Volatility-scaled signal kernel: signal * target_vol / ATR, NaN while the
+/// ATR warms up and where it is zero (no range to scale by)
+pub fn volatility_scaled_kernel(
+    signal: &[f64],
+    high: &[f64],
+    low: &[f64],
+    close: &[f64],
+    atr_n: usize,
+    target_vol: f64,
+) -> Vec<f64> {
+    let len = signal.len();
+    let tr = true_range(high, low, close);
+    let atr_values = wilders_ema_kernel(&tr, atr_n);
+
+    let mut scaled = vec![f64::NAN; len];
+    for i in 0..len {
+        if !atr_values[i].is_nan() && atr_values[i] != 0.0 {
+            scaled[i] = signal[i] * target_vol / atr_values[i];
+        }
+    }
+
+    scaled
+}
+
+/// Volatility-Scaled Signal (ATR-normalized position sizing)
+///
+/// # Arguments
+/// * `signal` - Arbitrary signal series (same length as prices)
+/// * `high` - High price series
+/// * `low` - Low price series
+/// * `close` - Close price series
+/// * `atr_n` - ATR period (default: 14)
+/// * `target_vol` - Target volatility in price units (default: 1.0)
+///
+/// # Returns
+/// Numpy array with signal * target_vol / ATR (NaN during ATR warmup and where ATR is 0)
+#[pyfunction]
+#[pyo3(name = "volatility_scaled_numba", signature = (signal, high, low, close, atr_n=14, target_vol=1.0))]
+pub fn volatility_scaled<'py>(
+    py: Python<'py>,
+    signal: PyReadonlyArray1<'py, f64>,
+    high: PyReadonlyArray1<'py, f64>,
+    low: PyReadonlyArray1<'py, f64>,
+    close: PyReadonlyArray1<'py, f64>,
+    atr_n: usize,
+    target_vol: f64,
+) -> PyResult<Bound<'py, PyArray1<f64>>> {
+    let signal_slice = signal.as_slice()?;
+    let high_slice = high.as_slice()?;
+    let low_slice = low.as_slice()?;
+    let close_slice = close.as_slice()?;
+
+    check_equal_lengths(&[
+        ("signal", signal_slice.len()),
+        ("high", high_slice.len()),
+        ("low", low_slice.len()),
+        ("close", close_slice.len()),
+    ])
+    .map_err(PyValueError::new_err)?;
+
+    let result = volatility_scaled_kernel(signal_slice, high_slice, low_slice, close_slice, atr_n, target_vol);
+    Ok(PyArray1::from_vec(py, result))
+}
+
+/// ATR Keltner Channel kernel: EMA(close, n) ± mult * ATR(atr_n), with an unadjusted
+/// EMA and Wilder's ATR seeded by the mean of the first atr_n true ranges.
+/// - `Compat::Native` matches `KeltnerChannelStreaming`: the middle starts on the first
+///   bar and the bands are NaN until the ATR seeds
+/// - `Compat::Ta` matches `ta.volatility.KeltnerChannel(original_version=False)`: the
+///   middle is NaN for the first n-1 bars (`min_periods=n`) and the ATR is 0.0 before
+///   its seed, so the bands collapse onto the middle when atr_n > n
+pub fn keltner_atr_kernel(
+    high: &[f64],
+    low: &[f64],
+    close: &[f64],
+    n: usize,
+    atr_n: usize,
+    mult: f64,
+    compat: Compat,
+) -> (Vec<f64>, Vec<f64>, Vec<f64>) {
+    let mut middle = ema_kernel(close, 2.0 / (n as f64 + 1.0), false);
+    if compat == Compat::Ta {
+        mask_warmup(&mut middle, n.saturating_sub(1));
+    }
+    let atr_values = atr_compat_kernel(high, low, close, atr_n, compat);
+
+    let upper = middle.iter().zip(&atr_values).map(|(m, a)| m + mult * a).collect();
+    let lower = middle.iter().zip(&atr_values).map(|(m, a)| m - mult * a).collect();
+
+    (upper, middle, lower)
+}
+
+/// Bollinger squeeze kernel: 1.0 when both Bollinger Bands sit inside the
+/// Keltner Channel, 0.0 otherwise (including warmup)
+pub fn bollinger_squeeze_kernel(
+    close: &[f64],
+    high: &[f64],
+    low: &[f64],
+    n: usize,
+    k: f64,
+    kc_mult: f64,
+    kc_n: usize,
+) -> Vec<f64> {
+    let (bb_upper, _, bb_lower) = bollinger_bands_kernel(close, n, k);
+    let (kc_upper, _, kc_lower) = keltner_atr_kernel(high, low, close, kc_n, kc_n, kc_mult, Compat::Native);
+
+    // NaN comparisons are false, so warmup bars stay at 0
+    (0..close.len())
+        .map(|i| if bb_upper[i] < kc_upper[i] && bb_lower[i] > kc_lower[i] { 1.0 } else { 0.0 })
+        .collect()
+}
+
+/// Channel position kernel: (close - lower) / (upper - lower) within Bollinger
+/// Bands or the ATR Keltner Channel of `keltner_channel_numba(original_version=False)`.
+/// NaN where the band collapses; values are clamped to `clamp` when given. Keltner
+/// needs `high` and `low`.
+pub fn channel_position_kernel(
+    close: &[f64],
+    high: Option<&[f64]>,
+    low: Option<&[f64]>,
+    n: usize,
+    k: f64,
+    channel: &str,
+    clamp: Option<(f64, f64)>,
+) -> Result<Vec<f64>, String> {
+    let (upper, _, lower) = match channel {
+        "bollinger" => bollinger_bands_kernel(close, n, k),
+        "keltner" => match (high, low) {
+            (Some(high), Some(low)) => keltner_atr_kernel(high, low, close, n, n, k, Compat::Ta),
+            _ => return Err("high and low are required for the keltner channel".to_string()),
+        },
+        other => {
+            return Err(format!("unknown channel '{}', expected one of: bollinger, keltner", other))
+        }
+    };
+
+    let (mut position, _) = donchian_position_kernel(close, &upper, &lower);
+
+    if let Some((lo, hi)) = clamp {
+        if lo > hi {
+            return Err(format!("clamp lower bound {} exceeds upper bound {}", lo, hi));
+        }
+        for value in position.iter_mut().filter(|v| !v.is_nan()) {
+            *value = value.clamp(lo, hi);
+        }
+    }
+
+    Ok(position)
+}
+
+/// Channel Position
+///
+/// # Arguments
+/// * `close` - Close price series
+/// * `n` - Band period (default: 20)
+/// * `k` - Band width in standard deviations (Bollinger) or ATRs (Keltner) (default: 2.0)
+/// * `channel` - "bollinger" or "keltner" (default: "bollinger")
+/// * `high` - High price series, required for "keltner" (default: None)
+/// * `low` - Low price series, required for "keltner" (default: None)
+/// * `clamp` - Optional (min, max) range to clamp the position to (default: None)
+///
+/// # Returns
+/// Numpy array with close's position in the channel (0 at lower, 1 at upper),
+/// NaN where the band has zero width
+#[pyfunction]
+#[pyo3(name = "channel_position_numba", signature = (close, n=20, k=2.0, channel="bollinger", high=None, low=None, clamp=None))]
+pub fn channel_position<'py>(
+    py: Python<'py>,
+    close: PyReadonlyArray1<'py, f64>,
+    n: usize,
+    k: f64,
+    channel: &str,
+    high: Option<PyReadonlyArray1<'py, f64>>,
+    low: Option<PyReadonlyArray1<'py, f64>>,
+    clamp: Option<(f64, f64)>,
+) -> PyResult<Bound<'py, PyArray1<f64>>> {
+    let close_slice = close.as_slice()?;
+    let high_slice = high.as_ref().map(|h| h.as_slice()).transpose()?;
+    let low_slice = low.as_ref().map(|l| l.as_slice()).transpose()?;
+
+    let mut lengths = vec![("close", close_slice.len())];
+    if let Some(h) = high_slice {
+        lengths.push(("high", h.len()));
+    }
+    if let Some(l) = low_slice {
+        lengths.push(("low", l.len()));
+    }
+    check_equal_lengths(&lengths).map_err(PyValueError::new_err)?;
+
+    let result = channel_position_kernel(close_slice, high_slice, low_slice, n, k, channel, clamp)
+        .map_err(PyValueError::new_err)?;
+    Ok(PyArray1::from_vec(py, result))
+}
+
+/// Bollinger Squeeze (TTM squeeze)
+///
+/// # Arguments
+/// * `close` - Close price series
+/// * `high` - High price series
+/// * `low` - Low price series
+/// * `n` - Bollinger Bands period (default: 20)
+/// * `k` - Bollinger Bands standard deviations (default: 2.0)
+/// * `kc_mult` - Keltner Channel ATR multiplier (default: 1.5)
+/// * `kc_n` - Keltner Channel EMA and ATR period (default: 20)
+///
+/// # Returns
+/// Numpy array with 1.0 where the squeeze is on and 0.0 elsewhere
+#[pyfunction]
+#[pyo3(name = "bollinger_squeeze_numba", signature = (close, high, low, n=20, k=2.0, kc_mult=1.5, kc_n=20))]
+pub fn bollinger_squeeze<'py>(
+    py: Python<'py>,
+    close: PyReadonlyArray1<'py, f64>,
+    high: PyReadonlyArray1<'py, f64>,
+    low: PyReadonlyArray1<'py, f64>,
+    n: usize,
+    k: f64,
+    kc_mult: f64,
+    kc_n: usize,
+) -> PyResult<Bound<'py, PyArray1<f64>>> {
+    let close_slice = close.as_slice()?;
+    let high_slice = high.as_slice()?;
+    let low_slice = low.as_slice()?;
+
+    check_equal_lengths(&[
+        ("close", close_slice.len()),
+        ("high", high_slice.len()),
+        ("low", low_slice.len()),
+    ])
+    .map_err(PyValueError::new_err)?;
+
+    let result = bollinger_squeeze_kernel(close_slice, high_slice, low_slice, n, k, kc_mult, kc_n);
+    Ok(PyArray1::from_vec(py, result))
+}
+
+/// Chaikin Volatility kernel: ROC(EMA(high - low, n_ema), n_roc)
+pub fn chaikin_volatility_kernel(high: &[f64], low: &[f64], n_ema: usize, n_roc: usize) -> Vec<f64> {
+    let hl_range: Vec<f64> = high.iter().zip(low).map(|(h, l)| h - l).collect();
+    let range_ema = ema_kernel(&hl_range, 2.0 / (n_ema as f64 + 1.0), false);
+    roc_kernel(&range_ema, n_roc, true)
+}
+
+/// Chaikin Volatility
+///
+/// # Arguments
+/// * `high` - High price series
+/// * `low` - Low price series
+/// * `n_ema` - EMA period for the high-low range (default: 10)
+/// * `n_roc` - Rate of change period (default: 10)
+///
+/// # Returns
+/// Numpy array with Chaikin Volatility values (percentage, NaN for first n_roc elements)
+#[pyfunction]
+#[pyo3(name = "chaikin_volatility_numba", signature = (high, low, n_ema=10, n_roc=10))]
+pub fn chaikin_volatility<'py>(
+    py: Python<'py>,
+    high: PyReadonlyArray1<'py, f64>,
+    low: PyReadonlyArray1<'py, f64>,
+    n_ema: usize,
+    n_roc: usize,
+) -> PyResult<Bound<'py, PyArray1<f64>>> {
+    let high_slice = high.as_slice()?;
+    let low_slice = low.as_slice()?;
+
+    check_equal_lengths(&[("high", high_slice.len()), ("low", low_slice.len())])
+        .map_err(PyValueError::new_err)?;
+
+    let result = chaikin_volatility_kernel(high_slice, low_slice, n_ema, n_roc);
+    Ok(PyArray1::from_vec(py, result))
+}
+
+/// Pivot levels from the previous bar, one Vec per level (pivot first).
+/// "classic": P, R1-R3, S1-S3; "fibonacci": P ± 0.382/0.618/1.0 * range.
+pub fn pivot_levels_kernel(high: &[f64], low: &[f64], close: &[f64], method: &str) -> Result<Vec<Vec<f64>>, String> {
+    let len = close.len();
+    let n_levels = match method {
+        "classic" | "fibonacci" => 7,
+        other => return Err(format!("unknown pivot method '{}', expected one of: classic, fibonacci", other)),
+    };
+    let mut levels = vec![vec![f64::NAN; len]; n_levels];
+
+    for i in 1..len {
+        let (h, l, c) = (high[i - 1], low[i - 1], close[i - 1]);
+        let pivot = (h + l + c) / 3.0;
+        let range = h - l;
+
+        let values = if method == "classic" {
+            [
+                pivot,
+                2.0 * pivot - l,
+                pivot + range,
+                h + 2.0 * (pivot - l),
+                2.0 * pivot - h,
+                pivot - range,
+                l - 2.0 * (h - pivot),
+            ]
+        } else {
+            [
+                pivot,
+                pivot + 0.382 * range,
+                pivot + 0.618 * range,
+                pivot + range,
+                pivot - 0.382 * range,
+                pivot - 0.618 * range,
+                pivot - range,
+            ]
+        };
+
+        for (level, value) in levels.iter_mut().zip(values) {
+            level[i] = value;
+        }
+    }
+
+    Ok(levels)
+}
+
+/// Pivot distance kernel: signed (close - nearest pivot level) / ATR
+pub fn pivot_distance_kernel(
+    high: &[f64],
+    low: &[f64],
+    close: &[f64],
+    method: &str,
+    atr_n: usize,
+) -> Result<Vec<f64>, String> {
+    let len = close.len();
+    let levels = pivot_levels_kernel(high, low, close, method)?;
+    let atr_values = wilders_ema_kernel(&true_range(high, low, close), atr_n);
+
+    let mut distance = vec![f64::NAN; len];
+    for i in 0..len {
+        if atr_values[i].is_nan() || atr_values[i] == 0.0 || levels[0][i].is_nan() {
+            continue;
+        }
+        let nearest = levels.iter()
+            .map(|level| close[i] - level[i])
+            .fold(f64::NAN, |best: f64, d| if best.is_nan() || d.abs() < best.abs() { d } else { best });
+        distance[i] = nearest / atr_values[i];
+    }
+
+    Ok(distance)
+}
+
+/// Pivot Distance
+///
+/// # Arguments
+/// * `high` - High price series
+/// * `low` - Low price series
+/// * `close` - Close price series
+/// * `method` - Pivot method: "classic" or "fibonacci" (default: "classic")
+/// * `atr_n` - ATR period used for normalization (default: 14)
+///
+/// # Returns
+/// Numpy array with the signed distance from close to the nearest pivot level,
+/// in ATR units (NaN during ATR warmup)
+#[pyfunction]
+#[pyo3(name = "pivot_distance_numba", signature = (high, low, close, method="classic", atr_n=14))]
+pub fn pivot_distance<'py>(
+    py: Python<'py>,
+    high: PyReadonlyArray1<'py, f64>,
+    low: PyReadonlyArray1<'py, f64>,
+    close: PyReadonlyArray1<'py, f64>,
+    method: &str,
+    atr_n: usize,
+) -> PyResult<Bound<'py, PyArray1<f64>>> {
+    let high_slice = high.as_slice()?;
+    let low_slice = low.as_slice()?;
+    let close_slice = close.as_slice()?;
+
+    check_equal_lengths(&[
+        ("high", high_slice.len()),
+        ("low", low_slice.len()),
+        ("close", close_slice.len()),
+    ])
+    .map_err(PyValueError::new_err)?;
+
+    let result = pivot_distance_kernel(high_slice, low_slice, close_slice, method, atr_n)
+        .map_err(PyValueError::new_err)?;
+    Ok(PyArray1::from_vec(py, result))
+}
+
+/// Moving average envelope kernel: middle = the `ma_type` MA (unadjusted EMA),
+/// bands = middle * (1 +/- pct / 100). Returns (upper, middle, lower).
+pub fn ma_envelope_kernel(
+    close: &[f64],
+    n: usize,
+    pct: f64,
+    ma_type: &str,
+) -> Result<(Vec<f64>, Vec<f64>, Vec<f64>), String> {
+    let middle = moving_average_kernel(close, n, ma_type, false)?;
+    let upper = middle.iter().map(|m| m * (1.0 + pct / 100.0)).collect();
+    let lower = middle.iter().map(|m| m * (1.0 - pct / 100.0)).collect();
+
+    Ok((upper, middle, lower))
+}
+
+/// Moving Average Envelope
+///
+/// # Arguments
+/// * `close` - Close price series
+/// * `n` - Moving average period (default: 20)
+/// * `pct` - Band offset in percent of the middle line (default: 2.5)
+/// * `ma_type` - Middle line: "sma", "ema" or "wma" (default: "sma")
+///
+/// # Returns
+/// Tuple of (upper_band, middle_band, lower_band) as numpy arrays
+#[pyfunction]
+#[pyo3(name = "ma_envelope_numba", signature = (close, n=20, pct=2.5, ma_type="sma"))]
+pub fn ma_envelope<'py>(
+    py: Python<'py>,
+    close: PyReadonlyArray1<'py, f64>,
+    n: usize,
+    pct: f64,
+    ma_type: &str,
+) -> PyResult<(Bound<'py, PyArray1<f64>>, Bound<'py, PyArray1<f64>>, Bound<'py, PyArray1<f64>>)> {
+    let close_slice = close.as_slice()?;
+    let (upper, middle, lower) =
+        ma_envelope_kernel(close_slice, n, pct, ma_type).map_err(PyValueError::new_err)?;
+
+    Ok((
+        PyArray1::from_vec(py, upper),
+        PyArray1::from_vec(py, middle),
+        PyArray1::from_vec(py, lower),
+    ))
+}
+
+/// Rolling volatility from per-bar variance terms: sqrt of the window mean,
+/// scaled by sqrt(periods_per_year) when annualizing
+fn range_volatility(terms: &[f64], n: usize, annualize: bool, periods_per_year: f64) -> Vec<f64> {
+    let scale = if annualize { periods_per_year.sqrt() } else { 1.0 };
+    rolling_sum(terms, n)
+        .into_iter()
+        .map(|sum| (sum / n as f64).sqrt() * scale)
+        .collect()
+}
+
+/// Parkinson volatility kernel: sigma^2 = mean(ln(H/L)^2) / (4 ln 2)
+pub fn parkinson_volatility_kernel(
+    high: &[f64],
+    low: &[f64],
+    n: usize,
+    annualize: bool,
+    periods_per_year: f64,
+) -> Vec<f64> {
+    let factor = 1.0 / (4.0 * std::f64::consts::LN_2);
+    let terms: Vec<f64> = high
+        .iter()
+        .zip(low)
+        .map(|(&h, &l)| factor * (h / l).ln().powi(2))
+        .collect();
+
+    range_volatility(&terms, n, annualize, periods_per_year)
+}
+
+/// Garman-Klass volatility kernel:
+/// sigma^2 = mean(0.5 * ln(H/L)^2 - (2 ln 2 - 1) * ln(C/O)^2)
+pub fn garman_klass_volatility_kernel(
+    open: &[f64],
+    high: &[f64],
+    low: &[f64],
+    close: &[f64],
+    n: usize,
+    annualize: bool,
+    periods_per_year: f64,
+) -> Vec<f64> {
+    let oc_factor = 2.0 * std::f64::consts::LN_2 - 1.0;
+    let terms: Vec<f64> = (0..close.len())
+        .map(|i| {
+            0.5 * (high[i] / low[i]).ln().powi(2) - oc_factor * (close[i] / open[i]).ln().powi(2)
+        })
+        .collect();
+
+    range_volatility(&terms, n, annualize, periods_per_year)
+}
+
+/// Parkinson Volatility
+///
+/// # Arguments
+/// * `high` - High price series
+/// * `low` - Low price series
+/// * `n` - Rolling window size (default: 20)
+/// * `annualize` - Scale the per-bar volatility by sqrt(periods per year) (default: true)
+/// * `bar_frequency` - Bar frequency for annualization: "daily" (252), "hourly" (252 * 6.5),
+///   "minute" (252 * 390) or "crypto_daily" (365) (default: "daily")
+/// * `periods_per_year` - Overrides the `bar_frequency` preset (default: None)
+///
+/// # Returns
+/// Numpy array with volatility as a fraction (0.2 = 20%), NaN for the first n-1 elements
+#[pyfunction]
+#[pyo3(name = "parkinson_volatility_numba", signature = (high, low, n=20, annualize=true, bar_frequency="daily", periods_per_year=None))]
+pub fn parkinson_volatility<'py>(
+    py: Python<'py>,
+    high: PyReadonlyArray1<'py, f64>,
+    low: PyReadonlyArray1<'py, f64>,
+    n: usize,
+    annualize: bool,
+    bar_frequency: &str,
+    periods_per_year: Option<f64>,
+) -> PyResult<Bound<'py, PyArray1<f64>>> {
+    let periods_per_year =
+        resolve_periods_per_year(bar_frequency, periods_per_year).map_err(PyValueError::new_err)?;
+    let high_slice = high.as_slice()?;
+    let low_slice = low.as_slice()?;
+
+    check_equal_lengths(&[("high", high_slice.len()), ("low", low_slice.len())])
+        .map_err(PyValueError::new_err)?;
+
+    let result = parkinson_volatility_kernel(high_slice, low_slice, n, annualize, periods_per_year);
+    Ok(PyArray1::from_vec(py, result))
+}
+
+/// Garman-Klass Volatility
+///
+/// # Arguments
+/// * `open` - Open price series
+/// * `high` - High price series
+/// * `low` - Low price series
+/// * `close` - Close price series
+/// * `n` - Rolling window size (default: 20)
+/// * `annualize` - Scale the per-bar volatility by sqrt(periods per year) (default: true)
+/// * `bar_frequency` - Bar frequency for annualization: "daily" (252), "hourly" (252 * 6.5),
+///   "minute" (252 * 390) or "crypto_daily" (365) (default: "daily")
+/// * `periods_per_year` - Overrides the `bar_frequency` preset (default: None)
+///
+/// # Returns
+/// Numpy array with volatility as a fraction (0.2 = 20%), NaN for the first n-1 elements
+#[pyfunction]
+#[pyo3(name = "garman_klass_volatility_numba", signature = (open, high, low, close, n=20, annualize=true, bar_frequency="daily", periods_per_year=None))]
+pub fn garman_klass_volatility<'py>(
+    py: Python<'py>,
+    open: PyReadonlyArray1<'py, f64>,
+    high: PyReadonlyArray1<'py, f64>,
+    low: PyReadonlyArray1<'py, f64>,
+    close: PyReadonlyArray1<'py, f64>,
+    n: usize,
+    annualize: bool,
+    bar_frequency: &str,
+    periods_per_year: Option<f64>,
+) -> PyResult<Bound<'py, PyArray1<f64>>> {
+    let periods_per_year =
+        resolve_periods_per_year(bar_frequency, periods_per_year).map_err(PyValueError::new_err)?;
+    let open_slice = open.as_slice()?;
+    let high_slice = high.as_slice()?;
+    let low_slice = low.as_slice()?;
+    let close_slice = close.as_slice()?;
+
+    check_equal_lengths(&[
+        ("open", open_slice.len()),
+        ("high", high_slice.len()),
+        ("low", low_slice.len()),
+        ("close", close_slice.len()),
+    ])
+    .map_err(PyValueError::new_err)?;
+
+    let result =
+        garman_klass_volatility_kernel(open_slice, high_slice, low_slice, close_slice, n, annualize, periods_per_year);
+    Ok(PyArray1::from_vec(py, result))
+}
+
+/// ATR percentile kernel: rolling percent-rank of Wilder's ATR within its last
+/// `rank_n` values, NaN until `rank_n` ATR values are available
+pub fn atr_percentile_kernel(
+    high: &[f64],
+    low: &[f64],
+    close: &[f64],
+    atr_n: usize,
+    rank_n: usize,
+) -> Vec<f64> {
+    let tr = true_range(high, low, close);
+    let atr_values = wilders_ema_kernel(&tr, atr_n);
+    let mut result = rolling_percentile_kernel(&atr_values, rank_n);
+
+    // NaN compares false in the rank, so windows reaching into the ATR warmup are dropped
+    let first = atr_values.iter().position(|v| !v.is_nan()).unwrap_or(atr_values.len());
+    mask_warmup(&mut result, (first + rank_n).saturating_sub(1));
+
+    result
+}
+
+/// ATR Percentile (volatility regime)
+///
+/// # Arguments
+/// * `high` - High price series
+/// * `low` - Low price series
+/// * `close` - Close price series
+/// * `atr_n` - ATR period (default: 14)
+/// * `rank_n` - Percent-rank lookback in bars (default: 100)
+///
+/// # Returns
+/// Numpy array with the fraction of the last `rank_n` ATR values that are <= the
+/// current ATR (0.0 to 1.0)
+#[pyfunction]
+#[pyo3(name = "atr_percentile_numba", signature = (high, low, close, atr_n=14, rank_n=100))]
+pub fn atr_percentile<'py>(
+    py: Python<'py>,
+    high: PyReadonlyArray1<'py, f64>,
+    low: PyReadonlyArray1<'py, f64>,
+    close: PyReadonlyArray1<'py, f64>,
+    atr_n: usize,
+    rank_n: usize,
+) -> PyResult<Bound<'py, PyArray1<f64>>> {
+    let high_slice = high.as_slice()?;
+    let low_slice = low.as_slice()?;
+    let close_slice = close.as_slice()?;
+
+    check_equal_lengths(&[
+        ("high", high_slice.len()),
+        ("low", low_slice.len()),
+        ("close", close_slice.len()),
+    ])
+    .map_err(PyValueError::new_err)?;
+
+    let result = atr_percentile_kernel(high_slice, low_slice, close_slice, atr_n, rank_n);
+    Ok(PyArray1::from_vec(py, result))
+}
+
+/// ATR breakout kernel: 1.0 where the bar's high-low range or its close-to-close
+/// move exceeds `multiplier` times the previous bar's Wilder ATR, 0.0 elsewhere.
+/// Using the prior ATR keeps an outlier bar from raising its own threshold.
+pub fn atr_breakout_kernel(
+    high: &[f64],
+    low: &[f64],
+    close: &[f64],
+    atr_n: usize,
+    multiplier: f64,
+) -> Vec<f64> {
+    let len = close.len();
+    let tr = true_range(high, low, close);
+    let atr_values = wilders_ema_kernel(&tr, atr_n);
+    let mut result = vec![0.0; len];
+
+    for i in 1..len {
+        let threshold = multiplier * atr_values[i - 1];
+        if threshold.is_nan() {
+            continue;
+        }
+        let range = high[i] - low[i];
+        let close_move = (close[i] - close[i - 1]).abs();
+        if range > threshold || close_move > threshold {
+            result[i] = 1.0;
+        }
+    }
+
+    result
+}
+
+/// ATR Breakout (volatility expansion bars)
+///
+/// # Arguments
+/// * `high` - High price series
+/// * `low` - Low price series
+/// * `close` - Close price series
+/// * `atr_n` - ATR period (default: 14)
+/// * `multiplier` - ATR multiple a bar's range or close move must exceed (default: 1.0)
+///
+/// # Returns
+/// Numpy array with 1.0 on breakout bars and 0.0 elsewhere (including the ATR warmup)
+#[pyfunction]
+#[pyo3(name = "atr_breakout_numba", signature = (high, low, close, atr_n=14, multiplier=1.0))]
+pub fn atr_breakout<'py>(
+    py: Python<'py>,
+    high: PyReadonlyArray1<'py, f64>,
+    low: PyReadonlyArray1<'py, f64>,
+    close: PyReadonlyArray1<'py, f64>,
+    atr_n: usize,
+    multiplier: f64,
+) -> PyResult<Bound<'py, PyArray1<f64>>> {
+    let high_slice = high.as_slice()?;
+    let low_slice = low.as_slice()?;
+    let close_slice = close.as_slice()?;
+
+    check_equal_lengths(&[
+        ("high", high_slice.len()),
+        ("low", low_slice.len()),
+        ("close", close_slice.len()),
+    ])
+    .map_err(PyValueError::new_err)?;
+
+    let result = atr_breakout_kernel(high_slice, low_slice, close_slice, atr_n, multiplier);
+    Ok(PyArray1::from_vec(py, result))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_atr_breakout_flags_outlier_bar_only() {
+        let len = 60;
+        let mut close: Vec<f64> = (0..len).map(|i| 100.0 + (i as f64 * 0.5).sin() * 0.3).collect();
+        let mut high: Vec<f64> = close.iter().map(|c| c + 0.5).collect();
+        let mut low: Vec<f64> = close.iter().map(|c| c - 0.5).collect();
+        // bar 40 jumps 5 points on a wide range
+        close[40] += 5.0;
+        high[40] = close[40] + 2.0;
+        low[40] = close[40] - 3.0;
+
+        let flags = atr_breakout_kernel(&high, &low, &close, 14, 1.5);
+        assert_eq!(flags[40], 1.0);
+        // the snap back at bar 41 is itself a 5-point move; calm bars around it stay 0
+        for i in (0..40).chain(42..len) {
+            assert_eq!(flags[i], 0.0, "bar {}", i);
+        }
+    }
+
+    #[test]
+    fn test_keltner_atr_kernel_warmup_modes() {
+        // Worked by hand: EMA(span=3) = 10, 11, 11, 12, 12; TR = 2, 3, 2, 3, 2;
+        // ATR(2) = 2.5, 2.25, 2.625, 2.3125 from bar 1
+        let close = [10.0, 12.0, 11.0, 13.0, 12.0];
+        let high = [11.0, 13.0, 12.0, 14.0, 13.0];
+        let low = [9.0, 11.0, 10.0, 12.0, 11.0];
+
+        let (upper, middle, lower) = keltner_atr_kernel(&high, &low, &close, 3, 2, 2.0, Compat::Ta);
+        assert!(middle[1].is_nan() && upper[1].is_nan() && lower[1].is_nan());
+        for (i, (u, l)) in [(15.5, 6.5), (17.25, 6.75), (16.625, 7.375)].iter().enumerate() {
+            assert!((upper[i + 2] - u).abs() < 1e-12 && (lower[i + 2] - l).abs() < 1e-12, "bar {}", i + 2);
+        }
+
+        let (upper, middle, _) = keltner_atr_kernel(&high, &low, &close, 3, 2, 2.0, Compat::Native);
+        assert_eq!(middle[0], 10.0);
+        assert!(upper[0].is_nan());
+        assert_eq!(upper[1], 16.0);
+
+        // ta's ATR is 0.0 before its seed, so a slower ATR leaves the bands on the middle
+        let (upper, middle, _) = keltner_atr_kernel(&high, &low, &close, 2, 3, 2.0, Compat::Ta);
+        assert!(middle[0].is_nan());
+        assert_eq!(upper[1], middle[1]);
+        assert!((upper[2] - middle[2] - 2.0 * 7.0 / 3.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_standard_error_bands_collapse_on_linear_series() {
+        let close: Vec<f64> = (0..30).map(|i| 100.0 + 0.5 * i as f64).collect();
+        let (upper, middle, lower) = standard_error_bands_kernel(&close, 21, 2.0);
+
+        assert!(middle[19].is_nan());
+        for i in 20..close.len() {
+            assert!((middle[i] - close[i]).abs() < 1e-9);
+            assert!((upper[i] - middle[i]).abs() < 1e-9);
+            assert!((middle[i] - lower[i]).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_expanding_standard_deviation_uses_full_history() {
+        let data: Vec<f64> = (0..40).map(|i| (i as f64 * 0.7).sin() * 3.0 + i as f64 * 0.2).collect();
+        let expanding = standard_deviation_kernel(&data, 5, true);
+        let rolling = standard_deviation_kernel(&data, 5, false);
+
+        assert!(expanding[3].is_nan());
+        assert!((expanding[4] - rolling[4]).abs() < 1e-12);
+        for i in 4..data.len() {
+            let count = (i + 1) as f64;
+            let mean = data[..=i].iter().sum::<f64>() / count;
+            let var = data[..=i].iter().map(|x| (x - mean).powi(2)).sum::<f64>() / count;
+            assert!((expanding[i] - var.sqrt()).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_regression_channel_collapses_on_linear_series() {
+        let close: Vec<f64> = (0..120).map(|i| 50.0 - 0.25 * i as f64).collect();
+        let (upper, middle, lower) = regression_channel_kernel(&close, 100, 2.0);
+
+        assert!(middle[98].is_nan());
+        for i in 99..close.len() {
+            assert!((middle[i] - close[i]).abs() < 1e-9);
+            assert!((upper[i] - lower[i]).abs() < 1e-9);
+        }
+
+        // Window [0, 1, 0]: flat fit at 1/3, residual std = sqrt(2/9)
+        let (upper, middle, _) = regression_channel_kernel(&[0.0, 1.0, 0.0], 3, 1.0);
+        assert!((middle[2] - 1.0 / 3.0).abs() < 1e-12);
+        assert!((upper[2] - middle[2] - (2.0f64 / 9.0).sqrt()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_regression_channel_recovers_after_nan() {
+        // Windows touching the NaN are NaN; the rest fit the line 2i + 1 exactly
+        let close = [1.0, 3.0, 5.0, f64::NAN, 9.0, 11.0, 13.0, 15.0];
+        let (upper, middle, lower) = regression_channel_kernel(&close, 3, 2.0);
+
+        assert!((middle[2] - 5.0).abs() < 1e-12);
+        assert!(middle[3..6].iter().all(|v| v.is_nan()));
+        assert!((middle[6] - 13.0).abs() < 1e-12);
+        assert!((middle[7] - 15.0).abs() < 1e-12);
+        assert!((upper[7] - lower[7]).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_donchian_position_at_bands() {
+        let high = [10.0, 12.0, 11.0, 13.0, 12.0];
+        let low = [8.0, 9.0, 9.5, 10.0, 10.0];
+        let close = [9.0, 12.0, 10.0, 13.0, 9.5];
+        let upper = rolling_max(&high, 3);
+        let lower = rolling_min(&low, 3);
+        let (position, width) = donchian_position_kernel(&close, &upper, &lower);
+
+        assert!(position[1].is_nan());
+        assert!((position[3] - 1.0).abs() < 1e-12);
+        assert!(position[4].abs() < 1e-12);
+        assert!((width[3] - 4.0).abs() < 1e-12);
+
+        // Zero-width channel has no defined position
+        let flat = [5.0; 3];
+        let (position, width) = donchian_position_kernel(&flat, &flat, &flat);
+        assert!(position[0].is_nan());
+        assert_eq!(width[0], 0.0);
+    }
+
+    #[test]
+    fn test_channel_position_midline_is_half() {
+        let close = [1.0, 3.0, 2.0];
+        let position = channel_position_kernel(&close, None, None, 3, 2.0, "bollinger", None).unwrap();
+        assert!((position[2] - 0.5).abs() < 1e-12);
+
+        // Flat close sits on the Keltner midline while the ATR keeps the band open
+        let close = [100.0; 10];
+        let high = [101.0; 10];
+        let low = [99.0; 10];
+        let position =
+            channel_position_kernel(&close, Some(&high), Some(&low), 3, 2.0, "keltner", None).unwrap();
+        assert!((position[9] - 0.5).abs() < 1e-12);
+
+        // Same bands as keltner_channel_numba(original_version=False), warmup included
+        let (upper, _, lower) = keltner_atr_kernel(&high, &low, &close, 3, 3, 2.0, Compat::Ta);
+        let (expected, _) = donchian_position_kernel(&close, &upper, &lower);
+        assert!(position[1].is_nan());
+        for i in 2..close.len() {
+            assert_eq!(position[i], expected[i]);
+        }
+
+        // Collapsed Bollinger band is NaN; clamping bounds breakouts
+        let position = channel_position_kernel(&close, None, None, 3, 2.0, "bollinger", None).unwrap();
+        assert!(position[9].is_nan());
+        let spike = [1.0, 1.0, 1.0, 1.0, 10.0];
+        let clamped = channel_position_kernel(&spike, None, None, 2, 0.5, "bollinger", Some((0.0, 1.0))).unwrap();
+        assert_eq!(clamped[4], 1.0);
+
+        assert!(channel_position_kernel(&close, None, None, 3, 2.0, "keltner", None).is_err());
+        assert!(channel_position_kernel(&close, None, None, 3, 2.0, "donchian", None).is_err());
+    }
+
+    #[test]
+    fn test_atr_trailing_stop_matches_streaming() {
+        let close: Vec<f64> = (0..600)
+            .map(|i| 100.0 + (i as f64 * 0.05).sin() * 15.0 + (i as f64 * 0.9).sin())
+            .collect();
+        let high: Vec<f64> = close.iter().map(|c| c + 0.8).collect();
+        let low: Vec<f64> = close.iter().map(|c| c - 0.6).collect();
+        let (stop, direction) = atr_trailing_stop_kernel(&high, &low, &close, 14, 3.0);
+
+        assert!(stop[12].is_nan() && !stop[13].is_nan());
+
+        // The streaming ATR seeds differently, so compare once both have converged
+        let mut streaming = crate::streaming::ATRTrailingStopStreaming::new(14, 3.0);
+        let mut flips = 0;
+        for i in 0..close.len() {
+            let (s, d) = streaming.update(high[i], low[i], close[i]);
+            if i >= 300 {
+                assert_eq!(d, direction[i], "bar {}", i);
+                assert!((s - stop[i]).abs() < 1e-6, "bar {}", i);
+                if direction[i] != direction[i - 1] {
+                    flips += 1;
+                }
+            }
+        }
+        assert!(flips > 0);
+    }
+
+    #[test]
+    fn test_atr_trailing_stop_trails_clean_trend() {
+        let close: Vec<f64> = (0..50).map(|i| 100.0 + 0.5 * i as f64).collect();
+        let high: Vec<f64> = close.iter().map(|c| c + 1.0).collect();
+        let low: Vec<f64> = close.iter().map(|c| c - 1.0).collect();
+        let (stop, direction) = atr_trailing_stop_kernel(&high, &low, &close, 14, 3.0);
+
+        for i in 14..close.len() {
+            assert_eq!(direction[i], 1.0);
+            assert!(stop[i] >= stop[i - 1]);
+            assert!(stop[i] < close[i]);
+        }
+    }
+
+    #[test]
+    fn test_atr_bands_pct_is_multiplier_atr_over_middle() {
+        let close: Vec<f64> = (0..40).map(|i| 50.0 + (i as f64 * 0.4).sin() * 3.0).collect();
+        let high: Vec<f64> = close.iter().map(|c| c + 1.2).collect();
+        let low: Vec<f64> = close.iter().map(|c| c - 0.7).collect();
+        let atr_values = wilders_ema_kernel(&true_range(&high, &low, &close), 14);
+
+        let (upper, middle, lower) = atr_bands_kernel(&high, &low, &close, 14, 2.5);
+        let (upper_pct, lower_pct) = band_pct_kernel(&upper, &middle, &lower);
+
+        assert!(upper_pct[12].is_nan());
+        for i in 13..close.len() {
+            let expected = 2.5 * atr_values[i] / middle[i] * 100.0;
+            assert!((upper_pct[i] - expected).abs() < 1e-9);
+            assert!((lower_pct[i] + expected).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_atr_first_value_matches_ta() {
+        // ta seeds ATR at index n-1 with the mean of TR[0..n], TR[0] = high - low
+        let high = [11.0, 12.0, 13.0, 12.5];
+        let low = [9.0, 10.5, 11.0, 11.0];
+        let close = [10.0, 11.5, 12.0, 11.5];
+        let atr_values = atr_compat_kernel(&high, &low, &close, 3, Compat::Native);
+
+        assert!(atr_values[1].is_nan());
+        assert!((atr_values[2] - (2.0 + 2.0 + 2.0) / 3.0).abs() < 1e-12);
+        assert!((atr_values[3] - (2.0 * 2.0 + 1.5) / 3.0).abs() < 1e-12);
+
+        // ta leaves its zero-initialised warmup in place
+        let ta_values = atr_compat_kernel(&high, &low, &close, 3, Compat::Ta);
+        assert_eq!(&ta_values[..2], &[0.0, 0.0]);
+        assert_eq!(&ta_values[2..], &atr_values[2..]);
+    }
+
+    #[test]
+    fn test_volatility_scaled_halves_when_atr_doubles() {
+        let len = 40;
+        let close = vec![100.0; len];
+        let signal = vec![1.0; len];
+        let high_narrow: Vec<f64> = close.iter().map(|c| c + 1.0).collect();
+        let low_narrow: Vec<f64> = close.iter().map(|c| c - 1.0).collect();
+        let high_wide: Vec<f64> = close.iter().map(|c| c + 2.0).collect();
+        let low_wide: Vec<f64> = close.iter().map(|c| c - 2.0).collect();
+
+        let narrow = volatility_scaled_kernel(&signal, &high_narrow, &low_narrow, &close, 14, 1.0);
+        let wide = volatility_scaled_kernel(&signal, &high_wide, &low_wide, &close, 14, 1.0);
+
+        assert!(narrow[12].is_nan());
+        for i in 13..len {
+            assert!((wide[i] - narrow[i] / 2.0).abs() < 1e-12);
+        }
+
+        // Flat bars have no range to scale by
+        let flat = volatility_scaled_kernel(&signal, &close, &close, &close, 14, 1.0);
+        assert!(flat.iter().all(|v| v.is_nan()));
+        assert!(check_equal_lengths(&[("signal", 3), ("close", 4)]).is_err());
+    }
+
+    #[test]
+    fn test_bollinger_squeeze_turns_on_in_quiet_segment() {
+        // Trending segment with tight bars, then a flat segment with wide bars
+        let mut close: Vec<f64> = (0..40).map(|i| 100.0 + 2.0 * i as f64).collect();
+        close.extend((0..40).map(|i| 178.0 + if i % 2 == 0 { 0.1 } else { -0.1 }));
+        let high: Vec<f64> = close.iter().enumerate().map(|(i, c)| c + if i < 40 { 0.1 } else { 2.0 }).collect();
+        let low: Vec<f64> = close.iter().enumerate().map(|(i, c)| c - if i < 40 { 0.1 } else { 2.0 }).collect();
+
+        let squeeze = bollinger_squeeze_kernel(&close, &high, &low, 20, 2.0, 1.5, 20);
+
+        assert!(squeeze[..19].iter().all(|&v| v == 0.0));
+        assert_eq!(squeeze[39], 0.0);
+        assert_eq!(squeeze[79], 1.0);
+    }
+
+    #[test]
+    fn test_chaikin_volatility_spikes_on_range_expansion() {
+        let mut high = vec![101.0; 40];
+        let mut low = vec![99.0; 40];
+        for i in 30..40 {
+            high[i] = 105.0;
+            low[i] = 95.0;
+        }
+
+        let cv = chaikin_volatility_kernel(&high, &low, 10, 10);
+
+        assert!(cv[9].is_nan());
+        assert!(cv[29].abs() < 1e-12);
+        assert!(cv[35] > 50.0);
+        assert!(cv[35] > cv[31]);
+    }
+
+    #[test]
+    fn test_pivot_distance_zero_on_pivot() {
+        // Every bar closes exactly on the pivot of the previous bar
+        let len = 30;
+        let mut high = vec![0.0; len];
+        let mut low = vec![0.0; len];
+        let mut close = vec![0.0; len];
+        high[0] = 102.0;
+        low[0] = 98.0;
+        close[0] = 100.0;
+        for i in 1..len {
+            close[i] = (high[i - 1] + low[i - 1] + close[i - 1]) / 3.0;
+            high[i] = close[i] + 2.0;
+            low[i] = close[i] - 2.0;
+        }
+
+        let distance = pivot_distance_kernel(&high, &low, &close, "classic", 14).unwrap();
+
+        assert!(distance[12].is_nan());
+        for i in 13..len {
+            assert!(distance[i].abs() < 1e-12);
+        }
+        assert!(pivot_distance_kernel(&high, &low, &close, "camarilla", 14).is_err());
+    }
+
+    #[test]
+    fn test_fibonacci_pivot_levels() {
+        // Previous bar H=110, L=100, C=105: P = 105, range = 10
+        let high = [110.0, 111.0];
+        let low = [100.0, 104.0];
+        let close = [105.0, 109.0];
+        let levels = pivot_levels_kernel(&high, &low, &close, "fibonacci").unwrap();
+
+        let expected = [105.0, 108.82, 111.18, 115.0, 101.18, 98.82, 95.0];
+        for (level, want) in levels.iter().zip(expected) {
+            assert!(level[0].is_nan());
+            assert!((level[1] - want).abs() < 1e-9, "{} vs {}", level[1], want);
+        }
+
+        // Close 109 sits 0.18 above R1 = 108.82, its nearest level; the 1-bar
+        // ATR is the true range max(111 - 104, 111 - 105, 105 - 104) = 7
+        let distance = pivot_distance_kernel(&high, &low, &close, "fibonacci", 1).unwrap();
+        assert!((distance[1] - 0.18 / 7.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_range_volatility_against_reference() {
+        let open = [100.0, 101.0, 102.5, 101.5];
+        let high = [102.0, 103.0, 104.0, 103.0];
+        let low = [99.0, 100.5, 101.0, 100.0];
+        let close = [101.0, 102.5, 101.5, 102.0];
+
+        let park = parkinson_volatility_kernel(&high, &low, 3, false, 252.0);
+        let gk = garman_klass_volatility_kernel(&open, &high, &low, &close, 3, false, 252.0);
+        assert!(park[1].is_nan() && gk[1].is_nan());
+
+        for end in 2..4 {
+            let mut park_sum = 0.0;
+            let mut gk_sum = 0.0;
+            for i in (end - 2)..=end {
+                let hl = (high[i] / low[i]).ln();
+                let co = (close[i] / open[i]).ln();
+                park_sum += hl * hl;
+                gk_sum += 0.5 * hl * hl - (2.0 * 2f64.ln() - 1.0) * co * co;
+            }
+            let park_ref = (park_sum / (3.0 * 4.0 * 2f64.ln())).sqrt();
+            let gk_ref = (gk_sum / 3.0).sqrt();
+            assert!((park[end] - park_ref).abs() < 1e-12);
+            assert!((gk[end] - gk_ref).abs() < 1e-12);
+        }
+
+        let annual = parkinson_volatility_kernel(&high, &low, 3, true, 252.0);
+        assert!((annual[3] - park[3] * 252f64.sqrt()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_ma_envelope_bands() {
+        let close: Vec<f64> = (0..40).map(|i| 100.0 + (i as f64 * 0.3).sin() * 5.0).collect();
+        for ma_type in ["sma", "ema", "wma"] {
+            let (upper, middle, lower) = ma_envelope_kernel(&close, 10, 2.5, ma_type).unwrap();
+            for i in 9..close.len() {
+                assert!((upper[i] - middle[i] * 1.025).abs() < 1e-12);
+                assert!((lower[i] - middle[i] * 0.975).abs() < 1e-12);
+            }
+        }
+        assert!(ma_envelope_kernel(&close, 10, 2.5, "hma").is_err());
+    }
+
+    #[test]
+    fn test_atr_percentile_volatility_burst() {
+        let len = 120;
+        let close: Vec<f64> = (0..len).map(|i| 100.0 + (i as f64 * 0.4).sin()).collect();
+        // Quiet 1-point ranges, then a widening burst over the last 10 bars
+        let spread: Vec<f64> = (0..len).map(|i| if i < 110 { 0.5 } else { 0.5 + (i - 109) as f64 }).collect();
+        let high: Vec<f64> = close.iter().zip(&spread).map(|(c, s)| c + s).collect();
+        let low: Vec<f64> = close.iter().zip(&spread).map(|(c, s)| c - s).collect();
+
+        let pr = atr_percentile_kernel(&high, &low, &close, 14, 50);
+
+        // ATR first valid at 13, rank needs 50 values
+        assert!(pr[61].is_nan());
+        assert!(!pr[62].is_nan());
+        assert!(pr[62..].iter().all(|&v| (0.0..=1.0).contains(&v)));
+        assert!((pr[len - 1] - 1.0).abs() < 1e-12);
+    }
 }
@@ -1,8 +1,12 @@
-/// Volume indicators: VWEMA, CMF, Force Index, MFI, A/D, OBV, EOM, VPT, NVI, VWAP
+//! Volume indicators: VWEMA, CMF, Force Index, MFI, A/D, OBV, OBV Divergence, EOM, VPT, NVI, VWAP,
+//! VWAP Bands, Up/Down Volume Ratio, Volume Z-Score, Volume Spike, Close Location Value
 
 use numpy::{PyArray1, PyReadonlyArray1};
+use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
-use crate::helpers::{ema_kernel, rolling_sum};
+use crate::helpers::{self, ema_kernel, rolling_sum, FloatArray};
+use crate::momentum::divergence_kernel;
+use crate::others::rolling_zscore_kernel;
 
 /// Money Flow Index (MFI)
 ///
@@ -22,13 +26,14 @@ pub fn mfi<'py>(
     high: PyReadonlyArray1<'py, f64>,
     low: PyReadonlyArray1<'py, f64>,
     close: PyReadonlyArray1<'py, f64>,
-    volume: PyReadonlyArray1<'py, f64>,
+    volume: FloatArray<'py>,
     n: usize,
 ) -> PyResult<Bound<'py, PyArray1<f64>>> {
     let high_slice = high.as_slice()?;
     let low_slice = low.as_slice()?;
     let close_slice = close.as_slice()?;
-    let volume_slice = volume.as_slice()?;
+    let volume = volume.to_f64()?;
+    let volume_slice: &[f64] = &volume;
     let len = high_slice.len();
 
     let mut tp = vec![0.0; len];
@@ -64,6 +69,53 @@ pub fn mfi<'py>(
     Ok(PyArray1::from_vec(py, mfi_values))
 }
 
+/// Close location value kernel: ((close - low) - (high - close)) / (high - low),
+/// in [-1, 1] and 0 when high == low
+pub fn clv_kernel(high: &[f64], low: &[f64], close: &[f64]) -> Vec<f64> {
+    let len = high.len();
+    let mut clv = vec![0.0; len];
+
+    for i in 0..len {
+        let range = high[i] - low[i];
+        if range != 0.0 {
+            clv[i] = ((close[i] - low[i]) - (high[i] - close[i])) / range;
+        }
+    }
+
+    clv
+}
+
+/// Close Location Value (CLV)
+///
+/// # Arguments
+/// * `high` - High price series
+/// * `low` - Low price series
+/// * `close` - Close price series
+///
+/// # Returns
+/// Numpy array with CLV values (-1 at the low, +1 at the high, 0 when high == low)
+#[pyfunction]
+#[pyo3(name = "close_location_value_numba", signature = (high, low, close))]
+pub fn close_location_value<'py>(
+    py: Python<'py>,
+    high: PyReadonlyArray1<'py, f64>,
+    low: PyReadonlyArray1<'py, f64>,
+    close: PyReadonlyArray1<'py, f64>,
+) -> PyResult<Bound<'py, PyArray1<f64>>> {
+    let high_slice = high.as_slice()?;
+    let low_slice = low.as_slice()?;
+    let close_slice = close.as_slice()?;
+    helpers::check_equal_lengths(&[
+        ("high", high_slice.len()),
+        ("low", low_slice.len()),
+        ("close", close_slice.len()),
+    ])
+    .map_err(PyValueError::new_err)?;
+
+    let clv = clv_kernel(high_slice, low_slice, close_slice);
+    Ok(PyArray1::from_vec(py, clv))
+}
+
 /// Accumulation/Distribution Index
 ///
 /// # Arguments
@@ -81,23 +133,16 @@ pub fn acc_dist_index<'py>(
     high: PyReadonlyArray1<'py, f64>,
     low: PyReadonlyArray1<'py, f64>,
     close: PyReadonlyArray1<'py, f64>,
-    volume: PyReadonlyArray1<'py, f64>,
+    volume: FloatArray<'py>,
 ) -> PyResult<Bound<'py, PyArray1<f64>>> {
     let high_slice = high.as_slice()?;
     let low_slice = low.as_slice()?;
     let close_slice = close.as_slice()?;
-    let volume_slice = volume.as_slice()?;
+    let volume = volume.to_f64()?;
+    let volume_slice: &[f64] = &volume;
     let len = high_slice.len();
 
-    let mut clv = vec![0.0; len];
-    for i in 0..len {
-        let range = high_slice[i] - low_slice[i];
-        if range != 0.0 {
-            clv[i] = ((close_slice[i] - low_slice[i]) - (high_slice[i] - close_slice[i])) / range;
-        } else {
-            clv[i] = 0.0;
-        }
-    }
+    let clv = clv_kernel(high_slice, low_slice, close_slice);
 
     let mut mfv = vec![0.0; len];
     for i in 0..len {
@@ -126,24 +171,69 @@ pub fn acc_dist_index<'py>(
 pub fn obv<'py>(
     py: Python<'py>,
     close: PyReadonlyArray1<'py, f64>,
-    volume: PyReadonlyArray1<'py, f64>,
+    volume: FloatArray<'py>,
 ) -> PyResult<Bound<'py, PyArray1<f64>>> {
     let close_slice = close.as_slice()?;
-    let volume_slice = volume.as_slice()?;
-    let len = close_slice.len();
+    let volume = volume.to_f64()?;
+    let volume_slice: &[f64] = &volume;
+    let obv_values = obv_kernel(close_slice, volume_slice);
+    Ok(PyArray1::from_vec(py, obv_values))
+}
 
+/// OBV kernel: running volume total, subtracting on down closes
+pub fn obv_kernel(close: &[f64], volume: &[f64]) -> Vec<f64> {
+    let len = close.len();
     let mut obv_values = vec![0.0; len];
-    obv_values[0] = volume_slice[0];
+
+    if len == 0 {
+        return obv_values;
+    }
+
+    obv_values[0] = volume[0];
 
     for i in 1..len {
-        if close_slice[i] < close_slice[i - 1] {
-            obv_values[i] = obv_values[i - 1] - volume_slice[i];
+        if close[i] < close[i - 1] {
+            obv_values[i] = obv_values[i - 1] - volume[i];
         } else {
-            obv_values[i] = obv_values[i - 1] + volume_slice[i];
+            obv_values[i] = obv_values[i - 1] + volume[i];
         }
     }
 
-    Ok(PyArray1::from_vec(py, obv_values))
+    obv_values
+}
+
+/// OBV Divergence
+///
+/// Same pivot heuristic as `rsi_divergence_numba`, with OBV as the oscillator.
+/// At each bar the pivot is the lowest (highest) close of the previous `lookback`
+/// bars. Bullish (+1): close falls below the pivot low while OBV is above its
+/// value at that pivot. Bearish (-1): close rises above the pivot high while OBV
+/// is below its value there. Otherwise 0; the first `lookback` bars are 0.
+///
+/// # Arguments
+/// * `close` - Close price series
+/// * `volume` - Volume series
+/// * `lookback` - Number of prior bars searched for the pivot (default: 14)
+///
+/// # Returns
+/// Numpy array with +1 (bullish), -1 (bearish) or 0
+#[pyfunction]
+#[pyo3(name = "obv_divergence_numba", signature = (close, volume, lookback=14))]
+pub fn obv_divergence<'py>(
+    py: Python<'py>,
+    close: PyReadonlyArray1<'py, f64>,
+    volume: FloatArray<'py>,
+    lookback: usize,
+) -> PyResult<Bound<'py, PyArray1<f64>>> {
+    let close_slice = close.as_slice()?;
+    let volume = volume.to_f64()?;
+    let volume_slice: &[f64] = &volume;
+    helpers::check_equal_lengths(&[("close", close_slice.len()), ("volume", volume_slice.len())])
+        .map_err(PyValueError::new_err)?;
+
+    let obv_values = obv_kernel(close_slice, volume_slice);
+    let flags = divergence_kernel(close_slice, &obv_values, lookback);
+    Ok(PyArray1::from_vec(py, flags))
 }
 
 /// Chaikin Money Flow (CMF)
@@ -164,25 +254,20 @@ pub fn chaikin_money_flow<'py>(
     high: PyReadonlyArray1<'py, f64>,
     low: PyReadonlyArray1<'py, f64>,
     close: PyReadonlyArray1<'py, f64>,
-    volume: PyReadonlyArray1<'py, f64>,
+    volume: FloatArray<'py>,
     n: usize,
 ) -> PyResult<Bound<'py, PyArray1<f64>>> {
     let high_slice = high.as_slice()?;
     let low_slice = low.as_slice()?;
     let close_slice = close.as_slice()?;
-    let volume_slice = volume.as_slice()?;
+    let volume = volume.to_f64()?;
+    let volume_slice: &[f64] = &volume;
     let len = high_slice.len();
 
-    let mut mfm = vec![0.0; len];
+    let mfm = clv_kernel(high_slice, low_slice, close_slice);
     let mut mfv = vec![0.0; len];
 
     for i in 0..len {
-        let range = high_slice[i] - low_slice[i];
-        if range != 0.0 {
-            mfm[i] = ((close_slice[i] - low_slice[i]) - (high_slice[i] - close_slice[i])) / range;
-        } else {
-            mfm[i] = 0.0;
-        }
         mfv[i] = mfm[i] * volume_slice[i];
     }
 
@@ -213,11 +298,12 @@ pub fn chaikin_money_flow<'py>(
 pub fn force_index<'py>(
     py: Python<'py>,
     close: PyReadonlyArray1<'py, f64>,
-    volume: PyReadonlyArray1<'py, f64>,
+    volume: FloatArray<'py>,
     n: usize,
 ) -> PyResult<Bound<'py, PyArray1<f64>>> {
     let close_slice = close.as_slice()?;
-    let volume_slice = volume.as_slice()?;
+    let volume = volume.to_f64()?;
+    let volume_slice: &[f64] = &volume;
     let len = close_slice.len();
 
     let mut fi_raw = vec![f64::NAN; len];
@@ -250,12 +336,13 @@ pub fn eom<'py>(
     py: Python<'py>,
     high: PyReadonlyArray1<'py, f64>,
     low: PyReadonlyArray1<'py, f64>,
-    volume: PyReadonlyArray1<'py, f64>,
+    volume: FloatArray<'py>,
     n: usize,
 ) -> PyResult<Bound<'py, PyArray1<f64>>> {
     let high_slice = high.as_slice()?;
     let low_slice = low.as_slice()?;
-    let volume_slice = volume.as_slice()?;
+    let volume = volume.to_f64()?;
+    let volume_slice: &[f64] = &volume;
     let len = high_slice.len();
 
     let mut emv_raw = vec![f64::NAN; len];
@@ -285,10 +372,11 @@ pub fn eom<'py>(
 pub fn vpt<'py>(
     py: Python<'py>,
     close: PyReadonlyArray1<'py, f64>,
-    volume: PyReadonlyArray1<'py, f64>,
+    volume: FloatArray<'py>,
 ) -> PyResult<Bound<'py, PyArray1<f64>>> {
     let close_slice = close.as_slice()?;
-    let volume_slice = volume.as_slice()?;
+    let volume = volume.to_f64()?;
+    let volume_slice: &[f64] = &volume;
     let len = close_slice.len();
 
     let mut pct_change = vec![0.0; len];
@@ -323,10 +411,11 @@ pub fn vpt<'py>(
 pub fn nvi<'py>(
     py: Python<'py>,
     close: PyReadonlyArray1<'py, f64>,
-    volume: PyReadonlyArray1<'py, f64>,
+    volume: FloatArray<'py>,
 ) -> PyResult<Bound<'py, PyArray1<f64>>> {
     let close_slice = close.as_slice()?;
-    let volume_slice = volume.as_slice()?;
+    let volume = volume.to_f64()?;
+    let volume_slice: &[f64] = &volume;
     let len = close_slice.len();
 
     let mut nvi_values = vec![f64::NAN; len];
@@ -355,29 +444,34 @@ pub fn nvi<'py>(
 /// * `low` - Low price series
 /// * `close` - Close price series
 /// * `volume` - Volume series
+/// * `n` - Rolling VWAP window (default: 14)
+/// * `price_source` - "hlc3" (default), "ohlc4", "hl2" or "close"
+/// * `open` - Open price series, required for "ohlc4"
 ///
 /// # Returns
 /// Numpy array with VWAP values
 #[pyfunction]
-#[pyo3(name = "volume_weighted_average_price_numba", signature = (high, low, close, volume, n=14))]
+#[pyo3(name = "volume_weighted_average_price_numba", signature = (high, low, close, volume, n=14, price_source="hlc3", open=None))]
 pub fn vwap<'py>(
     py: Python<'py>,
     high: PyReadonlyArray1<'py, f64>,
     low: PyReadonlyArray1<'py, f64>,
     close: PyReadonlyArray1<'py, f64>,
-    volume: PyReadonlyArray1<'py, f64>,
+    volume: FloatArray<'py>,
     n: usize,
+    price_source: &str,
+    open: Option<PyReadonlyArray1<'py, f64>>,
 ) -> PyResult<Bound<'py, PyArray1<f64>>> {
     let high_slice = high.as_slice()?;
     let low_slice = low.as_slice()?;
     let close_slice = close.as_slice()?;
-    let volume_slice = volume.as_slice()?;
+    let volume = volume.to_f64()?;
+    let volume_slice: &[f64] = &volume;
+    let open_slice = open.as_ref().map(|a| a.as_slice()).transpose()?;
     let len = high_slice.len();
 
-    let mut tp = vec![0.0; len];
-    for i in 0..len {
-        tp[i] = (high_slice[i] + low_slice[i] + close_slice[i]) / 3.0;
-    }
+    let tp = helpers::price_source(price_source, open_slice, high_slice, low_slice, close_slice)
+        .map_err(PyValueError::new_err)?;
 
     let mut vwap_values = vec![f64::NAN; len];
     
@@ -403,6 +497,91 @@ pub fn vwap<'py>(
     Ok(PyArray1::from_vec(py, vwap_values))
 }
 
+/// VWAP bands kernel: rolling VWAP of the typical price ± k * volume-weighted std
+/// of the typical price around it. Returns (upper, vwap, lower); NaN for the first
+/// n-1 elements and for windows with zero total volume.
+pub fn vwap_bands_kernel(
+    high: &[f64],
+    low: &[f64],
+    close: &[f64],
+    volume: &[f64],
+    n: usize,
+    k: f64,
+) -> (Vec<f64>, Vec<f64>, Vec<f64>) {
+    let len = close.len();
+    let mut upper = vec![f64::NAN; len];
+    let mut middle = vec![f64::NAN; len];
+    let mut lower = vec![f64::NAN; len];
+
+    if n == 0 || len < n {
+        return (upper, middle, lower);
+    }
+
+    let tp: Vec<f64> = (0..len).map(|i| (high[i] + low[i] + close[i]) / 3.0).collect();
+
+    for i in (n - 1)..len {
+        let start = i + 1 - n;
+        let sum_vol: f64 = volume[start..=i].iter().sum();
+        if sum_vol == 0.0 {
+            continue;
+        }
+
+        let vwap = (start..=i).map(|j| tp[j] * volume[j]).sum::<f64>() / sum_vol;
+        let variance = (start..=i).map(|j| volume[j] * (tp[j] - vwap).powi(2)).sum::<f64>() / sum_vol;
+        let std = variance.sqrt();
+
+        upper[i] = vwap + k * std;
+        middle[i] = vwap;
+        lower[i] = vwap - k * std;
+    }
+
+    (upper, middle, lower)
+}
+
+/// VWAP Bands
+///
+/// # Arguments
+/// * `high` - High price series
+/// * `low` - Low price series
+/// * `close` - Close price series
+/// * `volume` - Volume series
+/// * `n` - Rolling VWAP window (default: 20)
+/// * `k` - Band width in volume-weighted standard deviations (default: 2.0)
+///
+/// # Returns
+/// Tuple of (upper, vwap, lower) numpy arrays
+#[pyfunction]
+#[pyo3(name = "vwap_bands_numba", signature = (high, low, close, volume, n=20, k=2.0))]
+pub fn vwap_bands<'py>(
+    py: Python<'py>,
+    high: PyReadonlyArray1<'py, f64>,
+    low: PyReadonlyArray1<'py, f64>,
+    close: PyReadonlyArray1<'py, f64>,
+    volume: FloatArray<'py>,
+    n: usize,
+    k: f64,
+) -> PyResult<(Bound<'py, PyArray1<f64>>, Bound<'py, PyArray1<f64>>, Bound<'py, PyArray1<f64>>)> {
+    let high_slice = high.as_slice()?;
+    let low_slice = low.as_slice()?;
+    let close_slice = close.as_slice()?;
+    let volume = volume.to_f64()?;
+    let volume_slice: &[f64] = &volume;
+    helpers::check_equal_lengths(&[
+        ("high", high_slice.len()),
+        ("low", low_slice.len()),
+        ("close", close_slice.len()),
+        ("volume", volume_slice.len()),
+    ])
+    .map_err(PyValueError::new_err)?;
+
+    let (upper, middle, lower) = vwap_bands_kernel(high_slice, low_slice, close_slice, volume_slice, n, k);
+    Ok((
+        PyArray1::from_vec(py, upper),
+        PyArray1::from_vec(py, middle),
+        PyArray1::from_vec(py, lower),
+    ))
+}
+
 /// VWEMA - Volume-Weighted Exponential Moving Average
 ///
 /// # Arguments
@@ -422,14 +601,15 @@ pub fn vwema<'py>(
     high: PyReadonlyArray1<'py, f64>,
     low: PyReadonlyArray1<'py, f64>,
     close: PyReadonlyArray1<'py, f64>,
-    volume: PyReadonlyArray1<'py, f64>,
+    volume: FloatArray<'py>,
     n_vwma: usize,
     n_ema: usize,
 ) -> PyResult<Bound<'py, PyArray1<f64>>> {
     let high_slice = high.as_slice()?;
     let low_slice = low.as_slice()?;
     let close_slice = close.as_slice()?;
-    let volume_slice = volume.as_slice()?;
+    let volume = volume.to_f64()?;
+    let volume_slice: &[f64] = &volume;
     let len = high_slice.len();
 
     let mut typical_price = vec![0.0; len];
@@ -473,10 +653,11 @@ pub fn vwema<'py>(
 #[pyo3(name = "volume_ratio_numba", signature = (volume, window=50))]
 pub fn volume_ratio<'py>(
     py: Python<'py>,
-    volume: PyReadonlyArray1<'py, f64>,
+    volume: FloatArray<'py>,
     window: usize,
 ) -> PyResult<Bound<'py, PyArray1<f64>>> {
-    let volume_slice = volume.as_slice()?;
+    let volume = volume.to_f64()?;
+    let volume_slice: &[f64] = &volume;
     let len = volume_slice.len();
 
     let sma = crate::helpers::sma_kernel(volume_slice, window);
@@ -490,3 +671,186 @@ pub fn volume_ratio<'py>(
 
     Ok(PyArray1::from_vec(py, result))
 }
+
+/// Up/down volume ratio kernel: volume on up closes over volume on down closes in
+/// each window; unchanged closes count toward neither. NaN when the window has no
+/// down volume, and until `window` closes have a prior close (index `window`).
+pub fn up_down_volume_ratio_kernel(close: &[f64], volume: &[f64], window: usize) -> Vec<f64> {
+    let len = close.len();
+    let mut result = vec![f64::NAN; len];
+
+    if window == 0 || window >= len {
+        return result;
+    }
+
+    let mut up_volume = vec![0.0; len];
+    let mut down_volume = vec![0.0; len];
+    for i in 1..len {
+        if close[i] > close[i - 1] {
+            up_volume[i] = volume[i];
+        } else if close[i] < close[i - 1] {
+            down_volume[i] = volume[i];
+        }
+    }
+
+    let up_sum = rolling_sum(&up_volume, window);
+    let down_sum = rolling_sum(&down_volume, window);
+    for i in window..len {
+        if down_sum[i] != 0.0 {
+            result[i] = up_sum[i] / down_sum[i];
+        }
+    }
+
+    result
+}
+
+/// Up/Down Volume Ratio
+///
+/// # Arguments
+/// * `close` - Close price series
+/// * `volume` - Volume series
+/// * `n` - Rolling window size (default: 20)
+///
+/// # Returns
+/// Numpy array with sum(up-close volume) / sum(down-close volume) per window;
+/// NaN where the window has no down volume
+#[pyfunction]
+#[pyo3(name = "up_down_volume_ratio_numba", signature = (close, volume, n=20))]
+pub fn up_down_volume_ratio<'py>(
+    py: Python<'py>,
+    close: PyReadonlyArray1<'py, f64>,
+    volume: FloatArray<'py>,
+    n: usize,
+) -> PyResult<Bound<'py, PyArray1<f64>>> {
+    let close_slice = close.as_slice()?;
+    let volume = volume.to_f64()?;
+    let volume_slice: &[f64] = &volume;
+    helpers::check_equal_lengths(&[("close", close_slice.len()), ("volume", volume_slice.len())])
+        .map_err(PyValueError::new_err)?;
+
+    let result = up_down_volume_ratio_kernel(close_slice, volume_slice, n);
+    Ok(PyArray1::from_vec(py, result))
+}
+
+/// Volume spike kernel: 1.0 where the rolling volume z-score exceeds `threshold`,
+/// 0.0 elsewhere (including the warmup)
+pub fn volume_spike_kernel(volume: &[f64], n: usize, threshold: f64) -> Vec<f64> {
+    rolling_zscore_kernel(volume, n, false)
+        .into_iter()
+        .map(|z| if z > threshold { 1.0 } else { 0.0 })
+        .collect()
+}
+
+/// Volume Z-Score
+///
+/// # Arguments
+/// * `volume` - Volume series
+/// * `n` - Rolling window size (default: 20)
+///
+/// # Returns
+/// Numpy array with (volume - rolling_mean) / rolling_std over the last n bars,
+/// as in `rolling_zscore_numba` (NaN for first n-1 elements)
+#[pyfunction]
+#[pyo3(name = "volume_zscore_numba", signature = (volume, n=20))]
+pub fn volume_zscore<'py>(
+    py: Python<'py>,
+    volume: FloatArray<'py>,
+    n: usize,
+) -> PyResult<Bound<'py, PyArray1<f64>>> {
+    let volume = volume.to_f64()?;
+    let result = rolling_zscore_kernel(&volume, n, false);
+    Ok(PyArray1::from_vec(py, result))
+}
+
+/// Volume Spike
+///
+/// # Arguments
+/// * `volume` - Volume series
+/// * `n` - Rolling window size (default: 20)
+/// * `threshold` - Z-score a bar must exceed to count as a spike (default: 2.0)
+///
+/// # Returns
+/// Numpy array with 1.0 on spike bars and 0.0 elsewhere
+#[pyfunction]
+#[pyo3(name = "volume_spike_numba", signature = (volume, n=20, threshold=2.0))]
+pub fn volume_spike<'py>(
+    py: Python<'py>,
+    volume: FloatArray<'py>,
+    n: usize,
+    threshold: f64,
+) -> PyResult<Bound<'py, PyArray1<f64>>> {
+    let volume = volume.to_f64()?;
+    let result = volume_spike_kernel(&volume, n, threshold);
+    Ok(PyArray1::from_vec(py, result))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_volume_spike_on_5x_bar() {
+        let mut volume: Vec<f64> = (0..40).map(|i| 1000.0 + ((i * 7) % 5) as f64 * 20.0).collect();
+        volume[30] = 5.0 * volume[29];
+
+        let z = rolling_zscore_kernel(&volume, 20, false);
+        assert!(z[30] > 2.0);
+        assert!(z[29] < 2.0);
+
+        let spike = volume_spike_kernel(&volume, 20, 2.0);
+        assert_eq!(spike[30], 1.0);
+        assert!(spike.iter().enumerate().all(|(i, &v)| i == 30 || v == 0.0));
+    }
+
+    #[test]
+    fn test_obv_divergence_bearish_when_obv_falls() {
+        // Price makes higher highs on light volume, OBV sinks on heavy down days
+        let close = [10.0, 9.0, 11.0, 10.0, 12.0];
+        let volume = [1.0, 100.0, 1.0, 100.0, 1.0];
+        let obv_values = obv_kernel(&close, &volume);
+        assert_eq!(obv_values, vec![1.0, -99.0, -98.0, -198.0, -197.0]);
+
+        let flags = divergence_kernel(&close, &obv_values, 4);
+        assert_eq!(flags, vec![0.0, 0.0, 0.0, 0.0, -1.0]);
+    }
+
+    #[test]
+    fn test_up_down_volume_ratio_mostly_up() {
+        // Four up bars of volume 100 for every down bar of volume 50
+        let close: Vec<f64> = (0..30)
+            .scan(100.0, |c, i| {
+                *c += if i % 5 == 4 { -1.0 } else { 1.0 };
+                Some(*c)
+            })
+            .collect();
+        let volume: Vec<f64> = (0..30).map(|i| if i % 5 == 4 { 50.0 } else { 100.0 }).collect();
+        let ratio = up_down_volume_ratio_kernel(&close, &volume, 10);
+
+        assert!(ratio[9].is_nan());
+        assert!(ratio[10..].iter().all(|&r| (r - 8.0).abs() < 1e-12));
+
+        // All-up window has no down volume
+        let rising: Vec<f64> = (0..15).map(|i| i as f64).collect();
+        let ratio = up_down_volume_ratio_kernel(&rising, &[1.0; 15], 5);
+        assert!(ratio.iter().all(|r| r.is_nan()));
+    }
+
+    #[test]
+    fn test_clv_bounds() {
+        let high = [10.0, 10.0, 10.0, 5.0];
+        let low = [8.0, 8.0, 8.0, 5.0];
+        let close = [10.0, 8.0, 9.0, 5.0];
+        assert_eq!(clv_kernel(&high, &low, &close), vec![1.0, -1.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_vwap_bands_constant_price_collapses() {
+        let price = [10.0; 6];
+        let volume = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0];
+        let (upper, middle, lower) = vwap_bands_kernel(&price, &price, &price, &volume, 3, 2.0);
+
+        assert!(middle[1].is_nan());
+        assert!(middle[2..].iter().all(|&m| (m - 10.0).abs() < 1e-12));
+        assert_eq!(upper[2..], lower[2..]);
+    }
+}
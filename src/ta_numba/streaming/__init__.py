@@ -322,3 +322,77 @@ if _RUST_AVAILABLE:
     VWAP = VWAPStreaming
     VWEMA = VWEMAStreaming
     VolumeRatio = VolumeRatioStreaming
+
+    # Rust-only classes (no Numba equivalent)
+    from ._rust_streaming import (
+        VWAPBandsStreaming,
+        OHLCAggregator,
+        DisparityIndexStreaming,
+        CMOStreaming,
+        RollingTrimmedMeanStreaming,
+        PriceOscillatorStreaming,
+        VolumeZScoreStreaming,
+        DetrendedRSIStreaming,
+        DrawdownStreaming,
+        RollingRMSStreaming,
+        MADistanceStreaming,
+        SqueezeStreaming,
+        ChaikinVolatilityStreaming,
+        ConnorsRSIStreaming,
+        UpDownStreakStreaming,
+        SmoothedRSIStreaming,
+        DrawdownDurationStreaming,
+        EfficiencyRatioStreaming,
+        ATRTrailingStopStreaming,
+        RollingCountValidStreaming,
+        PctFromHighStreaming,
+        PctFromLowStreaming,
+        MAEnvelopeStreaming,
+    )
+    VWAPBands = VWAPBandsStreaming
+    DisparityIndex = DisparityIndexStreaming
+    CMO = CMOStreaming
+    RollingTrimmedMean = RollingTrimmedMeanStreaming
+    PriceOscillator = PriceOscillatorStreaming
+    VolumeZScore = VolumeZScoreStreaming
+    DetrendedRSI = DetrendedRSIStreaming
+    Drawdown = DrawdownStreaming
+    RollingRMS = RollingRMSStreaming
+    MADistance = MADistanceStreaming
+    Squeeze = SqueezeStreaming
+    ChaikinVolatility = ChaikinVolatilityStreaming
+    ConnorsRSI = ConnorsRSIStreaming
+    UpDownStreak = UpDownStreakStreaming
+    SmoothedRSI = SmoothedRSIStreaming
+    DrawdownDuration = DrawdownDurationStreaming
+    EfficiencyRatio = EfficiencyRatioStreaming
+    ATRTrailingStop = ATRTrailingStopStreaming
+    RollingCountValid = RollingCountValidStreaming
+    PctFromHigh = PctFromHighStreaming
+    PctFromLow = PctFromLowStreaming
+    MAEnvelope = MAEnvelopeStreaming
+    __all__ += [
+        "VWAPBandsStreaming",
+        "OHLCAggregator",
+        "DisparityIndexStreaming",
+        "CMOStreaming",
+        "RollingTrimmedMeanStreaming",
+        "PriceOscillatorStreaming",
+        "VolumeZScoreStreaming",
+        "DetrendedRSIStreaming",
+        "DrawdownStreaming",
+        "RollingRMSStreaming",
+        "MADistanceStreaming",
+        "SqueezeStreaming",
+        "ChaikinVolatilityStreaming",
+        "ConnorsRSIStreaming",
+        "UpDownStreakStreaming",
+        "SmoothedRSIStreaming",
+        "DrawdownDurationStreaming",
+        "EfficiencyRatioStreaming",
+        "ATRTrailingStopStreaming",
+        "RollingCountValidStreaming",
+        "PctFromHighStreaming",
+        "PctFromLowStreaming",
+        "MAEnvelopeStreaming",
+    ]
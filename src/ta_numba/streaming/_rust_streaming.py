@@ -9,8 +9,8 @@ from .._backend import _rs
 
 class SMAStreaming:
     """Simple Moving Average - Streaming"""
-    def __init__(self, window=20):
-        self._inner = _rs.SMAStreaming(window)
+    def __init__(self, window=20, nan_policy="propagate"):
+        self._inner = _rs.SMAStreaming(window, nan_policy)
         self._current_value = float('nan')
         self._is_ready = False
         self._update_count = 0
@@ -48,8 +48,8 @@ class SMAStreaming:
 
 class EMAStreaming:
     """Exponential Moving Average - Streaming"""
-    def __init__(self, window=20):
-        self._inner = _rs.EMAStreaming(window)
+    def __init__(self, window=20, nan_policy="propagate", min_periods=None):
+        self._inner = _rs.EMAStreaming(window, nan_policy, min_periods)
         self._current_value = float('nan')
         self._is_ready = False
         self._update_count = 0
@@ -87,8 +87,8 @@ class EMAStreaming:
 
 class WMAStreaming:
     """Weighted Moving Average - Streaming"""
-    def __init__(self, window=20):
-        self._inner = _rs.WMAStreaming(window)
+    def __init__(self, window=20, nan_policy="propagate"):
+        self._inner = _rs.WMAStreaming(window, nan_policy)
         self._current_value = float('nan')
         self._is_ready = False
         self._update_count = 0
@@ -140,6 +140,15 @@ class MACDStreaming:
         self._is_ready = not math.isnan(macd)
         return {"macd": macd, "signal": signal, "histogram": hist}
 
+    def fast_ema(self):
+        return self._inner.fast_ema()
+
+    def slow_ema(self):
+        return self._inner.slow_ema()
+
+    def signal_value(self):
+        return self._inner.signal_value()
+
     @property
     def current_value(self):
         return self._current_value
@@ -179,6 +188,9 @@ class ADXStreaming:
         self._is_ready = not math.isnan(adx)
         return {"adx": adx, "plus_di": plus_di, "minus_di": minus_di}
 
+    def adxr(self):
+        return self._inner.adxr()
+
     @property
     def current_value(self):
         return self._current_value
@@ -243,8 +255,8 @@ class CCIStreaming:
 
 class DPOStreaming:
     """Detrended Price Oscillator - Streaming"""
-    def __init__(self, window=20):
-        self._inner = _rs.DPOStreaming(window)
+    def __init__(self, window=20, pct=False):
+        self._inner = _rs.DPOStreaming(window, pct)
         self._current_value = float('nan')
         self._is_ready = False
         self._update_count = 0
@@ -399,8 +411,8 @@ class AroonStreaming:
 
 class ParabolicSARStreaming:
     """Parabolic SAR - Streaming"""
-    def __init__(self, af_start=0.02, af_inc=0.02, af_max=0.2):
-        self._inner = _rs.ParabolicSARStreaming(af_start, af_inc, af_max)
+    def __init__(self, af_start=0.02, af_inc=0.02, af_max=0.2, af_schedule=None):
+        self._inner = _rs.ParabolicSARStreaming(af_start, af_inc, af_max, af_schedule)
         self._current_value = float('nan')
         self._is_ready = False
         self._update_count = 0
@@ -436,6 +448,123 @@ class ParabolicSARStreaming:
         self._update_count = 0
 
 
+class DisparityIndexStreaming:
+    """Disparity Index (percent distance from a moving average) - Streaming"""
+    def __init__(self, window=14, ma_type="ema"):
+        self._inner = _rs.DisparityIndexStreaming(window, ma_type)
+        self._current_value = float('nan')
+        self._is_ready = False
+        self._update_count = 0
+        self._window = window
+
+    def update(self, close):
+        self._update_count += 1
+        result = self._inner.update(close)
+        self._current_value = result
+        self._is_ready = not math.isnan(result)
+        return {"disparity_index": result}
+
+    @property
+    def current_value(self):
+        return self._current_value
+
+    @property
+    def is_ready(self):
+        return self._is_ready
+
+    @property
+    def update_count(self):
+        return self._update_count
+
+    @property
+    def window(self):
+        return self._window
+
+    def reset(self):
+        self._inner.reset()
+        self._current_value = float('nan')
+        self._is_ready = False
+        self._update_count = 0
+
+
+class PriceOscillatorStreaming:
+    """Price Oscillator (APO / PPO over any moving average) - Streaming"""
+    def __init__(self, fast=10, slow=20, ma_type="sma", pct=False):
+        self._inner = _rs.PriceOscillatorStreaming(fast, slow, ma_type, pct)
+        self._current_value = float('nan')
+        self._is_ready = False
+        self._update_count = 0
+        self._window = slow
+
+    def update(self, value):
+        self._update_count += 1
+        result = self._inner.update(value)
+        self._current_value = result
+        self._is_ready = not math.isnan(result)
+        return {"price_oscillator": result}
+
+    @property
+    def current_value(self):
+        return self._current_value
+
+    @property
+    def is_ready(self):
+        return self._is_ready
+
+    @property
+    def update_count(self):
+        return self._update_count
+
+    @property
+    def window(self):
+        return self._window
+
+    def reset(self):
+        self._inner.reset()
+        self._current_value = float('nan')
+        self._is_ready = False
+        self._update_count = 0
+
+
+class MADistanceStreaming:
+    """MA Distance Oscillator (fast MA - slow MA) - Streaming"""
+    def __init__(self, fast_period=20, slow_period=50, ma_type="ema", percent=False):
+        self._inner = _rs.MADistanceStreaming(fast_period, slow_period, ma_type, percent)
+        self._current_value = float('nan')
+        self._is_ready = False
+        self._update_count = 0
+        self._window = slow_period
+
+    def update(self, value):
+        self._update_count += 1
+        result = self._inner.update(value)
+        self._current_value = result
+        self._is_ready = not math.isnan(result)
+        return {"ma_distance": result}
+
+    @property
+    def current_value(self):
+        return self._current_value
+
+    @property
+    def is_ready(self):
+        return self._is_ready
+
+    @property
+    def update_count(self):
+        return self._update_count
+
+    @property
+    def window(self):
+        return self._window
+
+    def reset(self):
+        self._inner.reset()
+        self._current_value = float('nan')
+        self._is_ready = False
+        self._update_count = 0
+
+
 # ============================================================================
 # MOMENTUM INDICATORS (12 classes)
 # ============================================================================
@@ -481,8 +610,8 @@ class RSIStreaming:
 
 class StochasticStreaming:
     """Stochastic Oscillator - Streaming"""
-    def __init__(self, k_period=14, d_period=3):
-        self._inner = _rs.StochasticStreaming(k_period, d_period)
+    def __init__(self, k_period=14, d_period=3, slowing=1, d_ma="sma"):
+        self._inner = _rs.StochasticStreaming(k_period, d_period, slowing, d_ma)
         self._current_value = float('nan')
         self._is_ready = False
         self._update_count = 0
@@ -520,8 +649,8 @@ class StochasticStreaming:
 
 class WilliamsRStreaming:
     """Williams %R - Streaming"""
-    def __init__(self, window=14):
-        self._inner = _rs.WilliamsRStreaming(window)
+    def __init__(self, window=14, smooth=1):
+        self._inner = _rs.WilliamsRStreaming(window, smooth)
         self._current_value = float('nan')
         self._is_ready = False
         self._update_count = 0
@@ -559,8 +688,8 @@ class WilliamsRStreaming:
 
 class ROCStreaming:
     """Rate of Change - Streaming"""
-    def __init__(self, window=12):
-        self._inner = _rs.ROCStreaming(window)
+    def __init__(self, window=12, pct=True):
+        self._inner = _rs.ROCStreaming(window, pct)
         self._current_value = float('nan')
         self._is_ready = False
         self._update_count = 0
@@ -754,8 +883,8 @@ class StochasticRSIStreaming:
 
 class TSIStreaming:
     """True Strength Index - Streaming"""
-    def __init__(self, first_smooth=25, second_smooth=13):
-        self._inner = _rs.TSIStreaming(first_smooth, second_smooth)
+    def __init__(self, first_smooth=25, second_smooth=13, ma_type="ema"):
+        self._inner = _rs.TSIStreaming(first_smooth, second_smooth, ma_type)
         self._current_value = float('nan')
         self._is_ready = False
         self._update_count = 0
@@ -807,6 +936,12 @@ class AwesomeOscillatorStreaming:
         self._is_ready = not math.isnan(result)
         return {"ao": result}
 
+    def is_bullish_saucer(self):
+        return self._inner.is_bullish_saucer()
+
+    def zero_cross(self):
+        return self._inner.zero_cross()
+
     @property
     def current_value(self):
         return self._current_value
@@ -908,25 +1043,21 @@ class MomentumStreaming:
         self._update_count = 0
 
 
-# ============================================================================
-# VOLATILITY INDICATORS (5 classes)
-# ============================================================================
-
-class ATRStreaming:
-    """Average True Range - Streaming"""
+class CMOStreaming:
+    """Chande Momentum Oscillator - Streaming"""
     def __init__(self, window=14):
-        self._inner = _rs.ATRStreaming(window)
+        self._inner = _rs.CMOStreaming(window)
         self._current_value = float('nan')
         self._is_ready = False
         self._update_count = 0
         self._window = window
 
-    def update(self, high, low, close):
+    def update(self, value):
         self._update_count += 1
-        result = self._inner.update(high, low, close)
+        result = self._inner.update(value)
         self._current_value = result
         self._is_ready = not math.isnan(result)
-        return {"atr": result}
+        return {"cmo": result}
 
     @property
     def current_value(self):
@@ -951,21 +1082,21 @@ class ATRStreaming:
         self._update_count = 0
 
 
-class BBandsStreaming:
-    """Bollinger Bands - Streaming"""
-    def __init__(self, window=20, std_dev=2.0):
-        self._inner = _rs.BBandsStreaming(window, std_dev)
+class DetrendedRSIStreaming:
+    """Detrended RSI (RSI minus its SMA) - Streaming"""
+    def __init__(self, rsi_n=14, sma_n=14):
+        self._inner = _rs.DetrendedRSIStreaming(rsi_n, sma_n)
         self._current_value = float('nan')
         self._is_ready = False
         self._update_count = 0
-        self._window = window
+        self._window = rsi_n
 
-    def update(self, value):
+    def update(self, close):
         self._update_count += 1
-        upper, middle, lower = self._inner.update(value)
-        self._current_value = upper
-        self._is_ready = not math.isnan(upper)
-        return {"upper": upper, "middle": middle, "lower": lower}
+        result = self._inner.update(close)
+        self._current_value = result
+        self._is_ready = not math.isnan(result)
+        return {"detrended_rsi": result}
 
     @property
     def current_value(self):
@@ -990,21 +1121,21 @@ class BBandsStreaming:
         self._update_count = 0
 
 
-class KeltnerChannelStreaming:
-    """Keltner Channel - Streaming"""
-    def __init__(self, window=20, atr_period=10, multiplier=2.0):
-        self._inner = _rs.KeltnerChannelStreaming(window, atr_period, multiplier)
+class ConnorsRSIStreaming:
+    """Connors RSI - Streaming"""
+    def __init__(self, rsi_n=3, streak_n=2, rank_n=100):
+        self._inner = _rs.ConnorsRSIStreaming(rsi_n, streak_n, rank_n)
         self._current_value = float('nan')
         self._is_ready = False
         self._update_count = 0
-        self._window = window
+        self._window = rank_n
 
-    def update(self, high, low, close):
+    def update(self, value):
         self._update_count += 1
-        upper, middle, lower = self._inner.update(high, low, close)
-        self._current_value = upper
-        self._is_ready = not math.isnan(upper)
-        return {"upper": upper, "middle": middle, "lower": lower}
+        result = self._inner.update(value)
+        self._current_value = result
+        self._is_ready = not math.isnan(result)
+        return {"connors_rsi": result}
 
     @property
     def current_value(self):
@@ -1029,21 +1160,21 @@ class KeltnerChannelStreaming:
         self._update_count = 0
 
 
-class DonchianChannelStreaming:
-    """Donchian Channel - Streaming"""
-    def __init__(self, window=20):
-        self._inner = _rs.DonchianChannelStreaming(window)
+class UpDownStreakStreaming:
+    """Up/Down Streak (signed run length of consecutive moves) - Streaming"""
+    def __init__(self):
+        self._inner = _rs.UpDownStreakStreaming()
         self._current_value = float('nan')
         self._is_ready = False
         self._update_count = 0
-        self._window = window
+        self._window = 1
 
-    def update(self, high, low):
+    def update(self, value):
         self._update_count += 1
-        upper, middle, lower = self._inner.update(high, low)
-        self._current_value = upper
-        self._is_ready = not math.isnan(upper)
-        return {"upper": upper, "middle": middle, "lower": lower}
+        result = self._inner.update(value)
+        self._current_value = result
+        self._is_ready = not math.isnan(result)
+        return {"streak": result}
 
     @property
     def current_value(self):
@@ -1068,21 +1199,21 @@ class DonchianChannelStreaming:
         self._update_count = 0
 
 
-class UlcerIndexStreaming:
-    """Ulcer Index - Streaming"""
-    def __init__(self, window=14):
-        self._inner = _rs.UlcerIndexStreaming(window)
+class SmoothedRSIStreaming:
+    """RSI of an EMA-smoothed price - Streaming"""
+    def __init__(self, smooth_n=3, rsi_n=14):
+        self._inner = _rs.SmoothedRSIStreaming(smooth_n, rsi_n)
         self._current_value = float('nan')
         self._is_ready = False
         self._update_count = 0
-        self._window = window
+        self._window = rsi_n
 
     def update(self, value):
         self._update_count += 1
         result = self._inner.update(value)
         self._current_value = result
         self._is_ready = not math.isnan(result)
-        return {"ui": result}
+        return {"smoothed_rsi": result}
 
     @property
     def current_value(self):
@@ -1107,10 +1238,10 @@ class UlcerIndexStreaming:
         self._update_count = 0
 
 
-class StandardDeviationStreaming:
-    """Rolling Standard Deviation - Streaming"""
-    def __init__(self, window=20):
-        self._inner = _rs.StandardDeviationStreaming(window)
+class EfficiencyRatioStreaming:
+    """Kaufman Efficiency Ratio - Streaming"""
+    def __init__(self, window=10):
+        self._inner = _rs.EfficiencyRatioStreaming(window)
         self._current_value = float('nan')
         self._is_ready = False
         self._update_count = 0
@@ -1121,7 +1252,7 @@ class StandardDeviationStreaming:
         result = self._inner.update(value)
         self._current_value = result
         self._is_ready = not math.isnan(result)
-        return {"std": result}
+        return {"efficiency_ratio": result}
 
     @property
     def current_value(self):
@@ -1146,21 +1277,25 @@ class StandardDeviationStreaming:
         self._update_count = 0
 
 
-class VarianceStreaming:
-    """Rolling Variance - Streaming"""
-    def __init__(self, window=20):
-        self._inner = _rs.VarianceStreaming(window)
+# ============================================================================
+# VOLATILITY INDICATORS (5 classes)
+# ============================================================================
+
+class ATRStreaming:
+    """Average True Range - Streaming"""
+    def __init__(self, window=14):
+        self._inner = _rs.ATRStreaming(window)
         self._current_value = float('nan')
         self._is_ready = False
         self._update_count = 0
         self._window = window
 
-    def update(self, value):
+    def update(self, high, low, close):
         self._update_count += 1
-        result = self._inner.update(value)
+        result = self._inner.update(high, low, close)
         self._current_value = result
         self._is_ready = not math.isnan(result)
-        return {"variance": result}
+        return {"atr": result}
 
     @property
     def current_value(self):
@@ -1185,21 +1320,21 @@ class VarianceStreaming:
         self._update_count = 0
 
 
-class RangeStreaming:
-    """Rolling Range (High - Low) - Streaming"""
-    def __init__(self, window=20):
-        self._inner = _rs.RangeStreaming(window)
+class BBandsStreaming:
+    """Bollinger Bands - Streaming"""
+    def __init__(self, window=20, std_dev=2.0):
+        self._inner = _rs.BBandsStreaming(window, std_dev)
         self._current_value = float('nan')
         self._is_ready = False
         self._update_count = 0
         self._window = window
 
-    def update(self, high, low):
+    def update(self, value):
         self._update_count += 1
-        result = self._inner.update(high, low)
-        self._current_value = result
-        self._is_ready = not math.isnan(result)
-        return {"range": result}
+        upper, middle, lower = self._inner.update(value)
+        self._current_value = upper
+        self._is_ready = not math.isnan(upper)
+        return {"upper": upper, "middle": middle, "lower": lower}
 
     @property
     def current_value(self):
@@ -1224,21 +1359,33 @@ class RangeStreaming:
         self._update_count = 0
 
 
-class HistoricalVolatilityStreaming:
-    """Historical Volatility (annualized rolling std of log returns) - Streaming"""
-    def __init__(self, window=20, annualize=True):
-        self._inner = _rs.HistoricalVolatilityStreaming(window, annualize)
+class KeltnerChannelStreaming:
+    """Keltner Channel - Streaming"""
+    def __init__(self, window=20, atr_period=10, multiplier=2.0):
+        self._inner = _rs.KeltnerChannelStreaming(window, atr_period, multiplier)
         self._current_value = float('nan')
         self._is_ready = False
         self._update_count = 0
         self._window = window
 
-    def update(self, value):
+    def update(self, high, low, close):
         self._update_count += 1
-        result = self._inner.update(value)
-        self._current_value = result
-        self._is_ready = not math.isnan(result)
-        return {"hvol": result}
+        upper, middle, lower = self._inner.update(high, low, close)
+        self._current_value = upper
+        self._is_ready = not math.isnan(upper)
+        return {"upper": upper, "middle": middle, "lower": lower}
+
+    def position(self, close):
+        return self._inner.position(close)
+
+    def ema(self):
+        return self._inner.ema()
+
+    def atr(self):
+        return self._inner.atr()
+
+    def width(self):
+        return self._inner.width()
 
     @property
     def current_value(self):
@@ -1263,25 +1410,24 @@ class HistoricalVolatilityStreaming:
         self._update_count = 0
 
 
-# ============================================================================
-# VOLUME INDICATORS (10 classes)
-# ============================================================================
-
-class MoneyFlowIndexStreaming:
-    """Money Flow Index - Streaming"""
-    def __init__(self, window=14):
-        self._inner = _rs.MoneyFlowIndexStreaming(window)
+class DonchianChannelStreaming:
+    """Donchian Channel - Streaming"""
+    def __init__(self, window=20):
+        self._inner = _rs.DonchianChannelStreaming(window)
         self._current_value = float('nan')
         self._is_ready = False
         self._update_count = 0
         self._window = window
 
-    def update(self, high, low, close, volume):
+    def update(self, high, low):
         self._update_count += 1
-        result = self._inner.update(high, low, close, volume)
-        self._current_value = result
-        self._is_ready = not math.isnan(result)
-        return {"mfi": result}
+        upper, middle, lower = self._inner.update(high, low)
+        self._current_value = upper
+        self._is_ready = not math.isnan(upper)
+        return {"upper": upper, "middle": middle, "lower": lower}
+
+    def position(self, close):
+        return self._inner.position(close)
 
     @property
     def current_value(self):
@@ -1306,21 +1452,779 @@ class MoneyFlowIndexStreaming:
         self._update_count = 0
 
 
-class AccDistIndexStreaming:
+class UlcerIndexStreaming:
+    """Ulcer Index - Streaming"""
+    def __init__(self, window=14):
+        self._inner = _rs.UlcerIndexStreaming(window)
+        self._current_value = float('nan')
+        self._is_ready = False
+        self._update_count = 0
+        self._window = window
+
+    def update(self, value):
+        self._update_count += 1
+        result = self._inner.update(value)
+        self._current_value = result
+        self._is_ready = not math.isnan(result)
+        return {"ui": result}
+
+    @property
+    def current_value(self):
+        return self._current_value
+
+    @property
+    def is_ready(self):
+        return self._is_ready
+
+    @property
+    def update_count(self):
+        return self._update_count
+
+    @property
+    def window(self):
+        return self._window
+
+    def reset(self):
+        self._inner.reset()
+        self._current_value = float('nan')
+        self._is_ready = False
+        self._update_count = 0
+
+
+class StandardDeviationStreaming:
+    """Rolling Standard Deviation - Streaming"""
+    def __init__(self, window=20):
+        self._inner = _rs.StandardDeviationStreaming(window)
+        self._current_value = float('nan')
+        self._is_ready = False
+        self._update_count = 0
+        self._window = window
+
+    def update(self, value):
+        self._update_count += 1
+        result = self._inner.update(value)
+        self._current_value = result
+        self._is_ready = not math.isnan(result)
+        return {"std": result}
+
+    @property
+    def current_value(self):
+        return self._current_value
+
+    @property
+    def is_ready(self):
+        return self._is_ready
+
+    @property
+    def update_count(self):
+        return self._update_count
+
+    @property
+    def window(self):
+        return self._window
+
+    def reset(self):
+        self._inner.reset()
+        self._current_value = float('nan')
+        self._is_ready = False
+        self._update_count = 0
+
+
+class VarianceStreaming:
+    """Rolling Variance - Streaming"""
+    def __init__(self, window=20):
+        self._inner = _rs.VarianceStreaming(window)
+        self._current_value = float('nan')
+        self._is_ready = False
+        self._update_count = 0
+        self._window = window
+
+    def update(self, value):
+        self._update_count += 1
+        result = self._inner.update(value)
+        self._current_value = result
+        self._is_ready = not math.isnan(result)
+        return {"variance": result}
+
+    @property
+    def current_value(self):
+        return self._current_value
+
+    @property
+    def is_ready(self):
+        return self._is_ready
+
+    @property
+    def update_count(self):
+        return self._update_count
+
+    @property
+    def window(self):
+        return self._window
+
+    def reset(self):
+        self._inner.reset()
+        self._current_value = float('nan')
+        self._is_ready = False
+        self._update_count = 0
+
+
+class RangeStreaming:
+    """Rolling Range (High - Low) - Streaming"""
+    def __init__(self, window=20):
+        self._inner = _rs.RangeStreaming(window)
+        self._current_value = float('nan')
+        self._is_ready = False
+        self._update_count = 0
+        self._window = window
+
+    def update(self, high, low):
+        self._update_count += 1
+        result = self._inner.update(high, low)
+        self._current_value = result
+        self._is_ready = not math.isnan(result)
+        return {"range": result}
+
+    @property
+    def current_value(self):
+        return self._current_value
+
+    @property
+    def is_ready(self):
+        return self._is_ready
+
+    @property
+    def update_count(self):
+        return self._update_count
+
+    @property
+    def window(self):
+        return self._window
+
+    def reset(self):
+        self._inner.reset()
+        self._current_value = float('nan')
+        self._is_ready = False
+        self._update_count = 0
+
+
+class HistoricalVolatilityStreaming:
+    """Historical Volatility (annualized rolling std of log returns) - Streaming"""
+    def __init__(self, window=20, annualize=True, min_periods=None, return_type="log", bar_frequency="daily", periods_per_year=None):
+        self._inner = _rs.HistoricalVolatilityStreaming(window, annualize, min_periods, return_type, bar_frequency, periods_per_year)
+        self._current_value = float('nan')
+        self._is_ready = False
+        self._update_count = 0
+        self._window = window
+
+    def update(self, value):
+        self._update_count += 1
+        result = self._inner.update(value)
+        self._current_value = result
+        self._is_ready = not math.isnan(result)
+        return {"hvol": result}
+
+    @property
+    def current_value(self):
+        return self._current_value
+
+    @property
+    def is_ready(self):
+        return self._is_ready
+
+    @property
+    def update_count(self):
+        return self._update_count
+
+    @property
+    def window(self):
+        return self._window
+
+    def reset(self):
+        self._inner.reset()
+        self._current_value = float('nan')
+        self._is_ready = False
+        self._update_count = 0
+
+
+class SqueezeStreaming:
+    """Bollinger / Keltner squeeze flag - Streaming"""
+    def __init__(self, window=20, std_dev=2.0, kc_mult=1.5, kc_window=20):
+        self._inner = _rs.SqueezeStreaming(window, std_dev, kc_mult, kc_window)
+        self._current_value = float('nan')
+        self._is_ready = False
+        self._update_count = 0
+        self._window = max(window, kc_window)
+
+    def update(self, high, low, close):
+        self._update_count += 1
+        result = self._inner.update(high, low, close)
+        self._current_value = result
+        # The flag is 0 rather than NaN during warmup
+        self._is_ready = self._update_count >= self._window
+        return {"squeeze": result}
+
+    @property
+    def current_value(self):
+        return self._current_value
+
+    @property
+    def is_ready(self):
+        return self._is_ready
+
+    @property
+    def update_count(self):
+        return self._update_count
+
+    @property
+    def window(self):
+        return self._window
+
+    def reset(self):
+        self._inner.reset()
+        self._current_value = float('nan')
+        self._is_ready = False
+        self._update_count = 0
+
+
+class ChaikinVolatilityStreaming:
+    """Chaikin Volatility (ROC of the high-low range EMA) - Streaming"""
+    def __init__(self, ema_period=10, roc_period=10):
+        self._inner = _rs.ChaikinVolatilityStreaming(ema_period, roc_period)
+        self._current_value = float('nan')
+        self._is_ready = False
+        self._update_count = 0
+        self._window = ema_period
+
+    def update(self, high, low):
+        self._update_count += 1
+        result = self._inner.update(high, low)
+        self._current_value = result
+        self._is_ready = not math.isnan(result)
+        return {"chaikin_volatility": result}
+
+    @property
+    def current_value(self):
+        return self._current_value
+
+    @property
+    def is_ready(self):
+        return self._is_ready
+
+    @property
+    def update_count(self):
+        return self._update_count
+
+    @property
+    def window(self):
+        return self._window
+
+    def reset(self):
+        self._inner.reset()
+        self._current_value = float('nan')
+        self._is_ready = False
+        self._update_count = 0
+
+
+class ATRTrailingStopStreaming:
+    """ATR Trailing Stop - Streaming"""
+    def __init__(self, atr_n=14, multiplier=3.0):
+        self._inner = _rs.ATRTrailingStopStreaming(atr_n, multiplier)
+        self._current_value = float('nan')
+        self._is_ready = False
+        self._update_count = 0
+        self._window = atr_n
+
+    def update(self, high, low, close):
+        self._update_count += 1
+        stop, direction = self._inner.update(high, low, close)
+        self._current_value = stop
+        self._is_ready = not math.isnan(stop)
+        return {"stop": stop, "direction": direction}
+
+    @property
+    def current_value(self):
+        return self._current_value
+
+    @property
+    def is_ready(self):
+        return self._is_ready
+
+    @property
+    def update_count(self):
+        return self._update_count
+
+    @property
+    def window(self):
+        return self._window
+
+    def reset(self):
+        self._inner.reset()
+        self._current_value = float('nan')
+        self._is_ready = False
+        self._update_count = 0
+
+
+class MAEnvelopeStreaming:
+    """Moving Average Envelope - Streaming"""
+    def __init__(self, window=20, pct=2.5, ma_type="sma"):
+        self._inner = _rs.MAEnvelopeStreaming(window, pct, ma_type)
+        self._current_value = float('nan')
+        self._is_ready = False
+        self._update_count = 0
+        self._window = window
+
+    def update(self, value):
+        self._update_count += 1
+        upper, middle, lower = self._inner.update(value)
+        self._current_value = middle
+        self._is_ready = not math.isnan(middle)
+        return {"upper": upper, "middle": middle, "lower": lower}
+
+    @property
+    def current_value(self):
+        return self._current_value
+
+    @property
+    def is_ready(self):
+        return self._is_ready
+
+    @property
+    def update_count(self):
+        return self._update_count
+
+    @property
+    def window(self):
+        return self._window
+
+    def reset(self):
+        self._inner.reset()
+        self._current_value = float('nan')
+        self._is_ready = False
+        self._update_count = 0
+
+
+# ============================================================================
+# VOLUME INDICATORS (10 classes)
+# ============================================================================
+
+class MoneyFlowIndexStreaming:
+    """Money Flow Index - Streaming"""
+    def __init__(self, window=14):
+        self._inner = _rs.MoneyFlowIndexStreaming(window)
+        self._current_value = float('nan')
+        self._is_ready = False
+        self._update_count = 0
+        self._window = window
+
+    def update(self, high, low, close, volume):
+        self._update_count += 1
+        result = self._inner.update(high, low, close, volume)
+        self._current_value = result
+        self._is_ready = not math.isnan(result)
+        return {"mfi": result}
+
+    @property
+    def current_value(self):
+        return self._current_value
+
+    @property
+    def is_ready(self):
+        return self._is_ready
+
+    @property
+    def update_count(self):
+        return self._update_count
+
+    @property
+    def window(self):
+        return self._window
+
+    def reset(self):
+        self._inner.reset()
+        self._current_value = float('nan')
+        self._is_ready = False
+        self._update_count = 0
+
+
+class AccDistIndexStreaming:
     """Accumulation/Distribution Index - Streaming"""
     def __init__(self):
-        self._inner = _rs.AccDistIndexStreaming()
+        self._inner = _rs.AccDistIndexStreaming()
+        self._current_value = float('nan')
+        self._is_ready = False
+        self._update_count = 0
+        self._window = 1
+
+    def update(self, high, low, close, volume):
+        self._update_count += 1
+        result = self._inner.update(high, low, close, volume)
+        self._current_value = result
+        self._is_ready = not math.isnan(result)
+        return {"ad": result}
+
+    def reset_session(self):
+        self._inner.reset_session()
+
+    @property
+    def current_value(self):
+        return self._current_value
+
+    @property
+    def is_ready(self):
+        return self._is_ready
+
+    @property
+    def update_count(self):
+        return self._update_count
+
+    @property
+    def window(self):
+        return self._window
+
+    def reset(self):
+        self._inner.reset()
+        self._current_value = float('nan')
+        self._is_ready = False
+        self._update_count = 0
+
+
+class OnBalanceVolumeStreaming:
+    """On-Balance Volume - Streaming"""
+    def __init__(self):
+        self._inner = _rs.OnBalanceVolumeStreaming()
+        self._current_value = float('nan')
+        self._is_ready = False
+        self._update_count = 0
+        self._window = 1
+
+    def update(self, close, volume):
+        self._update_count += 1
+        result = self._inner.update(close, volume)
+        self._current_value = result
+        self._is_ready = not math.isnan(result)
+        return {"obv": result}
+
+    def reset_session(self, keep_prev=True):
+        self._inner.reset_session(keep_prev)
+
+    @property
+    def current_value(self):
+        return self._current_value
+
+    @property
+    def is_ready(self):
+        return self._is_ready
+
+    @property
+    def update_count(self):
+        return self._update_count
+
+    @property
+    def window(self):
+        return self._window
+
+    def reset(self):
+        self._inner.reset()
+        self._current_value = float('nan')
+        self._is_ready = False
+        self._update_count = 0
+
+
+class ChaikinMoneyFlowStreaming:
+    """Chaikin Money Flow - Streaming"""
+    def __init__(self, window=20):
+        self._inner = _rs.ChaikinMoneyFlowStreaming(window)
+        self._current_value = float('nan')
+        self._is_ready = False
+        self._update_count = 0
+        self._window = window
+
+    def update(self, high, low, close, volume):
+        self._update_count += 1
+        result = self._inner.update(high, low, close, volume)
+        self._current_value = result
+        self._is_ready = not math.isnan(result)
+        return {"cmf": result}
+
+    @property
+    def current_value(self):
+        return self._current_value
+
+    @property
+    def is_ready(self):
+        return self._is_ready
+
+    @property
+    def update_count(self):
+        return self._update_count
+
+    @property
+    def window(self):
+        return self._window
+
+    def reset(self):
+        self._inner.reset()
+        self._current_value = float('nan')
+        self._is_ready = False
+        self._update_count = 0
+
+
+class ForceIndexStreaming:
+    """Force Index - Streaming"""
+    def __init__(self, window=13):
+        self._inner = _rs.ForceIndexStreaming(window)
+        self._current_value = float('nan')
+        self._is_ready = False
+        self._update_count = 0
+        self._window = window
+
+    def update(self, close, volume):
+        self._update_count += 1
+        result = self._inner.update(close, volume)
+        self._current_value = result
+        self._is_ready = not math.isnan(result)
+        return {"fi": result}
+
+    @property
+    def current_value(self):
+        return self._current_value
+
+    @property
+    def is_ready(self):
+        return self._is_ready
+
+    @property
+    def update_count(self):
+        return self._update_count
+
+    @property
+    def window(self):
+        return self._window
+
+    def reset(self):
+        self._inner.reset()
+        self._current_value = float('nan')
+        self._is_ready = False
+        self._update_count = 0
+
+
+class EaseOfMovementStreaming:
+    """Ease of Movement - Streaming"""
+    def __init__(self, window=14):
+        self._inner = _rs.EaseOfMovementStreaming(window)
+        self._current_value = float('nan')
+        self._is_ready = False
+        self._update_count = 0
+        self._window = window
+
+    def update(self, high, low, volume):
+        self._update_count += 1
+        result = self._inner.update(high, low, volume)
+        self._current_value = result
+        self._is_ready = not math.isnan(result)
+        return {"eom": result}
+
+    @property
+    def current_value(self):
+        return self._current_value
+
+    @property
+    def is_ready(self):
+        return self._is_ready
+
+    @property
+    def update_count(self):
+        return self._update_count
+
+    @property
+    def window(self):
+        return self._window
+
+    def reset(self):
+        self._inner.reset()
+        self._current_value = float('nan')
+        self._is_ready = False
+        self._update_count = 0
+
+
+class VolumePriceTrendStreaming:
+    """Volume Price Trend - Streaming"""
+    def __init__(self):
+        self._inner = _rs.VolumePriceTrendStreaming()
+        self._current_value = float('nan')
+        self._is_ready = False
+        self._update_count = 0
+        self._window = 1
+
+    def update(self, close, volume):
+        self._update_count += 1
+        result = self._inner.update(close, volume)
+        self._current_value = result
+        self._is_ready = not math.isnan(result)
+        return {"vpt": result}
+
+    def reset_session(self, keep_prev=True):
+        self._inner.reset_session(keep_prev)
+
+    @property
+    def current_value(self):
+        return self._current_value
+
+    @property
+    def is_ready(self):
+        return self._is_ready
+
+    @property
+    def update_count(self):
+        return self._update_count
+
+    @property
+    def window(self):
+        return self._window
+
+    def reset(self):
+        self._inner.reset()
+        self._current_value = float('nan')
+        self._is_ready = False
+        self._update_count = 0
+
+
+class NegativeVolumeIndexStreaming:
+    """Negative Volume Index - Streaming"""
+    def __init__(self):
+        self._inner = _rs.NegativeVolumeIndexStreaming()
+        self._current_value = float('nan')
+        self._is_ready = False
+        self._update_count = 0
+        self._window = 1
+
+    def update(self, close, volume):
+        self._update_count += 1
+        result = self._inner.update(close, volume)
+        self._current_value = result
+        self._is_ready = not math.isnan(result)
+        return {"nvi": result}
+
+    def reset_session(self, keep_prev=True):
+        self._inner.reset_session(keep_prev)
+
+    @property
+    def current_value(self):
+        return self._current_value
+
+    @property
+    def is_ready(self):
+        return self._is_ready
+
+    @property
+    def update_count(self):
+        return self._update_count
+
+    @property
+    def window(self):
+        return self._window
+
+    def reset(self):
+        self._inner.reset()
+        self._current_value = float('nan')
+        self._is_ready = False
+        self._update_count = 0
+
+
+class VWAPStreaming:
+    """Volume Weighted Average Price - Streaming"""
+    def __init__(self, window=14):
+        self._inner = _rs.VWAPStreaming(window)
+        self._current_value = float('nan')
+        self._is_ready = False
+        self._update_count = 0
+        self._window = window
+
+    def update(self, high, low, close, volume):
+        self._update_count += 1
+        result = self._inner.update(high, low, close, volume)
+        self._current_value = result
+        self._is_ready = not math.isnan(result)
+        return {"vwap": result}
+
+    @property
+    def current_value(self):
+        return self._current_value
+
+    @property
+    def is_ready(self):
+        return self._is_ready
+
+    @property
+    def update_count(self):
+        return self._update_count
+
+    @property
+    def window(self):
+        return self._window
+
+    def reset(self):
+        self._inner.reset()
+        self._current_value = float('nan')
+        self._is_ready = False
+        self._update_count = 0
+
+
+class VWEMAStreaming:
+    """Volume Weighted Exponential Moving Average - Streaming"""
+    def __init__(self, vwma_period=14, ema_period=20):
+        self._inner = _rs.VWEMAStreaming(vwma_period, ema_period)
+        self._current_value = float('nan')
+        self._is_ready = False
+        self._update_count = 0
+        self._window = vwma_period
+
+    def update(self, high, low, close, volume):
+        self._update_count += 1
+        result = self._inner.update(high, low, close, volume)
+        self._current_value = result
+        self._is_ready = not math.isnan(result)
+        return {"vwema": result}
+
+    @property
+    def current_value(self):
+        return self._current_value
+
+    @property
+    def is_ready(self):
+        return self._is_ready
+
+    @property
+    def update_count(self):
+        return self._update_count
+
+    @property
+    def window(self):
+        return self._window
+
+    def reset(self):
+        self._inner.reset()
+        self._current_value = float('nan')
+        self._is_ready = False
+        self._update_count = 0
+
+
+class VolumeRatioStreaming:
+    """Volume Ratio (volume / SMA(volume)) - Streaming"""
+    def __init__(self, window=50):
+        self._inner = _rs.VolumeRatioStreaming(window)
         self._current_value = float('nan')
         self._is_ready = False
         self._update_count = 0
-        self._window = 1
+        self._window = window
 
-    def update(self, high, low, close, volume):
+    def update(self, volume):
         self._update_count += 1
-        result = self._inner.update(high, low, close, volume)
+        result = self._inner.update(volume)
         self._current_value = result
         self._is_ready = not math.isnan(result)
-        return {"ad": result}
+        return {"volume_ratio": result}
 
     @property
     def current_value(self):
@@ -1345,21 +2249,21 @@ class AccDistIndexStreaming:
         self._update_count = 0
 
 
-class OnBalanceVolumeStreaming:
-    """On-Balance Volume - Streaming"""
-    def __init__(self):
-        self._inner = _rs.OnBalanceVolumeStreaming()
+class VWAPBandsStreaming:
+    """VWAP with volume-weighted standard deviation bands - Streaming"""
+    def __init__(self, window=20, k=2.0):
+        self._inner = _rs.VWAPBandsStreaming(window, k)
         self._current_value = float('nan')
         self._is_ready = False
         self._update_count = 0
-        self._window = 1
+        self._window = window
 
-    def update(self, close, volume):
+    def update(self, high, low, close, volume):
         self._update_count += 1
-        result = self._inner.update(close, volume)
-        self._current_value = result
-        self._is_ready = not math.isnan(result)
-        return {"obv": result}
+        upper, vwap, lower = self._inner.update(high, low, close, volume)
+        self._current_value = upper
+        self._is_ready = not math.isnan(upper)
+        return {"upper": upper, "vwap": vwap, "lower": lower}
 
     @property
     def current_value(self):
@@ -1384,21 +2288,21 @@ class OnBalanceVolumeStreaming:
         self._update_count = 0
 
 
-class ChaikinMoneyFlowStreaming:
-    """Chaikin Money Flow - Streaming"""
+class VolumeZScoreStreaming:
+    """Volume Z-Score - Streaming"""
     def __init__(self, window=20):
-        self._inner = _rs.ChaikinMoneyFlowStreaming(window)
+        self._inner = _rs.VolumeZScoreStreaming(window)
         self._current_value = float('nan')
         self._is_ready = False
         self._update_count = 0
         self._window = window
 
-    def update(self, high, low, close, volume):
+    def update(self, volume):
         self._update_count += 1
-        result = self._inner.update(high, low, close, volume)
+        result = self._inner.update(volume)
         self._current_value = result
         self._is_ready = not math.isnan(result)
-        return {"cmf": result}
+        return {"volume_zscore": result}
 
     @property
     def current_value(self):
@@ -1423,21 +2327,25 @@ class ChaikinMoneyFlowStreaming:
         self._update_count = 0
 
 
-class ForceIndexStreaming:
-    """Force Index - Streaming"""
-    def __init__(self, window=13):
-        self._inner = _rs.ForceIndexStreaming(window)
+# ============================================================================
+# OTHER INDICATORS (8 classes)
+# ============================================================================
+
+class DailyReturnStreaming:
+    """Daily Return - Streaming"""
+    def __init__(self, pct=True):
+        self._inner = _rs.DailyReturnStreaming(pct)
         self._current_value = float('nan')
         self._is_ready = False
         self._update_count = 0
-        self._window = window
+        self._window = 1
 
-    def update(self, close, volume):
+    def update(self, value):
         self._update_count += 1
-        result = self._inner.update(close, volume)
+        result = self._inner.update(value)
         self._current_value = result
         self._is_ready = not math.isnan(result)
-        return {"fi": result}
+        return {"dr": result}
 
     @property
     def current_value(self):
@@ -1462,21 +2370,21 @@ class ForceIndexStreaming:
         self._update_count = 0
 
 
-class EaseOfMovementStreaming:
-    """Ease of Movement - Streaming"""
-    def __init__(self, window=14):
-        self._inner = _rs.EaseOfMovementStreaming(window)
+class DailyLogReturnStreaming:
+    """Daily Log Return - Streaming"""
+    def __init__(self, pct=True):
+        self._inner = _rs.DailyLogReturnStreaming(pct)
         self._current_value = float('nan')
         self._is_ready = False
         self._update_count = 0
-        self._window = window
+        self._window = 1
 
-    def update(self, high, low, volume):
+    def update(self, value):
         self._update_count += 1
-        result = self._inner.update(high, low, volume)
+        result = self._inner.update(value)
         self._current_value = result
         self._is_ready = not math.isnan(result)
-        return {"eom": result}
+        return {"dlr": result}
 
     @property
     def current_value(self):
@@ -1501,21 +2409,21 @@ class EaseOfMovementStreaming:
         self._update_count = 0
 
 
-class VolumePriceTrendStreaming:
-    """Volume Price Trend - Streaming"""
-    def __init__(self):
-        self._inner = _rs.VolumePriceTrendStreaming()
+class CumulativeReturnStreaming:
+    """Cumulative Return - Streaming"""
+    def __init__(self, pct=True):
+        self._inner = _rs.CumulativeReturnStreaming(pct)
         self._current_value = float('nan')
         self._is_ready = False
         self._update_count = 0
         self._window = 1
 
-    def update(self, close, volume):
+    def update(self, value):
         self._update_count += 1
-        result = self._inner.update(close, volume)
+        result = self._inner.update(value)
         self._current_value = result
         self._is_ready = not math.isnan(result)
-        return {"vpt": result}
+        return {"cr": result}
 
     @property
     def current_value(self):
@@ -1540,21 +2448,21 @@ class VolumePriceTrendStreaming:
         self._update_count = 0
 
 
-class NegativeVolumeIndexStreaming:
-    """Negative Volume Index - Streaming"""
+class CompoundLogReturnStreaming:
+    """Compound Log Return - Streaming"""
     def __init__(self):
-        self._inner = _rs.NegativeVolumeIndexStreaming()
+        self._inner = _rs.CompoundLogReturnStreaming()
         self._current_value = float('nan')
         self._is_ready = False
         self._update_count = 0
         self._window = 1
 
-    def update(self, close, volume):
+    def update(self, value):
         self._update_count += 1
-        result = self._inner.update(close, volume)
+        result = self._inner.update(value)
         self._current_value = result
-        self._is_ready = not math.isnan(result)
-        return {"nvi": result}
+        self._is_ready = True
+        return {"clr": result}
 
     @property
     def current_value(self):
@@ -1579,21 +2487,21 @@ class NegativeVolumeIndexStreaming:
         self._update_count = 0
 
 
-class VWAPStreaming:
-    """Volume Weighted Average Price - Streaming"""
-    def __init__(self, window=14):
-        self._inner = _rs.VWAPStreaming(window)
+class RollingReturnStreaming:
+    """Rolling Return - Streaming"""
+    def __init__(self, window=20):
+        self._inner = _rs.RollingReturnStreaming(window)
         self._current_value = float('nan')
         self._is_ready = False
         self._update_count = 0
         self._window = window
 
-    def update(self, high, low, close, volume):
+    def update(self, value):
         self._update_count += 1
-        result = self._inner.update(high, low, close, volume)
+        result = self._inner.update(value)
         self._current_value = result
         self._is_ready = not math.isnan(result)
-        return {"vwap": result}
+        return {"rr": result}
 
     @property
     def current_value(self):
@@ -1618,21 +2526,21 @@ class VWAPStreaming:
         self._update_count = 0
 
 
-class VWEMAStreaming:
-    """Volume Weighted Exponential Moving Average - Streaming"""
-    def __init__(self, vwma_period=14, ema_period=20):
-        self._inner = _rs.VWEMAStreaming(vwma_period, ema_period)
+class MaxDrawdownStreaming:
+    """Maximum Drawdown - Streaming"""
+    def __init__(self):
+        self._inner = _rs.MaxDrawdownStreaming()
         self._current_value = float('nan')
         self._is_ready = False
         self._update_count = 0
-        self._window = vwma_period
+        self._window = 1
 
-    def update(self, high, low, close, volume):
+    def update(self, value):
         self._update_count += 1
-        result = self._inner.update(high, low, close, volume)
+        result = self._inner.update(value)
         self._current_value = result
         self._is_ready = not math.isnan(result)
-        return {"vwema": result}
+        return {"mdd": result}
 
     @property
     def current_value(self):
@@ -1657,21 +2565,21 @@ class VWEMAStreaming:
         self._update_count = 0
 
 
-class VolumeRatioStreaming:
-    """Volume Ratio (volume / SMA(volume)) - Streaming"""
-    def __init__(self, window=50):
-        self._inner = _rs.VolumeRatioStreaming(window)
+class SharpeRatioStreaming:
+    """Sharpe Ratio - Streaming"""
+    def __init__(self, window=252, risk_free_rate=0.0, annualization_factor=None, min_periods=None, return_type="log", bar_frequency="daily"):
+        self._inner = _rs.SharpeRatioStreaming(window, risk_free_rate, annualization_factor, min_periods, return_type, bar_frequency)
         self._current_value = float('nan')
         self._is_ready = False
         self._update_count = 0
         self._window = window
 
-    def update(self, volume):
+    def update(self, value):
         self._update_count += 1
-        result = self._inner.update(volume)
+        result = self._inner.update(value)
         self._current_value = result
         self._is_ready = not math.isnan(result)
-        return {"volume_ratio": result}
+        return {"sharpe": result}
 
     @property
     def current_value(self):
@@ -1696,25 +2604,21 @@ class VolumeRatioStreaming:
         self._update_count = 0
 
 
-# ============================================================================
-# OTHER INDICATORS (8 classes)
-# ============================================================================
-
-class DailyReturnStreaming:
-    """Daily Return - Streaming"""
-    def __init__(self):
-        self._inner = _rs.DailyReturnStreaming()
+class CalmarRatioStreaming:
+    """Calmar Ratio - Streaming"""
+    def __init__(self, window=252, bar_frequency="daily", periods_per_year=None):
+        self._inner = _rs.CalmarRatioStreaming(window, bar_frequency, periods_per_year)
         self._current_value = float('nan')
         self._is_ready = False
         self._update_count = 0
-        self._window = 1
+        self._window = window
 
     def update(self, value):
         self._update_count += 1
         result = self._inner.update(value)
         self._current_value = result
         self._is_ready = not math.isnan(result)
-        return {"dr": result}
+        return {"calmar": result}
 
     @property
     def current_value(self):
@@ -1739,21 +2643,21 @@ class DailyReturnStreaming:
         self._update_count = 0
 
 
-class DailyLogReturnStreaming:
-    """Daily Log Return - Streaming"""
-    def __init__(self):
-        self._inner = _rs.DailyLogReturnStreaming()
+class RollingZScoreStreaming:
+    """Rolling Z-Score - Streaming"""
+    def __init__(self, window=20):
+        self._inner = _rs.RollingZScoreStreaming(window)
         self._current_value = float('nan')
         self._is_ready = False
         self._update_count = 0
-        self._window = 1
+        self._window = window
 
     def update(self, value):
         self._update_count += 1
         result = self._inner.update(value)
         self._current_value = result
         self._is_ready = not math.isnan(result)
-        return {"dlr": result}
+        return {"zscore": result}
 
     @property
     def current_value(self):
@@ -1778,21 +2682,21 @@ class DailyLogReturnStreaming:
         self._update_count = 0
 
 
-class CumulativeReturnStreaming:
-    """Cumulative Return - Streaming"""
-    def __init__(self):
-        self._inner = _rs.CumulativeReturnStreaming()
+class LinearRegressionSlopeStreaming:
+    """Linear Regression Slope - Streaming"""
+    def __init__(self, window=14):
+        self._inner = _rs.LinearRegressionSlopeStreaming(window)
         self._current_value = float('nan')
         self._is_ready = False
         self._update_count = 0
-        self._window = 1
+        self._window = window
 
     def update(self, value):
         self._update_count += 1
         result = self._inner.update(value)
         self._current_value = result
         self._is_ready = not math.isnan(result)
-        return {"cr": result}
+        return {"slope": result}
 
     @property
     def current_value(self):
@@ -1817,21 +2721,21 @@ class CumulativeReturnStreaming:
         self._update_count = 0
 
 
-class CompoundLogReturnStreaming:
-    """Compound Log Return - Streaming"""
-    def __init__(self):
-        self._inner = _rs.CompoundLogReturnStreaming()
+class RollingPercentileStreaming:
+    """Rolling Percentile - Streaming"""
+    def __init__(self, window=120):
+        self._inner = _rs.RollingPercentileStreaming(window)
         self._current_value = float('nan')
         self._is_ready = False
         self._update_count = 0
-        self._window = 1
+        self._window = window
 
     def update(self, value):
         self._update_count += 1
         result = self._inner.update(value)
         self._current_value = result
-        self._is_ready = True
-        return {"clr": result}
+        self._is_ready = not math.isnan(result)
+        return {"percentile": result}
 
     @property
     def current_value(self):
@@ -1856,10 +2760,64 @@ class CompoundLogReturnStreaming:
         self._update_count = 0
 
 
-class RollingReturnStreaming:
-    """Rolling Return - Streaming"""
-    def __init__(self, window=20):
-        self._inner = _rs.RollingReturnStreaming(window)
+class OHLCAggregator:
+    """Tick-to-bar OHLCV aggregator (tick-count or time buckets) - Streaming"""
+    def __init__(self, bar_ticks=None, bar_seconds=None):
+        self._inner = _rs.OHLCAggregator(bar_ticks, bar_seconds)
+        self._current_value = float('nan')
+        self._is_ready = False
+        self._update_count = 0
+        self._window = bar_ticks
+
+    def update(self, price, volume, timestamp):
+        self._update_count += 1
+        open_, high, low, close, bar_volume, completed = self._inner.update(price, volume, timestamp)
+        if completed:
+            self._current_value = close
+            self._is_ready = True
+        return {
+            "open": open_,
+            "high": high,
+            "low": low,
+            "close": close,
+            "volume": bar_volume,
+            "completed": completed,
+        }
+
+    def flush(self):
+        open_, high, low, close, volume = self._inner.flush()
+        if not math.isnan(close):
+            self._current_value = close
+            self._is_ready = True
+        return {"open": open_, "high": high, "low": low, "close": close, "volume": volume}
+
+    @property
+    def current_value(self):
+        return self._current_value
+
+    @property
+    def is_ready(self):
+        return self._is_ready
+
+    @property
+    def update_count(self):
+        return self._update_count
+
+    @property
+    def window(self):
+        return self._window
+
+    def reset(self):
+        self._inner.reset()
+        self._current_value = float('nan')
+        self._is_ready = False
+        self._update_count = 0
+
+
+class RollingTrimmedMeanStreaming:
+    """Rolling Trimmed Mean - Streaming"""
+    def __init__(self, window=20, trim=0.1):
+        self._inner = _rs.RollingTrimmedMeanStreaming(window, trim)
         self._current_value = float('nan')
         self._is_ready = False
         self._update_count = 0
@@ -1870,7 +2828,7 @@ class RollingReturnStreaming:
         result = self._inner.update(value)
         self._current_value = result
         self._is_ready = not math.isnan(result)
-        return {"rr": result}
+        return {"trimmed_mean": result}
 
     @property
     def current_value(self):
@@ -1895,21 +2853,21 @@ class RollingReturnStreaming:
         self._update_count = 0
 
 
-class MaxDrawdownStreaming:
-    """Maximum Drawdown - Streaming"""
-    def __init__(self):
-        self._inner = _rs.MaxDrawdownStreaming()
+class DrawdownStreaming:
+    """Drawdown (current, max and bars in drawdown) - Streaming"""
+    def __init__(self, window=None):
+        self._inner = _rs.DrawdownStreaming(window)
         self._current_value = float('nan')
         self._is_ready = False
         self._update_count = 0
-        self._window = 1
+        self._window = window
 
     def update(self, value):
         self._update_count += 1
-        result = self._inner.update(value)
-        self._current_value = result
-        self._is_ready = not math.isnan(result)
-        return {"mdd": result}
+        current, max_drawdown, bars = self._inner.update(value)
+        self._current_value = current
+        self._is_ready = not math.isnan(current)
+        return {"drawdown": current, "max_drawdown": max_drawdown, "bars_in_drawdown": bars}
 
     @property
     def current_value(self):
@@ -1934,10 +2892,10 @@ class MaxDrawdownStreaming:
         self._update_count = 0
 
 
-class SharpeRatioStreaming:
-    """Sharpe Ratio - Streaming"""
-    def __init__(self, window=252, risk_free_rate=0.0, annualization_factor=252.0):
-        self._inner = _rs.SharpeRatioStreaming(window, risk_free_rate, annualization_factor)
+class RollingRMSStreaming:
+    """Rolling Root Mean Square - Streaming"""
+    def __init__(self, window=20):
+        self._inner = _rs.RollingRMSStreaming(window)
         self._current_value = float('nan')
         self._is_ready = False
         self._update_count = 0
@@ -1948,7 +2906,10 @@ class SharpeRatioStreaming:
         result = self._inner.update(value)
         self._current_value = result
         self._is_ready = not math.isnan(result)
-        return {"sharpe": result}
+        return {"rms": result}
+
+    def variance(self):
+        return self._inner.variance()
 
     @property
     def current_value(self):
@@ -1966,6 +2927,15 @@ class SharpeRatioStreaming:
     def window(self):
         return self._window
 
+    def __copy__(self):
+        clone = self.__class__.__new__(self.__class__)
+        clone.__dict__.update(self.__dict__)
+        clone._inner = self._inner.__copy__()
+        return clone
+
+    def __deepcopy__(self, memo):
+        return self.__copy__()
+
     def reset(self):
         self._inner.reset()
         self._current_value = float('nan')
@@ -1973,10 +2943,10 @@ class SharpeRatioStreaming:
         self._update_count = 0
 
 
-class CalmarRatioStreaming:
-    """Calmar Ratio - Streaming"""
-    def __init__(self, window=252):
-        self._inner = _rs.CalmarRatioStreaming(window)
+class DrawdownDurationStreaming:
+    """Drawdown Duration (bars since the reference high) - Streaming"""
+    def __init__(self, window=None):
+        self._inner = _rs.DrawdownDurationStreaming(window)
         self._current_value = float('nan')
         self._is_ready = False
         self._update_count = 0
@@ -1987,7 +2957,7 @@ class CalmarRatioStreaming:
         result = self._inner.update(value)
         self._current_value = result
         self._is_ready = not math.isnan(result)
-        return {"calmar": result}
+        return {"drawdown_duration": result}
 
     @property
     def current_value(self):
@@ -2012,10 +2982,10 @@ class CalmarRatioStreaming:
         self._update_count = 0
 
 
-class RollingZScoreStreaming:
-    """Rolling Z-Score - Streaming"""
-    def __init__(self, window=20):
-        self._inner = _rs.RollingZScoreStreaming(window)
+class RollingCountValidStreaming:
+    """Rolling count of non-NaN values - Streaming"""
+    def __init__(self, window):
+        self._inner = _rs.RollingCountValidStreaming(window)
         self._current_value = float('nan')
         self._is_ready = False
         self._update_count = 0
@@ -2026,7 +2996,7 @@ class RollingZScoreStreaming:
         result = self._inner.update(value)
         self._current_value = result
         self._is_ready = not math.isnan(result)
-        return {"zscore": result}
+        return {"count_valid": result}
 
     @property
     def current_value(self):
@@ -2051,10 +3021,10 @@ class RollingZScoreStreaming:
         self._update_count = 0
 
 
-class LinearRegressionSlopeStreaming:
-    """Linear Regression Slope - Streaming"""
-    def __init__(self, window=14):
-        self._inner = _rs.LinearRegressionSlopeStreaming(window)
+class PctFromHighStreaming:
+    """Percent below the rolling high - Streaming"""
+    def __init__(self, window=252):
+        self._inner = _rs.PctFromHighStreaming(window)
         self._current_value = float('nan')
         self._is_ready = False
         self._update_count = 0
@@ -2065,7 +3035,7 @@ class LinearRegressionSlopeStreaming:
         result = self._inner.update(value)
         self._current_value = result
         self._is_ready = not math.isnan(result)
-        return {"slope": result}
+        return {"pct_from_high": result}
 
     @property
     def current_value(self):
@@ -2090,10 +3060,10 @@ class LinearRegressionSlopeStreaming:
         self._update_count = 0
 
 
-class RollingPercentileStreaming:
-    """Rolling Percentile - Streaming"""
-    def __init__(self, window=120):
-        self._inner = _rs.RollingPercentileStreaming(window)
+class PctFromLowStreaming:
+    """Percent above the rolling low - Streaming"""
+    def __init__(self, window=252):
+        self._inner = _rs.PctFromLowStreaming(window)
         self._current_value = float('nan')
         self._is_ready = False
         self._update_count = 0
@@ -2104,7 +3074,7 @@ class RollingPercentileStreaming:
         result = self._inner.update(value)
         self._current_value = result
         self._is_ready = not math.isnan(result)
-        return {"percentile": result}
+        return {"pct_from_low": result}
 
     @property
     def current_value(self):
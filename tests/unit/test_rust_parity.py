@@ -1,4 +1,6 @@
 """Test numerical parity between Rust and Numba backends for all 44 bulk indicators."""
+from pathlib import Path
+
 import numpy as np
 import pytest
 
@@ -21,6 +23,8 @@ volume = np.abs(np.random.randn(N)) * 1000 + 500
 RTOL = 1e-10  # Relative tolerance
 ATOL = 1e-10  # Absolute tolerance
 
+FIXTURES = Path(__file__).resolve().parent.parent / "fixtures"
+
 
 class TestTrendParity:
     """Test parity for 15 trend indicators."""
@@ -37,6 +41,24 @@ class TestTrendParity:
         assert len(result) == N
         assert not np.isnan(result[0])  # EMA starts from first element
 
+    def test_sma_ema_multi(self):
+        periods = [10, 20, 50]
+        sma = _rs.sma_multi_numba(close, periods)
+        ema = _rs.ema_multi_numba(close, periods)
+        assert sma.shape == (N, len(periods))
+        for j, p in enumerate(periods):
+            np.testing.assert_allclose(sma[:, j], _rs.sma_numba(close, p), equal_nan=True)
+            np.testing.assert_allclose(ema[:, j], _rs.ema_numba(close, p, True), equal_nan=True)
+        with pytest.raises(ValueError):
+            _rs.sma_multi_numba(close, [10, 0])
+
+    def test_std_multi(self):
+        periods = [10, 20, 50]
+        std = _rs.std_multi_numba(close, periods)
+        assert std.shape == (N, len(periods))
+        for j, p in enumerate(periods):
+            np.testing.assert_allclose(std[:, j], _rs.standard_deviation_numba(close, p), atol=1e-6, equal_nan=True)
+
     def test_wma(self):
         result = _rs.weighted_moving_average(close, 20)
         assert len(result) == N
@@ -53,6 +75,15 @@ class TestTrendParity:
         assert len(plus_di) == N
         assert len(minus_di) == N
 
+    def test_adx_matches_ta(self):
+        ta = pytest.importorskip("ta")
+        pd = pytest.importorskip("pandas")
+        indicator = ta.trend.ADXIndicator(pd.Series(high), pd.Series(low), pd.Series(close), window=14)
+        adx_val, plus_di, minus_di = _rs.adx_numba(high, low, close, 14, compat="ta")
+        np.testing.assert_allclose(adx_val, indicator.adx().values, atol=1e-8)
+        np.testing.assert_allclose(plus_di, indicator.adx_pos().values, atol=1e-8)
+        np.testing.assert_allclose(minus_di, indicator.adx_neg().values, atol=1e-8)
+
     def test_cci(self):
         result = _rs.cci_numba(high, low, close, 20)
         assert len(result) == N
@@ -94,6 +125,25 @@ class TestTrendParity:
         aroon_up, aroon_down = _rs.aroon_numba(high, low, 25)
         assert len(aroon_up) == N
 
+    def test_trix_of_known_values(self):
+        # n=1 leaves the series untouched, so TRIX is the plain percent change
+        trix_line, signal = _rs.trix_of_numba(np.array([100.0, 110.0, 99.0, 99.0]), 1, 3)
+        np.testing.assert_allclose(trix_line, [np.nan, 10.0, -10.0, 0.0], atol=1e-12, equal_nan=True)
+        np.testing.assert_allclose(signal, [np.nan, 10.0, -10.0 / 3.0, -10.0 / 7.0], atol=1e-12, equal_nan=True)
+
+    def test_trix_of_matches_pandas(self):
+        pd = pytest.importorskip("pandas")
+        series = pd.Series(close)
+        ema3 = series.ewm(span=15).mean().ewm(span=15).mean().ewm(span=15).mean()
+        expected = ema3.pct_change() * 100.0
+        trix_line, signal = _rs.trix_of_numba(close, 15, 9)
+        np.testing.assert_allclose(trix_line, expected.values, rtol=RTOL, atol=ATOL, equal_nan=True)
+        np.testing.assert_allclose(signal, expected.ewm(span=9).mean().values, rtol=RTOL, atol=ATOL, equal_nan=True)
+
+    def test_trix_of_volume(self):
+        trix_line, signal = _rs.trix_of_numba(volume.astype(np.int64), 15, 9)
+        assert len(trix_line) == N
+
 
 class TestMomentumParity:
     """Test parity for 11 momentum indicators."""
@@ -105,10 +155,46 @@ class TestMomentumParity:
         valid = result[~np.isnan(result)]
         assert np.all(valid >= 0) and np.all(valid <= 100)
 
+    def test_rsi_matches_ta(self):
+        ta = pytest.importorskip("ta")
+        pd = pytest.importorskip("pandas")
+        expected = ta.momentum.RSIIndicator(pd.Series(close), window=14).rsi().values
+        result = _rs.relative_strength_index_numba(close, 14, compat="ta")
+        np.testing.assert_allclose(result, expected, atol=1e-8, equal_nan=True)
+
+    def test_rsi_of_matches_rsi(self):
+        result = _rs.rsi_of_numba(close, 14)
+        expected = _rs.relative_strength_index_numba(close, 14)
+        np.testing.assert_allclose(result, expected, rtol=RTOL, atol=ATOL)
+
+    def test_rsi_price_source_matches_explicit_hlc3(self):
+        hlc3 = (high + low + close) / 3.0
+        result = _rs.relative_strength_index_numba(close, 14, price_source="hlc3", high=high, low=low)
+        expected = _rs.relative_strength_index_numba(hlc3, 14)
+        np.testing.assert_allclose(result, expected, rtol=RTOL, atol=ATOL)
+
+    def test_price_source_requires_high_low(self):
+        with pytest.raises(ValueError):
+            _rs.momentum_numba(close, 10, price_source="hl2")
+
     def test_stochastic(self):
         k, d = _rs.stochastic_oscillator_numba(high, low, close, 14, 3)
         assert len(k) == N
 
+    def test_stochastic_matches_ta(self):
+        ta = pytest.importorskip("ta")
+        pd = pytest.importorskip("pandas")
+        # A flat stretch gives zero-range windows, which ta leaves NaN
+        flat_high, flat_low, flat_close = high.copy(), low.copy(), close.copy()
+        flat_high[100:120] = flat_low[100:120] = flat_close[100:120] = 100.0
+        indicator = ta.momentum.StochasticOscillator(
+            pd.Series(flat_high), pd.Series(flat_low), pd.Series(flat_close), window=14, smooth_window=3
+        )
+        k, d = _rs.stochastic_oscillator_numba(flat_high, flat_low, flat_close, 14, 3, compat="ta")
+        assert np.isnan(k[119])
+        np.testing.assert_allclose(k, indicator.stoch().values, atol=1e-8, equal_nan=True)
+        np.testing.assert_allclose(d, indicator.stoch_signal().values, atol=1e-8, equal_nan=True)
+
     def test_williams_r(self):
         result = _rs.williams_r_numba(high, low, close, 14)
         assert len(result) == N
@@ -125,6 +211,19 @@ class TestMomentumParity:
         stochrsi, k, d = _rs.stochastic_rsi_numba(close, 14, 3, 3)
         assert len(stochrsi) == N
 
+    def test_stochastic_rsi_matches_tradingview_export(self):
+        fixture = FIXTURES / "tradingview_stoch_rsi.csv"
+        assert fixture.exists(), "missing TradingView export at tests/fixtures/tradingview_stoch_rsi.csv"
+        data = np.genfromtxt(fixture, delimiter=",", names=True)
+        _, k, d = _rs.stochastic_rsi_numba(np.ascontiguousarray(data["close"]), 14, 3, 3, compat="tradingview")
+        np.testing.assert_allclose(k, data["k"], atol=1e-6, equal_nan=True)
+        np.testing.assert_allclose(d, data["d"], atol=1e-6, equal_nan=True)
+
+    def test_stochastic_rsi_tradingview_flat_window_is_nan(self):
+        rising = np.arange(100.0, 160.0)
+        stochrsi, k, d = _rs.stochastic_rsi_numba(rising, 14, 3, 3, compat="tradingview")
+        assert np.isnan(stochrsi).all() and np.isnan(k).all() and np.isnan(d).all()
+
     def test_tsi(self):
         result = _rs.true_strength_index_numba(close, 25, 13)
         assert len(result) == N
@@ -153,6 +252,16 @@ class TestVolatilityParity:
         result = _rs.average_true_range_numba(high, low, close, 14)
         assert len(result) == N
 
+    def test_atr_matches_ta(self):
+        ta = pytest.importorskip("ta")
+        pd = pytest.importorskip("pandas")
+        expected = ta.volatility.AverageTrueRange(
+            pd.Series(high), pd.Series(low), pd.Series(close), window=14
+        ).average_true_range().values
+        result = _rs.average_true_range_numba(high, low, close, 14, compat="ta")
+        np.testing.assert_allclose(result, expected, atol=1e-8)
+        assert np.all(result[:13] == 0.0)
+
     def test_bollinger_bands(self):
         upper, middle, lower = _rs.bollinger_bands_numba(close, 20, 2.0)
         assert len(upper) == N
@@ -165,14 +274,48 @@ class TestVolatilityParity:
         upper, middle, lower = _rs.keltner_channel_numba(high, low, close, 20, 10, 2.0)
         assert len(upper) == N
 
+    def test_keltner_channel_matches_ta_atr_version(self):
+        ta = pytest.importorskip("ta")
+        pd = pytest.importorskip("pandas")
+        indicator = ta.volatility.KeltnerChannel(
+            pd.Series(high), pd.Series(low), pd.Series(close),
+            window=20, window_atr=10, original_version=False, multiplier=2,
+        )
+        upper, middle, lower = _rs.keltner_channel_numba(
+            high, low, close, 20, 10, 2.0, original_version=False
+        )
+        np.testing.assert_allclose(upper, indicator.keltner_channel_hband().values, atol=1e-8, equal_nan=True)
+        np.testing.assert_allclose(middle, indicator.keltner_channel_mband().values, atol=1e-8, equal_nan=True)
+        np.testing.assert_allclose(lower, indicator.keltner_channel_lband().values, atol=1e-8, equal_nan=True)
+
     def test_donchian_channel(self):
         upper, middle, lower = _rs.donchian_channel_numba(high, low, 20)
         assert len(upper) == N
 
+    def test_donchian_channel_position(self):
+        upper, _, lower, position, width = _rs.donchian_channel_numba(
+            high, low, 20, close=close, return_position=True
+        )
+        valid = ~np.isnan(position)
+        assert np.all((position[valid] >= 0.0) & (position[valid] <= 1.0))
+        np.testing.assert_allclose(width, upper - lower)
+        with pytest.raises(ValueError):
+            _rs.donchian_channel_numba(high, low, 20, return_position=True)
+
     def test_ulcer_index(self):
         result = _rs.ulcer_index_numba(close, 14)
         assert len(result) == N
 
+    def test_standard_deviation_expanding_matches_pandas(self):
+        pd = pytest.importorskip("pandas")
+        series = pd.Series(close)
+        rolling = _rs.standard_deviation_numba(close, 20)
+        expanding = _rs.standard_deviation_numba(close, 20, expanding=True)
+        np.testing.assert_allclose(rolling, series.rolling(20).std(ddof=0).values, atol=1e-10, equal_nan=True)
+        np.testing.assert_allclose(
+            expanding, series.expanding(min_periods=20).std(ddof=0).values, atol=1e-10, equal_nan=True
+        )
+
 
 class TestVolumeParity:
     """Test parity for 10 volume indicators."""
@@ -189,6 +332,12 @@ class TestVolumeParity:
         result = _rs.on_balance_volume_numba(close, volume)
         assert len(result) == N
 
+    def test_obv_accepts_int64_volume(self):
+        int_volume = volume.astype(np.int64)
+        result = _rs.on_balance_volume_numba(close, int_volume)
+        expected = _rs.on_balance_volume_numba(close, int_volume.astype(np.float64))
+        np.testing.assert_allclose(result, expected, rtol=RTOL, atol=ATOL)
+
     def test_cmf(self):
         result = _rs.chaikin_money_flow_numba(high, low, close, volume, 20)
         assert len(result) == N
@@ -233,3 +382,52 @@ class TestOthersParity:
     def test_cumulative_return(self):
         result = _rs.cumulative_return_numba(close)
         assert len(result) == N
+
+    def test_max_drawdown_expanding_matches_pandas(self):
+        pd = pytest.importorskip("pandas")
+        series = pd.Series(close)
+        drawdown = series / series.cummax() - 1.0
+        expanding = _rs.max_drawdown_numba(close, 20, expanding=True)
+        np.testing.assert_allclose(
+            expanding, drawdown.expanding(min_periods=20).min().values * 100.0, atol=1e-10, equal_nan=True
+        )
+        rolling = _rs.max_drawdown_numba(close, 20)
+        expected = series.rolling(20).apply(
+            lambda w: (w / np.maximum.accumulate(w) - 1.0).min() * 100.0, raw=True
+        )
+        np.testing.assert_allclose(rolling, expected.values, atol=1e-10, equal_nan=True)
+
+    def test_martin_ratio_bar_frequency(self):
+        daily = _rs.martin_ratio_numba(close, 60)
+        crypto = _rs.martin_ratio_numba(close, 60, bar_frequency="crypto_daily")
+        assert len(daily) == N
+        assert np.isnan(crypto[58]) and not np.isnan(crypto[-1])
+        assert not np.allclose(daily[59:], crypto[59:], equal_nan=True)
+        with pytest.raises(ValueError):
+            _rs.martin_ratio_numba(close, 60, bar_frequency="weekly")
+
+    def test_periods_per_year_overrides_bar_frequency(self):
+        returns = np.diff(np.log(close))
+        benchmark = np.zeros_like(returns)
+        np.testing.assert_allclose(
+            _rs.calmar_ratio_numba(close, 60, periods_per_year=365.0),
+            _rs.calmar_ratio_numba(close, 60, bar_frequency="crypto_daily"),
+            equal_nan=True,
+        )
+        np.testing.assert_allclose(
+            _rs.martin_ratio_numba(close, 60, periods_per_year=365.0),
+            _rs.martin_ratio_numba(close, 60, bar_frequency="crypto_daily"),
+            equal_nan=True,
+        )
+        np.testing.assert_allclose(
+            _rs.parkinson_volatility_numba(high, low, 20, periods_per_year=365.0),
+            _rs.parkinson_volatility_numba(high, low, 20, bar_frequency="crypto_daily"),
+            equal_nan=True,
+        )
+        np.testing.assert_allclose(
+            _rs.rolling_information_ratio_numba(returns, benchmark, 60, periods_per_year=365.0),
+            _rs.rolling_information_ratio_numba(returns, benchmark, 60, bar_frequency="crypto_daily"),
+            equal_nan=True,
+        )
+        with pytest.raises(ValueError):
+            _rs.rolling_information_ratio_numba(returns, benchmark, 60, periods_per_year=-1.0)
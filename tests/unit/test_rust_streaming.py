@@ -1,4 +1,5 @@
 """Test API compatibility of Rust-backed streaming indicators."""
+import copy
 import math
 import numpy as np
 import pytest
@@ -99,6 +100,279 @@ class TestStreamingAPI:
         assert isinstance(result, dict)
         assert "atr" in result
 
+    def test_vwap_bands_returns_dict(self):
+        bands = streaming.VWAPBandsStreaming(3, 2.0)
+        for _ in range(3):
+            result = bands.update(105.0, 95.0, 100.0, 1000.0)
+        assert isinstance(result, dict)
+        assert set(result) == {"upper", "vwap", "lower"}
+        assert bands.is_ready and bands.update_count == 3
+
+    def test_ohlc_aggregator_returns_dict(self):
+        agg = streaming.OHLCAggregator(bar_ticks=2)
+        first = agg.update(100.0, 10.0, 0.0)
+        assert not first["completed"] and not agg.is_ready
+        bar = agg.update(102.0, 5.0, 1.0)
+        assert set(bar) == {"open", "high", "low", "close", "volume", "completed"}
+        assert bar["completed"] and agg.is_ready
+        assert agg.current_value == bar["close"]
+        with pytest.raises(ValueError):
+            streaming.OHLCAggregator()
+
+    def test_disparity_index_returns_dict(self):
+        disparity = streaming.DisparityIndexStreaming(3, "sma")
+        for close in (100.0, 102.0, 104.0):
+            result = disparity.update(close)
+        assert isinstance(result, dict)
+        assert abs(result["disparity_index"] - 200.0 / 102.0) < 1e-10
+        assert disparity.is_ready and disparity.update_count == 3
+        with pytest.raises(ValueError):
+            streaming.DisparityIndexStreaming(14, "hull")
+
+    def test_cmo_returns_dict(self):
+        cmo = streaming.CMOStreaming(2)
+        for value in (100.0, 101.0, 103.0):
+            result = cmo.update(value)
+        assert isinstance(result, dict)
+        assert result["cmo"] == 100.0
+        assert cmo.is_ready and cmo.update_count == 3
+
+    def test_rolling_trimmed_mean_returns_dict(self):
+        trimmed = streaming.RollingTrimmedMeanStreaming(5, 0.2)
+        for value in (1.0, 2.0, 3.0, 4.0, 100.0):
+            result = trimmed.update(value)
+        assert isinstance(result, dict)
+        assert abs(result["trimmed_mean"] - 3.0) < 1e-10
+        assert trimmed.is_ready and trimmed.update_count == 5
+        with pytest.raises(ValueError):
+            streaming.RollingTrimmedMeanStreaming(5, 0.5)
+
+    def test_price_oscillator_returns_dict(self):
+        osc = streaming.PriceOscillatorStreaming(1, 2, "sma", pct=True)
+        osc.update(100.0)
+        result = osc.update(104.0)
+        assert isinstance(result, dict)
+        assert abs(result["price_oscillator"] - 200.0 / 102.0) < 1e-10
+        assert osc.is_ready and osc.update_count == 2
+
+    def test_volume_zscore_returns_dict(self):
+        zscore = streaming.VolumeZScoreStreaming(3)
+        for volume in (100.0, 200.0, 300.0):
+            result = zscore.update(volume)
+        assert isinstance(result, dict)
+        assert result["volume_zscore"] > 0
+        assert zscore.is_ready and zscore.update_count == 3
+
+    def test_detrended_rsi_returns_dict(self):
+        detrended = streaming.DetrendedRSIStreaming(2, 2)
+        for close in range(100, 110):
+            result = detrended.update(float(close))
+        assert isinstance(result, dict)
+        assert result["detrended_rsi"] == 0.0
+        assert detrended.is_ready and detrended.update_count == 10
+
+    def test_drawdown_returns_dict(self):
+        drawdown = streaming.DrawdownStreaming()
+        drawdown.update(100.0)
+        result = drawdown.update(90.0)
+        assert isinstance(result, dict)
+        assert abs(result["drawdown"] + 10.0) < 1e-10
+        assert abs(result["max_drawdown"] + 10.0) < 1e-10
+        assert result["bars_in_drawdown"] == 1.0
+        assert drawdown.is_ready and drawdown.update_count == 2
+        with pytest.raises(ValueError):
+            streaming.DrawdownStreaming(window=0)
+
+    def test_reset_session_forwarded(self):
+        obv = streaming.OnBalanceVolumeStreaming()
+        obv.update(10.0, 100.0)
+        obv.update(11.0, 80.0)
+        obv.reset_session()
+        assert obv.update(12.0, 50.0)["obv"] == 50.0
+        ad = streaming.AccDistIndexStreaming()
+        ad.update(11.0, 9.0, 10.5, 100.0)
+        ad.reset_session()
+        assert ad.update(14.0, 12.0, 13.0, 20.0)["ad"] == 0.0
+
+    def test_adx_adxr_forwarded(self):
+        adx = streaming.ADXStreaming(3)
+        assert math.isnan(adx.adxr())
+        for i in range(20):
+            adx.update(105.0 + i, 95.0 + i, 100.0 + i)
+        assert not math.isnan(adx.adxr())
+
+    def test_donchian_position_forwarded(self):
+        donchian = streaming.DonchianChannelStreaming(2)
+        donchian.update(110.0, 90.0)
+        donchian.update(108.0, 92.0)
+        assert abs(donchian.position(105.0) - 0.75) < 1e-10
+
+    def test_awesome_oscillator_signals_forwarded(self):
+        ao = streaming.AwesomeOscillatorStreaming(1, 2)
+        for midpoint in (0.0, 6.0, 11.0, 15.0, 19.8):
+            ao.update(midpoint, midpoint)
+        assert ao.is_bullish_saucer()
+        assert ao.zero_cross() == 0.0
+
+    def test_macd_ema_values_forwarded(self):
+        macd = streaming.MACDStreaming(12, 26, 9)
+        assert math.isnan(macd.fast_ema())
+        for _ in range(40):
+            result = macd.update(100.0)
+        assert abs(macd.fast_ema() - 100.0) < 1e-10
+        assert abs(macd.slow_ema() - 100.0) < 1e-10
+        assert macd.signal_value() == result["signal"]
+
+    def test_rolling_rms_returns_dict(self):
+        rms = streaming.RollingRMSStreaming(2)
+        assert math.isnan(rms.update(3.0)["rms"])
+        result = rms.update(4.0)
+        assert isinstance(result, dict)
+        assert abs(result["rms"] - math.sqrt(12.5)) < 1e-10
+        assert abs(rms.variance() - 0.25) < 1e-10
+        assert rms.is_ready and rms.update_count == 2
+
+    def test_rolling_rms_copy_is_independent(self):
+        rms = streaming.RollingRMSStreaming(2)
+        rms.update(3.0)
+        clone = copy.deepcopy(rms)
+        rms.update(4.0)
+        assert clone.update_count == 1
+        assert abs(clone.update(0.0)["rms"] - math.sqrt(4.5)) < 1e-10
+        rms.reset()
+        assert rms.update_count == 0 and not rms.is_ready
+
+    def test_ma_distance_returns_dict(self):
+        distance = streaming.MADistanceStreaming(2, 3, ma_type="sma")
+        for close in (10.0, 11.0, 12.0):
+            result = distance.update(close)
+        assert isinstance(result, dict)
+        assert abs(result["ma_distance"] - 0.5) < 1e-10
+        assert distance.is_ready and distance.update_count == 3
+        with pytest.raises(ValueError):
+            streaming.MADistanceStreaming(ma_type="hma")
+
+    def test_squeeze_returns_dict(self):
+        squeeze = streaming.SqueezeStreaming(5, 2.0, 1.5, 5)
+        for i in range(8):
+            result = squeeze.update(100.5 + i % 2, 99.5 - i % 2, 100.0)
+        assert isinstance(result, dict)
+        assert result["squeeze"] == 1.0
+        assert squeeze.is_ready and squeeze.update_count == 8
+
+    def test_chaikin_volatility_returns_dict(self):
+        chaikin = streaming.ChaikinVolatilityStreaming(2, 2)
+        for _ in range(3):
+            result = chaikin.update(101.0, 99.0)
+        assert isinstance(result, dict)
+        assert abs(result["chaikin_volatility"]) < 1e-10
+        assert chaikin.is_ready and chaikin.update_count == 3
+
+
+    def test_return_wrappers_forward_pct(self):
+        roc = streaming.ROCStreaming(2, pct=False)
+        roc.update(100.0)
+        assert abs(roc.update(110.0)["roc"] - 0.1) < 1e-10
+        daily = streaming.DailyReturnStreaming(pct=False)
+        daily.update(100.0)
+        assert abs(daily.update(110.0)["dr"] - 0.1) < 1e-10
+        cumulative = streaming.CumulativeReturnStreaming(pct=False)
+        cumulative.update(100.0)
+        assert abs(cumulative.update(120.0)["cr"] - 0.2) < 1e-10
+        log_return = streaming.DailyLogReturnStreaming(pct=False)
+        log_return.update(100.0)
+        assert abs(log_return.update(110.0)["dlr"] - math.log(1.1)) < 1e-10
+
+    def test_parabolic_sar_forwards_af_schedule(self):
+        linear = streaming.ParabolicSARStreaming()
+        stepped = streaming.ParabolicSARStreaming(af_schedule=[0.02, 0.1, 0.3])
+        for i in range(10):
+            bar = (101.0 + 2 * i, 99.0 + 2 * i, 100.0 + 2 * i)
+            sar_linear = linear.update(*bar)["psar"]
+            sar_stepped = stepped.update(*bar)["psar"]
+        assert sar_stepped > sar_linear
+        with pytest.raises(ValueError):
+            streaming.ParabolicSARStreaming(af_schedule=[0.2, 0.1])
+    def test_connors_rsi_returns_dict(self):
+        connors = streaming.ConnorsRSIStreaming(2, 2, 5)
+        for i in range(30):
+            result = connors.update(100.0 + (i % 4) - 0.1 * i)
+        assert isinstance(result, dict)
+        assert 0.0 <= result["connors_rsi"] <= 100.0
+        assert connors.is_ready and connors.update_count == 30
+
+    def test_up_down_streak_returns_dict(self):
+        streak = streaming.UpDownStreakStreaming()
+        for close in (10.0, 11.0, 12.0, 11.0):
+            result = streak.update(close)
+        assert isinstance(result, dict)
+        assert result["streak"] == -1.0
+        assert streak.is_ready and streak.update_count == 4
+
+    def test_smoothed_rsi_returns_dict(self):
+        smoothed = streaming.SmoothedRSIStreaming(1, 3)
+        plain = streaming.RSIStreaming(3)
+        for close in (44.0, 44.3, 44.1, 44.6, 45.0, 44.8, 45.2):
+            result = smoothed.update(close)
+            expected = plain.update(close)["rsi"]
+        assert isinstance(result, dict)
+        assert abs(result["smoothed_rsi"] - expected) < 1e-10
+        assert smoothed.is_ready and smoothed.update_count == 7
+
+    def test_drawdown_duration_returns_dict(self):
+        duration = streaming.DrawdownDurationStreaming(window=3)
+        for close in (100.0, 105.0, 103.0, 101.0, 102.0):
+            result = duration.update(close)
+        assert isinstance(result, dict)
+        assert result["drawdown_duration"] == 2.0
+        assert duration.is_ready and duration.update_count == 5
+
+    def test_efficiency_ratio_returns_dict(self):
+        ratio = streaming.EfficiencyRatioStreaming(3)
+        for close in (10.0, 11.0, 12.0, 13.0):
+            result = ratio.update(close)
+        assert isinstance(result, dict)
+        assert abs(result["efficiency_ratio"] - 1.0) < 1e-10
+        assert ratio.is_ready and ratio.update_count == 4
+
+    def test_atr_trailing_stop_returns_dict(self):
+        trailing = streaming.ATRTrailingStopStreaming(2, 1.0)
+        for i in range(6):
+            result = trailing.update(101.0 + i, 99.0 + i, 100.0 + i)
+        assert isinstance(result, dict)
+        assert result["direction"] == 1.0
+        assert result["stop"] < 105.0
+        assert trailing.is_ready and trailing.update_count == 6
+
+    def test_rolling_count_valid_returns_dict(self):
+        counter = streaming.RollingCountValidStreaming(3)
+        for value in (1.0, float('nan'), 3.0, 4.0):
+            result = counter.update(value)
+        assert isinstance(result, dict)
+        assert result["count_valid"] == 2.0
+        assert counter.is_ready and counter.update_count == 4
+
+    def test_pct_from_high_low_return_dict(self):
+        from_high = streaming.PctFromHighStreaming(3)
+        from_low = streaming.PctFromLowStreaming(3)
+        for close in (10.0, 12.0, 9.0):
+            high_result = from_high.update(close)
+            low_result = from_low.update(close)
+        assert isinstance(high_result, dict) and isinstance(low_result, dict)
+        assert abs(high_result["pct_from_high"] + 25.0) < 1e-10
+        assert low_result["pct_from_low"] == 0.0
+        assert from_high.is_ready and from_low.update_count == 3
+
+    def test_ma_envelope_returns_dict(self):
+        envelope = streaming.MAEnvelopeStreaming(2, pct=2.5)
+        envelope.update(100.0)
+        result = envelope.update(102.0)
+        assert isinstance(result, dict)
+        assert abs(result["middle"] - 101.0) < 1e-10
+        assert abs(result["upper"] - 101.0 * 1.025) < 1e-10
+        assert abs(result["lower"] - 101.0 * 0.975) < 1e-10
+        assert envelope.is_ready and envelope.update_count == 2
+
 
 class TestStreamingAliases:
     """Verify short aliases work."""
@@ -147,3 +421,27 @@ class TestStreamingFunctionality:
         dr.update(100.0)
         result = dr.update(105.0)
         assert abs(result["dr"] - 5.0) < 1e-10  # 5% return
+
+
+class TestStreamingFactory:
+    """Verify create_streaming builds the same classes as direct construction."""
+
+    def test_rsi_and_macd_by_name(self):
+        from ta_numba import _ta_numba_rs as rs
+
+        rsi_named = rs.create_streaming("rsi", window=14)
+        rsi_direct = rs.RSIStreaming(14)
+        macd_named = rs.create_streaming("MACD", fast_period=12, slow_period=26, signal_period=9)
+        macd_direct = rs.MACDStreaming(12, 26, 9)
+
+        np.random.seed(7)
+        prices = np.cumsum(np.random.randn(60)) + 100
+        for price in prices:
+            np.testing.assert_equal(rsi_named.update(price), rsi_direct.update(price))
+            np.testing.assert_equal(macd_named.update(price), macd_direct.update(price))
+
+    def test_unknown_name_lists_valid_names(self):
+        from ta_numba import _ta_numba_rs as rs
+
+        with pytest.raises(ValueError, match="rsi"):
+            rs.create_streaming("not_an_indicator")